@@ -0,0 +1,108 @@
+// benches/book.rs — Throughput of the per-message and per-update book paths
+//
+//   cargo bench                 all of them
+//   cargo bench -- merge        one group
+//
+// Books are synthetic but shaped like the real ones: both venues quoting the
+// same price grid around 100k, with `LEVELS` levels a side.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use rust_decimal::Decimal;
+use serde_json::json;
+
+use obt::config::{FeeSchedule, FeesConfig};
+use obt::hyperliquid_mda::parse_l2book;
+use obt::merger::MergedBook;
+use obt::paradex_mda::LocalBook;
+use obt::types::{Exchange, Level, OrderBook, PdxBookData, RawEnvelope};
+
+/// Levels per side in each synthetic book.
+const LEVELS: i64 = 100;
+/// Merged depth, as the default `[merge] depth`.
+const DEPTH: usize = 10;
+
+/// Price of the `i`th level from the touch, in tenths: 100000.0 ∓ i × step.
+fn px(i: i64, bid: bool, step: i64) -> Decimal {
+    Decimal::new(1_000_000 + if bid { -1 - i * step } else { 1 + i * step }, 1)
+}
+
+fn book(exchange: Exchange, step: i64) -> OrderBook {
+    let mut book = OrderBook::new(exchange, "BTC");
+    let side = |bid| (0..LEVELS).map(|i| Level::new(px(i, bid, step), Decimal::new(1_000 + i, 3), 1)).collect();
+    book.bids = side(true);
+    book.asks = side(false);
+    book
+}
+
+fn bench_merge(c: &mut Criterion) {
+    // Different steps, so the two books interleave rather than tie
+    let (hl, pdx) = (book(Exchange::Hyperliquid, 5), book(Exchange::Paradex, 3));
+    let fees = FeesConfig {
+        hl:  FeeSchedule { maker_bps: 1.5, taker_bps: 4.5 },
+        pdx: FeeSchedule { maker_bps: 0.0, taker_bps: 2.0 },
+    };
+    c.bench_function("merge/build", |b| {
+        b.iter(|| MergedBook::build(black_box(&hl), black_box(&pdx), DEPTH, &fees))
+    });
+}
+
+/// A Paradex book message: `inserts` for a snapshot, or the given changes.
+fn pdx_data(update_type: &str, inserts: &[(i64, bool)], updates: &[(i64, bool)], deletes: &[(i64, bool)]) -> PdxBookData {
+    let levels = |ls: &[(i64, bool)]| ls.iter().map(|&(i, bid)| json!({
+        "price": px(i, bid, 3).to_string(),
+        "side":  if bid { "BUY" } else { "SELL" },
+        "size":  "0.750",
+    })).collect::<Vec<_>>();
+    serde_json::from_value(json!({
+        "inserts":         levels(inserts),
+        "updates":         levels(updates),
+        "deletes":         levels(deletes),
+        "last_updated_at": 1_700_000_000_000_000_u64,
+        "market":          "BTC-USD-PERP",
+        "update_type":     update_type,
+    })).expect("valid PdxBookData")
+}
+
+fn bench_pdx_delta(c: &mut Criterion) {
+    let all: Vec<(i64, bool)> = (0..LEVELS).flat_map(|i| [(i, true), (i, false)]).collect();
+    let snapshot = pdx_data("s", &all, &[], &[]);
+    // A typical delta: the touch and a few levels behind it change on both sides
+    let delta = pdx_data(
+        "d",
+        &[(LEVELS, true), (LEVELS, false)],
+        &[(0, true), (0, false), (1, true), (2, false), (4, true)],
+        &[(7, true), (9, false)],
+    );
+    c.bench_function("pdx/apply_delta", |b| {
+        b.iter_batched(
+            || {
+                let mut book = LocalBook::default();
+                book.apply_snapshot(&snapshot);
+                book
+            },
+            |mut book| {
+                let flow = book.apply_delta(black_box(&delta));
+                (flow, book.to_levels(DEPTH * 5))
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_hl_parse(c: &mut Criterion) {
+    // Hyperliquid sends 20 levels a side
+    let side = |bid| (0..20).map(|i| json!({ "px": px(i, bid, 5).to_string(), "sz": "1.2345", "n": 3 })).collect::<Vec<_>>();
+    let frame = json!({
+        "channel": "l2Book",
+        "data": { "coin": "BTC", "time": 1_700_000_000_000_u64, "levels": [side(true), side(false)] },
+    }).to_string();
+    c.bench_function("hl/parse_l2book", |b| {
+        b.iter(|| {
+            let env: RawEnvelope = serde_json::from_str(black_box(&frame)).expect("valid envelope");
+            parse_l2book(env.data).expect("valid l2Book")
+        })
+    });
+}
+
+criterion_group!(benches, bench_merge, bench_pdx_delta, bench_hl_parse);
+criterion_main!(benches);
@@ -0,0 +1,13 @@
+// build.rs — Compile the gRPC service definition in proto/
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Use the vendored protoc so building doesn't require a system install
+    if std::env::var_os("PROTOC").is_none() {
+        // SAFETY: build scripts are single-threaded at this point
+        unsafe { std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?) };
+    }
+    tonic_build::configure()
+        .build_client(false)
+        .compile_protos(&["proto/obt.proto"], &["proto"])?;
+    Ok(())
+}
@@ -0,0 +1,134 @@
+// src/account.rs — Private account state (fills, positions, balances) per venue
+
+use std::collections::{HashSet, VecDeque};
+
+use serde::Serialize;
+
+use crate::types::{now_ms, Exchange, Side};
+
+/// Fills kept in `AccountState::fills` (oldest dropped first).
+const MAX_FILLS: usize = 50;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountFill {
+    pub ts_ms:      u64,
+    pub market:     String,
+    pub side:       Side,
+    pub price:      f64,
+    pub size:       f64,
+    pub fee:        f64, // quote currency, negative for rebates
+    pub closed_pnl: f64, // realized by this fill, before fees
+    pub order_id:   String,
+}
+
+impl AccountFill {
+    /// Two fills with the same key are the same fill, e.g. replayed after a reconnect.
+    fn key(&self) -> String {
+        format!("{}:{}:{}:{}", self.order_id, self.ts_ms, self.price, self.size)
+    }
+}
+
+/// Open position in one market. `qty` is signed (+long / −short).
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountPosition {
+    pub market:         String,
+    pub qty:            f64,
+    pub entry_px:       f64,
+    pub unrealized_pnl: f64,
+}
+
+/// What the venue's private channels have told us about the account.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountState {
+    pub exchange:        Exchange,
+    pub connected:       bool,
+    pub account_value:   Option<f64>, // equity incl. unrealized PnL
+    pub free_collateral: Option<f64>, // available for new orders / withdrawal
+    pub positions:       Vec<AccountPosition>,
+    pub fills:           VecDeque<AccountFill>, // newest first
+    pub last_update_ms:  u64,
+    /// Positions (and, where the venue replays them, today's fills) have been
+    /// received since the channel last connected; until then the account is
+    /// unknown rather than flat.
+    pub synced:          bool,
+    pub realized_today:  DailyRealized,
+}
+
+/// Realized PnL net of fees since UTC midnight, over every fill seen today, not
+/// only those `AccountState::fills` still keeps.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DailyRealized {
+    pub day_start_ms: u64,
+    pub pnl:          f64,
+    #[serde(skip)]
+    seen:             HashSet<String>, // keys of the fills counted
+}
+
+impl DailyRealized {
+    /// Counts `f` once, if it is from the day starting at `day_start_ms`.
+    fn add(&mut self, f: &AccountFill, day_start_ms: u64) {
+        if self.day_start_ms != day_start_ms {
+            *self = Self { day_start_ms, ..Default::default() };
+        }
+        if f.ts_ms >= day_start_ms && self.seen.insert(f.key()) {
+            self.pnl += f.closed_pnl - f.fee;
+        }
+    }
+}
+
+impl AccountState {
+    pub fn new(exchange: Exchange) -> Self {
+        Self {
+            exchange,
+            connected:       false,
+            account_value:   None,
+            free_collateral: None,
+            positions:       Vec::new(),
+            fills:           VecDeque::new(),
+            last_update_ms:  0,
+            synced:          false,
+            realized_today:  DailyRealized::default(),
+        }
+    }
+
+    /// Position in `market`, matched case-insensitively.
+    pub fn position(&self, market: &str) -> Option<&AccountPosition> {
+        self.positions.iter().find(|p| p.market.eq_ignore_ascii_case(market))
+    }
+
+    /// Realized PnL net of fees since UTC midnight, plus the unrealized PnL of
+    /// every open position.
+    pub fn pnl_today(&self) -> f64 {
+        let realized = if self.realized_today.day_start_ms == day_start_ms() { self.realized_today.pnl } else { 0.0 };
+        realized + self.positions.iter().map(|p| p.unrealized_pnl).sum::<f64>()
+    }
+
+    /// Replaces or removes (when flat) the position in `pos.market`.
+    pub fn set_position(&mut self, pos: AccountPosition) {
+        self.positions.retain(|p| p.market != pos.market);
+        if pos.qty != 0.0 {
+            self.positions.push(pos);
+        }
+    }
+
+    /// Adds fills, keeping the list newest first. Fills already seen (same
+    /// order id, time, price and size — e.g. replayed after a reconnect) are skipped.
+    pub fn add_fills(&mut self, fills: impl IntoIterator<Item = AccountFill>) {
+        let day_start = day_start_ms();
+        for f in fills {
+            self.realized_today.add(&f, day_start);
+            let key = f.key();
+            if !self.fills.iter().any(|g| g.key() == key) {
+                self.fills.push_back(f);
+            }
+        }
+        self.fills.make_contiguous().sort_by_key(|f| std::cmp::Reverse(f.ts_ms));
+        self.fills.truncate(MAX_FILLS);
+    }
+}
+
+/// Start of the current UTC day, the reset point for `max_daily_loss`.
+pub fn day_start_ms() -> u64 {
+    let now = now_ms();
+    now - now % 86_400_000
+}
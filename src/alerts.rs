@@ -0,0 +1,453 @@
+// src/alerts.rs — Config-declared alert rules evaluated each tick, dispatched to sinks
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::{mpsc, watch};
+use tokio::time::interval;
+use tracing::{info, warn};
+
+use crate::config::{AlertRule, AlertsConfig};
+use crate::merger::MergedBook;
+use crate::sim::SimAction;
+use crate::supervisor::FeedStatus;
+use crate::types::{now_ms, OrderBook};
+
+// ─── Conditions ───────────────────────────────────────────────────────────────
+
+/// Values a rule can test, read from the latest books each tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metric {
+    // Numeric
+    CrossSpread,
+    CrossSpreadPct,
+    NetCrossSpread,
+    NetCrossSpreadPct,
+    ArbSize,
+    ArbProfitUsd,
+    ArbConfidence,
+    LiquidityImbalance,
+    ImbalanceTop1,
+    ImbalanceTop5,
+    ImbalanceTop20,
+    TotalBidUsd,
+    TotalAskUsd,
+    HlAgeMs,
+    PdxAgeMs,
+    HlMarkDivBps,
+    PdxMarkDivBps,
+    Ofi,
+    DwMidBps,
+    // Boolean (1.0 / 0.0)
+    Arb,
+    HlDisconnected,
+    PdxDisconnected,
+    AnyDisconnected,
+    HlStale,
+    PdxStale,
+    HlFailed,
+    PdxFailed,
+}
+
+impl Metric {
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "cross_spread"         => Metric::CrossSpread,
+            "cross_spread_pct"     => Metric::CrossSpreadPct,
+            "net_cross_spread"     => Metric::NetCrossSpread,
+            "net_cross_spread_pct" => Metric::NetCrossSpreadPct,
+            "arb_size"             => Metric::ArbSize,
+            "arb_profit_usd"       => Metric::ArbProfitUsd,
+            "arb_confidence"       => Metric::ArbConfidence,
+            "liquidity_imbalance"  => Metric::LiquidityImbalance,
+            "imbalance_top1"       => Metric::ImbalanceTop1,
+            "imbalance_top5"       => Metric::ImbalanceTop5,
+            "imbalance_top20"      => Metric::ImbalanceTop20,
+            "total_bid_usd"        => Metric::TotalBidUsd,
+            "total_ask_usd"        => Metric::TotalAskUsd,
+            "hl_age_ms"            => Metric::HlAgeMs,
+            "pdx_age_ms"           => Metric::PdxAgeMs,
+            "hl_mark_div_bps"      => Metric::HlMarkDivBps,
+            "pdx_mark_div_bps"     => Metric::PdxMarkDivBps,
+            "ofi"                  => Metric::Ofi,
+            "dw_mid_bps"           => Metric::DwMidBps,
+            "arb"                  => Metric::Arb,
+            "hl_disconnected"      => Metric::HlDisconnected,
+            "pdx_disconnected"     => Metric::PdxDisconnected,
+            "any_disconnected"     => Metric::AnyDisconnected,
+            "hl_stale"             => Metric::HlStale,
+            "pdx_stale"            => Metric::PdxStale,
+            "hl_failed"            => Metric::HlFailed,
+            "pdx_failed"           => Metric::PdxFailed,
+            _ => return None,
+        })
+    }
+
+    fn is_bool(self) -> bool {
+        matches!(
+            self,
+            Metric::Arb | Metric::HlDisconnected | Metric::PdxDisconnected | Metric::AnyDisconnected
+                | Metric::HlStale | Metric::PdxStale | Metric::HlFailed | Metric::PdxFailed
+        )
+    }
+
+    /// Current value, or `None` when it can't be computed yet (e.g. empty book).
+    fn value(self, s: &Snapshot) -> Option<f64> {
+        let flag = |b: bool| Some(if b { 1.0 } else { 0.0 });
+        let sig = &s.merged.signals;
+        match self {
+            Metric::CrossSpread        => sig.cross_spread,
+            Metric::CrossSpreadPct     => sig.cross_spread_pct,
+            Metric::NetCrossSpread     => sig.net_cross_spread,
+            Metric::NetCrossSpreadPct  => sig.net_cross_spread_pct,
+            Metric::ArbSize            => Some(sig.arb_size),
+            Metric::ArbProfitUsd       => Some(sig.arb_profit_usd),
+            Metric::ArbConfidence      => Some(sig.arb_confidence),
+            Metric::LiquidityImbalance => sig.liquidity_imbalance,
+            Metric::ImbalanceTop1      => sig.imbalance_by_depth[0],
+            Metric::ImbalanceTop5      => sig.imbalance_by_depth[1],
+            Metric::ImbalanceTop20     => sig.imbalance_by_depth[2],
+            Metric::TotalBidUsd        => Some(sig.total_bid_usd),
+            Metric::TotalAskUsd        => Some(sig.total_ask_usd),
+            Metric::HlAgeMs            => s.hl.age_ms(s.now_ms).map(|a| a as f64),
+            Metric::PdxAgeMs           => s.pdx.age_ms(s.now_ms).map(|a| a as f64),
+            Metric::HlMarkDivBps       => s.hl.mark_divergence_bps(),
+            Metric::PdxMarkDivBps      => s.pdx.mark_divergence_bps(),
+            Metric::Ofi                => sig.ofi,
+            Metric::DwMidBps           => sig.depth_weighted_mid_bps,
+            Metric::Arb                => flag(sig.is_arb()),
+            Metric::HlDisconnected     => flag(!s.hl.status.is_connected()),
+            Metric::PdxDisconnected    => flag(!s.pdx.status.is_connected()),
+            Metric::AnyDisconnected    => flag(!s.hl.status.is_connected() || !s.pdx.status.is_connected()),
+            Metric::HlStale            => flag(s.hl.status == FeedStatus::Stale),
+            Metric::PdxStale           => flag(s.pdx.status == FeedStatus::Stale),
+            Metric::HlFailed           => flag(s.hl.status == FeedStatus::Failed),
+            Metric::PdxFailed          => flag(s.pdx.status == FeedStatus::Failed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op { Lt, Le, Gt, Ge }
+
+/// `<metric> <op> <number>`, or a bare boolean metric such as `any_disconnected`.
+/// The spaces around the operator are optional (`spread>5`).
+#[derive(Debug, Clone)]
+pub struct Condition {
+    metric:    Metric,
+    op:        Op,
+    threshold: f64,
+}
+
+impl Condition {
+    pub fn parse(s: &str) -> Result<Self> {
+        let metric_of = |name: &str| {
+            Metric::parse(name).ok_or_else(|| anyhow::anyhow!("unknown metric '{name}'"))
+        };
+        let malformed = || anyhow::anyhow!("expected '<metric> <op> <value>' or a boolean metric, got '{s}'");
+
+        // The operator splits the condition, so it needn't be spaced out
+        let Some(at) = s.find(['<', '>']) else {
+            return match s.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [name] => {
+                    let metric = metric_of(name)?;
+                    if !metric.is_bool() {
+                        bail!("'{name}' is numeric; use '{name} <op> <value>'");
+                    }
+                    Ok(Condition { metric, op: Op::Gt, threshold: 0.5 })
+                }
+                [_, op, _] => bail!("unknown operator '{op}' (use <, <=, >, >=)"),
+                _ => Err(malformed()),
+            };
+        };
+        let (name, rest) = (s[..at].trim(), &s[at..]);
+        let (op, value) = match rest.as_bytes() {
+            [b'<', b'=', ..] => (Op::Le, &rest[2..]),
+            [b'>', b'=', ..] => (Op::Ge, &rest[2..]),
+            [b'<', ..]       => (Op::Lt, &rest[1..]),
+            _                => (Op::Gt, &rest[1..]),
+        };
+        let value = value.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) || value.is_empty() {
+            return Err(malformed());
+        }
+        let metric = metric_of(name)?;
+        let threshold: f64 = value.parse()
+            .map_err(|_| anyhow::anyhow!("'{value}' is not a number"))?;
+        Ok(Condition { metric, op, threshold })
+    }
+
+    fn eval(&self, s: &Snapshot) -> Option<(bool, f64)> {
+        let v = self.metric.value(s)?;
+        let hit = match self.op {
+            Op::Lt => v < self.threshold,
+            Op::Le => v <= self.threshold,
+            Op::Gt => v > self.threshold,
+            Op::Ge => v >= self.threshold,
+        };
+        Some((hit, v))
+    }
+}
+
+// ─── Events + sinks ───────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState { Fired, Resolved }
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertEvent {
+    pub rule:  String,
+    pub when:  String,
+    pub state: AlertState,
+    pub value: f64,
+    pub ts_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SinkKind { Log, Banner, Webhook }
+
+impl SinkKind {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "log"     => Some(SinkKind::Log),
+            "banner"  => Some(SinkKind::Banner),
+            "webhook" => Some(SinkKind::Webhook),
+            _ => None,
+        }
+    }
+}
+
+/// A destination for alert events. Implementations must not block.
+pub trait AlertSink: Send {
+    fn kind(&self) -> SinkKind;
+    fn dispatch(&mut self, event: &AlertEvent);
+}
+
+/// Logs through `tracing` (warn on fire, info on resolve).
+struct LogSink;
+
+impl AlertSink for LogSink {
+    fn kind(&self) -> SinkKind { SinkKind::Log }
+
+    fn dispatch(&mut self, ev: &AlertEvent) {
+        match ev.state {
+            AlertState::Fired    => warn!("[ALERT] {} fired: {} (value {:.4})", ev.rule, ev.when, ev.value),
+            AlertState::Resolved => info!("[ALERT] {} resolved (value {:.4})", ev.rule, ev.value),
+        }
+    }
+}
+
+/// An alert currently shown in the TUI banner.
+#[derive(Debug, Clone)]
+pub struct ActiveAlert {
+    pub rule:     String,
+    pub when:     String,
+    pub since_ms: u64,
+}
+
+/// Maintains the list of firing alerts the TUI renders as a banner.
+struct BannerSink {
+    tx: watch::Sender<Vec<ActiveAlert>>,
+}
+
+impl AlertSink for BannerSink {
+    fn kind(&self) -> SinkKind { SinkKind::Banner }
+
+    fn dispatch(&mut self, ev: &AlertEvent) {
+        self.tx.send_modify(|active| {
+            active.retain(|a| a.rule != ev.rule);
+            if ev.state == AlertState::Fired {
+                active.push(ActiveAlert { rule: ev.rule.clone(), when: ev.when.clone(), since_ms: ev.ts_ms });
+            }
+        });
+    }
+}
+
+/// POSTs each event as JSON to a generic webhook, off the evaluation task.
+struct WebhookSink {
+    client: reqwest::Client,
+    url:    String,
+}
+
+impl AlertSink for WebhookSink {
+    fn kind(&self) -> SinkKind { SinkKind::Webhook }
+
+    fn dispatch(&mut self, ev: &AlertEvent) {
+        let req = self.client.post(&self.url).json(&json!({ "alert": ev }));
+        tokio::spawn(async move {
+            match req.send().await {
+                Ok(r) if !r.status().is_success() => warn!("[ALERT] Webhook returned {}", r.status()),
+                Err(e) => warn!("[ALERT] Webhook failed: {}", e.without_url()),
+                _ => {}
+            }
+        });
+    }
+}
+
+// ─── Engine ───────────────────────────────────────────────────────────────────
+
+struct Snapshot<'a> {
+    hl:     &'a OrderBook,
+    pdx:    &'a OrderBook,
+    merged: &'a MergedBook,
+    now_ms: u64,
+}
+
+struct RuleState {
+    rule:       AlertRule,
+    true_since: Option<u64>,
+    firing:     bool,
+}
+
+/// Spawns the evaluation task and returns the banner channel for the TUI.
+pub fn spawn_alert_engine(
+    cfg: &AlertsConfig,
+    tick_ms: u64,
+    hl_rx: watch::Receiver<Arc<OrderBook>>,
+    pdx_rx: watch::Receiver<Arc<OrderBook>>,
+    merged_rx: watch::Receiver<MergedBook>,
+    sim_tx: Option<mpsc::Sender<SimAction>>,
+) -> watch::Receiver<Vec<ActiveAlert>> {
+    let (banner_tx, banner_rx) = watch::channel(Vec::new());
+
+    let mut sinks: Vec<Box<dyn AlertSink>> = vec![Box::new(LogSink), Box::new(BannerSink { tx: banner_tx })];
+    if let Some(url) = &cfg.webhook_url {
+        sinks.push(Box::new(WebhookSink { client: reqwest::Client::new(), url: url.clone() }));
+    }
+
+    let mut rules: Vec<RuleState> = cfg.rules.iter()
+        .map(|r| RuleState { rule: r.clone(), true_since: None, firing: false })
+        .collect();
+
+    tokio::spawn(async move {
+        let mut ticker = interval(Duration::from_millis(tick_ms));
+        loop {
+            ticker.tick().await;
+            let (hl, pdx, merged) = (hl_rx.borrow().clone(), pdx_rx.borrow().clone(), merged_rx.borrow().clone());
+            let snap = Snapshot { hl: &hl, pdx: &pdx, merged: &merged, now_ms: now_ms() };
+
+            for st in &mut rules {
+                if let Some(event) = evaluate(st, &snap) {
+                    for sink in sinks.iter_mut().filter(|s| st.rule.sinks.contains(&s.kind())) {
+                        sink.dispatch(&event);
+                    }
+                    if event.state == AlertState::Fired
+                        && let (Some(action), Some(tx)) = (st.rule.sim_order, &sim_tx)
+                        && tx.try_send(action).is_err()
+                    {
+                        warn!("[ALERT] {} could not submit sim order (queue full)", st.rule.name);
+                    }
+                }
+            }
+        }
+    });
+
+    banner_rx
+}
+
+/// Advances one rule's state machine; returns an event on fire/resolve transitions.
+fn evaluate(st: &mut RuleState, snap: &Snapshot) -> Option<AlertEvent> {
+    let (hit, value) = st.rule.condition.eval(snap).unwrap_or((false, f64::NAN));
+
+    if !hit {
+        st.true_since = None;
+        if st.firing {
+            st.firing = false;
+            return Some(event(&st.rule, AlertState::Resolved, value, snap.now_ms));
+        }
+        return None;
+    }
+
+    let since = *st.true_since.get_or_insert(snap.now_ms);
+    if !st.firing && snap.now_ms.saturating_sub(since) >= st.rule.for_ms {
+        st.firing = true;
+        return Some(event(&st.rule, AlertState::Fired, value, snap.now_ms));
+    }
+    None
+}
+
+fn event(rule: &AlertRule, state: AlertState, value: f64, ts_ms: u64) -> AlertEvent {
+    AlertEvent { rule: rule.name.clone(), when: rule.when.clone(), state, value, ts_ms }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Exchange;
+
+    fn parsed(s: &str) -> (Metric, Op, f64) {
+        let c = Condition::parse(s).unwrap();
+        (c.metric, c.op, c.threshold)
+    }
+
+    #[test]
+    fn parse_takes_the_operator_with_or_without_spaces() {
+        assert_eq!(parsed("cross_spread > 5"), (Metric::CrossSpread, Op::Gt, 5.0));
+        assert_eq!(parsed("cross_spread>5"), (Metric::CrossSpread, Op::Gt, 5.0));
+        assert_eq!(parsed("arb_size>=0.5"), (Metric::ArbSize, Op::Ge, 0.5));
+        assert_eq!(parsed("ofi <-2"), (Metric::Ofi, Op::Lt, -2.0));
+        assert_eq!(parsed("  dw_mid_bps<= 1e1 "), (Metric::DwMidBps, Op::Le, 10.0));
+        // A bare boolean metric is `> 0.5`
+        assert_eq!(parsed("any_disconnected"), (Metric::AnyDisconnected, Op::Gt, 0.5));
+    }
+
+    #[test]
+    fn parse_rejects_malformed_conditions() {
+        let err = |s: &str| Condition::parse(s).unwrap_err().to_string();
+        assert!(err("spread > 5").contains("unknown metric 'spread'"));
+        assert!(err("cross_spread = 5").contains("unknown operator '='"));
+        assert!(err("cross_spread >> 5").contains("'> 5' is not a number"));
+        assert!(err("cross_spread >").starts_with("expected"));
+        assert!(err("> 5").starts_with("expected"));
+        assert!(err("cross spread > 5").starts_with("expected"));
+        assert!(err("cross_spread").contains("is numeric"));
+    }
+
+    fn rule(when: &str, for_ms: u64) -> RuleState {
+        let rule = AlertRule {
+            name: "wide".into(), when: when.into(), condition: Condition::parse(when).unwrap(),
+            for_ms, sinks: vec![SinkKind::Log], sim_order: None,
+        };
+        RuleState { rule, true_since: None, firing: false }
+    }
+
+    /// Evaluates `st` at `now_ms` against a merged book whose cross spread is `spread`.
+    fn step(st: &mut RuleState, now_ms: u64, spread: f64) -> Option<AlertState> {
+        let (hl, pdx) = (OrderBook::new(Exchange::Hyperliquid, "ETH"), OrderBook::new(Exchange::Paradex, "ETH"));
+        let mut merged = MergedBook::default();
+        merged.signals.cross_spread = Some(spread);
+        let snap = Snapshot { hl: &hl, pdx: &pdx, merged: &merged, now_ms };
+        evaluate(st, &snap).map(|e| e.state)
+    }
+
+    #[test]
+    fn fires_only_after_holding_for_ms() {
+        let mut st = rule("cross_spread>5", 1_000);
+        assert_eq!(step(&mut st, 0, 6.0), None);
+        assert_eq!(step(&mut st, 999, 6.0), None);
+        assert_eq!(step(&mut st, 1_000, 6.0), Some(AlertState::Fired));
+        // Fires once, not on every tick it holds
+        assert_eq!(step(&mut st, 2_000, 7.0), None);
+
+        // A dip restarts the hold
+        let mut st = rule("cross_spread>5", 1_000);
+        assert_eq!(step(&mut st, 0, 6.0), None);
+        assert_eq!(step(&mut st, 500, 4.0), None);
+        assert_eq!(step(&mut st, 1_200, 6.0), None);
+        assert_eq!(step(&mut st, 2_200, 6.0), Some(AlertState::Fired));
+    }
+
+    #[test]
+    fn resolves_as_soon_as_it_stops_holding() {
+        let mut st = rule("cross_spread > 5", 0);
+        assert_eq!(step(&mut st, 0, 6.0), Some(AlertState::Fired));
+        assert_eq!(step(&mut st, 100, 5.0), Some(AlertState::Resolved));
+        assert_eq!(step(&mut st, 200, 5.0), None);
+        // And can fire again
+        assert_eq!(step(&mut st, 300, 6.0), Some(AlertState::Fired));
+    }
+}
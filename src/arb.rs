@@ -0,0 +1,207 @@
+// src/arb.rs — Arbitrage window detection over successive merged books
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::{mpsc, watch};
+use tokio::time::interval;
+
+use crate::merger::MergedBook;
+use crate::types::{now_ms, Exchange};
+
+/// How often an open window is re-checked when no new merge arrives.
+const SUSTAINED_CHECK_MS: u64 = 250;
+
+/// Windows kept by the arb log (oldest dropped first).
+const ARB_LOG_CAPACITY: usize = 500;
+
+// ─── Arb window ───────────────────────────────────────────────────────────────
+
+/// One contiguous period during which the cross-exchange spread stayed negative
+/// after taker fees.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArbWindow {
+    pub opened_ms: u64,
+    pub closed_ms: Option<u64>, // None while still open
+
+    /// Venue with the crossing bid / ask at the worst (most negative) point.
+    pub bid_exchange: Exchange,
+    pub ask_exchange: Exchange,
+
+    /// Cross spread at the worst (most negative net) point, gross and after
+    /// taker fees, and the prices at that point.
+    pub min_spread:     f64,
+    pub min_spread_pct: f64,
+    pub min_net_spread: f64,
+    pub bid_price:      f64,
+    pub ask_price:      f64,
+
+    /// Size executable at a profit after fees at the worst point, walking both
+    /// books, and the profit of executing it.
+    pub size:       f64,
+    pub profit_usd: f64,
+}
+
+impl ArbWindow {
+    /// Duration so far (open window) or total duration (closed window).
+    pub fn duration_ms(&self, now_ms: u64) -> u64 {
+        self.closed_ms.unwrap_or(now_ms).saturating_sub(self.opened_ms)
+    }
+
+    /// Human-readable multi-line summary used by the chat notifiers.
+    pub fn alert_text(&self, coin: &str, now_ms: u64) -> String {
+        format!(
+            "⚡ {coin} arbitrage open for {:.1}s\n\
+             Buy {} @ {:.2} / Sell {} @ {:.2}\n\
+             Spread: {:.4} ({:.4}%), net of fees {:.4}\n\
+             Size: {:.4} → est. profit ${:.2}",
+            self.duration_ms(now_ms) as f64 / 1000.0,
+            self.ask_exchange.short(), self.ask_price,
+            self.bid_exchange.short(), self.bid_price,
+            self.min_spread, self.min_spread_pct, self.min_net_spread,
+            self.size, self.profit_usd,
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ArbEvent {
+    Opened(ArbWindow),
+    Closed(ArbWindow),
+}
+
+// ─── Tracker ──────────────────────────────────────────────────────────────────
+
+/// Feed every merged book through `update` to get open/close transitions.
+#[derive(Debug, Default)]
+pub struct ArbTracker {
+    open: Option<ArbWindow>,
+}
+
+impl ArbTracker {
+    /// The window currently open, if any.
+    pub fn current(&self) -> Option<&ArbWindow> {
+        self.open.as_ref()
+    }
+
+    pub fn update(&mut self, now_ms: u64, merged: &MergedBook) -> Option<ArbEvent> {
+        let sample = Self::sample(now_ms, merged);
+
+        match (&mut self.open, sample) {
+            // Still crossed: keep the worst point
+            (Some(win), Some(s)) => {
+                if s.min_net_spread < win.min_net_spread {
+                    *win = ArbWindow { opened_ms: win.opened_ms, ..s };
+                }
+                None
+            }
+            (None, Some(s)) => {
+                self.open = Some(s.clone());
+                Some(ArbEvent::Opened(s))
+            }
+            (Some(_), None) => {
+                let mut win = self.open.take()?;
+                win.closed_ms = Some(now_ms);
+                Some(ArbEvent::Closed(win))
+            }
+            (None, None) => None,
+        }
+    }
+
+    /// An arb sample from the current book, or `None` if it isn't crossed after fees.
+    fn sample(now_ms: u64, merged: &MergedBook) -> Option<ArbWindow> {
+        let sig = &merged.signals;
+        if !sig.is_arb() {
+            return None;
+        }
+        let bid = merged.bids.first()?;
+        let ask = merged.asks.first()?;
+        Some(ArbWindow {
+            opened_ms:      now_ms,
+            closed_ms:      None,
+            bid_exchange:   bid.exchange.clone(),
+            ask_exchange:   ask.exchange.clone(),
+            min_spread:     sig.cross_spread?,
+            min_spread_pct: sig.cross_spread_pct.unwrap_or(0.0),
+            min_net_spread: sig.net_cross_spread?,
+            bid_price:      bid.price,
+            ask_price:      ask.price,
+            size:           sig.arb_size,
+            profit_usd:     sig.arb_profit_usd,
+        })
+    }
+}
+
+// ─── Sustained-arb watcher ────────────────────────────────────────────────────
+
+/// Spawns a task that yields each arb window once, as soon as it has stayed
+/// open for `min_duration_ms` with at least `min_size` executable.
+pub fn spawn_sustained_arb_watcher(
+    mut merged_rx: watch::Receiver<MergedBook>,
+    min_duration_ms: u64,
+    min_size: f64,
+) -> mpsc::Receiver<ArbWindow> {
+    let (tx, rx) = mpsc::channel(16);
+    tokio::spawn(async move {
+        let mut tracker = ArbTracker::default();
+        let mut ticker = interval(Duration::from_millis(SUSTAINED_CHECK_MS));
+        let mut alerted: Option<u64> = None; // opened_ms of the window already yielded
+
+        loop {
+            tokio::select! {
+                r = merged_rx.changed() => {
+                    if r.is_err() { break; }
+                    tracker.update(now_ms(), &merged_rx.borrow_and_update());
+                }
+                _ = ticker.tick() => {}
+            }
+
+            let Some(win) = tracker.current() else { continue };
+            if alerted == Some(win.opened_ms)
+                || win.duration_ms(now_ms()) < min_duration_ms
+                || win.size < min_size
+            {
+                continue;
+            }
+            alerted = Some(win.opened_ms);
+            if tx.send(win.clone()).await.is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+// ─── Arb log ──────────────────────────────────────────────────────────────────
+
+/// Spawns a task tracking every merge and publishing the detected windows,
+/// newest first. The open window (if any) is at the front and kept current.
+pub fn spawn_arb_log(mut merged_rx: watch::Receiver<MergedBook>) -> watch::Receiver<VecDeque<ArbWindow>> {
+    let (tx, rx) = watch::channel(VecDeque::new());
+    tokio::spawn(async move {
+        let mut tracker = ArbTracker::default();
+        while merged_rx.changed().await.is_ok() {
+            let event = tracker.update(now_ms(), &merged_rx.borrow_and_update());
+            match event {
+                Some(ArbEvent::Opened(win)) => tx.send_modify(|log| {
+                    log.push_front(win);
+                    log.truncate(ARB_LOG_CAPACITY);
+                }),
+                Some(ArbEvent::Closed(win)) => tx.send_modify(|log| match log.front_mut() {
+                    Some(front) => *front = win,
+                    None => log.push_front(win),
+                }),
+                // Still open: the worst point may have moved
+                None => if let Some(cur) = tracker.current() {
+                    tx.send_modify(|log| {
+                        if let Some(front) = log.front_mut() {
+                            *front = cur.clone();
+                        }
+                    });
+                },
+            }
+        }
+    });
+    rx
+}
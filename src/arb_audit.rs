@@ -0,0 +1,117 @@
+// src/arb_audit.rs — Append-only CSV / JSONL audit file of arbitrage windows
+
+use anyhow::{Context, Result};
+use serde_json::json;
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{oneshot, watch};
+use tracing::{error, info};
+
+use crate::arb::{ArbEvent, ArbTracker, ArbWindow};
+use crate::config::{ArbAuditConfig, ArbAuditFormat};
+use crate::merger::MergedBook;
+use crate::types::now_ms;
+
+const CSV_HEADER: &str = "coin,opened_ms,closed_ms,duration_ms,buy_exchange,buy_price,\
+sell_exchange,sell_price,min_spread,min_spread_pct,min_net_spread,size,profit_usd\n";
+
+// ─── Handle ───────────────────────────────────────────────────────────────────
+
+/// Owns the writer task. Call `shutdown()` on exit so a window still open at
+/// that moment is recorded (closed at shutdown time).
+pub struct ArbAudit {
+    stop_tx: oneshot::Sender<()>,
+    task:    tokio::task::JoinHandle<()>,
+}
+
+impl ArbAudit {
+    pub async fn shutdown(self) {
+        let _ = self.stop_tx.send(());
+        let _ = self.task.await;
+    }
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Opens the file for appending (so a bad path fails at startup) and writes one
+/// record per arb window as it closes.
+pub async fn spawn_arb_audit(
+    cfg: &ArbAuditConfig,
+    coin: &str,
+    mut merged_rx: watch::Receiver<MergedBook>,
+) -> Result<ArbAudit> {
+    if let Some(dir) = cfg.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("Cannot create audit directory '{}'", dir.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&cfg.path)
+        .await
+        .with_context(|| format!("Cannot open '{}' for appending", cfg.path.display()))?;
+
+    // New or empty CSV files get a header row
+    if cfg.format == ArbAuditFormat::Csv && file.metadata().await?.len() == 0 {
+        file.write_all(CSV_HEADER.as_bytes()).await?;
+    }
+
+    let format = cfg.format;
+    let coin = coin.to_string();
+    let (stop_tx, mut stop_rx) = oneshot::channel();
+    let task = tokio::spawn(async move {
+        let mut tracker = ArbTracker::default();
+        loop {
+            tokio::select! {
+                _ = &mut stop_rx => break,
+                r = merged_rx.changed() => {
+                    if r.is_err() { break; }
+                    let event = tracker.update(now_ms(), &merged_rx.borrow_and_update());
+                    if let Some(ArbEvent::Closed(win)) = event {
+                        write_record(&mut file, format, &coin, &win).await;
+                    }
+                }
+            }
+        }
+        if let Some(win) = tracker.current() {
+            let win = ArbWindow { closed_ms: Some(now_ms()), ..win.clone() };
+            write_record(&mut file, format, &coin, &win).await;
+        }
+    });
+
+    info!("[AUDIT] Appending arb windows to {}", cfg.path.display());
+    Ok(ArbAudit { stop_tx, task })
+}
+
+// ─── Records ──────────────────────────────────────────────────────────────────
+
+async fn write_record(file: &mut File, format: ArbAuditFormat, coin: &str, w: &ArbWindow) {
+    let line = match format {
+        ArbAuditFormat::Csv => csv_line(coin, w),
+        ArbAuditFormat::Jsonl => {
+            let mut record = json!({ "coin": coin, "duration_ms": w.duration_ms(now_ms()) });
+            if let (Some(obj), Ok(serde_json::Value::Object(win))) = (record.as_object_mut(), serde_json::to_value(w)) {
+                obj.extend(win);
+            }
+            format!("{record}\n")
+        }
+    };
+    // Flushed per record so the file is complete even after a crash
+    if let Err(e) = async { file.write_all(line.as_bytes()).await?; file.flush().await }.await {
+        error!("[AUDIT] Write failed: {e}");
+    }
+}
+
+fn csv_line(coin: &str, w: &ArbWindow) -> String {
+    format!(
+        "{coin},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+        w.opened_ms,
+        w.closed_ms.map(|t| t.to_string()).unwrap_or_default(),
+        w.duration_ms(now_ms()),
+        w.ask_exchange.short(), w.ask_price,
+        w.bid_exchange.short(), w.bid_price,
+        w.min_spread, w.min_spread_pct, w.min_net_spread,
+        w.size, w.profit_usd,
+    )
+}
@@ -0,0 +1,189 @@
+// src/arrow_sink.rs — Arrow IPC stream of normalised per-exchange book updates
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use arrow::array::{
+    ArrayRef, Float64Builder, StringBuilder, TimestampMillisecondBuilder, UInt16Builder,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::ipc::writer::StreamWriter;
+use arrow::record_batch::RecordBatch;
+use tokio::sync::{oneshot, watch};
+use tokio::time::interval;
+use tracing::{error, info};
+
+use crate::config::{ArrowIpcConfig, ArrowIpcTarget};
+use crate::types::{now_ms, Level, OrderBook};
+
+// ─── Handle ───────────────────────────────────────────────────────────────────
+
+/// Owns the collector task and writer thread. Call `shutdown()` on exit so the
+/// last rows are flushed and the stream gets its end-of-stream marker.
+pub struct ArrowSink {
+    stop_tx:   oneshot::Sender<()>,
+    collector: tokio::task::JoinHandle<()>,
+    thread:    JoinHandle<()>,
+}
+
+impl ArrowSink {
+    pub async fn shutdown(self) {
+        let _ = self.stop_tx.send(());
+        let _ = self.collector.await;
+        if self.thread.join().is_err() {
+            error!("[ARROW] Writer thread panicked");
+        }
+    }
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Opens the output (so a bad path fails at startup), collects one row per
+/// level on every book update, and writes a record batch every `flush_ms`.
+pub fn spawn_arrow_sink(
+    cfg: &ArrowIpcConfig,
+    mut hl_rx: watch::Receiver<Arc<OrderBook>>,
+    mut pdx_rx: watch::Receiver<Arc<OrderBook>>,
+) -> Result<ArrowSink> {
+    let out: Box<dyn Write + Send> = match &cfg.target {
+        ArrowIpcTarget::Stdout => Box::new(io::stdout()),
+        ArrowIpcTarget::File(path) => {
+            if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                fs::create_dir_all(dir)
+                    .with_context(|| format!("Cannot create Arrow output directory '{}'", dir.display()))?;
+            }
+            let file = File::create(path)
+                .with_context(|| format!("Cannot create '{}'", path.display()))?;
+            Box::new(BufWriter::new(file))
+        }
+    };
+    let mut writer = StreamWriter::try_new(out, &schema()).context("Failed to start Arrow IPC stream")?;
+
+    let (batch_tx, batch_rx) = mpsc::channel::<RecordBatch>();
+    let thread = thread::Builder::new()
+        .name("arrow-sink".into())
+        .spawn(move || {
+            // Ends when the collector drops its sender
+            while let Ok(batch) = batch_rx.recv() {
+                if let Err(e) = writer.write(&batch).and_then(|_| writer.flush()) {
+                    error!("[ARROW] Write failed: {e}");
+                    return;
+                }
+            }
+            if let Err(e) = writer.finish() {
+                error!("[ARROW] Failed to finish stream: {e}");
+            }
+        })
+        .context("Failed to spawn Arrow writer thread")?;
+
+    let (stop_tx, mut stop_rx) = oneshot::channel();
+    let flush = Duration::from_millis(cfg.flush_ms);
+    let collector = tokio::spawn(async move {
+        let mut ticker = interval(flush);
+        let mut rows = BatchBuilder::default();
+        loop {
+            tokio::select! {
+                _ = &mut stop_rx => break,
+                _ = ticker.tick() => {
+                    if let Some(batch) = rows.finish()
+                        && batch_tx.send(batch).is_err()
+                    {
+                        return;
+                    }
+                }
+                r = hl_rx.changed() => {
+                    if r.is_err() { break; }
+                    rows.push_book(&hl_rx.borrow_and_update());
+                }
+                r = pdx_rx.changed() => {
+                    if r.is_err() { break; }
+                    rows.push_book(&pdx_rx.borrow_and_update());
+                }
+            }
+        }
+        if let Some(batch) = rows.finish() {
+            let _ = batch_tx.send(batch);
+        }
+    });
+
+    match &cfg.target {
+        ArrowIpcTarget::Stdout     => info!("[ARROW] Streaming to stdout"),
+        ArrowIpcTarget::File(path) => info!("[ARROW] Streaming to {}", path.display()),
+    }
+    Ok(ArrowSink { stop_tx, collector, thread })
+}
+
+// ─── Schema + batch building ──────────────────────────────────────────────────
+
+/// Long format: one row per level per book update.
+fn schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("ts", DataType::Timestamp(TimeUnit::Millisecond, None), false),      // exchange time
+        Field::new("recv_ts", DataType::Timestamp(TimeUnit::Millisecond, None), false), // local time
+        Field::new("exchange", DataType::Utf8, false), // "HL" | "PDX"
+        Field::new("coin", DataType::Utf8, false),
+        Field::new("side", DataType::Utf8, false),     // "bid" | "ask"
+        Field::new("level", DataType::UInt16, false),  // 0 = best
+        Field::new("price", DataType::Float64, false),
+        Field::new("size", DataType::Float64, false),
+    ]))
+}
+
+#[derive(Default)]
+struct BatchBuilder {
+    ts:       TimestampMillisecondBuilder,
+    recv_ts:  TimestampMillisecondBuilder,
+    exchange: StringBuilder,
+    coin:     StringBuilder,
+    side:     StringBuilder,
+    level:    UInt16Builder,
+    price:    Float64Builder,
+    size:     Float64Builder,
+    rows:     usize,
+}
+
+impl BatchBuilder {
+    fn push_book(&mut self, book: &OrderBook) {
+        let recv = now_ms();
+        self.push_side(book, recv, "bid", &book.bids);
+        self.push_side(book, recv, "ask", &book.asks);
+    }
+
+    fn push_side(&mut self, book: &OrderBook, recv_ms: u64, side: &str, levels: &[Level]) {
+        for (i, lvl) in levels.iter().enumerate() {
+            self.ts.append_value(book.last_update_ms as i64);
+            self.recv_ts.append_value(recv_ms as i64);
+            self.exchange.append_value(book.exchange.short());
+            self.coin.append_value(&book.coin);
+            self.side.append_value(side);
+            self.level.append_value(i as u16);
+            self.price.append_value(lvl.price_f64());
+            self.size.append_value(lvl.size_f64());
+            self.rows += 1;
+        }
+    }
+
+    /// Drain the builders into a record batch (`None` if there are no rows).
+    fn finish(&mut self) -> Option<RecordBatch> {
+        if self.rows == 0 {
+            return None;
+        }
+        self.rows = 0;
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(self.ts.finish()),
+            Arc::new(self.recv_ts.finish()),
+            Arc::new(self.exchange.finish()),
+            Arc::new(self.coin.finish()),
+            Arc::new(self.side.finish()),
+            Arc::new(self.level.finish()),
+            Arc::new(self.price.finish()),
+            Arc::new(self.size.finish()),
+        ];
+        RecordBatch::try_new(schema(), columns).ok()
+    }
+}
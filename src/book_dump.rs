@@ -0,0 +1,36 @@
+// src/book_dump.rs — Both venue books and the merged book, written to a JSON file
+//
+// `o` in the TUI captures the books on screen (the frozen ones while paused)
+// with the signals computed from them, so an odd moment can be looked at
+// later or attached to a bug report. The fields are those of the `/book/*`
+// HTTP routes.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::merger::MergedBook;
+use crate::types::OrderBook;
+
+/// Writes `{dir}/book_<pair>_<timestamp>.json` and returns its path.
+pub fn write(dir: &Path, pair: &str, hl: &OrderBook, pdx: &OrderBook, merged: &MergedBook) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("Cannot create '{}'", dir.display()))?;
+    let now = Utc::now();
+    let path = dir.join(format!("book_{pair}_{}.json", now.format("%Y%m%d_%H%M%S_%3f")));
+    let file = File::create(&path).with_context(|| format!("Cannot create '{}'", path.display()))?;
+    let mut out = BufWriter::new(file);
+    let dump = serde_json::json!({
+        "ts":     now.to_rfc3339(),
+        "pair":   pair,
+        "hl":     hl,
+        "pdx":    pdx,
+        "merged": merged, // levels, with the signals under "signals"
+    });
+    serde_json::to_writer_pretty(&mut out, &dump)?;
+    writeln!(out)?;
+    out.flush()?;
+    Ok(path)
+}
@@ -0,0 +1,39 @@
+// src/breaker.rs — Reconnect circuit breaker shared by the market data feeds
+//
+// A connection that ends without delivering a single book counts as a failed
+// reconnect. After `max_failures` in a row the feed is degraded: it keeps
+// retrying, but only every `degraded_delay_secs`, until a connection delivers.
+
+use std::time::Duration;
+
+use crate::config::ReconnectConfig;
+
+/// Delay before reconnecting while the feed is healthy.
+const RECONNECT_DELAY_SECS: u64 = 3;
+
+#[derive(Debug)]
+pub struct Breaker {
+    cfg:      ReconnectConfig,
+    failures: u32,
+}
+
+impl Breaker {
+    pub fn new(cfg: ReconnectConfig) -> Self {
+        Self { cfg, failures: 0 }
+    }
+
+    /// Records how a connection ended and returns the delay before the next one.
+    pub fn record(&mut self, delivered: bool) -> Duration {
+        self.failures = if delivered { 0 } else { self.failures + 1 };
+        Duration::from_secs(if self.degraded() { self.cfg.degraded_delay_secs } else { RECONNECT_DELAY_SECS })
+    }
+
+    /// Failed reconnects in a row.
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    pub fn degraded(&self) -> bool {
+        self.failures >= self.cfg.max_failures
+    }
+}
@@ -0,0 +1,77 @@
+// src/candles.rs — OHLC candles of the merged mid at 1s / 5s / 1m
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+use tokio::sync::watch;
+
+use crate::merger::MergedBook;
+use crate::types::now_ms;
+
+/// Candle lengths built side by side.
+pub const INTERVALS_MS: [u64; 3] = [1_000, 5_000, 60_000];
+/// Candles kept per interval (oldest dropped first).
+const MAX_CANDLES: usize = 300;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Candle {
+    pub start_ms: u64,
+    pub open:     f64,
+    pub high:     f64,
+    pub low:      f64,
+    pub close:    f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CandleSeries {
+    pub interval_ms: u64,
+    pub candles:     VecDeque<Candle>, // oldest first; the last one is still forming
+}
+
+impl CandleSeries {
+    fn record(&mut self, ts_ms: u64, px: f64) {
+        let start_ms = ts_ms - ts_ms % self.interval_ms;
+        match self.candles.back_mut() {
+            Some(c) if c.start_ms == start_ms => {
+                c.high = c.high.max(px);
+                c.low = c.low.min(px);
+                c.close = px;
+            }
+            _ => {
+                self.candles.push_back(Candle { start_ms, open: px, high: px, low: px, close: px });
+                if self.candles.len() > MAX_CANDLES {
+                    self.candles.pop_front();
+                }
+            }
+        }
+    }
+}
+
+/// Spawns a task folding every merged mid into candles at each of `INTERVALS_MS`.
+/// Returns one series per interval, in the same order.
+pub fn spawn_candles(mut merged_rx: watch::Receiver<MergedBook>) -> watch::Receiver<Vec<CandleSeries>> {
+    let series: Vec<CandleSeries> = INTERVALS_MS.iter()
+        .map(|&interval_ms| CandleSeries { interval_ms, candles: VecDeque::new() })
+        .collect();
+    let (tx, rx) = watch::channel(series);
+    tokio::spawn(async move {
+        let mut coins = Default::default();
+        while merged_rx.changed().await.is_ok() {
+            let mid = {
+                let merged = merged_rx.borrow_and_update();
+                if merged.pair_changed(&mut coins) {
+                    tx.send_modify(|all| all.iter_mut().for_each(|s| s.candles.clear()));
+                }
+                match (merged.bids.first(), merged.asks.first()) {
+                    (Some(b), Some(a)) => Some((b.price + a.price) / 2.0),
+                    _ => None,
+                }
+            };
+            if let Some(mid) = mid {
+                let now = now_ms();
+                tx.send_modify(|all| all.iter_mut().for_each(|s| s.record(now, mid)));
+            }
+        }
+    });
+    rx
+}
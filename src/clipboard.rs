@@ -0,0 +1,70 @@
+// src/clipboard.rs — Copy the merged quote to the system clipboard
+//
+// `c`, `m` and `v` put the merged BBO, the mid or the cross spread on the
+// clipboard as plain text, for pasting into chat or a ticket. The clipboard is
+// opened on the first copy and kept open: on X11 the copied text only lasts as
+// long as the program that owns it.
+
+use anyhow::{Context, Result};
+
+use crate::merger::MergedBook;
+
+/// What a copy key puts on the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quote {
+    Bbo,    // best bid / best ask, with their venues
+    Mid,    // halfway between them
+    Spread, // best ask − best bid, in $ and %
+}
+
+impl Quote {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Quote::Bbo    => "BBO",
+            Quote::Mid    => "mid",
+            Quote::Spread => "cross spread",
+        }
+    }
+
+    /// The text copied for `pair`, or None while either side of the merged
+    /// book is empty. Prices are printed as the venues quote them, the mid to
+    /// one decimal more than the finer of the two.
+    pub fn text(&self, pair: &str, merged: &MergedBook) -> Option<String> {
+        let (bid, ask) = (merged.bids.first()?, merged.asks.first()?);
+        Some(match self {
+            Quote::Bbo => format!(
+                "{pair} bid {} ({}) / ask {} ({})",
+                bid.price, bid.exchange.short(), ask.price, ask.exchange.short(),
+            ),
+            Quote::Mid => {
+                let dp = decimals(bid.price).max(decimals(ask.price)) + 1;
+                format!("{pair} mid {:.dp$}", (bid.price + ask.price) / 2.0)
+            }
+            Quote::Spread => {
+                let sig = &merged.signals;
+                format!("{pair} cross spread {:.4} ({:.4}%)", sig.cross_spread?, sig.cross_spread_pct?)
+            }
+        })
+    }
+}
+
+/// Decimal places in the shortest form of `price`.
+fn decimals(price: f64) -> usize {
+    price.to_string().split_once('.').map_or(0, |(_, frac)| frac.len())
+}
+
+/// The system clipboard, opened on first use.
+#[derive(Default)]
+pub struct Clipboard {
+    inner: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    pub fn copy(&mut self, text: String) -> Result<()> {
+        let clipboard = match &mut self.inner {
+            Some(c) => c,
+            None => self.inner.insert(arboard::Clipboard::new().context("no clipboard available")?),
+        };
+        clipboard.set_text(text).context("clipboard write failed")
+    }
+}
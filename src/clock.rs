@@ -0,0 +1,122 @@
+// src/clock.rs — Local clock offset per venue, estimated from REST server time
+//
+// Each poll brackets a server timestamp between a local send and receive time
+// and takes the midpoint as the moment it was stamped (as NTP does). Of the
+// last few samples the one with the shortest round trip is trusted, since it
+// leaves the least room for asymmetric network delay.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use tokio::sync::watch;
+use tokio::time::interval;
+use tracing::{debug, info, warn};
+
+use crate::config::ClockSyncConfig;
+use crate::types::{now_ms, Exchange};
+
+const HL_INFO_URL: &str = "https://api.hyperliquid.xyz/info";
+const PDX_TIME_URL: &str = "https://api.prod.paradex.trade/v1/system/time";
+/// Samples considered when picking the best estimate.
+const MAX_SAMPLES: usize = 8;
+
+/// Venue clock minus local clock in ms; add it to a local time to get venue
+/// time, subtract it from a venue timestamp to get local time.
+pub type ClockOffset = Option<i64>;
+
+/// Venue timestamp converted to the local clock.
+pub fn to_local_ms(exchange_ts_ms: u64, offset: ClockOffset) -> u64 {
+    (exchange_ts_ms as i64 - offset.unwrap_or(0)).max(0) as u64
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Spawns one poller per venue; returns `(hl_rx, pdx_rx)`. Both stay `None`
+/// until the first successful poll, and a failed poll keeps the last estimate.
+pub fn spawn_clock_sync(
+    cfg: &ClockSyncConfig,
+    hl_coin: String,
+) -> (watch::Receiver<ClockOffset>, watch::Receiver<ClockOffset>) {
+    let http = reqwest::Client::new();
+    let every = Duration::from_secs(cfg.poll_secs);
+    info!("[CLOCK] Polling server time every {}s", cfg.poll_secs);
+    (
+        spawn_poller(Exchange::Hyperliquid, every, http.clone(), hl_coin),
+        spawn_poller(Exchange::Paradex, every, http, String::new()),
+    )
+}
+
+fn spawn_poller(
+    exchange: Exchange,
+    every: Duration,
+    http: reqwest::Client,
+    coin: String,
+) -> watch::Receiver<ClockOffset> {
+    let (tx, rx) = watch::channel(None);
+    tokio::spawn(async move {
+        let mut samples: VecDeque<(u64, i64)> = VecDeque::new(); // (rtt, offset)
+        let mut ticker = interval(every);
+        loop {
+            ticker.tick().await;
+            let sent_ms = now_ms();
+            let polled = match exchange {
+                Exchange::Hyperliquid => poll_hl(&http, &coin).await,
+                Exchange::Paradex     => poll_pdx(&http).await,
+            };
+            let recv_ms = now_ms();
+            let server_ms = match polled {
+                Ok(ms) => ms,
+                Err(e) => { warn!("[CLOCK] {} poll failed: {e:#}", exchange.short()); continue; }
+            };
+
+            let rtt = recv_ms.saturating_sub(sent_ms);
+            let offset = server_ms as i64 - (sent_ms + rtt / 2) as i64;
+            if samples.len() == MAX_SAMPLES {
+                samples.pop_front();
+            }
+            samples.push_back((rtt, offset));
+            let best = samples.iter().min_by_key(|(rtt, _)| *rtt).map(|(_, o)| *o);
+            debug!("[CLOCK] {} sample {offset:+}ms (rtt {rtt}ms)", exchange.short());
+            tx.send_replace(best);
+            if tx.is_closed() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+// ─── Venue requests ───────────────────────────────────────────────────────────
+
+/// Hyperliquid has no time endpoint; an `l2Book` snapshot carries the time it
+/// was taken, which is as close to "now" on their side as a response gets.
+async fn poll_hl(http: &reqwest::Client, coin: &str) -> Result<u64> {
+    let resp: Value = http
+        .post(HL_INFO_URL)
+        .json(&serde_json::json!({ "type": "l2Book", "coin": coin }))
+        .send()
+        .await
+        .context("request failed")?
+        .json()
+        .await
+        .context("unparseable response")?;
+
+    resp["time"].as_u64().context("no time")
+}
+
+/// `{server_time: "1681493415023"}`
+async fn poll_pdx(http: &reqwest::Client) -> Result<u64> {
+    let resp: Value = http
+        .get(PDX_TIME_URL)
+        .send()
+        .await
+        .context("request failed")?
+        .json()
+        .await
+        .context("unparseable response")?;
+
+    let t = &resp["server_time"];
+    t.as_u64().or_else(|| t.as_str().and_then(|s| s.parse().ok())).context("no server_time")
+}
@@ -1,8 +1,20 @@
 // src/config.rs — Load and validate config.toml at startup
 
 use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use rust_decimal::Decimal;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use crate::alerts::{Condition, SinkKind};
+use crate::merger::{MergeMode, MergePolicy, QuoteRates};
+use crate::paradex_mda::MAX_BOOK_DEPTH;
+use crate::sim::SimAction;
+use crate::theme::Theme;
+use crate::types::Exchange;
 
 const CONFIG_PATH: &str = "config.toml";
 
@@ -10,20 +22,752 @@ const CONFIG_PATH: &str = "config.toml";
 
 #[derive(Deserialize, Debug)]
 struct RawConfig {
-    pair:    RawPair,
-    display: RawDisplay,
+    pair:      RawPair,
+    #[serde(default)]
+    pairs:     IndexMap<String, RawPairEntry>, // kept in file order
+    display:   RawDisplay,
+    #[serde(default)]
+    fees:      RawFees,
+    #[serde(default)]
+    risk:      RawRisk,
+    #[serde(default)]
+    http:      RawHttp,
+    #[serde(default)]
+    ws_server: RawWsServer,
+    #[serde(default)]
+    grpc:      RawGrpc,
+    #[serde(default)]
+    nats:      RawNats,
+    #[serde(default)]
+    redis:     RawRedis,
+    #[serde(default)]
+    parquet:   RawParquet,
+    #[serde(default)]
+    sqlite:    RawSqlite,
+    #[serde(default)]
+    arrow_ipc: RawArrowIpc,
+    #[serde(default)]
+    arb_audit: RawArbAudit,
+    #[serde(default)]
+    telegram:  RawTelegram,
+    #[serde(default)]
+    slack:     RawSlack,
+    #[serde(default)]
+    sim:       RawSim,
+    #[serde(default)]
+    alerts:    RawAlerts,
+    #[serde(default)]
+    desktop_notify: RawDesktopNotify,
+    #[serde(default)]
+    hl_exec:   RawHlExec,
+    #[serde(default)]
+    pdx_exec:  RawPdxExec,
+    #[serde(default)]
+    market_stats: RawMarketStats,
+    #[serde(default)]
+    cvd:       RawCvd,
+    #[serde(default)]
+    spread_stats: RawSpreadStats,
+    #[serde(default)]
+    volatility: RawVolatility,
+    #[serde(default)]
+    clock_sync: RawClockSync,
+    #[serde(default)]
+    reconnect: RawReconnect,
+    #[serde(default)]
+    logging:   RawLogging,
+    #[serde(default)]
+    otel:      RawOtel,
+    #[serde(default)]
+    quarantine: RawQuarantine,
+    #[serde(default)]
+    socket:    RawSocket,
+    #[serde(default)]
+    merge:     RawMerge,
+    #[serde(default)]
+    book:      RawBook,
+    #[serde(default)]
+    quote:     RawQuote,
+    #[serde(default)]
+    theme:     RawTheme,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawTheme {
+    preset: String,
+    #[serde(flatten)]
+    colors: BTreeMap<String, String>, // field → colour, over the preset
+}
+
+impl Default for RawTheme {
+    fn default() -> Self {
+        Self { preset: "dark".to_string(), colors: BTreeMap::new() }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawQuote {
+    unit:  String,
+    hl:    String,
+    pdx:   String,
+    rates: BTreeMap<String, f64>, // currency → value of one unit in `unit`
+}
+
+impl Default for RawQuote {
+    fn default() -> Self {
+        Self {
+            unit:  "USD".to_string(),
+            hl:    "USDC".to_string(),
+            pdx:   "USD".to_string(),
+            rates: BTreeMap::from([("USDC".to_string(), 1.0)]),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawBook {
+    hl_depth:  usize,
+    pdx_depth: usize,
+    hl_sig_figs: u32,
+    hl_mantissa: u32,
+    bbo:         bool,
+}
+
+impl Default for RawBook {
+    fn default() -> Self {
+        Self { hl_depth: 20, pdx_depth: 15, hl_sig_figs: 0, hl_mantissa: 0, bbo: true }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawMerge {
+    mode:  String,
+    depth: Option<usize>, // falls back to [display] depth
+    group: f64,
+    min_notional: f64,
+    outlier_band_pct: f64,
+}
+
+impl Default for RawMerge {
+    fn default() -> Self {
+        Self { mode: "tagged".to_string(), depth: None, group: 0.0, min_notional: 0.0, outlier_band_pct: 0.0 }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawReconnect {
+    max_failures:        u32,
+    degraded_delay_secs: u64,
+}
+
+impl Default for RawReconnect {
+    fn default() -> Self {
+        Self { max_failures: 5, degraded_delay_secs: 60 }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawLogging {
+    enabled:     bool,
+    dir:         String,
+    rotation:    String,
+    max_size_mb: u64,
+    max_files:   usize,
+    format:      String,
+}
+
+impl Default for RawLogging {
+    fn default() -> Self {
+        Self {
+            enabled:     false,
+            dir:         "logs".to_string(),
+            rotation:    "daily".to_string(),
+            max_size_mb: 100,
+            max_files:   7,
+            format:      "text".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawOtel {
+    enabled:       bool,
+    endpoint:      String,
+    service_name:  String,
+    message_ratio: f64,
+}
+
+impl Default for RawOtel {
+    fn default() -> Self {
+        Self {
+            enabled:       false,
+            endpoint:      "http://127.0.0.1:4318".to_string(),
+            service_name:  "obt".to_string(),
+            message_ratio: 0.01,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawSocket {
+    tcp_nodelay:     bool,
+    recv_buffer_kb:  u32,
+    write_buffer_kb: usize,
+    max_message_mb:  usize,
+    max_frame_mb:    usize,
+}
+
+/// tungstenite's own defaults, and the OS default receive buffer.
+impl Default for RawSocket {
+    fn default() -> Self {
+        Self { tcp_nodelay: false, recv_buffer_kb: 0, write_buffer_kb: 128, max_message_mb: 64, max_frame_mb: 16 }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawQuarantine {
+    max_payloads: usize,
+    dir:          String,
+}
+
+impl Default for RawQuarantine {
+    fn default() -> Self {
+        Self { max_payloads: 50, dir: "data/quarantine".to_string() }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawClockSync {
+    enabled:   bool,
+    poll_secs: u64,
+}
+
+impl Default for RawClockSync {
+    fn default() -> Self {
+        Self { enabled: false, poll_secs: 60 }
+    }
 }
 
 #[derive(Deserialize, Debug)]
 struct RawPair {
+    #[serde(default)]
+    name:       String, // a [pairs] entry, instead of the symbols
+    #[serde(default)]
     hl_symbol:  String,
-    pdx_symbol: String,
+    #[serde(default)]
+    pdx_symbol: String, // empty = derived from hl_symbol at startup
+}
+
+/// One `[pairs]` entry: `ETH = { hl = "ETH", pdx = "ETH-USD-PERP" }`.
+#[derive(Deserialize, Debug)]
+struct RawPairEntry {
+    hl:  String,
+    #[serde(default)]
+    pdx: String, // empty = derived from hl at startup
 }
 
 #[derive(Deserialize, Debug)]
 struct RawDisplay {
-    depth:   usize,
-    tick_ms: u64,
+    #[serde(default)]
+    depth:     Option<usize>, // before [merge] depth existed
+    tick_ms:   u64,
+    #[serde(default)]
+    max_tick_ms: Option<u64>,
+    #[serde(default)]
+    arb_bell:  bool,
+    #[serde(default)]
+    arb_flash: bool,
+    #[serde(default)]
+    liquidations: bool,
+    #[serde(default)]
+    trade_tape: bool,
+    #[serde(default)]
+    vwap_notional: f64,
+    #[serde(default = "default_liquidity_bands")]
+    liquidity_bands_bps: Vec<f64>,
+    #[serde(default)]
+    sweep_size: f64,
+    #[serde(default = "default_spread_history_secs")]
+    spread_history_secs: u64,
+    #[serde(default = "default_stale_ms")]
+    stale_ms: u64,
+    #[serde(default = "default_age_warn_ms")]
+    age_warn_ms: u64,
+    #[serde(default = "default_age_alert_ms")]
+    age_alert_ms: u64,
+    #[serde(default)]
+    venue_depth: RawPanelDepth,
+    #[serde(default)]
+    hl_venue_depth: Option<RawPanelDepth>, // falls back to venue_depth
+    #[serde(default)]
+    pdx_venue_depth: Option<RawPanelDepth>,
+    #[serde(default = "default_narrow_width")]
+    narrow_width: u16,
+    #[serde(default = "default_dump_dir")]
+    dump_dir: String,
+    #[serde(default)]
+    carousel: bool,
+    #[serde(default = "default_carousel_secs")]
+    carousel_secs: u64,
+}
+
+fn default_liquidity_bands() -> Vec<f64> {
+    vec![5.0, 10.0, 25.0]
+}
+
+fn default_spread_history_secs() -> u64 {
+    60
+}
+
+fn default_stale_ms() -> u64 {
+    5_000
+}
+
+fn default_age_warn_ms() -> u64 {
+    1_000
+}
+
+fn default_age_alert_ms() -> u64 {
+    3_000
+}
+
+/// A panel depth as written: one count for both sides, or `{ bids, asks }`.
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(untagged)]
+enum RawPanelDepth {
+    Both(usize),
+    Sides {
+        #[serde(default)]
+        bids: usize,
+        #[serde(default)]
+        asks: usize,
+    },
+}
+
+impl Default for RawPanelDepth {
+    fn default() -> Self {
+        RawPanelDepth::Both(0)
+    }
+}
+
+impl From<RawPanelDepth> for PanelDepth {
+    fn from(raw: RawPanelDepth) -> Self {
+        match raw {
+            RawPanelDepth::Both(n) => PanelDepth { bids: n, asks: n },
+            RawPanelDepth::Sides { bids, asks } => PanelDepth { bids, asks },
+        }
+    }
+}
+
+fn default_narrow_width() -> u16 {
+    120
+}
+
+fn default_dump_dir() -> String {
+    "data/dumps".to_string()
+}
+
+fn default_carousel_secs() -> u64 {
+    30
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawFees {
+    hl_maker_bps:  f64,
+    hl_taker_bps:  f64,
+    pdx_maker_bps: f64,
+    pdx_taker_bps: f64,
+}
+
+impl Default for RawFees {
+    fn default() -> Self {
+        Self { hl_maker_bps: 1.5, hl_taker_bps: 4.5, pdx_maker_bps: 0.0, pdx_taker_bps: 2.0 }
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct RawRisk {
+    max_position:   f64,
+    max_order_size: f64,
+    max_daily_loss: f64,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawHttp {
+    enabled:           bool,
+    bind:              String,
+    health_max_age_ms: u64,
+}
+
+impl Default for RawHttp {
+    fn default() -> Self {
+        Self {
+            enabled:           false,
+            bind:              "127.0.0.1:9090".to_string(),
+            health_max_age_ms: 5_000,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawWsServer {
+    enabled: bool,
+    bind:    String,
+}
+
+impl Default for RawWsServer {
+    fn default() -> Self {
+        Self { enabled: false, bind: "127.0.0.1:9091".to_string() }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawGrpc {
+    enabled: bool,
+    bind:    String,
+}
+
+impl Default for RawGrpc {
+    fn default() -> Self {
+        Self { enabled: false, bind: "127.0.0.1:50051".to_string() }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawNats {
+    enabled: bool,
+    url:     String,
+}
+
+impl Default for RawNats {
+    fn default() -> Self {
+        Self { enabled: false, url: "nats://127.0.0.1:4222".to_string() }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawRedis {
+    enabled:    bool,
+    url:        String,
+    key_prefix: String,
+}
+
+impl Default for RawRedis {
+    fn default() -> Self {
+        Self {
+            enabled:    false,
+            url:        "redis://127.0.0.1:6379".to_string(),
+            key_prefix: "obt".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawParquet {
+    enabled:     bool,
+    dir:         String,
+    interval_ms: u64,
+    batch_ticks: usize,
+    rotate_secs: u64,
+}
+
+impl Default for RawParquet {
+    fn default() -> Self {
+        Self {
+            enabled:     false,
+            dir:         "data/parquet".to_string(),
+            interval_ms: 1_000,
+            batch_ticks: 60,
+            rotate_secs: 3_600,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawSqlite {
+    enabled:     bool,
+    path:        String,
+    interval_ms: u64,
+}
+
+impl Default for RawSqlite {
+    fn default() -> Self {
+        Self { enabled: false, path: "data/obt.sqlite".to_string(), interval_ms: 1_000 }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawArrowIpc {
+    enabled:  bool,
+    path:     String,
+    flush_ms: u64,
+}
+
+impl Default for RawArrowIpc {
+    fn default() -> Self {
+        Self { enabled: false, path: "data/book_updates.arrows".to_string(), flush_ms: 1_000 }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawArbAudit {
+    enabled: bool,
+    path:    String,
+    format:  String,
+}
+
+impl Default for RawArbAudit {
+    fn default() -> Self {
+        Self { enabled: false, path: "data/arb_audit.csv".to_string(), format: "csv".to_string() }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawTelegram {
+    enabled:         bool,
+    bot_token:       String,
+    chat_id:         String,
+    min_duration_ms: u64,
+    min_size:        f64,
+}
+
+impl Default for RawTelegram {
+    fn default() -> Self {
+        Self {
+            enabled:         false,
+            bot_token:       String::new(),
+            chat_id:         String::new(),
+            min_duration_ms: 1_000,
+            min_size:        0.0,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawSlack {
+    enabled:           bool,
+    webhook_url:       String,
+    min_duration_ms:   u64,
+    min_size:          f64,
+    min_interval_secs: u64,
+}
+
+impl Default for RawSlack {
+    fn default() -> Self {
+        Self {
+            enabled:           false,
+            webhook_url:       String::new(),
+            min_duration_ms:   1_000,
+            min_size:          0.0,
+            min_interval_secs: 60,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawSim {
+    enabled:    bool,
+    order_size: f64,
+}
+
+impl Default for RawSim {
+    fn default() -> Self {
+        Self { enabled: false, order_size: 0.01 }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawDesktopNotify {
+    enabled:         bool,
+    min_duration_ms: u64,
+    min_size:        f64,
+    feeds_down_ms:   u64,
+}
+
+impl Default for RawDesktopNotify {
+    fn default() -> Self {
+        Self { enabled: false, min_duration_ms: 1_000, min_size: 0.0, feeds_down_ms: 10_000 }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawMarketStats {
+    enabled:   bool,
+    poll_secs: u64,
+}
+
+impl Default for RawMarketStats {
+    fn default() -> Self {
+        Self { enabled: false, poll_secs: 15 }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawCvd {
+    enabled:      bool,
+    windows_secs: Vec<u64>,
+}
+
+impl Default for RawCvd {
+    fn default() -> Self {
+        Self { enabled: false, windows_secs: vec![60, 300, 900] }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawSpreadStats {
+    enabled:     bool,
+    window_secs: u64,
+}
+
+impl Default for RawSpreadStats {
+    fn default() -> Self {
+        Self { enabled: false, window_secs: 300 }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct RawVolatility {
+    enabled:      bool,
+    windows_secs: Vec<u64>,
+}
+
+impl Default for RawVolatility {
+    fn default() -> Self {
+        Self { enabled: false, windows_secs: vec![60, 300] }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct RawHlExec {
+    enabled:         bool,
+    private_key:     String,
+    account_address: String,
+    testnet:         bool,
+    order_size:      f64,
+    tif:             String,
+}
+
+impl Default for RawHlExec {
+    fn default() -> Self {
+        Self {
+            enabled:         false,
+            private_key:     String::new(),
+            account_address: String::new(),
+            testnet:         false,
+            order_size:      0.01,
+            tif:             "Ioc".to_string(),
+        }
+    }
+}
+
+// Hand-written so the private key never ends up in logs
+impl std::fmt::Debug for RawHlExec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawHlExec")
+            .field("enabled", &self.enabled)
+            .field("account_address", &self.account_address)
+            .field("testnet", &self.testnet)
+            .field("order_size", &self.order_size)
+            .field("tif", &self.tif)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct RawPdxExec {
+    enabled:         bool,
+    account_address: String,
+    private_key:     String,
+    eth_address:     String,
+    testnet:         bool,
+    allow_mainnet:   bool,
+    order_size:      f64,
+    instruction:     String,
+}
+
+impl Default for RawPdxExec {
+    fn default() -> Self {
+        Self {
+            enabled:         false,
+            account_address: String::new(),
+            private_key:     String::new(),
+            eth_address:     String::new(),
+            testnet:         false,
+            allow_mainnet:   false,
+            order_size:      0.01,
+            instruction:     "IOC".to_string(),
+        }
+    }
+}
+
+// Hand-written so the private key never ends up in logs
+impl std::fmt::Debug for RawPdxExec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawPdxExec")
+            .field("enabled", &self.enabled)
+            .field("account_address", &self.account_address)
+            .field("testnet", &self.testnet)
+            .field("allow_mainnet", &self.allow_mainnet)
+            .field("order_size", &self.order_size)
+            .field("instruction", &self.instruction)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct RawAlerts {
+    enabled:     bool,
+    webhook_url: String,
+    rules:       Vec<RawAlertRule>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RawAlertRule {
+    name:      String,
+    when:      String,
+    #[serde(default)]
+    for_ms:    u64,
+    #[serde(default = "default_alert_sinks")]
+    sinks:     Vec<String>,
+    #[serde(default)]
+    sim_order: Option<String>,
+}
+
+fn default_alert_sinks() -> Vec<String> {
+    vec!["log".to_string(), "banner".to_string()]
 }
 
 // ─── Validated config (used by the rest of the app) ──────────────────────────
@@ -31,9 +775,397 @@ struct RawDisplay {
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     pub hl_symbol:  String, // e.g. "BTC"
-    pub pdx_symbol: String, // e.g. "BTC-USD-PERP"
-    pub depth:      usize,  // 1..=10
-    pub tick_ms:    u64,    // 50..=1000
+    pub pdx_symbol: String, // e.g. "BTC-USD-PERP"; empty until `resolve_pdx_symbol`
+    pub pair_name:  String, // canonical name of the pair, e.g. "BTC"
+    pub pairs:      Vec<PairConfig>, // [pairs] in file order, plus the [pair] one when it isn't listed
+    pub merge:      MergePolicy, // merged-book depth, mode and price buckets at startup
+    pub quote_unit: String, // currency merged prices and spreads are in
+    pub hl_depth:   usize,  // levels per side each feed keeps, 1..=20
+    pub pdx_depth:  usize,  // 1..=15
+    pub venue_depth: (PanelDepth, PanelDepth), // (HL, PDX) rows in the venue panels
+    pub narrow_width: u16,  // terminal columns below which the body panels become tabs; 0 = never
+    pub dump_dir:   PathBuf, // where `o` writes the books
+    pub carousel:   bool,    // rotate through the pairs from startup, rather than from `r`
+    pub carousel_secs: u64,  // seconds on each pair while rotating
+    pub theme:      Theme,  // TUI colours: the preset with any overrides applied
+    pub hl_aggregation: HlAggregation, // l2Book levels pre-aggregated by Hyperliquid
+    pub bbo:        bool,   // subscribe to both venues' BBO channels for the touch
+    pub tick_ms:    u64,    // 50..=2000; shortest render interval, used during bursts
+    pub max_tick_ms: u64,   // longest render interval, used when the feeds are quiet
+    pub arb_bell:   bool,   // ring the terminal bell when an arb opens
+    pub arb_flash:  bool,   // blink the merged-book border while an arb is open
+    pub liquidations: bool, // show the liquidations panel
+    pub trade_tape: bool,   // show the trade tape panel
+    pub vwap_notional: f64, // USD per side for the VWAP-to-depth block; 0 = off
+    pub liquidity_bands_bps: Vec<f64>, // ascending; empty = off
+    pub sweep_size: f64,    // base units highlighted in the merged book; 0 = off
+    pub spread_history_secs: u64, // cross spread sparkline length; 0 = off
+    pub stale_ms:   u64,        // connected feed with no update this long is stale; 0 = off
+    pub age_ms:     (u64, u64), // book age the footer shows amber / red past
+    pub fees:       FeesConfig,
+    pub risk:       RiskConfig,
+    pub reconnect:  ReconnectConfig,
+    pub quarantine: QuarantineConfig,
+    pub socket:     SocketConfig,
+    pub http:       Option<HttpConfig>,     // None = server disabled
+    pub ws_server:  Option<WsServerConfig>, // None = server disabled
+    pub grpc:       Option<GrpcConfig>,     // None = server disabled
+    pub nats:       Option<NatsConfig>,     // None = sink disabled
+    pub redis:      Option<RedisConfig>,    // None = sink disabled
+    pub parquet:    Option<ParquetConfig>,  // None = sink disabled
+    pub sqlite:     Option<SqliteConfig>,   // None = sink disabled
+    pub arrow_ipc:  Option<ArrowIpcConfig>, // None = sink disabled
+    pub arb_audit:  Option<ArbAuditConfig>, // None = audit file disabled
+    pub telegram:   Option<TelegramConfig>, // None = alerts disabled
+    pub slack:      Option<SlackConfig>,    // None = alerts disabled
+    pub sim:        Option<SimConfig>,      // None = paper trading disabled
+    pub alerts:     Option<AlertsConfig>,   // None = alert engine disabled
+    pub desktop_notify: Option<DesktopNotifyConfig>, // None = notifications disabled
+    pub hl_exec:    Option<HlExecConfig>,   // None = live trading disabled
+    pub pdx_exec:   Option<PdxExecConfig>,  // None = live trading disabled
+    pub market_stats: Option<MarketStatsConfig>, // None = no open interest polling
+    pub cvd:        Option<CvdConfig>,      // None = no CVD signal
+    pub spread_stats: Option<SpreadStatsConfig>, // None = no rolling spread statistics
+    pub volatility: Option<VolatilityConfig>, // None = no realized volatility
+    pub clock_sync: Option<ClockSyncConfig>, // None = exchange timestamps taken as-is
+    pub logging:    Option<LoggingConfig>,   // None = stderr only
+    pub log_format: LogFormat,               // stderr and file alike
+    pub otel:       Option<OtelConfig>,      // None = no span export
+}
+
+/// One pair by its canonical name and each venue's symbol for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairConfig {
+    pub name:       String, // e.g. "ETH"
+    pub hl_symbol:  String, // e.g. "ETH"
+    pub pdx_symbol: String, // e.g. "ETH-USD-PERP"; empty = derived at startup
+}
+
+/// Hyperliquid's `l2Book` aggregation; `None` = full precision.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HlAggregation {
+    pub n_sig_figs: Option<u32>, // 2..=5
+    pub mantissa:   Option<u32>, // 1, 2 or 5; only with 5 significant figures
+}
+
+/// Rows per side in a venue panel; 0 = all the feed keeps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PanelDepth {
+    pub bids: usize,
+    pub asks: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeesConfig {
+    pub hl:  FeeSchedule,
+    pub pdx: FeeSchedule,
+}
+
+impl FeesConfig {
+    pub fn for_exchange(&self, ex: &Exchange) -> &FeeSchedule {
+        match ex {
+            Exchange::Hyperliquid => &self.hl,
+            Exchange::Paradex     => &self.pdx,
+        }
+    }
+}
+
+/// Fees in basis points of notional; negative maker = rebate.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeeSchedule {
+    pub maker_bps: f64,
+    pub taker_bps: f64,
+}
+
+impl FeeSchedule {
+    /// Taker fee as a fraction of notional (4.5 bps → 0.00045).
+    pub fn taker_rate(&self) -> f64 {
+        self.taker_bps / 10_000.0
+    }
+}
+
+/// Limits enforced on every simulated and live order; `None` = no limit.
+/// Orders that only reduce a position are always allowed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RiskConfig {
+    pub max_position:   Option<f64>, // absolute base units per venue
+    pub max_order_size: Option<f64>, // base units per order
+    pub max_daily_loss: Option<f64>, // USD, positive
+}
+
+/// Market data reconnect circuit breaker (see `breaker`).
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectConfig {
+    pub max_failures:        u32, // failed reconnects in a row before the feed is degraded
+    pub degraded_delay_secs: u64, // reconnect interval while degraded
+}
+
+/// TCP and WebSocket settings for the market data connections (see `net`).
+#[derive(Debug, Clone, Copy)]
+pub struct SocketConfig {
+    pub tcp_nodelay:        bool,
+    pub recv_buffer_bytes:  Option<u32>, // SO_RCVBUF; None = OS default
+    pub write_buffer_bytes: usize,       // writes are batched up to this; 0 = each message at once
+    pub max_message_bytes:  usize,       // larger inbound messages end the connection
+    pub max_frame_bytes:    usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    pub bind:              SocketAddr,
+    pub health_max_age_ms: u64, // feed counts as stale past this age
+}
+
+#[derive(Debug, Clone)]
+pub struct WsServerConfig {
+    pub bind: SocketAddr,
+}
+
+#[derive(Debug, Clone)]
+pub struct GrpcConfig {
+    pub bind: SocketAddr,
+}
+
+#[derive(Debug, Clone)]
+pub struct NatsConfig {
+    pub url: String, // e.g. "nats://127.0.0.1:4222"
+}
+
+#[derive(Debug, Clone)]
+pub struct RedisConfig {
+    pub url:        String, // e.g. "redis://127.0.0.1:6379"
+    pub key_prefix: String, // channels/keys are "{prefix}:{kind}:{coin}"
+}
+
+#[derive(Debug, Clone)]
+pub struct ParquetConfig {
+    pub dir:         PathBuf,
+    pub interval_ms: u64,   // sampling interval for merged snapshots
+    pub batch_ticks: usize, // snapshots per record batch / row group
+    pub rotate_secs: u64,   // start a new file after this long
+}
+
+#[derive(Debug, Clone)]
+pub struct SqliteConfig {
+    pub path:        PathBuf,
+    pub interval_ms: u64, // one `ticks` row per interval
+}
+
+#[derive(Debug, Clone)]
+pub struct ArrowIpcConfig {
+    pub target:   ArrowIpcTarget,
+    pub flush_ms: u64, // one record batch per interval
+}
+
+#[derive(Debug, Clone)]
+pub struct ArbAuditConfig {
+    pub path:   PathBuf,
+    pub format: ArbAuditFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArbAuditFormat {
+    Csv,
+    Jsonl,
+}
+
+#[derive(Clone)]
+pub struct TelegramConfig {
+    pub bot_token:       String,
+    pub chat_id:         String,
+    pub min_duration_ms: u64, // arb must stay open at least this long
+    pub min_size:        f64, // and have at least this much executable size
+}
+
+// Hand-written so the bot token never ends up in logs
+impl std::fmt::Debug for TelegramConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelegramConfig")
+            .field("chat_id", &self.chat_id)
+            .field("min_duration_ms", &self.min_duration_ms)
+            .field("min_size", &self.min_size)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone)]
+pub struct SlackConfig {
+    pub webhook_url:       String,
+    pub min_duration_ms:   u64, // arb must stay open at least this long
+    pub min_size:          f64, // and have at least this much executable size
+    pub min_interval_secs: u64, // at most one message per interval
+}
+
+// Hand-written so the webhook URL (a secret) never ends up in logs
+impl std::fmt::Debug for SlackConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SlackConfig")
+            .field("min_duration_ms", &self.min_duration_ms)
+            .field("min_size", &self.min_size)
+            .field("min_interval_secs", &self.min_interval_secs)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    pub order_size: f64, // base units per simulated order
+}
+
+#[derive(Debug, Clone)]
+pub struct MarketStatsConfig {
+    pub poll_secs: u64, // REST poll interval per venue
+}
+
+#[derive(Debug, Clone)]
+pub struct LoggingConfig {
+    pub dir:       PathBuf,
+    pub rotation:  LogRotation,
+    pub max_files: usize, // newest log files kept; 0 = keep all
+}
+
+#[derive(Debug, Clone)]
+pub struct QuarantineConfig {
+    pub max_payloads: usize,   // unparseable payloads kept, newest first
+    pub dir:          PathBuf, // where `w` writes them
+}
+
+#[derive(Debug, Clone)]
+pub struct OtelConfig {
+    pub traces_url:    String, // "{endpoint}/v1/traces"
+    pub service_name:  String,
+    pub message_ratio: f64,    // share of per-message traces exported, 0..=1
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogFormat {
+    Text,
+    Json, // one JSON object per line (see `log_format`)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogRotation {
+    Daily,       // new file each UTC day
+    Size(u64),   // new file once the current one reaches this many bytes
+}
+
+#[derive(Debug, Clone)]
+pub struct ClockSyncConfig {
+    pub poll_secs: u64, // server time poll interval per venue
+}
+
+#[derive(Debug, Clone)]
+pub struct CvdConfig {
+    pub windows_secs: Vec<u64>, // rolling windows, ascending
+}
+
+#[derive(Debug, Clone)]
+pub struct SpreadStatsConfig {
+    pub window_secs: u64, // rolling window the statistics cover
+}
+
+#[derive(Debug, Clone)]
+pub struct VolatilityConfig {
+    pub windows_secs: Vec<u64>, // rolling windows, ascending
+}
+
+#[derive(Debug, Clone)]
+pub struct DesktopNotifyConfig {
+    pub min_duration_ms: u64, // arb must stay open at least this long
+    pub min_size:        f64, // and have at least this much executable size
+    pub feeds_down_ms:   u64, // both feeds disconnected at least this long
+}
+
+#[derive(Clone)]
+pub struct HlExecConfig {
+    pub private_key:     [u8; 32],       // API wallet secret key
+    pub account_address: Option<String>, // account the API wallet trades for; None = the wallet itself
+    pub testnet:         bool,
+    pub order_size:      f64,            // base units per order (capped by the arb size)
+    pub tif:             HlTif,
+}
+
+// Hand-written so the private key never ends up in logs
+impl std::fmt::Debug for HlExecConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HlExecConfig")
+            .field("account_address", &self.account_address)
+            .field("testnet", &self.testnet)
+            .field("order_size", &self.order_size)
+            .field("tif", &self.tif)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Hyperliquid limit-order time in force. Serialized exactly as the API expects.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub enum HlTif {
+    Ioc, // immediate-or-cancel: takes what crosses, never rests
+    Gtc, // rests until filled or cancelled
+    Alo, // add-liquidity-only (post-only)
+}
+
+#[derive(Clone)]
+pub struct PdxExecConfig {
+    pub account_address: String,         // Paradex (Starknet) account, hex
+    pub private_key:     String,         // Stark key of that account, hex
+    pub eth_address:     Option<String>, // only needed to onboard a new account
+    pub testnet:         bool,
+    pub order_size:      f64,            // base units per order (capped by the arb size)
+    pub instruction:     PdxInstruction,
+}
+
+// Hand-written so the private key never ends up in logs
+impl std::fmt::Debug for PdxExecConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PdxExecConfig")
+            .field("account_address", &self.account_address)
+            .field("testnet", &self.testnet)
+            .field("order_size", &self.order_size)
+            .field("instruction", &self.instruction)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Paradex order time in force. Serialized exactly as the API expects.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PdxInstruction {
+    Ioc,      // immediate-or-cancel
+    Gtc,      // rests until filled or cancelled
+    PostOnly, // rejected if it would take liquidity
+}
+
+#[derive(Clone)]
+pub struct AlertsConfig {
+    pub webhook_url: Option<String>, // required if any rule uses the "webhook" sink
+    pub rules:       Vec<AlertRule>,
+}
+
+// Hand-written so the webhook URL (may embed a token) never ends up in logs
+impl std::fmt::Debug for AlertsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlertsConfig")
+            .field("webhook", &self.webhook_url.is_some())
+            .field("rules", &self.rules)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AlertRule {
+    pub name:      String,
+    pub when:      String,    // as written in config, for messages
+    pub condition: Condition,
+    pub for_ms:    u64,       // condition must hold this long before firing
+    pub sinks:     Vec<SinkKind>,
+    pub sim_order: Option<SimAction>, // submitted to the paper-trading sim on fire
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrowIpcTarget {
+    Stdout, // path = "-"; the TUI then renders to /dev/tty instead
+    File(PathBuf),
 }
 
 impl AppConfig {
@@ -45,32 +1177,765 @@ impl AppConfig {
         let raw: RawConfig = toml::from_str(&raw_text)
             .with_context(|| format!("Failed to parse '{CONFIG_PATH}' as TOML"))?;
 
+        // ── Validate pairs fields ─────────────────────────────────────────────
+        let mut pairs: Vec<PairConfig> = Vec::new();
+        for (name, entry) in &raw.pairs {
+            let name = name.trim().to_uppercase();
+            if name.is_empty() {
+                bail!("config.toml: pairs names must not be empty");
+            }
+            if pairs.iter().any(|p| p.name == name) {
+                bail!("config.toml: pairs.{name} is listed twice");
+            }
+            let hl = entry.hl.trim().to_uppercase();
+            if hl.is_empty() {
+                bail!("config.toml: pairs.{name}.hl must not be empty");
+            }
+            pairs.push(PairConfig { name, hl_symbol: hl, pdx_symbol: entry.pdx.trim().to_uppercase() });
+        }
+
         // ── Validate pair fields ──────────────────────────────────────────────
-        let hl_symbol = raw.pair.hl_symbol.trim().to_uppercase();
-        if hl_symbol.is_empty() {
-            bail!("config.toml: pair.hl_symbol must not be empty");
+        // Either a [pairs] entry by name, or the symbols themselves
+        let pair_name = raw.pair.name.trim().to_uppercase();
+        let pair = if pair_name.is_empty() {
+            let hl_symbol = raw.pair.hl_symbol.trim().to_uppercase();
+            if hl_symbol.is_empty() {
+                bail!("config.toml: pair.hl_symbol must not be empty (or set pair.name to a [pairs] entry)");
+            }
+            // Empty is resolved against the Paradex markets at startup
+            let pdx_symbol = raw.pair.pdx_symbol.trim().to_uppercase();
+            // Named after its [pairs] entry when there is one for the same symbols
+            let name = pairs.iter()
+                .find(|p| p.hl_symbol == hl_symbol && (pdx_symbol.is_empty() || p.pdx_symbol == pdx_symbol))
+                .map_or_else(|| hl_symbol.clone(), |p| p.name.clone());
+            PairConfig { name, hl_symbol, pdx_symbol }
+        } else {
+            if !raw.pair.hl_symbol.trim().is_empty() || !raw.pair.pdx_symbol.trim().is_empty() {
+                bail!("config.toml: set either pair.name or pair.hl_symbol / pdx_symbol, not both");
+            }
+            pairs.iter().find(|p| p.name == pair_name).cloned().with_context(|| {
+                let names: Vec<&str> = pairs.iter().map(|p| p.name.as_str()).collect();
+                format!("config.toml: pair.name '{pair_name}' is not in [pairs] ({})", names.join(", "))
+            })?
+        };
+        if !pairs.iter().any(|p| p.name == pair.name) {
+            pairs.insert(0, pair.clone());
         }
+        let PairConfig { name: pair_name, hl_symbol, pdx_symbol } = pair;
 
-        let pdx_symbol = raw.pair.pdx_symbol.trim().to_uppercase();
-        if pdx_symbol.is_empty() {
-            bail!("config.toml: pair.pdx_symbol must not be empty");
+        // ── Validate book fields ──────────────────────────────────────────────
+        // Hyperliquid's l2Book carries 20 levels a side at most
+        let hl_depth = raw.book.hl_depth;
+        if !(1..=20).contains(&hl_depth) {
+            bail!("config.toml: book.hl_depth must be between 1 and 20, got {hl_depth}");
         }
+        // Only levels well inside Paradex's REST snapshot are whole from the start
+        let pdx_depth = raw.book.pdx_depth;
+        if !(1..=MAX_BOOK_DEPTH).contains(&pdx_depth) {
+            bail!("config.toml: book.pdx_depth must be between 1 and {MAX_BOOK_DEPTH}, got {pdx_depth}");
+        }
+        let (sig_figs, mantissa) = (raw.book.hl_sig_figs, raw.book.hl_mantissa);
+        if sig_figs != 0 && !(2..=5).contains(&sig_figs) {
+            bail!("config.toml: book.hl_sig_figs must be 2–5 (0 = full precision), got {sig_figs}");
+        }
+        if mantissa != 0 && !(sig_figs == 5 && [1, 2, 5].contains(&mantissa)) {
+            bail!("config.toml: book.hl_mantissa must be 1, 2 or 5 and needs hl_sig_figs = 5 (0 = off), got {mantissa}");
+        }
+        let hl_aggregation = HlAggregation {
+            n_sig_figs: (sig_figs != 0).then_some(sig_figs),
+            mantissa:   (mantissa != 0).then_some(mantissa),
+        };
 
-        // ── Validate display fields ───────────────────────────────────────────
-        let depth = raw.display.depth;
-        if depth == 0 || depth > 10 {
-            bail!("config.toml: display.depth must be between 1 and 10, got {depth}");
+        // ── Validate merge fields ─────────────────────────────────────────────
+        let m = &raw.merge;
+        let depth = m.depth.or(raw.display.depth).unwrap_or(10);
+        if depth == 0 || depth > hl_depth + pdx_depth {
+            bail!("config.toml: merge.depth must be between 1 and book.hl_depth + book.pdx_depth ({}), got {depth}",
+                hl_depth + pdx_depth);
+        }
+        let mode = match m.mode.trim().to_lowercase().as_str() {
+            "tagged"     => MergeMode::Tagged,
+            "aggregated" => MergeMode::Aggregated,
+            "netted"     => MergeMode::Netted,
+            other        => bail!("config.toml: merge.mode must be \"tagged\", \"aggregated\" or \"netted\", got '{other}'"),
+        };
+        if !(0.0..=100_000.0).contains(&m.group) {
+            bail!("config.toml: merge.group must be 0–100000 (0 = off), got {}", m.group);
+        }
+        // Via its shortest decimal form, so 0.1 is exactly 0.1
+        let group = (m.group > 0.0)
+            .then(|| m.group.to_string().parse::<Decimal>())
+            .transpose()
+            .with_context(|| format!("config.toml: merge.group {} can't be used as a price bucket", m.group))?;
+        if !(m.min_notional >= 0.0 && m.min_notional.is_finite()) {
+            bail!("config.toml: merge.min_notional must be >= 0 (0 = off), got {}", m.min_notional);
+        }
+        if !(0.0..=50.0).contains(&m.outlier_band_pct) {
+            bail!("config.toml: merge.outlier_band_pct must be 0–50 (0 = off), got {}", m.outlier_band_pct);
         }
 
+        // ── Validate quote fields ─────────────────────────────────────────────
+        let q = &raw.quote;
+        let unit = q.unit.trim().to_uppercase();
+        if unit.is_empty() {
+            bail!("config.toml: quote.unit must not be empty");
+        }
+        for (currency, rate) in &q.rates {
+            if !(rate.is_finite() && *rate > 0.0) {
+                bail!("config.toml: quote.rates.{currency} must be > 0, got {rate}");
+            }
+        }
+        // A venue quoted in the unit itself needs no rate
+        let rate = |field: &str, currency: &str| -> Result<Option<Decimal>> {
+            let currency = currency.trim().to_uppercase();
+            if currency == unit {
+                return Ok(None);
+            }
+            let Some((key, rate)) = q.rates.iter().find(|(c, _)| c.to_uppercase() == currency) else {
+                bail!("config.toml: quote.{field} is {currency} but quote.rates has no {currency} → {unit} rate");
+            };
+            // Via its shortest decimal form, as for merge.group
+            (*rate != 1.0)
+                .then(|| rate.to_string().parse::<Decimal>())
+                .transpose()
+                .with_context(|| format!("config.toml: quote.rates.{key} = {rate} can't be used as a rate"))
+        };
+        let quote = QuoteRates { hl: rate("hl", &q.hl)?, pdx: rate("pdx", &q.pdx)? };
+
+        let merge = MergePolicy {
+            depth, mode, group, min_notional: m.min_notional, outlier_band_pct: m.outlier_band_pct, quote,
+        };
+
+        // ── Validate display fields ───────────────────────────────────────────
         let tick_ms = raw.display.tick_ms;
-        if tick_ms < 50 || tick_ms > 2000 {
+        if !(50..=2000).contains(&tick_ms) {
             bail!("config.toml: display.tick_ms must be between 50 and 2000, got {tick_ms}");
         }
+        // Defaults to 1 s, or to tick_ms when that is already slower
+        let max_tick_ms = raw.display.max_tick_ms.unwrap_or(tick_ms.max(1_000));
+        if !(tick_ms..=5_000).contains(&max_tick_ms) {
+            bail!("config.toml: display.max_tick_ms must be between tick_ms ({tick_ms}) and 5000, got {max_tick_ms}");
+        }
+
+        let vwap_notional = raw.display.vwap_notional;
+        if !(vwap_notional >= 0.0 && vwap_notional.is_finite()) {
+            bail!("config.toml: display.vwap_notional must be >= 0 (0 = off), got {vwap_notional}");
+        }
+
+        let mut liquidity_bands_bps = raw.display.liquidity_bands_bps.clone();
+        if liquidity_bands_bps.len() > 4 {
+            bail!("config.toml: display.liquidity_bands_bps must list at most 4 bands, got {}", liquidity_bands_bps.len());
+        }
+        if let Some(b) = liquidity_bands_bps.iter().find(|b| !(**b > 0.0 && **b <= 1_000.0)) {
+            bail!("config.toml: display.liquidity_bands_bps entries must be > 0 and <= 1000 bps, got {b}");
+        }
+        liquidity_bands_bps.sort_by(f64::total_cmp);
+        liquidity_bands_bps.dedup();
+
+        let sweep_size = raw.display.sweep_size;
+        if !(sweep_size >= 0.0 && sweep_size.is_finite()) {
+            bail!("config.toml: display.sweep_size must be >= 0 (0 = off), got {sweep_size}");
+        }
+
+        let spread_history_secs = raw.display.spread_history_secs;
+        if spread_history_secs > 3_600 {
+            bail!("config.toml: display.spread_history_secs must be 0–3600 (0 = off), got {spread_history_secs}");
+        }
+
+        let stale_ms = raw.display.stale_ms;
+        if stale_ms != 0 && !(500..=600_000).contains(&stale_ms) {
+            bail!("config.toml: display.stale_ms must be 500–600000 (0 = off), got {stale_ms}");
+        }
+        let age_ms = (raw.display.age_warn_ms, raw.display.age_alert_ms);
+        if !(1..=600_000).contains(&age_ms.0) {
+            bail!("config.toml: display.age_warn_ms must be 1–600000, got {}", age_ms.0);
+        }
+        if !(age_ms.0 + 1..=600_000).contains(&age_ms.1) {
+            bail!("config.toml: display.age_alert_ms must be above age_warn_ms ({}) and at most 600000, got {}", age_ms.0, age_ms.1);
+        }
+        // The shared depth may reach the deeper book; a venue's own, only its book
+        let shared = PanelDepth::from(raw.display.venue_depth);
+        if shared.bids.max(shared.asks) > hl_depth.max(pdx_depth) {
+            bail!("config.toml: display.venue_depth must be at most the deeper book depth ({}, 0 = all), got bids {} / asks {}",
+                hl_depth.max(pdx_depth), shared.bids, shared.asks);
+        }
+        let panel_depth = |key: &str, own: Option<RawPanelDepth>, book_depth: usize| -> Result<PanelDepth> {
+            let Some(own) = own else { return Ok(shared) };
+            let own = PanelDepth::from(own);
+            if own.bids.max(own.asks) > book_depth {
+                bail!("config.toml: display.{key} must be at most book.{} ({book_depth}, 0 = all), got bids {} / asks {}",
+                    key.replace("venue_", ""), own.bids, own.asks);
+            }
+            Ok(own)
+        };
+        let venue_depth = (
+            panel_depth("hl_venue_depth", raw.display.hl_venue_depth, hl_depth)?,
+            panel_depth("pdx_venue_depth", raw.display.pdx_venue_depth, pdx_depth)?,
+        );
+
+        let narrow_width = raw.display.narrow_width;
+        if narrow_width > 1_000 {
+            bail!("config.toml: display.narrow_width must be 0–1000 columns (0 = never), got {narrow_width}");
+        }
+        if raw.display.dump_dir.trim().is_empty() {
+            bail!("config.toml: display.dump_dir must not be empty");
+        }
+        let dump_dir = PathBuf::from(raw.display.dump_dir.trim());
+        let carousel_secs = raw.display.carousel_secs;
+        if !(5..=3_600).contains(&carousel_secs) {
+            bail!("config.toml: display.carousel_secs must be 5–3600, got {carousel_secs}");
+        }
+
+        // ── Validate theme fields ─────────────────────────────────────────────
+        let Some(mut theme) = Theme::preset(raw.theme.preset.trim()) else {
+            let names: Vec<&str> = Theme::PRESETS.iter().map(|(n, _)| *n).collect();
+            bail!("config.toml: theme.preset must be one of {}, got '{}'", names.join(", "), raw.theme.preset);
+        };
+        for (field, value) in &raw.theme.colors {
+            if let Err(e) = theme.set(field, value) {
+                bail!("config.toml: theme.{field} {e}");
+            }
+        }
+
+        // ── Validate fee fields ───────────────────────────────────────────────
+        let f = &raw.fees;
+        for (field, bps) in [
+            ("hl_maker_bps", f.hl_maker_bps), ("hl_taker_bps", f.hl_taker_bps),
+            ("pdx_maker_bps", f.pdx_maker_bps), ("pdx_taker_bps", f.pdx_taker_bps),
+        ] {
+            if !(-100.0..=100.0).contains(&bps) {
+                bail!("config.toml: fees.{field} must be between -100 and 100 bps, got {bps}");
+            }
+        }
+        let fees = FeesConfig {
+            hl:  FeeSchedule { maker_bps: f.hl_maker_bps, taker_bps: f.hl_taker_bps },
+            pdx: FeeSchedule { maker_bps: f.pdx_maker_bps, taker_bps: f.pdx_taker_bps },
+        };
+
+        // ── Validate risk fields ──────────────────────────────────────────────
+        let r = &raw.risk;
+        let limit = |field: &str, v: f64| -> Result<Option<f64>> {
+            if !(v >= 0.0 && v.is_finite()) {
+                bail!("config.toml: risk.{field} must be >= 0 (0 = no limit), got {v}");
+            }
+            Ok((v > 0.0).then_some(v))
+        };
+        let risk = RiskConfig {
+            max_position:   limit("max_position", r.max_position)?,
+            max_order_size: limit("max_order_size", r.max_order_size)?,
+            max_daily_loss: limit("max_daily_loss", r.max_daily_loss)?,
+        };
+
+        // ── Validate reconnect fields ─────────────────────────────────────────
+        let reconnect = ReconnectConfig {
+            max_failures:        raw.reconnect.max_failures,
+            degraded_delay_secs: raw.reconnect.degraded_delay_secs,
+        };
+        if reconnect.max_failures == 0 {
+            bail!("config.toml: reconnect.max_failures must be at least 1");
+        }
+        if !(3..=3_600).contains(&reconnect.degraded_delay_secs) {
+            bail!(
+                "config.toml: reconnect.degraded_delay_secs must be 3–3600 seconds, got {}",
+                reconnect.degraded_delay_secs,
+            );
+        }
+
+        // ── Validate http fields ──────────────────────────────────────────────
+        let http = if raw.http.enabled {
+            let bind = parse_bind("http.bind", &raw.http.bind)?;
+            let health_max_age_ms = raw.http.health_max_age_ms;
+            if health_max_age_ms == 0 {
+                bail!("config.toml: http.health_max_age_ms must be greater than 0");
+            }
+            Some(HttpConfig { bind, health_max_age_ms })
+        } else {
+            None
+        };
+
+        // ── Validate ws_server fields ─────────────────────────────────────────
+        let ws_server = if raw.ws_server.enabled {
+            Some(WsServerConfig { bind: parse_bind("ws_server.bind", &raw.ws_server.bind)? })
+        } else {
+            None
+        };
+
+        // ── Validate grpc fields ──────────────────────────────────────────────
+        let grpc = if raw.grpc.enabled {
+            Some(GrpcConfig { bind: parse_bind("grpc.bind", &raw.grpc.bind)? })
+        } else {
+            None
+        };
 
-        Ok(AppConfig { hl_symbol, pdx_symbol, depth, tick_ms })
+        // ── Validate nats fields ──────────────────────────────────────────────
+        let nats = if raw.nats.enabled {
+            let url = raw.nats.url.trim().to_string();
+            if url.is_empty() {
+                bail!("config.toml: nats.url must not be empty");
+            }
+            Some(NatsConfig { url })
+        } else {
+            None
+        };
+
+        // ── Validate redis fields ─────────────────────────────────────────────
+        let redis = if raw.redis.enabled {
+            let url = raw.redis.url.trim().to_string();
+            if url.is_empty() {
+                bail!("config.toml: redis.url must not be empty");
+            }
+            let key_prefix = raw.redis.key_prefix.trim().to_string();
+            if key_prefix.is_empty() {
+                bail!("config.toml: redis.key_prefix must not be empty");
+            }
+            Some(RedisConfig { url, key_prefix })
+        } else {
+            None
+        };
+
+        // ── Validate parquet fields ───────────────────────────────────────────
+        let parquet = if raw.parquet.enabled {
+            let p = raw.parquet;
+            if p.dir.trim().is_empty() {
+                bail!("config.toml: parquet.dir must not be empty");
+            }
+            if p.interval_ms < 50 {
+                bail!("config.toml: parquet.interval_ms must be at least 50, got {}", p.interval_ms);
+            }
+            if p.batch_ticks == 0 {
+                bail!("config.toml: parquet.batch_ticks must be greater than 0");
+            }
+            if p.rotate_secs == 0 {
+                bail!("config.toml: parquet.rotate_secs must be greater than 0");
+            }
+            Some(ParquetConfig {
+                dir:         PathBuf::from(p.dir.trim()),
+                interval_ms: p.interval_ms,
+                batch_ticks: p.batch_ticks,
+                rotate_secs: p.rotate_secs,
+            })
+        } else {
+            None
+        };
+
+        // ── Validate sqlite fields ────────────────────────────────────────────
+        let sqlite = if raw.sqlite.enabled {
+            if raw.sqlite.path.trim().is_empty() {
+                bail!("config.toml: sqlite.path must not be empty");
+            }
+            if raw.sqlite.interval_ms < 50 {
+                bail!("config.toml: sqlite.interval_ms must be at least 50, got {}", raw.sqlite.interval_ms);
+            }
+            Some(SqliteConfig {
+                path:        PathBuf::from(raw.sqlite.path.trim()),
+                interval_ms: raw.sqlite.interval_ms,
+            })
+        } else {
+            None
+        };
+
+        // ── Validate arrow_ipc fields ─────────────────────────────────────────
+        let arrow_ipc = if raw.arrow_ipc.enabled {
+            let target = match raw.arrow_ipc.path.trim() {
+                ""  => bail!("config.toml: arrow_ipc.path must not be empty (use \"-\" for stdout)"),
+                "-" => ArrowIpcTarget::Stdout,
+                p   => ArrowIpcTarget::File(PathBuf::from(p)),
+            };
+            if raw.arrow_ipc.flush_ms < 50 {
+                bail!("config.toml: arrow_ipc.flush_ms must be at least 50, got {}", raw.arrow_ipc.flush_ms);
+            }
+            Some(ArrowIpcConfig { target, flush_ms: raw.arrow_ipc.flush_ms })
+        } else {
+            None
+        };
+
+        // ── Validate arb_audit fields ─────────────────────────────────────────
+        let arb_audit = if raw.arb_audit.enabled {
+            let path = raw.arb_audit.path.trim();
+            if path.is_empty() {
+                bail!("config.toml: arb_audit.path must not be empty");
+            }
+            let format = match raw.arb_audit.format.trim().to_lowercase().as_str() {
+                "csv"   => ArbAuditFormat::Csv,
+                "jsonl" => ArbAuditFormat::Jsonl,
+                other   => bail!("config.toml: arb_audit.format must be \"csv\" or \"jsonl\", got '{other}'"),
+            };
+            Some(ArbAuditConfig { path: PathBuf::from(path), format })
+        } else {
+            None
+        };
+
+        // ── Validate telegram fields ──────────────────────────────────────────
+        let telegram = if raw.telegram.enabled {
+            let t = raw.telegram;
+            // The token may come from the environment to keep it out of the file
+            let bot_token = match t.bot_token.trim() {
+                "" => std::env::var("TELEGRAM_BOT_TOKEN").unwrap_or_default(),
+                s  => s.to_string(),
+            };
+            if bot_token.is_empty() {
+                bail!("config.toml: telegram.bot_token is empty and TELEGRAM_BOT_TOKEN is not set");
+            }
+            let chat_id = t.chat_id.trim().to_string();
+            if chat_id.is_empty() {
+                bail!("config.toml: telegram.chat_id must not be empty");
+            }
+            if t.min_size.is_nan() || t.min_size < 0.0 {
+                bail!("config.toml: telegram.min_size must be >= 0, got {}", t.min_size);
+            }
+            Some(TelegramConfig { bot_token, chat_id, min_duration_ms: t.min_duration_ms, min_size: t.min_size })
+        } else {
+            None
+        };
+
+        // ── Validate slack fields ─────────────────────────────────────────────
+        let slack = if raw.slack.enabled {
+            let sl = raw.slack;
+            let webhook_url = match sl.webhook_url.trim() {
+                "" => std::env::var("SLACK_WEBHOOK_URL").unwrap_or_default(),
+                s  => s.to_string(),
+            };
+            if !webhook_url.starts_with("https://") {
+                bail!("config.toml: slack.webhook_url (or SLACK_WEBHOOK_URL) must be an https:// URL");
+            }
+            if sl.min_size.is_nan() || sl.min_size < 0.0 {
+                bail!("config.toml: slack.min_size must be >= 0, got {}", sl.min_size);
+            }
+            Some(SlackConfig {
+                webhook_url,
+                min_duration_ms:   sl.min_duration_ms,
+                min_size:          sl.min_size,
+                min_interval_secs: sl.min_interval_secs,
+            })
+        } else {
+            None
+        };
+
+        // ── Validate sim fields ───────────────────────────────────────────────
+        let sim = if raw.sim.enabled {
+            let order_size = raw.sim.order_size;
+            if !(order_size > 0.0 && order_size.is_finite()) {
+                bail!("config.toml: sim.order_size must be greater than 0, got {order_size}");
+            }
+            Some(SimConfig { order_size })
+        } else {
+            None
+        };
+
+        // ── Validate alerts fields ────────────────────────────────────────────
+        let alerts = if raw.alerts.enabled {
+            let webhook_url = match raw.alerts.webhook_url.trim() {
+                "" => None,
+                s if s.starts_with("http://") || s.starts_with("https://") => Some(s.to_string()),
+                s  => bail!("config.toml: alerts.webhook_url must be an http(s) URL, got '{s}'"),
+            };
+            let mut rules = Vec::with_capacity(raw.alerts.rules.len());
+            for (i, r) in raw.alerts.rules.into_iter().enumerate() {
+                let name = r.name.trim().to_string();
+                if name.is_empty() {
+                    bail!("config.toml: alerts.rules[{i}].name must not be empty");
+                }
+                let condition = Condition::parse(&r.when)
+                    .with_context(|| format!("config.toml: alerts.rules[{i}] ('{name}') has an invalid `when`"))?;
+                let mut sinks = Vec::with_capacity(r.sinks.len());
+                for s in &r.sinks {
+                    let Some(kind) = SinkKind::parse(s.trim()) else {
+                        bail!("config.toml: alerts.rules[{i}].sinks: unknown sink '{s}' (use log, banner, webhook)");
+                    };
+                    if kind == SinkKind::Webhook && webhook_url.is_none() {
+                        bail!("config.toml: alerts.rules[{i}] uses the webhook sink but alerts.webhook_url is empty");
+                    }
+                    sinks.push(kind);
+                }
+                let sim_order = match r.sim_order.as_deref().map(str::trim) {
+                    None => None,
+                    Some(a) => match SimAction::parse(a) {
+                        _ if sim.is_none() => bail!("config.toml: alerts.rules[{i}].sim_order needs [sim] enabled"),
+                        Some(action) => Some(action),
+                        None => bail!("config.toml: alerts.rules[{i}].sim_order: unknown order '{a}' (use buy, sell, arb, flatten)"),
+                    },
+                };
+                rules.push(AlertRule { name, when: r.when.trim().to_string(), condition, for_ms: r.for_ms, sinks, sim_order });
+            }
+            Some(AlertsConfig { webhook_url, rules })
+        } else {
+            None
+        };
+
+        // ── Validate desktop_notify fields ────────────────────────────────────
+        let desktop_notify = if raw.desktop_notify.enabled {
+            let d = raw.desktop_notify;
+            if d.min_size.is_nan() || d.min_size < 0.0 {
+                bail!("config.toml: desktop_notify.min_size must be >= 0, got {}", d.min_size);
+            }
+            Some(DesktopNotifyConfig {
+                min_duration_ms: d.min_duration_ms,
+                min_size:        d.min_size,
+                feeds_down_ms:   d.feeds_down_ms,
+            })
+        } else {
+            None
+        };
+
+        // ── Validate hl_exec fields ───────────────────────────────────────────
+        let hl_exec = if raw.hl_exec.enabled {
+            let h = raw.hl_exec;
+            // The key may come from the environment to keep it out of the file
+            let key_hex = match h.private_key.trim() {
+                "" => std::env::var("HL_PRIVATE_KEY").unwrap_or_default(),
+                s  => s.to_string(),
+            };
+            if key_hex.is_empty() {
+                bail!("config.toml: hl_exec.private_key is empty and HL_PRIVATE_KEY is not set");
+            }
+            let private_key: [u8; 32] = hex::decode(key_hex.trim().trim_start_matches("0x"))
+                .ok()
+                .and_then(|b| b.try_into().ok())
+                .context("config.toml: hl_exec.private_key (or HL_PRIVATE_KEY) must be 32 bytes of hex")?;
+            if !(h.order_size > 0.0 && h.order_size.is_finite()) {
+                bail!("config.toml: hl_exec.order_size must be greater than 0, got {}", h.order_size);
+            }
+            let tif = match h.tif.trim().to_ascii_lowercase().as_str() {
+                "ioc" => HlTif::Ioc,
+                "gtc" => HlTif::Gtc,
+                "alo" => HlTif::Alo,
+                other => bail!("config.toml: hl_exec.tif must be \"Ioc\", \"Gtc\" or \"Alo\", got '{other}'"),
+            };
+            let account_address = match h.account_address.trim() {
+                "" => None,
+                s if s.starts_with("0x") && s.len() == 42 && s[2..].chars().all(|c| c.is_ascii_hexdigit()) => Some(s.to_lowercase()),
+                s  => bail!("config.toml: hl_exec.account_address must be a 0x-prefixed 20-byte address, got '{s}'"),
+            };
+            Some(HlExecConfig {
+                private_key, account_address,
+                testnet:    h.testnet,
+                order_size: h.order_size,
+                tif,
+            })
+        } else {
+            None
+        };
+
+        // ── Validate pdx_exec fields ──────────────────────────────────────────
+        let pdx_exec = if raw.pdx_exec.enabled {
+            let p = raw.pdx_exec;
+            let is_felt = |s: &str| {
+                let digits = s.trim_start_matches("0x");
+                s.starts_with("0x") && (1..=64).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_hexdigit())
+            };
+            let account_address = p.account_address.trim().to_string();
+            if !is_felt(&account_address) {
+                bail!("config.toml: pdx_exec.account_address must be a 0x-prefixed hex address");
+            }
+            // The key may come from the environment to keep it out of the file
+            let private_key = match p.private_key.trim() {
+                "" => std::env::var("PARADEX_PRIVATE_KEY").unwrap_or_default().trim().to_string(),
+                s  => s.to_string(),
+            };
+            if private_key.is_empty() {
+                bail!("config.toml: pdx_exec.private_key is empty and PARADEX_PRIVATE_KEY is not set");
+            }
+            if !is_felt(&private_key) {
+                bail!("config.toml: pdx_exec.private_key (or PARADEX_PRIVATE_KEY) must be 0x-prefixed hex");
+            }
+            let eth_address = match p.eth_address.trim() {
+                "" => None,
+                s if s.starts_with("0x") && s.len() == 42 && s[2..].chars().all(|c| c.is_ascii_hexdigit()) => Some(s.to_string()),
+                s  => bail!("config.toml: pdx_exec.eth_address must be a 0x-prefixed 20-byte address, got '{s}'"),
+            };
+            // No Paradex SDK known answer pins the order hash yet (see paradex_exec.rs tests)
+            if !p.testnet && !p.allow_mainnet {
+                bail!("config.toml: pdx_exec.testnet is false but allow_mainnet isn't set; Paradex order signing \
+                       hasn't been checked against a Paradex SDK known answer, so trade on testnet or set \
+                       pdx_exec.allow_mainnet = true");
+            }
+            if !(p.order_size > 0.0 && p.order_size.is_finite()) {
+                bail!("config.toml: pdx_exec.order_size must be greater than 0, got {}", p.order_size);
+            }
+            let instruction = match p.instruction.trim().to_ascii_uppercase().as_str() {
+                "IOC"       => PdxInstruction::Ioc,
+                "GTC"       => PdxInstruction::Gtc,
+                "POST_ONLY" => PdxInstruction::PostOnly,
+                other => bail!("config.toml: pdx_exec.instruction must be \"IOC\", \"GTC\" or \"POST_ONLY\", got '{other}'"),
+            };
+            Some(PdxExecConfig {
+                account_address, private_key, eth_address,
+                testnet:    p.testnet,
+                order_size: p.order_size,
+                instruction,
+            })
+        } else {
+            None
+        };
+
+        // ── Validate market stats fields ──────────────────────────────────────
+        let market_stats = if raw.market_stats.enabled {
+            let poll_secs = raw.market_stats.poll_secs;
+            if poll_secs == 0 {
+                bail!("config.toml: market_stats.poll_secs must be at least 1");
+            }
+            Some(MarketStatsConfig { poll_secs })
+        } else {
+            None
+        };
+
+        // ── Validate CVD fields ───────────────────────────────────────────────
+        let cvd = if raw.cvd.enabled {
+            let mut windows_secs = raw.cvd.windows_secs.clone();
+            if windows_secs.is_empty() || windows_secs.len() > 4 {
+                bail!("config.toml: cvd.windows_secs must list 1 to 4 windows, got {}", windows_secs.len());
+            }
+            if let Some(w) = windows_secs.iter().find(|w| !(1..=86_400).contains(*w)) {
+                bail!("config.toml: cvd.windows_secs entries must be 1–86400 seconds, got {w}");
+            }
+            windows_secs.sort_unstable();
+            windows_secs.dedup();
+            Some(CvdConfig { windows_secs })
+        } else {
+            None
+        };
+
+        // ── Validate spread statistics fields ─────────────────────────────────
+        let spread_stats = if raw.spread_stats.enabled {
+            let window_secs = raw.spread_stats.window_secs;
+            if !(10..=86_400).contains(&window_secs) {
+                bail!("config.toml: spread_stats.window_secs must be 10–86400 seconds, got {window_secs}");
+            }
+            Some(SpreadStatsConfig { window_secs })
+        } else {
+            None
+        };
+
+        // ── Validate volatility fields ────────────────────────────────────────
+        let volatility = if raw.volatility.enabled {
+            let mut windows_secs = raw.volatility.windows_secs.clone();
+            if windows_secs.is_empty() || windows_secs.len() > 4 {
+                bail!("config.toml: volatility.windows_secs must list 1 to 4 windows, got {}", windows_secs.len());
+            }
+            if let Some(w) = windows_secs.iter().find(|w| !(10..=86_400).contains(*w)) {
+                bail!("config.toml: volatility.windows_secs entries must be 10–86400 seconds, got {w}");
+            }
+            windows_secs.sort_unstable();
+            windows_secs.dedup();
+            Some(VolatilityConfig { windows_secs })
+        } else {
+            None
+        };
+
+        // ── Validate clock sync fields ────────────────────────────────────────
+        let clock_sync = if raw.clock_sync.enabled {
+            let poll_secs = raw.clock_sync.poll_secs;
+            if !(5..=3_600).contains(&poll_secs) {
+                bail!("config.toml: clock_sync.poll_secs must be 5–3600 seconds, got {poll_secs}");
+            }
+            Some(ClockSyncConfig { poll_secs })
+        } else {
+            None
+        };
+
+        // ── Validate logging fields ───────────────────────────────────────────
+        let log_format = match raw.logging.format.trim().to_lowercase().as_str() {
+            "text" => LogFormat::Text,
+            "json" => LogFormat::Json,
+            other  => bail!("config.toml: logging.format must be \"text\" or \"json\", got '{other}'"),
+        };
+        let logging = if raw.logging.enabled {
+            let l = raw.logging;
+            if l.dir.trim().is_empty() {
+                bail!("config.toml: logging.dir must not be empty");
+            }
+            let rotation = match l.rotation.trim().to_lowercase().as_str() {
+                "daily" => LogRotation::Daily,
+                "size"  => {
+                    if !(1..=10_240).contains(&l.max_size_mb) {
+                        bail!("config.toml: logging.max_size_mb must be 1–10240, got {}", l.max_size_mb);
+                    }
+                    LogRotation::Size(l.max_size_mb * 1024 * 1024)
+                }
+                other   => bail!("config.toml: logging.rotation must be \"daily\" or \"size\", got '{other}'"),
+            };
+            Some(LoggingConfig { dir: PathBuf::from(l.dir.trim()), rotation, max_files: l.max_files })
+        } else {
+            None
+        };
+
+        // ── Validate quarantine fields ────────────────────────────────────────
+        let q = &raw.quarantine;
+        if !(1..=10_000).contains(&q.max_payloads) {
+            bail!("config.toml: quarantine.max_payloads must be 1–10000, got {}", q.max_payloads);
+        }
+        if q.dir.trim().is_empty() {
+            bail!("config.toml: quarantine.dir must not be empty");
+        }
+        let quarantine = QuarantineConfig { max_payloads: q.max_payloads, dir: PathBuf::from(q.dir.trim()) };
+
+        // ── Validate socket fields ────────────────────────────────────────────
+        let s = &raw.socket;
+        if s.recv_buffer_kb > 65_536 {
+            bail!("config.toml: socket.recv_buffer_kb must be 0–65536 (0 = OS default), got {}", s.recv_buffer_kb);
+        }
+        if s.write_buffer_kb > 16_384 {
+            bail!("config.toml: socket.write_buffer_kb must be 0–16384, got {}", s.write_buffer_kb);
+        }
+        if !(1..=1_024).contains(&s.max_message_mb) {
+            bail!("config.toml: socket.max_message_mb must be 1–1024, got {}", s.max_message_mb);
+        }
+        if !(1..=s.max_message_mb).contains(&s.max_frame_mb) {
+            bail!(
+                "config.toml: socket.max_frame_mb must be 1–max_message_mb ({}), got {}",
+                s.max_message_mb, s.max_frame_mb,
+            );
+        }
+        let socket = SocketConfig {
+            tcp_nodelay:        s.tcp_nodelay,
+            recv_buffer_bytes:  (s.recv_buffer_kb > 0).then(|| s.recv_buffer_kb * 1024),
+            write_buffer_bytes: s.write_buffer_kb * 1024,
+            max_message_bytes:  s.max_message_mb * 1024 * 1024,
+            max_frame_bytes:    s.max_frame_mb * 1024 * 1024,
+        };
+
+        // ── Validate otel fields ──────────────────────────────────────────────
+        let otel = if raw.otel.enabled {
+            let o = raw.otel;
+            let endpoint = o.endpoint.trim().trim_end_matches('/');
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                bail!("config.toml: otel.endpoint must be an http:// or https:// URL, got '{endpoint}'");
+            }
+            if o.service_name.trim().is_empty() {
+                bail!("config.toml: otel.service_name must not be empty");
+            }
+            if !(0.0..=1.0).contains(&o.message_ratio) {
+                bail!("config.toml: otel.message_ratio must be 0–1, got {}", o.message_ratio);
+            }
+            Some(OtelConfig {
+                traces_url:    format!("{endpoint}/v1/traces"),
+                service_name:  o.service_name.trim().to_string(),
+                message_ratio: o.message_ratio,
+            })
+        } else {
+            None
+        };
+
+        Ok(AppConfig {
+            hl_symbol, pdx_symbol, pair_name, pairs, merge, quote_unit: unit,
+            hl_depth, pdx_depth, venue_depth, narrow_width, dump_dir, carousel: raw.display.carousel, carousel_secs, theme, hl_aggregation, bbo: raw.book.bbo, tick_ms, max_tick_ms,
+            arb_bell: raw.display.arb_bell, arb_flash: raw.display.arb_flash,
+            liquidations: raw.display.liquidations, trade_tape: raw.display.trade_tape,
+            vwap_notional, liquidity_bands_bps, sweep_size, spread_history_secs, stale_ms, age_ms, fees, risk, reconnect, quarantine,
+            socket,
+            http, ws_server, grpc, nats, redis, parquet, sqlite, arrow_ipc, arb_audit,
+            telegram, slack, sim, alerts, desktop_notify, hl_exec, pdx_exec, market_stats, cvd,
+            spread_stats, volatility, clock_sync, logging, log_format, otel,
+        })
     }
 }
 
+fn parse_bind(field: &str, value: &str) -> Result<SocketAddr> {
+    value.trim().parse().map_err(|_| {
+        anyhow::anyhow!("config.toml: {field} must be a socket address like 127.0.0.1:9090, got '{value}'")
+    })
+}
+
 // ─── Runtime pair validation ──────────────────────────────────────────────────
 //
 // We call each exchange's REST API to check the symbol actually exists before
@@ -114,6 +1979,41 @@ pub async fn validate_hl_symbol(symbol: &str) -> Result<()> {
     }
 }
 
+/// The Paradex perp for a Hyperliquid coin, from the markets REST endpoint: the
+/// perp with that base currency, preferring the USD-quoted one.
+pub async fn resolve_pdx_symbol(hl_symbol: &str) -> Result<String> {
+    let url = "https://api.prod.paradex.trade/v1/markets";
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .context("Failed to reach Paradex API for symbol resolution")?
+        .json::<serde_json::Value>()
+        .await
+        .context("Failed to parse Paradex markets response")?;
+
+    let results = resp["results"]
+        .as_array()
+        .context("Unexpected Paradex markets response structure")?;
+
+    let mut perps: Vec<(&str, &str)> = results
+        .iter()
+        .filter(|m| m["asset_kind"].as_str() == Some("PERP"))
+        .filter(|m| m["base_currency"].as_str().is_some_and(|b| b.eq_ignore_ascii_case(hl_symbol)))
+        .filter_map(|m| Some((m["symbol"].as_str()?, m["quote_currency"].as_str().unwrap_or(""))))
+        .collect();
+    perps.sort_by_key(|&(_, quote)| quote != "USD");
+
+    match perps.first() {
+        Some((symbol, _)) => Ok(symbol.to_uppercase()),
+        None => bail!(
+            "No Paradex perp found for '{hl_symbol}'.\nSet pair.pdx_symbol in config.toml to pick the market yourself"
+        ),
+    }
+}
+
 /// Validate `pdx_symbol` against the Paradex markets REST endpoint.
 pub async fn validate_pdx_symbol(symbol: &str) -> Result<()> {
     let url = "https://api.prod.paradex.trade/v1/markets";
@@ -147,3 +2047,23 @@ pub async fn validate_pdx_symbol(symbol: &str) -> Result<()> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs_keep_file_order() {
+        #[derive(Deserialize)]
+        struct Pairs { pairs: IndexMap<String, RawPairEntry> }
+
+        let raw: Pairs = toml::from_str(r#"
+            [pairs]
+            SOL = { hl = "SOL" }
+            BTC = { hl = "BTC" }
+            ETH = { hl = "ETH", pdx = "ETH-USD-PERP" }
+        "#).unwrap();
+        let names: Vec<&str> = raw.pairs.keys().map(String::as_str).collect();
+        assert_eq!(names, ["SOL", "BTC", "ETH"]);
+    }
+}
@@ -0,0 +1,112 @@
+// src/cvd.rs — Cumulative volume delta (aggressive buys − sells) from the trade feeds
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::{broadcast, watch};
+use tokio::time::interval;
+use tracing::{info, warn};
+
+use crate::config::CvdConfig;
+use crate::merger::MergedBook;
+use crate::types::{now_ms, Exchange, Trade};
+
+/// How often the windows are recomputed and the sparkline sampled.
+const SAMPLE_MS: u64 = 1_000;
+/// Sparkline points kept (one per sample).
+const MAX_HISTORY: usize = 300;
+
+/// Signed volume over one rolling window, in base units.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct CvdWindow {
+    pub secs: u64,
+    pub hl:   f64,
+    pub pdx:  f64,
+}
+
+impl CvdWindow {
+    pub fn combined(&self) -> f64 { self.hl + self.pdx }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CvdState {
+    pub windows: Vec<CvdWindow>, // ascending by length
+    pub session: f64,            // combined CVD since startup or the last pair switch
+    pub history: VecDeque<f64>,  // `session`, sampled every SAMPLE_MS, oldest first
+}
+
+/// Spawns a task folding `trades_rx` into rolling per-venue and combined CVD,
+/// starting over whenever `merged_rx` moves to another pair.
+pub fn spawn_cvd(
+    cfg: &CvdConfig,
+    mut trades_rx: broadcast::Receiver<Trade>,
+    mut merged_rx: watch::Receiver<MergedBook>,
+) -> watch::Receiver<CvdState> {
+    let windows_secs = cfg.windows_secs.clone();
+    let longest_ms = windows_secs.iter().max().copied().unwrap_or(0) * 1_000;
+    let (tx, rx) = watch::channel(CvdState::default());
+    info!("[CVD] Windows {windows_secs:?}s");
+
+    tokio::spawn(async move {
+        // (ts_ms, exchange, signed size) for the longest window
+        let mut recent: VecDeque<(u64, Exchange, f64)> = VecDeque::new();
+        let mut session = 0.0;
+        // Hyperliquid replays recent trades on subscribe; count only what happens from now on
+        let mut started_ms = now_ms();
+        let mut coins = Default::default();
+        let mut ticker = interval(Duration::from_millis(SAMPLE_MS));
+
+        loop {
+            tokio::select! {
+                trade = trades_rx.recv() => match trade {
+                    Ok(t) if t.ts_ms >= started_ms => {
+                        let delta = t.side.sign() * t.size;
+                        session += delta;
+                        recent.push_back((t.ts_ms, t.exchange, delta));
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(n)) => warn!("[CVD] Skipped {n} trades (consumer lagging)"),
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                changed = merged_rx.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    // The new pair's feeds replay its recent trades too
+                    if merged_rx.borrow_and_update().pair_changed(&mut coins) {
+                        started_ms = now_ms();
+                        recent.clear();
+                        session = 0.0;
+                        tx.send_modify(|st| st.history.clear());
+                    }
+                }
+                _ = ticker.tick() => {
+                    let now = now_ms();
+                    while recent.front().is_some_and(|(ts, ..)| ts + longest_ms < now) {
+                        recent.pop_front();
+                    }
+                    let windows = windows_secs.iter().map(|&secs| {
+                        let mut w = CvdWindow { secs, ..Default::default() };
+                        for (_, ex, d) in recent.iter().filter(|(ts, ..)| ts + secs * 1_000 >= now) {
+                            match ex {
+                                Exchange::Hyperliquid => w.hl += d,
+                                Exchange::Paradex     => w.pdx += d,
+                            }
+                        }
+                        w
+                    }).collect();
+                    tx.send_modify(|st| {
+                        st.windows = windows;
+                        st.session = session;
+                        st.history.push_back(session);
+                        if st.history.len() > MAX_HISTORY {
+                            st.history.pop_front();
+                        }
+                    });
+                }
+            }
+        }
+    });
+    rx
+}
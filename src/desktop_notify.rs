@@ -0,0 +1,84 @@
+// src/desktop_notify.rs — Native OS notifications for high-severity events
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use notify_rust::{Notification, Timeout};
+use tokio::sync::watch;
+use tokio::time::interval;
+use tracing::{info, warn};
+
+use crate::arb::spawn_sustained_arb_watcher;
+use crate::config::DesktopNotifyConfig;
+use crate::merger::MergedBook;
+use crate::types::{now_ms, OrderBook};
+
+const APP_NAME: &str = "obt";
+
+/// How often feed connectivity is re-checked.
+const FEED_CHECK_MS: u64 = 500;
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Spawns a task raising one notification per sustained arb window, and one
+/// when both feeds have been disconnected for `feeds_down_ms`.
+pub fn spawn_desktop_notifications(
+    cfg: DesktopNotifyConfig,
+    coin: String,
+    hl_rx: watch::Receiver<Arc<OrderBook>>,
+    pdx_rx: watch::Receiver<Arc<OrderBook>>,
+    merged_rx: watch::Receiver<MergedBook>,
+) {
+    let mut arbs = spawn_sustained_arb_watcher(merged_rx, cfg.min_duration_ms, cfg.min_size);
+    tokio::spawn(async move {
+        let mut ticker = interval(Duration::from_millis(FEED_CHECK_MS));
+        let mut down_since: Option<u64> = None;
+        let mut down_notified = false;
+        info!("[NOTIFY] Desktop notifications enabled");
+
+        loop {
+            tokio::select! {
+                win = arbs.recv() => {
+                    let Some(win) = win else { break };
+                    notify(format!("{coin} arbitrage"), win.alert_text(&coin, now_ms()));
+                }
+                _ = ticker.tick() => {
+                    let both_down = !hl_rx.borrow().status.is_connected() && !pdx_rx.borrow().status.is_connected();
+                    let now = now_ms();
+                    match (both_down, down_since) {
+                        (true, None) => down_since = Some(now),
+                        (true, Some(since)) if !down_notified && now - since >= cfg.feeds_down_ms => {
+                            down_notified = true;
+                            notify(
+                                format!("{coin} feeds down"),
+                                format!("Hyperliquid and Paradex both disconnected for {:.0}s", (now - since) as f64 / 1000.0),
+                            );
+                        }
+                        (false, Some(_)) => {
+                            down_since = None;
+                            down_notified = false;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    });
+}
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+/// `show()` talks to the session bus synchronously, so keep it off the runtime.
+fn notify(summary: String, body: String) {
+    tokio::task::spawn_blocking(move || {
+        let result = Notification::new()
+            .appname(APP_NAME)
+            .summary(&summary)
+            .body(&body)
+            .timeout(Timeout::Milliseconds(10_000))
+            .show();
+        if let Err(e) = result {
+            warn!("[NOTIFY] Failed to show notification: {e}");
+        }
+    });
+}
@@ -0,0 +1,53 @@
+// src/direction.rs — Direction of the last price change, for ▲/▼ markers in the TUI
+
+use std::time::{Duration, Instant};
+
+use crate::types::OrderBook;
+
+/// How long a move stays marked. Long enough to read during rapid flipping,
+/// short enough that a quiet price goes back to neutral.
+const DECAY: Duration = Duration::from_millis(1_500);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dir { Up, Down }
+
+/// Last value of one price and which way it last moved.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tick {
+    last:  Option<f64>,
+    moved: Option<(Dir, Instant)>,
+}
+
+impl Tick {
+    fn update(&mut self, px: Option<f64>, now: Instant) {
+        if let (Some(prev), Some(px)) = (self.last, px)
+            && px != prev
+        {
+            self.moved = Some((if px > prev { Dir::Up } else { Dir::Down }, now));
+        }
+        if px.is_some() {
+            self.last = px;
+        }
+    }
+
+    /// Direction of the last move, or `None` once it is older than `DECAY`.
+    pub fn dir(&self, now: Instant) -> Option<Dir> {
+        self.moved.filter(|(_, at)| now.duration_since(*at) < DECAY).map(|(d, _)| d)
+    }
+}
+
+/// Mid, best bid and best ask direction for one venue.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BookTicks {
+    pub mid: Tick,
+    pub bid: Tick,
+    pub ask: Tick,
+}
+
+impl BookTicks {
+    pub fn update(&mut self, book: &OrderBook, now: Instant) {
+        self.mid.update(book.mid(), now);
+        self.bid.update(book.best_bid(), now);
+        self.ask.update(book.best_ask(), now);
+    }
+}
@@ -0,0 +1,180 @@
+// src/exec.rs — Live order state shared by the exchange execution clients
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+use tokio::sync::{mpsc, watch};
+
+use crate::account::AccountState;
+use crate::merger::{with_bbo, MergedBook};
+use crate::supervisor::FeedStatus;
+use crate::types::{Exchange, OrderBook, Side};
+
+/// Orders kept in `ExecState::orders` (oldest dropped first).
+const MAX_ORDERS: usize = 100;
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// What the TUI asks an execution client to do.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExecCommand {
+    /// Send this venue's leg of the current arb: buy at its ask if it is the
+    /// ask venue, sell at its bid if it is the bid venue.
+    ArbLeg,
+    /// Cancel every order still resting on this venue.
+    CancelAll,
+}
+
+// ─── Order state ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum OrderStatus {
+    Pending,                  // sent, no ack yet
+    Resting { oid: String },  // acked, on the book
+    Filled { oid: String, avg_px: f64, size: f64 },
+    Cancelled { oid: String },
+    Rejected(String),         // by us, the API or the matching engine
+}
+
+impl OrderStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrderStatus::Pending          => "pending",
+            OrderStatus::Resting { .. }   => "resting",
+            OrderStatus::Filled { .. }    => "filled",
+            OrderStatus::Cancelled { .. } => "cancelled",
+            OrderStatus::Rejected(_)      => "rejected",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveOrder {
+    pub id:       u64, // local sequence number
+    pub ts_ms:    u64,
+    pub exchange: Exchange,
+    pub side:     Side,
+    pub price:    f64,
+    pub size:     f64,
+    pub status:   OrderStatus,
+}
+
+/// Orders sent by one execution client this session, newest first.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExecState {
+    pub orders:   VecDeque<LiveOrder>,
+    pub last_msg: Option<String>, // outcome of the most recent command
+    next_id:      u64,
+}
+
+impl ExecState {
+    /// Records a new pending order and returns its local id.
+    pub fn open(&mut self, order: LiveOrder) -> u64 {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.orders.push_front(LiveOrder { id, ..order });
+        self.orders.truncate(MAX_ORDERS);
+        id
+    }
+
+    pub fn set_status(&mut self, id: u64, status: OrderStatus) {
+        if let Some(o) = self.orders.iter_mut().find(|o| o.id == id) {
+            o.status = status;
+        }
+    }
+
+    /// Exchange ids of orders currently resting.
+    pub fn resting_oids(&self) -> Vec<String> {
+        self.orders.iter()
+            .filter_map(|o| match &o.status {
+                OrderStatus::Resting { oid } => Some(oid.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Signed size of the orders on `side` still working, pending or resting,
+    /// which may yet fill.
+    pub fn working(&self, side: Side) -> f64 {
+        let size: f64 = self.orders.iter()
+            .filter(|o| o.side == side && matches!(o.status, OrderStatus::Pending | OrderStatus::Resting { .. }))
+            .map(|o| o.size)
+            .sum();
+        side.sign() * size
+    }
+
+    pub fn mark_cancelled(&mut self, oid: &str) {
+        for o in &mut self.orders {
+            if matches!(&o.status, OrderStatus::Resting { oid: r } if r == oid) {
+                o.status = OrderStatus::Cancelled { oid: oid.to_string() };
+            }
+        }
+    }
+}
+
+// ─── Handle ───────────────────────────────────────────────────────────────────
+
+/// Send commands through `tx`; read order state from `state_rx` and what the
+/// venue's private channels report from `account_rx`.
+pub struct ExecHandle {
+    pub tx:         mpsc::Sender<ExecCommand>,
+    pub state_rx:   watch::Receiver<ExecState>,
+    pub account_rx: watch::Receiver<AccountState>,
+}
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+/// The leg `book`'s venue takes in the current arb, if it is one of the two
+/// venues: `(side, price)` at the top of its own book. The price is the
+/// venue's own, not the merged book's, whose levels are in the `[quote]` unit
+/// and may belong to the other venue. None while the venue is stale.
+pub fn arb_leg(merged: &MergedBook, book: &OrderBook) -> Option<(Side, f64)> {
+    let sig = &merged.signals;
+    if !sig.is_arb() || book.status == FeedStatus::Stale {
+        return None;
+    }
+    // The touch the signal was computed from, BBO channel included
+    let book = with_bbo(book);
+    if sig.best_ask_exchange.as_ref() == Some(&book.exchange) {
+        book.best_ask().map(|px| (Side::Buy, px))
+    } else if sig.best_bid_exchange.as_ref() == Some(&book.exchange) {
+        book.best_bid().map(|px| (Side::Sell, px))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FeesConfig;
+    use crate::types::Level;
+
+    fn book(exchange: Exchange, bid: &str, ask: &str) -> OrderBook {
+        let mut book = OrderBook::new(exchange, "ETH");
+        book.bids = vec![Level::new(bid.parse().unwrap(), "1".parse().unwrap(), 1)];
+        book.asks = vec![Level::new(ask.parse().unwrap(), "1".parse().unwrap(), 1)];
+        book
+    }
+
+    #[test]
+    fn legs_are_priced_from_the_venues_own_book() {
+        // HL quotes in a currency worth 0.5 of the unit; merged from the converted book
+        let hl = book(Exchange::Hyperliquid, "198", "199");
+        let hl_in_unit = book(Exchange::Hyperliquid, "99", "99.5");
+        let pdx = book(Exchange::Paradex, "101", "102");
+        let merged = MergedBook::build(&hl_in_unit, &pdx, 10, &FeesConfig::default());
+        assert!(merged.signals.is_arb());
+        assert_eq!(arb_leg(&merged, &hl), Some((Side::Buy, 199.0)));
+        assert_eq!(arb_leg(&merged, &pdx), Some((Side::Sell, 101.0)));
+    }
+
+    #[test]
+    fn stale_venue_has_no_leg() {
+        let hl = book(Exchange::Hyperliquid, "99", "99.5");
+        let mut pdx = book(Exchange::Paradex, "101", "102");
+        let merged = MergedBook::build(&hl, &pdx, 10, &FeesConfig::default());
+        pdx.status = FeedStatus::Stale;
+        assert_eq!(arb_leg(&merged, &pdx), None);
+    }
+}
@@ -0,0 +1,92 @@
+// src/feeds.rs — The two exchange feeds for the current pair, restartable on another
+//
+// The book channels outlive the feeds. Switching pairs stops both feeds,
+// resets their books and starts them again on the new symbols, so the merger,
+// the TUI and everything else reading the books carry on without rewiring.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{broadcast, watch};
+use tracing::info;
+
+use crate::clock::ClockOffset;
+use crate::config::PairConfig;
+use crate::quarantine::Quarantine;
+use crate::supervisor::{FeedHandle, FeedSettings};
+use crate::types::{Exchange, OrderBook, Trade};
+use crate::{hyperliquid_mda, paradex_mda};
+
+pub struct Feeds {
+    pair:       PairConfig,
+    hl_tx:      watch::Sender<Arc<OrderBook>>,
+    pdx_tx:     watch::Sender<Arc<OrderBook>>,
+    trades_tx:  broadcast::Sender<Trade>,
+    clocks:     (watch::Receiver<ClockOffset>, watch::Receiver<ClockOffset>), // (HL, PDX)
+    quarantine: Quarantine,
+    settings:   (FeedSettings, FeedSettings),                                 // (HL, PDX)
+    handles:    Option<(FeedHandle, FeedHandle)>,                             // None once shut down
+}
+
+impl Feeds {
+    /// Starts both feeds on `pair`.
+    pub fn start(
+        pair: PairConfig,
+        books: (watch::Sender<Arc<OrderBook>>, watch::Sender<Arc<OrderBook>>),
+        trades_tx: broadcast::Sender<Trade>,
+        clocks: (watch::Receiver<ClockOffset>, watch::Receiver<ClockOffset>),
+        quarantine: Quarantine,
+        settings: (FeedSettings, FeedSettings),
+    ) -> Self {
+        let (hl_tx, pdx_tx) = books;
+        let mut feeds = Self { pair, hl_tx, pdx_tx, trades_tx, clocks, quarantine, settings, handles: None };
+        feeds.spawn();
+        feeds
+    }
+
+    pub fn pair(&self) -> &PairConfig {
+        &self.pair
+    }
+
+    fn spawn(&mut self) {
+        let hl = hyperliquid_mda::spawn_hl_feed(self.pair.hl_symbol.clone(), self.hl_tx.clone(), self.trades_tx.clone(),
+            self.clocks.0.clone(), self.quarantine.clone(), self.settings.0);
+        let pdx = paradex_mda::spawn_pdx_feed(self.pair.pdx_symbol.clone(), self.pdx_tx.clone(), self.trades_tx.clone(),
+            self.clocks.1.clone(), self.quarantine.clone(), self.settings.1);
+        self.handles = Some((hl, pdx));
+    }
+
+    /// Starts `pair`'s feeds with the same settings as these, on books of their
+    /// own; their trades go nowhere. For the pair grid.
+    pub fn start_other(&self, pair: PairConfig) -> (Feeds, watch::Receiver<Arc<OrderBook>>, watch::Receiver<Arc<OrderBook>>) {
+        let (hl_tx, hl_rx) = watch::channel(Arc::new(OrderBook::new(Exchange::Hyperliquid, &pair.hl_symbol)));
+        let (pdx_tx, pdx_rx) = watch::channel(Arc::new(OrderBook::new(Exchange::Paradex, &pair.pdx_symbol)));
+        let (trades_tx, _) = broadcast::channel(1);
+        let feeds = Feeds::start(pair, (hl_tx, pdx_tx), trades_tx, self.clocks.clone(), self.quarantine.clone(), self.settings);
+        (feeds, hl_rx, pdx_rx)
+    }
+
+    /// Stops both feeds, empties their books and starts them again on `pair`.
+    /// The book versions keep counting up, so readers see the reset as a change.
+    pub async fn switch(&mut self, pair: PairConfig, wait: Duration) {
+        self.shutdown(wait).await;
+        for (tx, exchange, symbol) in [
+            (&self.hl_tx, Exchange::Hyperliquid, &pair.hl_symbol),
+            (&self.pdx_tx, Exchange::Paradex, &pair.pdx_symbol),
+        ] {
+            let mut book = OrderBook::new(exchange, symbol);
+            book.version = tx.borrow().version + 1;
+            tx.send_replace(Arc::new(book));
+        }
+        info!("Switched pair {} → {} (HL={} PDX={})", self.pair.name, pair.name, pair.hl_symbol, pair.pdx_symbol);
+        self.pair = pair;
+        self.spawn();
+    }
+
+    /// Closes both sockets, waiting up to `wait` for each close handshake.
+    pub async fn shutdown(&mut self, wait: Duration) {
+        if let Some((hl, pdx)) = self.handles.take() {
+            tokio::join!(hl.shutdown(wait), pdx.shutdown(wait));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+// src/grpc.rs — tonic gRPC server streaming book updates (proto/obt.proto)
+
+// `tonic::Status` is the error type the service trait requires; we can't box it.
+#![allow(clippy::result_large_err)]
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use futures_util::{Stream, StreamExt};
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+use tokio_stream::wrappers::{TcpListenerStream, WatchStream};
+use tonic::{transport::Server, Request, Response, Status};
+use tracing::{error, info};
+
+use crate::config::GrpcConfig;
+use crate::merger::{MergedBook, MergedLevel, Signals};
+use crate::types::{Exchange, Level, OrderBook};
+
+/// Generated protobuf types and service traits.
+#[allow(clippy::all)]
+pub mod pb {
+    tonic::include_proto!("obt.v1");
+}
+
+use pb::book_service_server::{BookService, BookServiceServer};
+
+type BoxStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'static>>;
+
+// ─── Service ──────────────────────────────────────────────────────────────────
+
+struct BookServiceImpl {
+    hl_rx:     watch::Receiver<Arc<OrderBook>>,
+    pdx_rx:    watch::Receiver<Arc<OrderBook>>,
+    merged_rx: watch::Receiver<MergedBook>,
+}
+
+#[tonic::async_trait]
+impl BookService for BookServiceImpl {
+    type StreamMergedBookStream = BoxStream<pb::MergedBook>;
+    type StreamOrderBookStream  = BoxStream<pb::OrderBook>;
+
+    async fn stream_merged_book(
+        &self,
+        _req: Request<pb::StreamMergedBookRequest>,
+    ) -> Result<Response<Self::StreamMergedBookStream>, Status> {
+        // WatchStream yields the current value first, then every change
+        let stream = WatchStream::new(self.merged_rx.clone()).map(|m| Ok(pb::MergedBook::from(&m)));
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn stream_order_book(
+        &self,
+        req: Request<pb::StreamOrderBookRequest>,
+    ) -> Result<Response<Self::StreamOrderBookStream>, Status> {
+        let rx = match req.into_inner().exchange() {
+            pb::Exchange::Hyperliquid => self.hl_rx.clone(),
+            pb::Exchange::Paradex     => self.pdx_rx.clone(),
+        };
+        let stream = WatchStream::new(rx).map(|b| Ok(pb::OrderBook::from(&*b)));
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Binds the listener (so a bad address fails at startup) and serves in the background.
+pub async fn spawn_grpc_server(
+    cfg: &GrpcConfig,
+    hl_rx: watch::Receiver<Arc<OrderBook>>,
+    pdx_rx: watch::Receiver<Arc<OrderBook>>,
+    merged_rx: watch::Receiver<MergedBook>,
+) -> Result<()> {
+    let listener = TcpListener::bind(cfg.bind)
+        .await
+        .with_context(|| format!("Failed to bind gRPC server on {}", cfg.bind))?;
+
+    let service = BookServiceImpl { hl_rx, pdx_rx, merged_rx };
+
+    info!("[GRPC] Listening on {}", cfg.bind);
+    tokio::spawn(async move {
+        let result = Server::builder()
+            .add_service(BookServiceServer::new(service))
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await;
+        if let Err(e) = result {
+            error!("[GRPC] Server error: {e}");
+        }
+    });
+    Ok(())
+}
+
+// ─── Conversions ──────────────────────────────────────────────────────────────
+
+impl From<&Exchange> for pb::Exchange {
+    fn from(ex: &Exchange) -> Self {
+        match ex {
+            Exchange::Hyperliquid => pb::Exchange::Hyperliquid,
+            Exchange::Paradex     => pb::Exchange::Paradex,
+        }
+    }
+}
+
+fn ex_i32(ex: &Exchange) -> i32 {
+    pb::Exchange::from(ex) as i32
+}
+
+impl From<&Level> for pb::Level {
+    fn from(l: &Level) -> Self {
+        Self { price: l.price.to_string(), size: l.size.to_string(), count: l.count }
+    }
+}
+
+impl From<&OrderBook> for pb::OrderBook {
+    fn from(b: &OrderBook) -> Self {
+        Self {
+            exchange:       ex_i32(&b.exchange),
+            coin:           b.coin.clone(),
+            bids:           b.bids.iter().map(pb::Level::from).collect(),
+            asks:           b.asks.iter().map(pb::Level::from).collect(),
+            last_update_ms: b.last_update_ms,
+            connected:      b.status.is_connected(),
+            message_count:  b.message_count,
+        }
+    }
+}
+
+impl From<&MergedLevel> for pb::MergedLevel {
+    fn from(l: &MergedLevel) -> Self {
+        Self { price: l.price, size: l.size, exchange: ex_i32(&l.exchange) }
+    }
+}
+
+impl From<&Signals> for pb::Signals {
+    fn from(s: &Signals) -> Self {
+        Self {
+            cross_spread:         s.cross_spread,
+            cross_spread_pct:     s.cross_spread_pct,
+            best_bid_exchange:    s.best_bid_exchange.as_ref().map(ex_i32),
+            best_ask_exchange:    s.best_ask_exchange.as_ref().map(ex_i32),
+            liquidity_imbalance:  s.liquidity_imbalance,
+            total_bid_usd:        s.total_bid_usd,
+            total_ask_usd:        s.total_ask_usd,
+            net_cross_spread:     s.net_cross_spread,
+            net_cross_spread_pct: s.net_cross_spread_pct,
+            arb_size:             s.arb_size,
+            arb_profit_usd:       s.arb_profit_usd,
+            arb_confidence:       s.arb_confidence,
+        }
+    }
+}
+
+impl From<&MergedBook> for pb::MergedBook {
+    fn from(m: &MergedBook) -> Self {
+        Self {
+            bids:    m.bids.iter().map(pb::MergedLevel::from).collect(),
+            asks:    m.asks.iter().map(pb::MergedLevel::from).collect(),
+            signals: Some(pb::Signals::from(&m.signals)),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// src/http.rs — Embedded HTTP server: health check + JSON snapshot API
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+use tracing::{error, info};
+
+use crate::config::HttpConfig;
+use crate::merger::{MergedBook, Signals};
+use crate::types::{now_ms, OrderBook};
+
+// ─── Shared handler state ─────────────────────────────────────────────────────
+
+#[derive(Clone)]
+struct HttpState {
+    hl_rx:      watch::Receiver<Arc<OrderBook>>,
+    pdx_rx:     watch::Receiver<Arc<OrderBook>>,
+    merged_rx:  watch::Receiver<MergedBook>,
+    max_age_ms: u64,
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Binds the listener (so a bad address fails at startup) and serves in the background.
+pub async fn spawn_http_server(
+    cfg: &HttpConfig,
+    hl_rx: watch::Receiver<Arc<OrderBook>>,
+    pdx_rx: watch::Receiver<Arc<OrderBook>>,
+    merged_rx: watch::Receiver<MergedBook>,
+) -> Result<()> {
+    let listener = TcpListener::bind(cfg.bind)
+        .await
+        .with_context(|| format!("Failed to bind HTTP server on {}", cfg.bind))?;
+
+    let state = HttpState { hl_rx, pdx_rx, merged_rx, max_age_ms: cfg.health_max_age_ms };
+    let app = Router::new()
+        .route("/healthz", get(healthz))
+        .route("/book/merged", get(book_merged))
+        .route("/book/hl", get(book_hl))
+        .route("/book/pdx", get(book_pdx))
+        .route("/signals", get(signals))
+        .with_state(state);
+
+    info!("[HTTP] Listening on {}", cfg.bind);
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("[HTTP] Server error: {e}");
+        }
+    });
+    Ok(())
+}
+
+// ─── Handlers ─────────────────────────────────────────────────────────────────
+
+/// 200 when both feeds are connected and updated within `max_age_ms`, else 503.
+async fn healthz(State(state): State<HttpState>) -> (StatusCode, Json<Value>) {
+    let now = now_ms();
+    let hl  = feed_health(&state.hl_rx.borrow(), now, state.max_age_ms);
+    let pdx = feed_health(&state.pdx_rx.borrow(), now, state.max_age_ms);
+
+    let healthy = hl.0 && pdx.0;
+    let status = if healthy { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    let body = json!({
+        "status": if healthy { "ok" } else { "unhealthy" },
+        "hl":     hl.1,
+        "pdx":    pdx.1,
+    });
+    (status, Json(body))
+}
+
+/// Latest merged book (levels + signals).
+async fn book_merged(State(state): State<HttpState>) -> Json<MergedBook> {
+    Json(state.merged_rx.borrow().clone())
+}
+
+/// Latest Hyperliquid book as held by the feed.
+async fn book_hl(State(state): State<HttpState>) -> Json<OrderBook> {
+    Json(OrderBook::clone(&state.hl_rx.borrow()))
+}
+
+/// Latest Paradex book as held by the feed.
+async fn book_pdx(State(state): State<HttpState>) -> Json<OrderBook> {
+    Json(OrderBook::clone(&state.pdx_rx.borrow()))
+}
+
+/// Signals only, for lightweight polling.
+async fn signals(State(state): State<HttpState>) -> Json<Signals> {
+    Json(state.merged_rx.borrow().signals.clone())
+}
+
+fn feed_health(book: &OrderBook, now: u64, max_age_ms: u64) -> (bool, Value) {
+    let age = book.age_ms(now);
+    let fresh = age.is_some_and(|a| a <= max_age_ms);
+    let ok = book.status.is_connected() && fresh;
+    (ok, json!({
+        "connected": book.status.is_connected(),
+        "status": book.status,
+        "age_ms":    age,
+        "fresh":     fresh,
+    }))
+}
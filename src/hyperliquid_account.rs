@@ -0,0 +1,175 @@
+// src/hyperliquid_account.rs — Hyperliquid account feed (userFills + webData2)
+//
+// Hyperliquid's user channels are public by address, so no signature is
+// needed — only the account (not API wallet) address.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio::sync::{watch, Mutex};
+use tokio::time::{interval, sleep};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, error, info, warn};
+
+use crate::account::{AccountFill, AccountPosition, AccountState};
+use crate::types::{now_ms, Exchange, InboundEnvelope, OutboundMsg, Side, Subscription};
+
+const MAINNET_WS_URL: &str = "wss://api.hyperliquid.xyz/ws";
+const TESTNET_WS_URL: &str = "wss://api.hyperliquid-testnet.xyz/ws";
+const RECONNECT_DELAY_SECS: u64 = 3;
+const HEARTBEAT_SECS: u64 = 20;
+
+/// Spawns a background task keeping `AccountState` current for `user`.
+pub fn spawn_hl_account(user: String, testnet: bool) -> watch::Receiver<AccountState> {
+    let (tx, rx) = watch::channel(AccountState::new(Exchange::Hyperliquid));
+    let url = if testnet { TESTNET_WS_URL } else { MAINNET_WS_URL };
+    tokio::spawn(async move {
+        loop {
+            info!("[HL-ACCT] Connecting…");
+            match run_connection(url, &user, &tx).await {
+                Ok(_)  => warn!("[HL-ACCT] Connection closed cleanly — reconnecting"),
+                Err(e) => error!("[HL-ACCT] Connection error: {e:#} — reconnecting"),
+            }
+            tx.send_modify(|a| { a.connected = false; a.synced = false; });
+            sleep(Duration::from_secs(RECONNECT_DELAY_SECS)).await;
+        }
+    });
+    rx
+}
+
+async fn run_connection(url: &str, user: &str, tx: &watch::Sender<AccountState>) -> Result<()> {
+    let (ws_stream, _) = connect_async(url)
+        .await
+        .context("WebSocket connect failed")?;
+
+    info!("[HL-ACCT] Connected");
+    tx.send_modify(|a| a.connected = true);
+
+    let (write, mut read) = ws_stream.split();
+    let write = Arc::new(Mutex::new(write));
+
+    // Subscribe
+    for subscription in [
+        Subscription::UserFills { user: user.to_string() },
+        Subscription::WebData2 { user: user.to_string() },
+    ] {
+        let text = serde_json::to_string(&OutboundMsg::Subscribe { subscription })?;
+        write.lock().await.send(Message::Text(text)).await?;
+    }
+    info!("[HL-ACCT] Subscribed to userFills + webData2 for {user}");
+
+    // Heartbeat task
+    let write_clone = Arc::clone(&write);
+    let heartbeat = tokio::spawn(async move {
+        let mut ticker = interval(Duration::from_secs(HEARTBEAT_SECS));
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let ping = r#"{"method":"ping"}"#;
+            if let Err(e) = write_clone.lock().await.send(Message::Text(ping.to_string())).await {
+                error!("[HL-ACCT] Heartbeat send failed: {e}");
+                break;
+            }
+        }
+    });
+
+    // Message loop
+    let mut sync = SyncProgress::default();
+    while let Some(msg) = read.next().await {
+        match msg? {
+            Message::Text(text) => handle_text(&text, tx, &mut sync),
+            Message::Close(_)   => { info!("[HL-ACCT] Server sent close frame"); break; }
+            _ => {}
+        }
+    }
+
+    heartbeat.abort();
+    Ok(())
+}
+
+/// What this connection has received so far. The account is synced once both
+/// the userFills snapshot (today's fills) and a webData2 (positions) are in.
+#[derive(Default)]
+struct SyncProgress {
+    fills:     bool,
+    positions: bool,
+}
+
+fn handle_text(text: &str, tx: &watch::Sender<AccountState>, sync: &mut SyncProgress) {
+    if text.contains(r#""pong""#) {
+        return;
+    }
+
+    let env: InboundEnvelope = match serde_json::from_str(text) {
+        Ok(v)  => v,
+        Err(e) => { warn!("[HL-ACCT] Parse error: {e} | {text:.200}"); return; }
+    };
+
+    match env.channel.as_str() {
+        "subscriptionResponse" => debug!("[HL-ACCT] Subscription confirmed"),
+        "userFills" => {
+            let fills: Vec<AccountFill> = env.data["fills"].as_array()
+                .map(|a| a.iter().filter_map(parse_fill).collect())
+                .unwrap_or_default();
+            if env.data["isSnapshot"].as_bool().unwrap_or(false) {
+                sync.fills = true;
+            } else {
+                for f in &fills {
+                    info!("[HL-ACCT] Fill {:?} {} {} @ {} (fee {})", f.side, f.size, f.market, f.price, f.fee);
+                }
+            }
+            tx.send_modify(|a| {
+                a.add_fills(fills);
+                a.synced = sync.fills && sync.positions;
+                a.last_update_ms = now_ms();
+            });
+        }
+        "webData2" => {
+            let state = &env.data["clearinghouseState"];
+            let positions: Vec<AccountPosition> = state["assetPositions"].as_array()
+                .map(|a| a.iter().filter_map(|p| parse_position(&p["position"])).collect())
+                .unwrap_or_default();
+            sync.positions = true;
+            tx.send_modify(|a| {
+                a.synced = sync.fills && sync.positions;
+                a.account_value = num(&state["marginSummary"]["accountValue"]);
+                a.free_collateral = num(&state["withdrawable"]);
+                a.positions = positions;
+                a.last_update_ms = now_ms();
+            });
+        }
+        other => debug!("[HL-ACCT] Unhandled channel: {other}"),
+    }
+}
+
+/// Hyperliquid sends numbers as strings.
+fn num(v: &Value) -> Option<f64> {
+    v.as_str().and_then(|s| s.parse().ok())
+}
+
+/// `{coin, px, sz, side: "B" | "A", time, fee, closedPnl, oid, …}`
+fn parse_fill(v: &Value) -> Option<AccountFill> {
+    Some(AccountFill {
+        ts_ms:      v["time"].as_u64()?,
+        market:     v["coin"].as_str()?.to_string(),
+        side:       if v["side"] == "B" { Side::Buy } else { Side::Sell },
+        price:      num(&v["px"])?,
+        size:       num(&v["sz"])?,
+        fee:        num(&v["fee"]).unwrap_or(0.0),
+        closed_pnl: num(&v["closedPnl"]).unwrap_or(0.0),
+        order_id:   v["oid"].to_string(),
+    })
+}
+
+/// `{coin, szi, entryPx, unrealizedPnl, …}`; `szi` is signed.
+fn parse_position(v: &Value) -> Option<AccountPosition> {
+    Some(AccountPosition {
+        market:         v["coin"].as_str()?.to_string(),
+        qty:            num(&v["szi"])?,
+        entry_px:       num(&v["entryPx"]).unwrap_or(0.0),
+        unrealized_pnl: num(&v["unrealizedPnl"]).unwrap_or(0.0),
+    })
+}
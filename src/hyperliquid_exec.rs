@@ -0,0 +1,448 @@
+// src/hyperliquid_exec.rs — Signed order placement and cancels on Hyperliquid
+//
+// Hyperliquid "L1 actions" are signed by an API (agent) wallet:
+//   connection_id = keccak256(msgpack(action) ‖ nonce_be_u64 ‖ 0x00)
+// then EIP-712 signed as Agent { source, connectionId } under the "Exchange"
+// domain (chain id 1337). The action is posted to /exchange with the nonce
+// and the (r, s, v) signature.
+
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use k256::ecdsa::SigningKey;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tiny_keccak::{Hasher, Keccak};
+use tokio::sync::{mpsc, watch};
+use tracing::{info, warn};
+
+use crate::account::AccountState;
+use crate::config::{HlExecConfig, HlTif};
+use crate::hyperliquid_account::spawn_hl_account;
+use crate::exec::{arb_leg, ExecCommand, ExecHandle, ExecState, LiveOrder, OrderStatus};
+use crate::merger::MergedBook;
+use crate::risk::RiskGate;
+use crate::types::{now_ms, Exchange, OrderBook, Side};
+
+const MAINNET_URL: &str = "https://api.hyperliquid.xyz";
+const TESTNET_URL: &str = "https://api.hyperliquid-testnet.xyz";
+
+/// Perp prices may carry at most this many decimals, minus the asset's szDecimals.
+const MAX_PRICE_DECIMALS: i32 = 6;
+
+// ─── Wire actions ─────────────────────────────────────────────────────────────
+//
+// Field order and names matter: the msgpack encoding of these structs is what
+// gets hashed, and it must match what the exchange re-encodes.
+
+#[derive(Serialize)]
+struct OrderAction {
+    #[serde(rename = "type")]
+    kind:     &'static str, // "order"
+    orders:   Vec<OrderWire>,
+    grouping: &'static str, // "na"
+}
+
+#[derive(Serialize)]
+struct OrderWire {
+    a: u32,    // asset index
+    b: bool,   // is buy
+    p: String, // limit price
+    s: String, // size
+    r: bool,   // reduce only
+    t: OrderTypeWire,
+}
+
+#[derive(Serialize)]
+struct OrderTypeWire {
+    limit: LimitWire,
+}
+
+#[derive(Serialize)]
+struct LimitWire {
+    tif: HlTif,
+}
+
+#[derive(Serialize)]
+struct CancelAction {
+    #[serde(rename = "type")]
+    kind:    &'static str, // "cancel"
+    cancels: Vec<CancelWire>,
+}
+
+#[derive(Serialize)]
+struct CancelWire {
+    a: u32, // asset index
+    o: u64, // order id
+}
+
+// ─── Client ───────────────────────────────────────────────────────────────────
+
+struct HlClient {
+    http:        reqwest::Client,
+    base_url:    &'static str,
+    mainnet:     bool,
+    key:         SigningKey,
+    asset:       u32,
+    sz_decimals: i32,
+    last_nonce:  u64,
+    coin:        String,
+    risk:        RiskGate,
+    account_rx:  watch::Receiver<AccountState>,
+}
+
+impl HlClient {
+    /// Signs `action` and posts it to /exchange; returns the `response.data`
+    /// object of an "ok" reply.
+    async fn post_action<A: Serialize>(&mut self, action: &A) -> Result<Value> {
+        // Nonces must be unique and roughly current; keep them strictly increasing
+        let nonce = now_ms().max(self.last_nonce + 1);
+        self.last_nonce = nonce;
+
+        let (r, s, v) = sign_l1_action(&self.key, action, nonce, self.mainnet)?;
+        let body = json!({
+            "action":       action,
+            "nonce":        nonce,
+            "signature":    { "r": format!("0x{}", hex::encode(r)), "s": format!("0x{}", hex::encode(s)), "v": v },
+            "vaultAddress": null,
+        });
+
+        let resp: Value = self.http
+            .post(format!("{}/exchange", self.base_url))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.without_url())
+            .context("request failed")?
+            .json()
+            .await
+            .context("unparseable response")?;
+
+        if resp["status"] != "ok" {
+            bail!("{}", resp["response"].as_str().unwrap_or("exchange returned an error"));
+        }
+        Ok(resp["response"]["data"].clone())
+    }
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Looks up the asset (so a bad symbol or unreachable API fails at startup),
+/// then spawns a task executing commands one at a time.
+pub async fn spawn_hl_exec(
+    cfg: &HlExecConfig,
+    coin: &str,
+    risk: RiskGate,
+    merged_rx: watch::Receiver<MergedBook>,
+    book_rx: watch::Receiver<Arc<OrderBook>>,
+) -> Result<ExecHandle> {
+    let key = SigningKey::from_slice(&cfg.private_key).context("hl_exec.private_key is not a valid secp256k1 key")?;
+    let base_url = if cfg.testnet { TESTNET_URL } else { MAINNET_URL };
+    let http = reqwest::Client::new();
+    let (asset, sz_decimals) = fetch_asset(&http, base_url, coin).await?;
+
+    // Fills and positions belong to the account the API wallet trades for
+    let user = cfg.account_address.clone().unwrap_or_else(|| address(&key));
+    let account_rx = spawn_hl_account(user, cfg.testnet);
+
+    let mut client = HlClient {
+        http, base_url, mainnet: !cfg.testnet, key, asset, sz_decimals, last_nonce: 0,
+        coin: coin.to_string(), risk, account_rx: account_rx.clone(),
+    };
+    let (tx, mut rx) = mpsc::channel::<ExecCommand>(8);
+    let (state_tx, state_rx) = watch::channel(ExecState::default());
+    let (order_size, tif) = (cfg.order_size, cfg.tif);
+
+    info!(
+        "[HL-EXEC] Live trading enabled ({}) as API wallet {}, {coin} asset {asset}, order size {order_size} {tif:?}",
+        if cfg.testnet { "testnet" } else { "mainnet" },
+        address(&client.key),
+    );
+
+    tokio::spawn(async move {
+        while let Some(cmd) = rx.recv().await {
+            match cmd {
+                ExecCommand::ArbLeg => {
+                    let (merged, book) = (merged_rx.borrow().clone(), book_rx.borrow().clone());
+                    place_arb_leg(&mut client, &state_tx, &merged, &book, order_size, tif).await;
+                }
+                ExecCommand::CancelAll => cancel_all(&mut client, &state_tx).await,
+            }
+        }
+    });
+
+    Ok(ExecHandle { tx, state_rx, account_rx })
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+async fn place_arb_leg(
+    client: &mut HlClient,
+    state_tx: &watch::Sender<ExecState>,
+    merged: &MergedBook,
+    book: &OrderBook,
+    order_size: f64,
+    tif: HlTif,
+) {
+    let Some((side, price)) = arb_leg(merged, book) else {
+        return report(state_tx, "no HL arb leg right now".to_string());
+    };
+    let size = floor_to(order_size.min(merged.signals.arb_size), client.sz_decimals);
+    if size <= 0.0 {
+        return report(state_tx, format!("arb size rounds to 0 at {} size decimals", client.sz_decimals));
+    }
+    let price = match round_price(price, client.sz_decimals) {
+        Ok(px) => px,
+        Err(e) => return report(state_tx, format!("{side:?} not sent: {e:#}")),
+    };
+
+    let working = state_tx.borrow().working(side);
+    let verdict = client.risk.check_live("HL-EXEC", &client.account_rx.borrow(), working, &client.coin, side, size);
+    if let Err(reason) = verdict {
+        return report(state_tx, format!("{side:?} blocked: {reason}"));
+    }
+
+    let mut id = 0;
+    state_tx.send_modify(|st| {
+        id = st.open(LiveOrder {
+            id: 0, ts_ms: now_ms(), exchange: Exchange::Hyperliquid, side, price, size, status: OrderStatus::Pending,
+        });
+    });
+
+    let action = OrderAction {
+        kind: "order",
+        orders: vec![OrderWire {
+            a: client.asset,
+            b: side == Side::Buy,
+            p: float_to_wire(price),
+            s: float_to_wire(size),
+            r: false,
+            t: OrderTypeWire { limit: LimitWire { tif } },
+        }],
+        grouping: "na",
+    };
+    info!("[HL-EXEC] {side:?} {size} @ {price} {tif:?}");
+
+    let status = match client.post_action(&action).await {
+        Ok(data) => order_status(&data["statuses"][0]),
+        Err(e) => OrderStatus::Rejected(format!("{e:#}")),
+    };
+    let msg = match &status {
+        OrderStatus::Resting { oid }             => format!("{side:?} {size} @ {price} resting (oid {oid})"),
+        OrderStatus::Filled { avg_px, size, .. } => format!("{side:?} {size} filled @ {avg_px}"),
+        OrderStatus::Rejected(reason)            => format!("{side:?} rejected: {reason}"),
+        other                                    => format!("{side:?} {}", other.label()),
+    };
+    match status {
+        OrderStatus::Rejected(_) => warn!("[HL-EXEC] {msg}"),
+        _                        => info!("[HL-EXEC] {msg}"),
+    }
+    state_tx.send_modify(|st| {
+        st.set_status(id, status);
+        st.last_msg = Some(msg);
+    });
+}
+
+async fn cancel_all(client: &mut HlClient, state_tx: &watch::Sender<ExecState>) {
+    let oids = state_tx.borrow().resting_oids();
+    if oids.is_empty() {
+        return report(state_tx, "no resting orders to cancel".to_string());
+    }
+    let cancels = oids.iter()
+        .filter_map(|oid| oid.parse().ok())
+        .map(|o| CancelWire { a: client.asset, o })
+        .collect();
+
+    let data = match client.post_action(&CancelAction { kind: "cancel", cancels }).await {
+        Ok(data) => data,
+        Err(e) => return report(state_tx, format!("cancel failed: {e:#}")),
+    };
+    // One status per cancel, in request order: "success" or {"error": ...}
+    let statuses = data["statuses"].as_array().cloned().unwrap_or_default();
+    let mut cancelled = 0;
+    state_tx.send_modify(|st| {
+        for (oid, s) in oids.iter().zip(&statuses) {
+            if s == "success" {
+                st.mark_cancelled(oid);
+                cancelled += 1;
+            } else {
+                warn!("[HL-EXEC] Cancel of {oid} failed: {}", s["error"].as_str().unwrap_or("unknown error"));
+            }
+        }
+    });
+    report(state_tx, format!("cancelled {cancelled}/{}", oids.len()));
+}
+
+fn report(state_tx: &watch::Sender<ExecState>, msg: String) {
+    info!("[HL-EXEC] {msg}");
+    state_tx.send_modify(|st| st.last_msg = Some(msg));
+}
+
+/// `{"resting":{"oid"}}`, `{"filled":{"totalSz","avgPx","oid"}}` or `{"error"}`.
+fn order_status(v: &Value) -> OrderStatus {
+    let num = |v: &Value| v.as_str().and_then(|s| s.parse().ok()).unwrap_or(0.0);
+    if let Some(r) = v.get("resting") {
+        OrderStatus::Resting { oid: r["oid"].to_string() }
+    } else if let Some(f) = v.get("filled") {
+        OrderStatus::Filled { oid: f["oid"].to_string(), avg_px: num(&f["avgPx"]), size: num(&f["totalSz"]) }
+    } else {
+        OrderStatus::Rejected(v["error"].as_str().unwrap_or("unexpected order status").to_string())
+    }
+}
+
+// ─── Asset metadata ───────────────────────────────────────────────────────────
+
+/// Index of `coin` in the perp universe and its size decimals.
+async fn fetch_asset(http: &reqwest::Client, base_url: &str, coin: &str) -> Result<(u32, i32)> {
+    let meta: Value = http
+        .post(format!("{base_url}/info"))
+        .json(&json!({ "type": "meta" }))
+        .send()
+        .await
+        .context("Failed to reach Hyperliquid API for asset metadata")?
+        .json()
+        .await
+        .context("Failed to parse Hyperliquid meta response")?;
+
+    let universe = meta["universe"].as_array().context("Unexpected Hyperliquid meta response structure")?;
+    let (index, asset) = universe.iter()
+        .enumerate()
+        .find(|(_, a)| a["name"].as_str().is_some_and(|n| n.eq_ignore_ascii_case(coin)))
+        .with_context(|| format!("Symbol '{coin}' not found in Hyperliquid meta ({base_url})"))?;
+    let sz_decimals = asset["szDecimals"].as_i64().context("Hyperliquid meta entry has no szDecimals")?;
+    Ok((index as u32, sz_decimals as i32))
+}
+
+// ─── Signing ──────────────────────────────────────────────────────────────────
+
+fn keccak(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    for p in parts {
+        hasher.update(p);
+    }
+    let mut out = [0u8; 32];
+    hasher.finalize(&mut out);
+    out
+}
+
+/// Returns `(r, s, v)` for an L1 action.
+fn sign_l1_action<A: Serialize>(key: &SigningKey, action: &A, nonce: u64, mainnet: bool) -> Result<([u8; 32], [u8; 32], u8)> {
+    let packed = rmp_serde::to_vec_named(action).context("msgpack encoding failed")?;
+    // Trailing 0x00 = no vault address
+    let connection_id = keccak(&[&packed, &nonce.to_be_bytes(), &[0u8]]);
+
+    let domain_type = keccak(&[b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"]);
+    let mut chain_id = [0u8; 32];
+    chain_id[24..].copy_from_slice(&1337u64.to_be_bytes());
+    let domain_separator = keccak(&[&domain_type, &keccak(&[b"Exchange"]), &keccak(&[b"1"]), &chain_id, &[0u8; 32]]);
+
+    let agent_type = keccak(&[b"Agent(string source,bytes32 connectionId)"]);
+    let source: &[u8] = if mainnet { b"a" } else { b"b" };
+    let struct_hash = keccak(&[&agent_type, &keccak(&[source]), &connection_id]);
+
+    let digest = keccak(&[b"\x19\x01", &domain_separator, &struct_hash]);
+    let (sig, recid) = key.sign_prehash_recoverable(&digest).context("signing failed")?;
+    let bytes = sig.to_bytes();
+    let (mut r, mut s) = ([0u8; 32], [0u8; 32]);
+    r.copy_from_slice(&bytes[..32]);
+    s.copy_from_slice(&bytes[32..]);
+    Ok((r, s, 27 + recid.to_byte()))
+}
+
+/// Ethereum address of the signing key, for the startup log line.
+fn address(key: &SigningKey) -> String {
+    let point = key.verifying_key().to_encoded_point(false);
+    let hash = keccak(&[&point.as_bytes()[1..]]);
+    format!("0x{}", hex::encode(&hash[12..]))
+}
+
+// ─── Number formatting ────────────────────────────────────────────────────────
+
+/// At most 5 significant figures and `6 - szDecimals` decimals; integer
+/// prices are always accepted. Errors unless `px` is positive, which also
+/// keeps `log10` finite.
+fn round_price(px: f64, sz_decimals: i32) -> Result<f64> {
+    if !(px > 0.0 && px.is_finite()) {
+        bail!("price {px} is not positive");
+    }
+    let magnitude = px.log10().floor() as i32;
+    let decimals = (4 - magnitude).max(0).min(MAX_PRICE_DECIMALS - sz_decimals).max(0);
+    let f = 10f64.powi(decimals);
+    Ok((px * f).round() / f)
+}
+
+fn floor_to(v: f64, decimals: i32) -> f64 {
+    let f = 10f64.powi(decimals);
+    // Nudge before flooring so 0.3 isn't floored to 0.29999…
+    ((v * f) + 1e-9).floor() / f
+}
+
+/// Decimal string without trailing zeros, as the exchange hashes it ("0.01", "65000").
+fn float_to_wire(v: f64) -> String {
+    let s = format!("{v:.8}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" { "0".to_string() } else { s.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Known answers from the Hyperliquid Python SDK's tests/signing_test.py
+
+    fn buy(asset: u32, px: f64, sz: f64, tif: HlTif) -> OrderAction {
+        let order = OrderWire {
+            a: asset, b: true, p: float_to_wire(px), s: float_to_wire(sz), r: false,
+            t: OrderTypeWire { limit: LimitWire { tif } },
+        };
+        OrderAction { kind: "order", orders: vec![order], grouping: "na" }
+    }
+
+    #[test]
+    fn action_hash_matches_sdk() {
+        // test_phantom_agent_creation_matches_production
+        let packed = rmp_serde::to_vec_named(&buy(4, 1670.1, 0.0147, HlTif::Ioc)).unwrap();
+        let connection_id = keccak(&[&packed, &1_677_777_606_040u64.to_be_bytes(), &[0u8]]);
+        assert_eq!(hex::encode(connection_id), "0fcbeda5ae3c4950a548021552a4fea2226858c4453571bf3f24ba017eac2908");
+    }
+
+    #[test]
+    fn order_signature_matches_sdk() {
+        // test_l1_action_signing_order_matches
+        let key = SigningKey::from_slice(&hex::decode("0123456789012345678901234567890123456789012345678901234567890123").unwrap()).unwrap();
+        let action = buy(1, 100.0, 100.0, HlTif::Gtc);
+
+        let (r, s, v) = sign_l1_action(&key, &action, 0, true).unwrap();
+        assert_eq!(hex::encode(r), "d65369825a9df5d80099e513cce430311d7d26ddf477f5b3a33d2806b100d78e");
+        assert_eq!(hex::encode(s), "2b54116ff64054968aa237c20ca9ff68000f977c93289157748a3162b6ea940e");
+        assert_eq!(v, 28);
+
+        let (r, s, v) = sign_l1_action(&key, &action, 0, false).unwrap();
+        assert_eq!(hex::encode(r), "82b2ba28e76b3d761093aaded1b1cdad4960b3af30212b343fb2e6cdfa4e3d54");
+        assert_eq!(hex::encode(s), "6b53878fc99d26047f4d7e8c90eb98955a109f44209163f52d8dc4278cbbd9f5");
+        assert_eq!(v, 27);
+    }
+
+    #[test]
+    fn round_price_refuses_non_positive_prices() {
+        assert!(round_price(0.0, 2).is_err());
+        assert!(round_price(-1.5, 2).is_err());
+        assert!(round_price(f64::NAN, 2).is_err());
+        assert_eq!(round_price(65_432.1, 5).unwrap(), 65_432.0);
+        assert_eq!(round_price(1.234567, 0).unwrap(), 1.2346);
+        assert_eq!(round_price(0.00123456, 0).unwrap(), 0.001235);
+    }
+
+    #[test]
+    fn floor_to_keeps_exact_boundaries() {
+        // Each of these is a hair under the boundary in binary; the nudge keeps it
+        assert_eq!(floor_to(0.3, 1), 0.3);
+        assert_eq!(floor_to(0.29, 2), 0.29);
+        assert_eq!(floor_to(0.0147, 4), 0.0147);
+        assert_eq!(floor_to(4.35, 2), 4.35);
+        // …without rounding up what is really below it
+        assert_eq!(floor_to(0.2999, 2), 0.29);
+        assert_eq!(floor_to(1.005, 2), 1.0);
+        assert_eq!(floor_to(0.00999, 2), 0.0);
+    }
+}
@@ -1,72 +1,118 @@
 // src/hyperliquid_mda.rs — Hyperliquid WebSocket connection manager
 
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use futures_util::{SinkExt, StreamExt};
+use serde_json::value::RawValue;
 use serde_json::Value;
-use tokio::sync::{watch, Mutex};
-use tokio::time::{interval, sleep};
-use tokio_tungstenite::{connect_async, tungstenite::Message};
-use tracing::{debug, error, info, warn};
+use tokio::sync::{broadcast, watch, Mutex};
+use tokio::time::interval;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, debug_span, error, info, trace_span, warn, Instrument};
 
+use crate::clock::{self, ClockOffset};
+use crate::config::HlAggregation;
+use crate::net;
+use crate::ofi;
+use crate::quarantine::Quarantine;
+use crate::supervisor::{self, FeedHandle, FeedSettings, FeedStatus};
 use crate::types::{
-    Exchange, InboundEnvelope, Level, OrderBook, OutboundMsg, Subscription, WsBook,
+    modify_book, now_ms, Bbo, Exchange, Level, OrderBook, OutboundMsg, Side, RawEnvelope, Subscription, Trade, WsBbo,
+    WsBook,
 };
 
 const HL_WS_URL: &str = "wss://api.hyperliquid.xyz/ws";
-const RECONNECT_DELAY_SECS: u64 = 3;
+const HL_INFO_URL: &str = "https://api.hyperliquid.xyz/info";
 const HEARTBEAT_SECS: u64 = 20;
-const MAX_BOOK_DEPTH: usize = 20;
 
-/// Spawns a background task that maintains a live Hyperliquid L2 book.
-pub fn spawn_hl_feed(coin: String, book_tx: watch::Sender<OrderBook>) {
-    tokio::spawn(async move {
-        loop {
-            info!("[HL] Connecting…");
-            match run_connection(&coin, &book_tx).await {
-                Ok(_)  => warn!("[HL] Connection closed cleanly — reconnecting"),
-                Err(e) => error!("[HL] Connection error: {e:#} — reconnecting"),
-            }
-            book_tx.send_modify(|b| b.connected = false);
-            sleep(Duration::from_secs(RECONNECT_DELAY_SECS)).await;
-        }
-    });
+/// When the ping awaiting its pong was sent.
+type PingSent = Arc<std::sync::Mutex<Option<Instant>>>;
+
+/// Spawns a background task that maintains a live Hyperliquid L2 book and
+/// publishes the coin's trades to `trades_tx`. `clock_rx` is the venue clock
+/// offset used to correct message timestamps (see `clock`), and `reconnect`
+/// sets when failed reconnects slow down (see `breaker`). The connection is
+/// run by a supervisor, which also marks the feed stale after `stale_ms`;
+/// its handle closes the connection on exit.
+pub fn spawn_hl_feed(
+    coin: String,
+    book_tx: watch::Sender<Arc<OrderBook>>,
+    trades_tx: broadcast::Sender<Trade>,
+    clock_rx: watch::Receiver<ClockOffset>,
+    quarantine: Quarantine,
+    settings: FeedSettings,
+) -> FeedHandle {
+    supervisor::spawn_supervisor("HL", book_tx.clone(), settings.reconnect, settings.stale_ms, move |stop_rx| {
+        let (coin, book_tx, trades_tx, clock_rx, quarantine) =
+            (coin.clone(), book_tx.clone(), trades_tx.clone(), clock_rx.clone(), quarantine.clone());
+        async move { run_connection(&coin, &book_tx, &trades_tx, &clock_rx, &quarantine, &settings, stop_rx).await }
+    })
 }
 
-async fn run_connection(coin: &str, book_tx: &watch::Sender<OrderBook>) -> Result<()> {
-    let (ws_stream, _) = connect_async(HL_WS_URL)
+async fn run_connection(
+    coin: &str,
+    book_tx: &watch::Sender<Arc<OrderBook>>,
+    trades_tx: &broadcast::Sender<Trade>,
+    clock_rx: &watch::Receiver<ClockOffset>,
+    quarantine: &Quarantine,
+    settings: &FeedSettings,
+    mut stop_rx: watch::Receiver<bool>,
+) -> Result<()> {
+    let ws_stream = net::connect_ws(HL_WS_URL, &settings.socket)
+        .instrument(debug_span!("connect", exchange = "HL", url = HL_WS_URL))
         .await
         .context("WebSocket connect failed")?;
 
     info!("[HL] Connected");
-    book_tx.send_modify(|b| {
-        b.connected = true;
-        b.coin = coin.to_string();
-    });
+    modify_book(book_tx, |b| b.coin = coin.to_string());
+
+    // Load the book over REST meanwhile, so it shows before the first push
+    let bootstrap = tokio::spawn(bootstrap_book(coin.to_string(), settings.aggregation, settings.depth, book_tx.clone()));
 
     let (write, mut read) = ws_stream.split();
     let write = Arc::new(Mutex::new(write));
 
-    // Subscribe
-    let sub_msg = OutboundMsg::Subscribe {
-        subscription: Subscription::L2Book { coin: coin.to_string() },
-    };
-    {
-        let text = serde_json::to_string(&sub_msg)?;
-        write.lock().await.send(Message::Text(text)).await?;
-        info!("[HL] Subscribed to l2Book:{coin}");
+    // Subscribe: book, mark / oracle prices, trades and the BBO when enabled
+    let mut subscriptions = vec![
+        l2book(coin, &settings.aggregation),
+        Subscription::ActiveAssetCtx { coin: coin.to_string() },
+        Subscription::Trades { coin: coin.to_string() },
+    ];
+    if settings.bbo {
+        subscriptions.push(Subscription::Bbo { coin: coin.to_string() });
+    }
+    async {
+        for subscription in subscriptions {
+            let text = serde_json::to_string(&OutboundMsg::Subscribe { subscription })?;
+            write.lock().await.send(Message::Text(text)).await?;
+        }
+        anyhow::Ok(())
     }
+    .instrument(debug_span!("subscribe", exchange = "HL", coin))
+    .await?;
+    info!("[HL] Subscribed to l2Book, activeAssetCtx, trades{} for {coin}", if settings.bbo { " and bbo" } else { "" });
+    modify_book(book_tx, |b| {
+        b.status = FeedStatus::Subscribed;
+        b.connected_ms = Some(now_ms());
+    });
 
-    // Heartbeat task
+    // Heartbeat task; the send time of the ping in flight gives the round trip
+    let ping_sent: PingSent = Arc::default();
     let write_clone = Arc::clone(&write);
+    let ping_clone = Arc::clone(&ping_sent);
+    let mut hb_stop_rx = stop_rx.clone();
     let heartbeat = tokio::spawn(async move {
         let mut ticker = interval(Duration::from_secs(HEARTBEAT_SECS));
         ticker.tick().await;
         loop {
-            ticker.tick().await;
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = supervisor::stopped(&mut hb_stop_rx) => break,
+            }
             let ping = r#"{"method":"ping"}"#;
+            *ping_clone.lock().unwrap() = Some(Instant::now());
             if let Err(e) = write_clone.lock().await.send(Message::Text(ping.to_string())).await {
                 error!("[HL] Heartbeat send failed: {e}");
                 break;
@@ -75,52 +121,223 @@ async fn run_connection(coin: &str, book_tx: &watch::Sender<OrderBook>) -> Resul
         }
     });
 
-    // Message loop
-    while let Some(msg) = read.next().await {
+    // Message loop. The first book of a connection is only a baseline for order
+    // flow, since the previous one may be from before a gap. On stop, a close
+    // frame is sent and the loop runs on until the server's reply.
+    let mut session = Session { depth: settings.depth, synced: false };
+    let mut closing = false;
+    loop {
+        let msg = tokio::select! {
+            msg = read.next() => msg,
+            _ = supervisor::stopped(&mut stop_rx), if !closing => {
+                info!("[HL] Shutting down — sending close frame");
+                write.lock().await.send(Message::Close(None)).await?;
+                closing = true;
+                continue;
+            }
+        };
+        let Some(msg) = msg else { break };
         match msg? {
-            Message::Text(text) => handle_text(&text, book_tx),
-            Message::Close(_)   => { info!("[HL] Server sent close frame"); break; }
+            Message::Text(text)          => handle_text(&text, book_tx, trades_tx, clock_rx, quarantine, &ping_sent, &mut session),
+            Message::Close(_) if closing => { info!("[HL] Closed"); break; }
+            Message::Close(_)            => { info!("[HL] Server sent close frame"); break; }
             _ => {}
         }
     }
 
     heartbeat.abort();
+    bootstrap.abort();
     Ok(())
 }
 
-fn handle_text(text: &str, book_tx: &watch::Sender<OrderBook>) {
+/// Publishes the `info` endpoint's `l2Book` snapshot, unless a WebSocket push
+/// has arrived first. It isn't a push, so the message count, latency and order
+/// flow are left to the first one.
+async fn bootstrap_book(coin: String, aggregation: HlAggregation, depth: usize, book_tx: watch::Sender<Arc<OrderBook>>) {
+    let pushes = book_tx.borrow().message_count;
+    let book = match fetch_l2book(&coin, &aggregation).await {
+        Ok(b)  => b,
+        Err(e) => { warn!("[HL] REST snapshot failed: {e:#} — waiting for the WS push"); return; }
+    };
+    let loaded = book_tx.send_if_modified(|state| {
+        if state.message_count != pushes {
+            return false;
+        }
+        let state = Arc::make_mut(state);
+        state.bids = book.levels.0.iter().take(depth).map(Level::from_hl).collect();
+        state.asks = book.levels.1.iter().take(depth).map(Level::from_hl).collect();
+        state.last_update_ms = book.time;
+        state.version += 1;
+        true
+    });
+    if loaded {
+        info!("[HL] Book loaded from REST snapshot");
+    }
+}
+
+/// The `l2Book` subscription for `coin`, aggregated as configured.
+fn l2book(coin: &str, aggregation: &HlAggregation) -> Subscription {
+    Subscription::L2Book {
+        coin:       coin.to_string(),
+        n_sig_figs: aggregation.n_sig_figs,
+        mantissa:   aggregation.mantissa,
+    }
+}
+
+/// `{"type": "l2Book"}` answers with the same `{coin, time, levels}` as the
+/// push. The request body is the subscription itself, so the aggregation matches.
+async fn fetch_l2book(coin: &str, aggregation: &HlAggregation) -> Result<WsBook> {
+    reqwest::Client::new()
+        .post(HL_INFO_URL)
+        .json(&l2book(coin, aggregation))
+        .send()
+        .instrument(debug_span!("rest_snapshot", exchange = "HL", coin))
+        .await
+        .context("request failed")?
+        .error_for_status()?
+        .json()
+        .await
+        .context("unparseable response")
+}
+
+/// What the message handler keeps across one connection's messages.
+struct Session {
+    depth:  usize, // levels per side published
+    synced: bool,  // a book has arrived since connecting
+}
+
+fn handle_text(
+    text: &str,
+    book_tx: &watch::Sender<Arc<OrderBook>>,
+    trades_tx: &broadcast::Sender<Trade>,
+    clock_rx: &watch::Receiver<ClockOffset>,
+    quarantine: &Quarantine,
+    ping_sent: &PingSent,
+    session: &mut Session,
+) {
+    let _span = trace_span!("handle_message", exchange = "HL", bytes = text.len()).entered();
+    let recv_ms = now_ms();
     if text.contains(r#""pong""#) {
-        debug!("[HL] Received pong");
+        if let Some(sent) = ping_sent.lock().unwrap().take() {
+            let rtt_ms = sent.elapsed().as_secs_f64() * 1000.0;
+            debug!("[HL] Received pong after {rtt_ms:.1}ms");
+            modify_book(book_tx, |state| state.rtt_ms = Some(rtt_ms));
+        }
         return;
     }
 
-    let env: InboundEnvelope = match serde_json::from_str(text) {
+    let env: RawEnvelope = match serde_json::from_str(text) {
         Ok(v)  => v,
-        Err(e) => { warn!("[HL] Parse error: {e} | {text:.200}"); return; }
+        Err(e) => {
+            warn!("[HL] Parse error: {e} | {text:.200}");
+            modify_book(book_tx, |state| state.errors.parse += 1);
+            quarantine.record(Exchange::Hyperliquid, e.to_string(), text);
+            return;
+        }
     };
 
-    match env.channel.as_str() {
+    match &*env.channel {
         "subscriptionResponse" => debug!("[HL] Subscription confirmed"),
+        "error" => {
+            warn!("[HL] Error reply: {}", env.data);
+            modify_book(book_tx, |state| state.errors.rpc += 1);
+        }
         "l2Book" => {
-            if let Ok(book) = parse_l2book(&env.data) {
-                book_tx.send_modify(|state| {
-                    state.bids = book.levels.0.iter()
-                        .take(MAX_BOOK_DEPTH)
-                        .map(Level::from_hl)
-                        .collect();
-                    state.asks = book.levels.1.iter()
-                        .take(MAX_BOOK_DEPTH)
-                        .map(Level::from_hl)
-                        .collect();
-                    state.last_update_ms = book.time;
-                    state.message_count += 1;
-                });
+            let book = match parse_l2book(env.data) {
+                Ok(b)  => b,
+                Err(e) => {
+                    warn!("[HL] {e:#} | {text:.200}");
+                    modify_book(book_tx, |state| state.errors.parse += 1);
+                    quarantine.record(Exchange::Hyperliquid, format!("{e:#}"), text);
+                    return;
+                }
+            };
+            let (synced, depth) = (std::mem::replace(&mut session.synced, true), session.depth);
+            modify_book(book_tx, |state| {
+                let bids: Vec<Level> = book.levels.0.iter()
+                    .take(depth)
+                    .map(Level::from_hl)
+                    .collect();
+                let asks: Vec<Level> = book.levels.1.iter()
+                    .take(depth)
+                    .map(Level::from_hl)
+                    .collect();
+                // Snapshots only, so order flow comes from diffing consecutive books
+                if synced {
+                    state.ofi_cum += ofi::book_flow(&state.bids, &state.asks, &bids, &asks);
+                }
+                state.bids = bids;
+                state.asks = asks;
+                state.last_update_ms = book.time;
+                state.status = FeedStatus::Live;
+                state.reconnect_failures = 0;
+                state.message_count += 1;
+                state.clock_offset_ms = *clock_rx.borrow();
+                state.latency.record(clock::to_local_ms(book.time, state.clock_offset_ms), recv_ms);
+            });
+        }
+        "bbo" => {
+            let bbo: WsBbo = match serde_json::from_str(env.data.get()) {
+                Ok(b)  => b,
+                Err(e) => {
+                    warn!("[HL] Unparseable bbo: {e} | {text:.200}");
+                    modify_book(book_tx, |state| state.errors.parse += 1);
+                    quarantine.record(Exchange::Hyperliquid, format!("unparseable bbo: {e}"), text);
+                    return;
+                }
+            };
+            modify_book(book_tx, |state| state.bbo = Some(Bbo {
+                bid:   bbo.bbo.0.as_ref().map(Level::from_hl),
+                ask:   bbo.bbo.1.as_ref().map(Level::from_hl),
+                ts_ms: bbo.time,
+            }));
+        }
+        "activeAssetCtx" => {
+            // Perp context: {coin, ctx: {markPx, oraclePx, …}}, numbers as strings
+            let data: Value = serde_json::from_str(env.data.get()).unwrap_or_default();
+            let ctx = &data["ctx"];
+            let px = |v: &Value| v.as_str().and_then(|s| s.parse().ok());
+            modify_book(book_tx, |state| {
+                state.mark_px = px(&ctx["markPx"]).or(state.mark_px);
+                state.index_px = px(&ctx["oraclePx"]).or(state.index_px);
+            });
+        }
+        "trades" => {
+            // [{coin, side: "B" | "A", px, sz, time, …}], side being the aggressor
+            let mut bad = 0;
+            let data: Value = serde_json::from_str(env.data.get()).unwrap_or_default();
+            for v in data.as_array().into_iter().flatten() {
+                match parse_trade(v) {
+                    Some(trade) => { let _ = trades_tx.send(trade); } // no receivers is fine
+                    None        => bad += 1,
+                }
             }
+            if bad > 0 {
+                warn!("[HL] {bad} unparseable trades | {text:.200}");
+                modify_book(book_tx, |state| state.errors.parse += bad);
+                quarantine.record(Exchange::Hyperliquid, format!("{bad} unparseable trades"), text);
+            }
+        }
+        other => {
+            debug!("[HL] Unhandled channel: {other}");
+            modify_book(book_tx, |state| state.errors.dropped += 1);
         }
-        other => debug!("[HL] Unhandled channel: {other}"),
     }
 }
 
-fn parse_l2book(data: &Value) -> Result<WsBook> {
-    serde_json::from_value(data.clone()).context("Failed to deserialise WsBook")
+pub fn parse_l2book(data: &RawValue) -> Result<WsBook> {
+    serde_json::from_str(data.get()).context("Failed to deserialise WsBook")
+}
+
+/// Hyperliquid's public trades don't say whether a trade was a liquidation.
+fn parse_trade(v: &Value) -> Option<Trade> {
+    let num = |v: &Value| v.as_str().and_then(|s| s.parse().ok());
+    Some(Trade {
+        ts_ms:       v["time"].as_u64()?,
+        exchange:    Exchange::Hyperliquid,
+        side:        if v["side"] == "B" { Side::Buy } else { Side::Sell },
+        price:       num(&v["px"])?,
+        size:        num(&v["sz"])?,
+        liquidation: false,
+    })
 }
@@ -0,0 +1,123 @@
+// src/impact.rs — Market impact calculator: walk both books for a typed order size
+
+use crossterm::event::KeyCode;
+
+use crate::types::{Exchange, Level, OrderBook, Side};
+
+/// Digits kept in the size box.
+const MAX_INPUT: usize = 12;
+
+/// Expected fill of a market order swept across both venues, best price first.
+#[derive(Debug, Clone, Default)]
+pub struct Impact {
+    pub filled:       f64,         // base units; below the requested size when the books run out
+    pub avg_px:       Option<f64>, // None when nothing filled
+    pub worst_px:     Option<f64>, // last level touched
+    pub slippage_bps: Option<f64>, // avg price vs the merged mid, positive = cost
+    pub hl_size:      f64,
+    pub hl_usd:       f64,
+    pub pdx_size:     f64,
+    pub pdx_usd:      f64,
+}
+
+/// Walks the combined levels of both venues on the side a `side` order takes.
+pub fn estimate(hl: &OrderBook, pdx: &OrderBook, side: Side, size: f64) -> Impact {
+    let levels = |book: &OrderBook| -> Vec<(f64, f64, Exchange)> {
+        let side_levels: &[Level] = match side { Side::Buy => &book.asks, Side::Sell => &book.bids };
+        side_levels.iter().map(|l| (l.price_f64(), l.size_f64(), book.exchange.clone())).collect()
+    };
+    let mut all = levels(hl);
+    all.extend(levels(pdx));
+    match side {
+        Side::Buy  => all.sort_by(|a, b| a.0.total_cmp(&b.0)),
+        Side::Sell => all.sort_by(|a, b| b.0.total_cmp(&a.0)),
+    }
+
+    let mut imp = Impact::default();
+    for (px, sz, ex) in all {
+        let take = sz.min(size - imp.filled);
+        if take <= 0.0 {
+            break;
+        }
+        imp.filled += take;
+        imp.worst_px = Some(px);
+        match ex {
+            Exchange::Hyperliquid => { imp.hl_size += take;  imp.hl_usd += take * px; }
+            Exchange::Paradex     => { imp.pdx_size += take; imp.pdx_usd += take * px; }
+        }
+    }
+
+    if imp.filled > 0.0 {
+        let avg = (imp.hl_usd + imp.pdx_usd) / imp.filled;
+        imp.avg_px = Some(avg);
+        imp.slippage_bps = merged_mid(hl, pdx).map(|mid| side.sign() * (avg - mid) / mid * 10_000.0);
+    }
+    imp
+}
+
+/// Midpoint of the best bid and best ask across both venues.
+fn merged_mid(hl: &OrderBook, pdx: &OrderBook) -> Option<f64> {
+    let best = |a: Option<f64>, b: Option<f64>, pick: fn(f64, f64) -> f64| match (a, b) {
+        (Some(a), Some(b)) => Some(pick(a, b)),
+        (a, b) => a.or(b),
+    };
+    let bid = best(hl.best_bid(), pdx.best_bid(), f64::max)?;
+    let ask = best(hl.best_ask(), pdx.best_ask(), f64::min)?;
+    Some((bid + ask) / 2.0)
+}
+
+// ─── Input box ────────────────────────────────────────────────────────────────
+
+/// State of the calculator popup, driven by the TUI keys.
+#[derive(Debug, Clone)]
+pub struct ImpactCalc {
+    pub open:    bool,
+    pub editing: bool,   // keys go to the size box
+    pub input:   String, // order size in base units, as typed
+    pub side:    Side,
+    pub result:  Option<Impact>, // None until a valid size is entered
+}
+
+impl Default for ImpactCalc {
+    fn default() -> Self {
+        Self { open: false, editing: false, input: String::new(), side: Side::Buy, result: None }
+    }
+}
+
+impl ImpactCalc {
+    pub fn size(&self) -> Option<f64> {
+        self.input.parse().ok().filter(|s: &f64| *s > 0.0 && s.is_finite())
+    }
+
+    /// Re-walks the books for the typed size so the estimate tracks the market.
+    pub fn refresh(&mut self, hl: &OrderBook, pdx: &OrderBook) {
+        self.result = self.size().map(|size| estimate(hl, pdx, self.side, size));
+    }
+
+    /// Handles a key while the popup is open, or `i` to open it. Returns false
+    /// for keys it doesn't use, which then fall through to the normal bindings.
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        if !self.open {
+            if code == KeyCode::Char('i') {
+                (self.open, self.editing) = (true, true);
+                return true;
+            }
+            return false;
+        }
+        match code {
+            KeyCode::Esc => (self.open, self.editing) = (false, false),
+            KeyCode::Tab => self.side = match self.side { Side::Buy => Side::Sell, Side::Sell => Side::Buy },
+            KeyCode::Char('i') if !self.editing => self.editing = true,
+            KeyCode::Enter if self.editing => self.editing = false,
+            KeyCode::Backspace if self.editing => { self.input.pop(); }
+            KeyCode::Char(c) if self.editing && (c.is_ascii_digit() || (c == '.' && !self.input.contains('.'))) => {
+                if self.input.len() < MAX_INPUT {
+                    self.input.push(c);
+                }
+            }
+            // Swallow everything else while typing so `q`, `b`, … can't fire
+            _ => return self.editing,
+        }
+        true
+    }
+}
@@ -0,0 +1,54 @@
+// src/latency.rs — Exchange-timestamp → local-receive latency histogram per feed
+//
+// Latency here is local receive time minus the exchange's own timestamp on the
+// message. Without `[clock_sync]` it includes the clock error between the two,
+// so a negative reading means the local clock is behind the exchange's, not
+// that the message time-travelled. With it, the timestamp is corrected first.
+
+use serde::Serialize;
+
+/// Upper bounds (ms, inclusive) of the histogram buckets; one more bucket
+/// catches everything slower.
+const BOUNDS_MS: [i64; 22] = [
+    1, 2, 3, 5, 7, 10, 15, 20, 30, 50, 75, 100, 150, 200, 300, 500, 750, 1_000, 1_500, 2_000, 5_000, 10_000,
+];
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LatencyHistogram {
+    buckets:      [u64; BOUNDS_MS.len() + 1],
+    pub count:    u64,
+    pub negative: u64,         // samples below zero (counted in the first bucket)
+    pub max_ms:   Option<i64>,
+    pub last_ms:  Option<i64>,
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, exchange_ts_ms: u64, recv_ms: u64) {
+        let ms = recv_ms as i64 - exchange_ts_ms as i64;
+        let i = BOUNDS_MS.iter().position(|b| ms <= *b).unwrap_or(BOUNDS_MS.len());
+        self.buckets[i] += 1;
+        self.count += 1;
+        if ms < 0 {
+            self.negative += 1;
+        }
+        self.max_ms = Some(self.max_ms.map_or(ms, |m| m.max(ms)));
+        self.last_ms = Some(ms);
+    }
+
+    /// Upper bound of the bucket holding quantile `q` (0..=1), or `None` with
+    /// no samples. `Some(None)` means slower than the last bound.
+    pub fn quantile_ms(&self, q: f64) -> Option<Option<i64>> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = ((self.count as f64 * q).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (i, n) in self.buckets.iter().enumerate() {
+            seen += n;
+            if seen >= rank {
+                return Some(BOUNDS_MS.get(i).copied());
+            }
+        }
+        Some(None)
+    }
+}
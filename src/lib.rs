@@ -0,0 +1,66 @@
+// src/lib.rs — The feeds, merger, sinks and TUI rendering, as a library
+//
+// `main.rs` wires these together; the library split also lets the benchmarks
+// in `benches/` call into the book handling directly.
+
+pub mod account;
+pub mod alerts;
+pub mod arb;
+pub mod arb_audit;
+pub mod arrow_sink;
+pub mod book_dump;
+pub mod breaker;
+pub mod candles;
+pub mod clipboard;
+pub mod clock;
+pub mod config;
+pub mod cvd;
+pub mod desktop_notify;
+pub mod direction;
+pub mod exec;
+pub mod feeds;
+pub mod grpc;
+pub mod http;
+pub mod impact;
+pub mod latency;
+pub mod hyperliquid_account;
+pub mod hyperliquid_exec;
+pub mod hyperliquid_mda;
+pub mod liquidations;
+pub mod log_file;
+pub mod log_format;
+pub mod log_panel;
+pub mod market_stats;
+pub mod markets;
+pub mod merger;
+pub mod mid_history;
+pub mod msg_rate;
+pub mod nats_sink;
+pub mod net;
+pub mod ofi;
+pub mod otel;
+pub mod pair_grid;
+pub mod pair_picker;
+pub mod paradex_account;
+pub mod paradex_exec;
+pub mod paradex_mda;
+pub mod parquet_sink;
+pub mod perf;
+pub mod quarantine;
+pub mod redis_sink;
+pub mod render_tick;
+pub mod risk;
+pub mod session;
+pub mod sim;
+pub mod slack;
+pub mod spread_stats;
+pub mod sqlite_sink;
+pub mod supervisor;
+pub mod telegram;
+pub mod term;
+pub mod theme;
+pub mod trade_tape;
+pub mod types;
+pub mod ui;
+pub mod volatility;
+pub mod ws_server;
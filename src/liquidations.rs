@@ -0,0 +1,71 @@
+// src/liquidations.rs — Rolling tape of liquidation prints from the trade feeds
+//
+// Only Paradex flags liquidations on its public trade channel; Hyperliquid's
+// public trades carry no such flag, so its totals stay at zero.
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+use tokio::sync::{broadcast, watch};
+use tracing::{info, warn};
+
+use crate::types::{Exchange, Side, Trade};
+
+/// Liquidations kept in `LiquidationTape::recent` (oldest dropped first).
+const MAX_LIQUIDATIONS: usize = 50;
+
+/// Session totals for one venue. A long is liquidated by a forced sell, a
+/// short by a forced buy.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct LiqTotals {
+    pub count:      u64,
+    pub longs_usd:  f64,
+    pub shorts_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LiquidationTape {
+    pub recent: VecDeque<Trade>, // newest first
+    pub hl:     LiqTotals,
+    pub pdx:    LiqTotals,
+}
+
+impl LiquidationTape {
+    fn record(&mut self, trade: Trade) {
+        let totals = match trade.exchange {
+            Exchange::Hyperliquid => &mut self.hl,
+            Exchange::Paradex     => &mut self.pdx,
+        };
+        totals.count += 1;
+        match trade.side {
+            Side::Sell => totals.longs_usd += trade.notional(),
+            Side::Buy  => totals.shorts_usd += trade.notional(),
+        }
+        self.recent.push_front(trade);
+        self.recent.truncate(MAX_LIQUIDATIONS);
+    }
+}
+
+/// Spawns a task collecting the liquidations among `trades_rx`.
+pub fn spawn_liquidation_tape(mut trades_rx: broadcast::Receiver<Trade>) -> watch::Receiver<LiquidationTape> {
+    let (tx, rx) = watch::channel(LiquidationTape::default());
+    tokio::spawn(async move {
+        loop {
+            match trades_rx.recv().await {
+                Ok(trade) if trade.liquidation => {
+                    info!(
+                        "[LIQ] {} {} liquidated: {} @ {:.2} (${:.0})",
+                        trade.exchange.short(),
+                        if trade.side == Side::Sell { "long" } else { "short" },
+                        trade.size, trade.price, trade.notional(),
+                    );
+                    tx.send_modify(|tape| tape.record(trade));
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[LIQ] Skipped {n} trades (consumer lagging)"),
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+    rx
+}
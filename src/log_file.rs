@@ -0,0 +1,110 @@
+// src/log_file.rs — Rotating log file written alongside stderr
+//
+// Files are named so they sort by age: `obt_<YYYYmmdd>.log` per UTC day, or
+// `obt_<YYYYmmdd_HHMMSS>.log` per size-based rotation. Each event is written
+// straight through, so nothing is lost if the process is killed.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::config::{LogRotation, LoggingConfig};
+
+const PREFIX: &str = "obt_";
+const SUFFIX: &str = ".log";
+
+/// `io::Write` for the tracing file layer; rotates before a write that would
+/// cross into a new day or past the size limit.
+pub struct RollingFile {
+    cfg:     LoggingConfig,
+    file:    File,
+    day:     String, // UTC day the current file belongs to
+    written: u64,    // bytes in the current file
+}
+
+impl RollingFile {
+    /// Creates the directory and opens (or appends to) the first file, so a bad
+    /// path fails at startup.
+    pub fn open(cfg: &LoggingConfig) -> Result<Self> {
+        fs::create_dir_all(&cfg.dir)
+            .with_context(|| format!("Cannot create log directory '{}'", cfg.dir.display()))?;
+        let (file, written) = open_file(&cfg.dir, cfg.rotation)?;
+        let rolling = Self { cfg: cfg.clone(), file, day: today(), written };
+        rolling.prune();
+        Ok(rolling)
+    }
+
+    fn due(&self, len: usize) -> bool {
+        match self.cfg.rotation {
+            LogRotation::Daily     => today() != self.day,
+            LogRotation::Size(max) => self.written > 0 && self.written + len as u64 > max,
+        }
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        let (file, written) = open_file(&self.cfg.dir, self.cfg.rotation).map_err(io::Error::other)?;
+        self.file = file;
+        self.day = today();
+        self.written = written;
+        self.prune();
+        Ok(())
+    }
+
+    /// Deletes the oldest log files beyond `max_files`. Best effort: a file
+    /// that can't be listed or removed is left alone.
+    fn prune(&self) {
+        if self.cfg.max_files == 0 {
+            return;
+        }
+        let Ok(entries) = fs::read_dir(&self.cfg.dir) else { return };
+        let mut logs: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with(PREFIX) && n.ends_with(SUFFIX))
+            })
+            .collect();
+        logs.sort();
+        let excess = logs.len().saturating_sub(self.cfg.max_files);
+        for path in &logs[..excess] {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+impl Write for RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.due(buf.len()) {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn today() -> String {
+    Utc::now().format("%Y%m%d").to_string()
+}
+
+/// Opens the file for now and returns it with its current length.
+fn open_file(dir: &Path, rotation: LogRotation) -> Result<(File, u64)> {
+    let stamp = match rotation {
+        LogRotation::Daily   => today(),
+        LogRotation::Size(_) => Utc::now().format("%Y%m%d_%H%M%S").to_string(),
+    };
+    let path = dir.join(format!("{PREFIX}{stamp}{SUFFIX}"));
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Cannot open '{}'", path.display()))?;
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    Ok((file, len))
+}
@@ -0,0 +1,167 @@
+// src/log_format.rs — Text or JSON-lines formatting for stderr and file logs
+//
+// Log messages carry their component as a `[TAG]` prefix ("[HL] Connected",
+// "[PDX-EXEC] Order rejected", "[PARQUET] Opened …"). The JSON format lifts it
+// into fields, one object per line (keys sorted):
+//
+//   {"event":"feed","exchange":"Paradex","level":"WARN","message":"…",
+//    "symbol":"BTC-USD-PERP","target":"obt::paradex_mda","timestamp":"2026-01-01T12:00:00.000Z"}
+//
+// `exchange` and `symbol` are only present for venue tags. `event` is the kind
+// of component: feed, account or exec for venue tags, the lowercased tag for
+// the rest ("parquet", "clock", …), and "app" for untagged messages. Any other
+// fields on the event are added as they are.
+
+use std::fmt;
+
+use chrono::{SecondsFormat, Utc};
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::{Format, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::config::LogFormat;
+use crate::types::Exchange;
+
+/// Event formatter for every `fmt` layer, chosen by `[logging] format`.
+pub enum EventFormat {
+    Text(Format),
+    Json(JsonFormat),
+}
+
+impl EventFormat {
+    pub fn new(format: LogFormat, hl_symbol: &str, pdx_symbol: &str) -> Self {
+        match format {
+            LogFormat::Text => EventFormat::Text(Format::default()),
+            LogFormat::Json => EventFormat::Json(JsonFormat {
+                hl_symbol:  hl_symbol.to_string(),
+                pdx_symbol: pdx_symbol.to_string(),
+            }),
+        }
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for EventFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        match self {
+            EventFormat::Text(text) => text.format_event(ctx, writer, event),
+            EventFormat::Json(json) => json.format_event(writer, event),
+        }
+    }
+}
+
+// ─── JSON lines ───────────────────────────────────────────────────────────────
+
+pub struct JsonFormat {
+    hl_symbol:  String,
+    pdx_symbol: String,
+}
+
+impl JsonFormat {
+    fn format_event(&self, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let mut fields = FieldVisitor::default();
+        event.record(&mut fields);
+        let message = fields.message.unwrap_or_default();
+        let (tag, message) = split_tag(&message);
+
+        let mut obj = Map::new();
+        obj.insert("timestamp".into(), Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true).into());
+        obj.insert("level".into(), event.metadata().level().as_str().into());
+        obj.insert("target".into(), event.metadata().target().into());
+        let (exchange, kind) = classify(tag);
+        if let Some(exchange) = exchange {
+            let symbol = match &exchange {
+                Exchange::Hyperliquid => &self.hl_symbol,
+                Exchange::Paradex     => &self.pdx_symbol,
+            };
+            obj.insert("exchange".into(), exchange.label().into());
+            obj.insert("symbol".into(), symbol.as_str().into());
+        }
+        obj.insert("event".into(), kind.into());
+        obj.insert("message".into(), message.into());
+        for (k, v) in fields.rest {
+            obj.entry(k).or_insert(v);
+        }
+
+        let line = serde_json::to_string(&Value::Object(obj)).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{line}")
+    }
+}
+
+/// `("HL", "Connected")` from `"[HL] Connected"`; no tag without the prefix.
+fn split_tag(message: &str) -> (Option<&str>, &str) {
+    message.strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .map_or((None, message), |(tag, rest)| (Some(tag), rest.trim_start()))
+}
+
+/// Venue and event kind for a message tag.
+fn classify(tag: Option<&str>) -> (Option<Exchange>, String) {
+    let Some(tag) = tag else { return (None, "app".into()) };
+    let (venue, component) = tag.split_once('-').unwrap_or((tag, ""));
+    let exchange = match venue {
+        "HL"  => Some(Exchange::Hyperliquid),
+        "PDX" => Some(Exchange::Paradex),
+        _     => None,
+    };
+    let kind = match (exchange.is_some(), component) {
+        (true, "")     => "feed".to_string(),
+        (true, "ACCT") => "account".to_string(),
+        (true, other)  => other.to_lowercase(),
+        (false, _)     => tag.to_lowercase(),
+    };
+    (exchange, kind)
+}
+
+/// Collects the message and any other fields as JSON values.
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    rest:    Vec<(String, Value)>,
+}
+
+impl FieldVisitor {
+    fn put(&mut self, field: &Field, value: Value) {
+        self.rest.push((field.name().to_string(), value));
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{value:?}"));
+        } else {
+            self.put(field, format!("{value:?}").into());
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+        } else {
+            self.put(field, value.into());
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.put(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.put(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.put(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.put(field, value.into());
+    }
+}
@@ -0,0 +1,65 @@
+// src/log_panel.rs — Tracing layer keeping recent log events for the TUI panel
+//
+// Logs still go to stderr; this layer keeps a copy of the latest ones so feed
+// warnings can be read without leaving the TUI. It sits behind the same
+// `RUST_LOG` filter as the stderr output.
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use tokio::sync::watch;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::types::now_ms;
+
+/// Events kept for the panel (oldest dropped first).
+const MAX_LINES: usize = 500;
+
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub ts_ms:   u64,
+    pub level:   Level,
+    pub message: String, // message, then any other fields as `key=value`
+}
+
+/// Publishes every event it sees to a watch channel, newest first.
+pub struct LogLayer {
+    tx: watch::Sender<VecDeque<LogLine>>,
+}
+
+/// The layer to add to the subscriber, and the lines it captures.
+pub fn layer() -> (LogLayer, watch::Receiver<VecDeque<LogLine>>) {
+    let (tx, rx) = watch::channel(VecDeque::new());
+    (LogLayer { tx }, rx)
+}
+
+impl<S: Subscriber> Layer<S> for LogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let line = LogLine { ts_ms: now_ms(), level: *event.metadata().level(), message: visitor.message };
+        self.tx.send_modify(|lines| {
+            lines.push_front(line);
+            lines.truncate(MAX_LINES);
+        });
+    }
+}
+
+/// Formats an event's fields the way the stderr output does.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let fields = std::mem::take(&mut self.message);
+            let _ = write!(self.message, "{value:?}{fields}");
+        } else {
+            let _ = write!(self.message, " {}={value:?}", field.name());
+        }
+    }
+}
@@ -1,49 +1,117 @@
 // src/main.rs — Dual-exchange L2 MDA entry point
 
-mod config;
-mod hyperliquid_mda;
-mod merger;
-mod paradex_mda;
-mod types;
-mod ui;
-
-use std::io;
-use std::time::Duration;
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{enable_raw_mode, EnterAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use tokio::sync::watch;
-use tracing::info;
-use tracing_subscriber::{fmt, EnvFilter};
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use tracing_subscriber::{filter::filter_fn, fmt, prelude::*, EnvFilter};
 
-use crate::config::AppConfig;
-use crate::merger::MergedBook;
-use crate::types::{Exchange, OrderBook};
+use obt::{
+    alerts, arb, arb_audit, arrow_sink, book_dump, candles, clock, config, cvd, desktop_notify, grpc,
+    http, hyperliquid_exec, liquidations, log_file, log_format, log_panel, market_stats, markets,
+    merger, mid_history, msg_rate, nats_sink, otel, pair_picker, paradex_exec, parquet_sink,
+    redis_sink, risk, session, sim, slack, spread_stats, sqlite_sink, telegram, term, theme, trade_tape, ui,
+    volatility, ws_server,
+};
+use obt::alerts::ActiveAlert;
+use obt::arb::ArbWindow;
+use obt::candles::CandleSeries;
+use obt::clipboard::{Clipboard, Quote};
+use obt::config::{AppConfig, ArrowIpcTarget, HlAggregation, PairConfig};
+use obt::cvd::CvdState;
+use obt::direction::BookTicks;
+use obt::exec::{ExecCommand, ExecHandle};
+use obt::feeds::Feeds;
+use obt::impact::ImpactCalc;
+use obt::liquidations::LiquidationTape;
+use obt::log_panel::LogLine;
+use obt::market_stats::MarketStats;
+use obt::markets::Market;
+use obt::merger::{MergePolicy, MergedBook};
+use obt::mid_history::MidHistory;
+use obt::msg_rate::MsgRates;
+use obt::pair_grid::PairGrid;
+use obt::pair_picker::PairPicker;
+use obt::perf::Timing;
+use obt::quarantine::Quarantine;
+use obt::render_tick::AdaptiveTick;
+use obt::risk::RiskBlock;
+use obt::sim::{SimAction, SimHandle};
+use obt::spread_stats::SpreadStats;
+use obt::supervisor::FeedSettings;
+use obt::theme::Theme;
+use obt::types::{now_ms, Exchange, OrderBook, Trade};
+use obt::volatility::VolState;
 
 // ─── Entry point ─────────────────────────────────────────────────────────────
 
+/// Trades buffered per consumer before the slowest one starts skipping.
+const TRADES_CAPACITY: usize = 1024;
+/// How long each feed gets to finish its close handshake on quit.
+const FEED_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Logging to stderr so it doesn't interfere with the TUI on stdout
-    fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .with_writer(io::stderr)
-        .init();
+    // Leave the TUI's raw mode and alternate screen before a panic message prints
+    term::install_panic_hook();
 
     // ── Load and validate config ──────────────────────────────────────────────
-    let cfg = AppConfig::load().unwrap_or_else(|e| {
+    let mut cfg = AppConfig::load().unwrap_or_else(|e| {
         eprintln!("\n❌  Configuration error:\n    {e}\n");
         std::process::exit(1);
     });
 
-    info!("Config loaded: HL={} PDX={}", cfg.hl_symbol, cfg.pdx_symbol);
+    // No pdx_symbol: look up the Paradex perp for the HL coin
+    let pdx_derived = cfg.pdx_symbol.is_empty();
+    if pdx_derived {
+        cfg.pdx_symbol = config::resolve_pdx_symbol(&cfg.hl_symbol).await.unwrap_or_else(|e| {
+            eprintln!("\n❌  Paradex symbol resolution failed:\n    {e}\n");
+            std::process::exit(1);
+        });
+        if let Some(pair) = cfg.pairs.iter_mut().find(|p| p.name == cfg.pair_name) {
+            pair.pdx_symbol = cfg.pdx_symbol.clone();
+        }
+    }
+
+    // Logging to stderr so it doesn't interfere with the TUI on stdout, with a
+    // copy of recent events kept for the log panel and optionally a log file,
+    // as text or JSON lines. Each output has its own filter, so the spans
+    // enabled for OpenTelemetry don't show up in the logs.
+    let env_filter = || EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let format = || log_format::EventFormat::new(cfg.log_format, &cfg.hl_symbol, &cfg.pdx_symbol);
+    let log_file = cfg.logging.as_ref().map(|log_cfg| {
+        log_file::RollingFile::open(log_cfg).unwrap_or_else(|e| {
+            eprintln!("\n❌  Log file failed to open:\n    {e:#}\n");
+            std::process::exit(1);
+        })
+    });
+    let (log_layer, logs) = log_panel::layer();
+    let (otel_layer, otel) = cfg.otel.as_ref().map(otel::spawn_otel).unzip();
+    tracing_subscriber::registry()
+        .with(fmt::layer().event_format(format()).with_writer(io::stderr).with_filter(env_filter()))
+        .with(log_file.map(|file| {
+            fmt::layer().event_format(format()).with_ansi(false).with_writer(Mutex::new(file)).with_filter(env_filter())
+        }))
+        .with(log_layer.with_filter(env_filter()))
+        .with(otel_layer.map(|layer| layer.with_filter(filter_fn(otel::wanted))))
+        .init();
+    if let Some(otel_cfg) = &cfg.otel {
+        info!("[OTEL] Exporting spans to {}", otel_cfg.traces_url);
+    }
+
+    info!("Config loaded: {} (HL={} PDX={}), {} pairs listed", cfg.pair_name, cfg.hl_symbol, cfg.pdx_symbol, cfg.pairs.len());
 
     // Validate symbols against both exchanges before connecting WebSockets
     eprintln!("Validating symbols against exchanges…");
@@ -54,65 +122,716 @@ async fn main() -> Result<()> {
     }
     eprintln!("  ✓ Hyperliquid: {}", cfg.hl_symbol);
 
-    if let Err(e) = config::validate_pdx_symbol(&cfg.pdx_symbol).await {
+    // A derived symbol came from the same markets list
+    if !pdx_derived && let Err(e) = config::validate_pdx_symbol(&cfg.pdx_symbol).await {
         eprintln!("\n❌  Paradex symbol validation failed:\n    {e}\n");
         std::process::exit(1);
     }
-    eprintln!("  ✓ Paradex: {}", cfg.pdx_symbol);
+    eprintln!("  ✓ Paradex: {}{}", cfg.pdx_symbol, if pdx_derived { " (derived)" } else { "" });
     eprintln!("Starting feeds…\n");
 
     // ── Shared state channels ─────────────────────────────────────────────────
-    let (hl_tx, hl_rx)   = watch::channel(OrderBook::new(Exchange::Hyperliquid, &cfg.hl_symbol));
-    let (pdx_tx, pdx_rx) = watch::channel(OrderBook::new(Exchange::Paradex, &cfg.pdx_symbol));
+    let (hl_tx, hl_rx)   = watch::channel(Arc::new(OrderBook::new(Exchange::Hyperliquid, &cfg.hl_symbol)));
+    let (pdx_tx, pdx_rx) = watch::channel(Arc::new(OrderBook::new(Exchange::Paradex, &cfg.pdx_symbol)));
+    let (merged_tx, merged_rx) = watch::channel(MergedBook::default());
+    let (trades_tx, _) = broadcast::channel(TRADES_CAPACITY);
+
+    // ── Optional clock sync (feeds take raw exchange timestamps without it) ──
+    let (hl_clock_rx, pdx_clock_rx) = match &cfg.clock_sync {
+        Some(clock_cfg) => clock::spawn_clock_sync(clock_cfg, cfg.hl_symbol.clone()),
+        None            => (watch::channel(None).1, watch::channel(None).1),
+    };
 
     // ── Spawn exchange feeds ──────────────────────────────────────────────────
-    hyperliquid_mda::spawn_hl_feed(cfg.hl_symbol.clone(), hl_tx);
-    paradex_mda::spawn_pdx_feed(cfg.pdx_symbol.clone(), pdx_tx);
+    let quarantine = Quarantine::new(&cfg.quarantine);
+    let feed_settings = |depth, aggregation| FeedSettings {
+        reconnect: cfg.reconnect, stale_ms: cfg.stale_ms, socket: cfg.socket, depth, aggregation, bbo: cfg.bbo,
+    };
+    let active = PairConfig { name: cfg.pair_name.clone(), hl_symbol: cfg.hl_symbol.clone(), pdx_symbol: cfg.pdx_symbol.clone() };
+    let mut feeds = Feeds::start(active, (hl_tx, pdx_tx), trades_tx.clone(), (hl_clock_rx, pdx_clock_rx), quarantine.clone(),
+        (feed_settings(cfg.hl_depth, cfg.hl_aggregation), feed_settings(cfg.pdx_depth, HlAggregation::default())));
+    let (policy_tx, policy_rx) = watch::channel(cfg.merge);
+    merger::spawn_merger(hl_rx.clone(), pdx_rx.clone(), policy_rx, cfg.fees, cfg.vwap_notional,
+        cfg.liquidity_bands_bps.clone(), merged_tx);
+
+    // ── Optional HTTP server (health check + snapshot API) ───────────────────
+    if let Some(http_cfg) = &cfg.http
+        && let Err(e) = http::spawn_http_server(http_cfg, hl_rx.clone(), pdx_rx.clone(), merged_rx.clone()).await
+    {
+        eprintln!("\n❌  HTTP server failed to start:\n    {e:#}\n");
+        std::process::exit(1);
+    }
+
+    // ── Optional WebSocket re-broadcast server ───────────────────────────────
+    if let Some(ws_cfg) = &cfg.ws_server
+        && let Err(e) = ws_server::spawn_ws_server(ws_cfg, merged_rx.clone()).await
+    {
+        eprintln!("\n❌  WebSocket server failed to start:\n    {e:#}\n");
+        std::process::exit(1);
+    }
+
+    // ── Optional gRPC streaming server ───────────────────────────────────────
+    if let Some(grpc_cfg) = &cfg.grpc
+        && let Err(e) = grpc::spawn_grpc_server(grpc_cfg, hl_rx.clone(), pdx_rx.clone(), merged_rx.clone()).await
+    {
+        eprintln!("\n❌  gRPC server failed to start:\n    {e:#}\n");
+        std::process::exit(1);
+    }
+
+    // ── Optional NATS publisher ──────────────────────────────────────────────
+    if let Some(nats_cfg) = &cfg.nats
+        && let Err(e) = nats_sink::spawn_nats_sink(
+            nats_cfg, &cfg.hl_symbol, hl_rx.clone(), pdx_rx.clone(), merged_rx.clone(),
+        ).await
+    {
+        eprintln!("\n❌  NATS sink failed to start:\n    {e:#}\n");
+        std::process::exit(1);
+    }
+
+    // ── Optional Redis publisher ─────────────────────────────────────────────
+    if let Some(redis_cfg) = &cfg.redis
+        && let Err(e) = redis_sink::spawn_redis_sink(redis_cfg, &cfg.hl_symbol, merged_rx.clone()).await
+    {
+        eprintln!("\n❌  Redis sink failed to start:\n    {e:#}\n");
+        std::process::exit(1);
+    }
+
+    // ── Optional Parquet writer ──────────────────────────────────────────────
+    let parquet = cfg.parquet.as_ref().map(|pq_cfg| {
+        parquet_sink::spawn_parquet_sink(pq_cfg, &cfg.hl_symbol, merged_rx.clone()).unwrap_or_else(|e| {
+            eprintln!("\n❌  Parquet sink failed to start:\n    {e:#}\n");
+            std::process::exit(1);
+        })
+    });
+
+    // ── Optional SQLite persistence ──────────────────────────────────────────
+    let sqlite = cfg.sqlite.as_ref().map(|db_cfg| {
+        sqlite_sink::spawn_sqlite_sink(db_cfg, &cfg.hl_symbol, &cfg.pdx_symbol, merged_rx.clone())
+            .unwrap_or_else(|e| {
+                eprintln!("\n❌  SQLite sink failed to start:\n    {e:#}\n");
+                std::process::exit(1);
+            })
+    });
+
+    // ── Optional arb audit file ──────────────────────────────────────────────
+    let arb_audit = match &cfg.arb_audit {
+        Some(audit_cfg) => Some(
+            arb_audit::spawn_arb_audit(audit_cfg, &cfg.hl_symbol, merged_rx.clone()).await.unwrap_or_else(|e| {
+                eprintln!("\n❌  Arb audit file failed to open:\n    {e:#}\n");
+                std::process::exit(1);
+            }),
+        ),
+        None => None,
+    };
+
+    // ── Optional Telegram arb alerts ─────────────────────────────────────────
+    if let Some(tg_cfg) = &cfg.telegram {
+        telegram::spawn_telegram_alerts(tg_cfg.clone(), cfg.hl_symbol.clone(), merged_rx.clone());
+    }
+
+    // ── Optional Slack arb alerts ────────────────────────────────────────────
+    if let Some(slack_cfg) = &cfg.slack {
+        slack::spawn_slack_alerts(slack_cfg.clone(), cfg.hl_symbol.clone(), merged_rx.clone());
+    }
+
+    // ── Optional desktop notifications ───────────────────────────────────────
+    if let Some(dn_cfg) = &cfg.desktop_notify {
+        desktop_notify::spawn_desktop_notifications(
+            dn_cfg.clone(), cfg.hl_symbol.clone(), hl_rx.clone(), pdx_rx.clone(), merged_rx.clone(),
+        );
+    }
+
+    // ── Risk limits (shared by paper and live order entry) ───────────────────
+    let (risk, risk_rx) = risk::RiskGate::new(cfg.risk);
+
+    // ── Optional paper trading ───────────────────────────────────────────────
+    let sim = cfg.sim.as_ref().map(|sim_cfg| sim::spawn_sim(sim_cfg, cfg.fees, risk.clone(), merged_rx.clone(), hl_rx.clone(), pdx_rx.clone()));
+
+    // ── Optional live trading on Hyperliquid ─────────────────────────────────
+    let hl_exec = match &cfg.hl_exec {
+        Some(exec_cfg) => Some(
+            hyperliquid_exec::spawn_hl_exec(exec_cfg, &cfg.hl_symbol, risk.clone(), merged_rx.clone(), hl_rx.clone()).await.unwrap_or_else(|e| {
+                eprintln!("\n❌  Hyperliquid execution failed to start:\n    {e:#}\n");
+                std::process::exit(1);
+            }),
+        ),
+        None => None,
+    };
+
+    // ── Optional live trading on Paradex ─────────────────────────────────────
+    let pdx_exec = match &cfg.pdx_exec {
+        Some(exec_cfg) => Some(
+            paradex_exec::spawn_pdx_exec(exec_cfg, &cfg.pdx_symbol, risk, merged_rx.clone(), pdx_rx.clone()).await.unwrap_or_else(|e| {
+                eprintln!("\n❌  Paradex execution failed to start:\n    {e:#}\n");
+                std::process::exit(1);
+            }),
+        ),
+        None => None,
+    };
+
+    // ── Optional rule-based alerts (banner feed for the TUI) ─────────────────
+    let alerts_rx = match &cfg.alerts {
+        Some(alerts_cfg) => alerts::spawn_alert_engine(
+            alerts_cfg, cfg.tick_ms, hl_rx.clone(), pdx_rx.clone(), merged_rx.clone(),
+            sim.as_ref().map(|s| s.tx.clone()),
+        ),
+        None => watch::channel(Vec::new()).1,
+    };
+
+    // ── Optional Arrow IPC stream ────────────────────────────────────────────
+    let arrow = cfg.arrow_ipc.as_ref().map(|ipc_cfg| {
+        arrow_sink::spawn_arrow_sink(ipc_cfg, hl_rx.clone(), pdx_rx.clone()).unwrap_or_else(|e| {
+            eprintln!("\n❌  Arrow IPC sink failed to start:\n    {e:#}\n");
+            std::process::exit(1);
+        })
+    });
+
+    // ── Optional open interest polling ───────────────────────────────────────
+    let stats = cfg.market_stats.as_ref().map(|stats_cfg| {
+        market_stats::spawn_market_stats(stats_cfg, cfg.hl_symbol.clone(), cfg.pdx_symbol.clone())
+    });
+
+    // ── Optional liquidations panel ──────────────────────────────────────────
+    let liquidations = cfg.liquidations.then(|| liquidations::spawn_liquidation_tape(trades_tx.subscribe()));
+
+    // ── Optional trade tape panel ────────────────────────────────────────────
+    let trades = cfg.trade_tape.then(|| trade_tape::spawn_trade_tape(trades_tx.subscribe()));
+
+    // ── Optional cumulative volume delta ─────────────────────────────────────
+    let cvd = cfg.cvd.as_ref().map(|cvd_cfg| cvd::spawn_cvd(cvd_cfg, trades_tx.subscribe(), merged_rx.clone()));
+
+    // ── Optional rolling spread statistics ───────────────────────────────────
+    let spreads = cfg.spread_stats.as_ref().map(|spread_cfg| {
+        spread_stats::spawn_spread_stats(spread_cfg, hl_rx.clone(), pdx_rx.clone(), merged_rx.clone())
+    });
+
+    // ── Optional realized volatility ─────────────────────────────────────────
+    let vol = cfg.volatility.as_ref().map(|vol_cfg| volatility::spawn_volatility(vol_cfg, merged_rx.clone()));
+
+    // ── Cross spread sparkline ───────────────────────────────────────────────
+    let spread_history = (cfg.spread_history_secs > 0)
+        .then(|| spread_stats::spawn_spread_history(cfg.spread_history_secs, merged_rx.clone()));
+
+    // ── Chart and rate history (recorded all session, shown on demand) ───────
+    let mids = mid_history::spawn_mid_history(hl_rx.clone(), pdx_rx.clone(), merged_rx.clone());
+    let candles = candles::spawn_candles(merged_rx.clone());
+    let msg_rates = msg_rate::spawn_msg_rates(hl_rx.clone(), pdx_rx.clone());
+
+    // ── Both venues' listings, for the pair picker ───────────────────────────
+    let markets = markets::spawn_market_list();
+
+    // ── Session totals, printed on exit ──────────────────────────────────────
+    let arb_log_rx = arb::spawn_arb_log(merged_rx.clone());
+    let session = session::spawn_session_summary(hl_rx.clone(), pdx_rx.clone(), merged_rx.clone(), arb_log_rx.clone());
 
     // ── Run TUI ───────────────────────────────────────────────────────────────
-    run_tui(hl_rx, pdx_rx, cfg).await?;
+    let panels = Panels { alerts_rx, arb_log_rx, stats, liquidations, trades, cvd, spreads, vol,
+        spread_history, mids, candles, msg_rates, logs, markets, quarantine, policy_tx };
+    let quit_rx = term::spawn_quit_signals()?;
+    theme::set(cfg.theme);
+    let quote_unit = cfg.quote_unit.clone();
+    run_tui((hl_rx, pdx_rx), merged_rx, &mut feeds, panels, OrderEntry { sim, hl_exec, pdx_exec, risk_rx }, quit_rx, cfg).await?;
+
+    // Close both sockets rather than dropping them with the runtime
+    feeds.shutdown(FEED_SHUTDOWN_TIMEOUT).await;
+    // Finalise the open Parquet file so it has a valid footer
+    if let Some(parquet) = parquet {
+        parquet.shutdown();
+    }
+    // Record the session end time
+    if let Some(sqlite) = sqlite {
+        sqlite.shutdown();
+    }
+    // Flush the last rows and write the end-of-stream marker
+    if let Some(arrow) = arrow {
+        arrow.shutdown().await;
+    }
+    // Record a window still open at exit
+    if let Some(arb_audit) = arb_audit {
+        arb_audit.shutdown().await;
+    }
+    // Send the spans still batched, including the feeds' last ones
+    if let Some(otel) = otel {
+        otel.shutdown().await;
+    }
 
+    eprintln!("\n{}\n", session.borrow().report(&quote_unit));
     Ok(())
 }
 
 // ─── TUI loop ────────────────────────────────────────────────────────────────
 
+/// Half-period of the merged-book border blink while an arb is open.
+const ARB_FLASH_MS: u128 = 250;
+/// How long the risk banner stays up after a limit blocks an order.
+const RISK_BANNER_MS: u64 = 5_000;
+/// Longest the screen goes without a redraw while nothing changes.
+const IDLE_REDRAW: Duration = Duration::from_secs(1);
+/// Levels PgUp / PgDn move the book cursor.
+const BOOK_PAGE: usize = 10;
+/// How long an armed live order waits for Enter before it is dropped.
+const LIVE_CONFIRM: Duration = Duration::from_secs(3);
+
+/// Everything the TUI shows besides the books and order entry.
+struct Panels {
+    alerts_rx:  watch::Receiver<Vec<ActiveAlert>>,
+    arb_log_rx: watch::Receiver<VecDeque<ArbWindow>>,
+    stats:      Option<(watch::Receiver<MarketStats>, watch::Receiver<MarketStats>)>, // (HL, PDX)
+    liquidations: Option<watch::Receiver<LiquidationTape>>,
+    trades:     Option<watch::Receiver<VecDeque<Trade>>>,
+    cvd:        Option<watch::Receiver<CvdState>>,
+    spreads:    Option<watch::Receiver<SpreadStats>>,
+    vol:        Option<watch::Receiver<VolState>>,
+    spread_history: Option<watch::Receiver<VecDeque<Option<f64>>>>, // cross spread bps, oldest first
+    mids:       watch::Receiver<MidHistory>,
+    candles:    watch::Receiver<Vec<CandleSeries>>, // one per `candles::INTERVALS_MS`
+    msg_rates:  watch::Receiver<MsgRates>,
+    logs:       watch::Receiver<VecDeque<LogLine>>, // newest first
+    markets:    watch::Receiver<Vec<Market>>, // both venues' listings for the pair picker; empty until fetched
+    quarantine: Quarantine,
+    policy_tx:  watch::Sender<MergePolicy>, // how the merger shapes the displayed book
+}
+
+/// Order entry the TUI keys drive; each is `None` when disabled in config.
+struct OrderEntry {
+    sim:      Option<SimHandle>,
+    hl_exec:  Option<ExecHandle>,
+    pdx_exec: Option<ExecHandle>,
+    risk_rx:  watch::Receiver<Option<RiskBlock>>,
+}
+
+/// A live order `H`, `P` or `C` has armed; only Enter sends it.
+#[derive(Clone)]
+enum LiveAction {
+    ArbLeg(Exchange),
+    CancelAll,
+}
+
+impl LiveAction {
+    /// What Enter will do, for the confirmation banner.
+    fn label(&self) -> String {
+        match self {
+            LiveAction::ArbLeg(venue) => format!("send {}'s leg of the current arb", venue.short()),
+            LiveAction::CancelAll     => "cancel every resting order on both venues".into(),
+        }
+    }
+}
+
+impl OrderEntry {
+    fn live(&self, venue: &Exchange) -> bool {
+        match venue {
+            Exchange::Hyperliquid => self.hl_exec.is_some(),
+            Exchange::Paradex     => self.pdx_exec.is_some(),
+        }
+    }
+
+    fn send_live(&self, action: &LiveAction) {
+        match action {
+            LiveAction::ArbLeg(venue) => self.send_exec(venue, ExecCommand::ArbLeg),
+            LiveAction::CancelAll => {
+                self.send_exec(&Exchange::Hyperliquid, ExecCommand::CancelAll);
+                self.send_exec(&Exchange::Paradex, ExecCommand::CancelAll);
+            }
+        }
+    }
+
+    /// No-op when that venue's live trading is disabled.
+    fn send_exec(&self, venue: &Exchange, cmd: ExecCommand) {
+        let exec = match venue {
+            Exchange::Hyperliquid => &self.hl_exec,
+            Exchange::Paradex     => &self.pdx_exec,
+        };
+        if let Some(exec) = exec {
+            let _ = exec.tx.try_send(cmd);
+        }
+    }
+}
+
+/// Deepest level index in any book side on screen, for the book cursor.
+fn deepest(
+    hl_rx: &watch::Receiver<Arc<OrderBook>>,
+    pdx_rx: &watch::Receiver<Arc<OrderBook>>,
+    merged_rx: &watch::Receiver<MergedBook>,
+) -> usize {
+    let merged = merged_rx.borrow();
+    let (bids, asks) = merged.shown();
+    let (hl, pdx) = (hl_rx.borrow(), pdx_rx.borrow());
+    [bids.len(), asks.len(), hl.bids.len(), hl.asks.len(), pdx.bids.len(), pdx.asks.len()]
+        .into_iter().max().unwrap_or(0).saturating_sub(1)
+}
+
 async fn run_tui(
-    mut hl_rx:  watch::Receiver<OrderBook>,
-    mut pdx_rx: watch::Receiver<OrderBook>,
+    (mut hl_rx, mut pdx_rx): (watch::Receiver<Arc<OrderBook>>, watch::Receiver<Arc<OrderBook>>),
+    mut merged_rx: watch::Receiver<MergedBook>,
+    feeds: &mut Feeds,
+    panels: Panels,
+    orders: OrderEntry,
+    quit_rx: watch::Receiver<bool>, // set by SIGINT / SIGTERM / SIGHUP
     cfg: AppConfig,
 ) -> Result<()> {
     enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
+    let restore = term::RestoreGuard;
+    // stdout carries the Arrow stream in that mode, so draw on the terminal directly
+    let mut out: Box<dyn Write + Send> = match cfg.arrow_ipc.as_ref().map(|a| &a.target) {
+        Some(ArrowIpcTarget::Stdout) => Box::new(OpenOptions::new().write(true).open("/dev/tty")?),
+        _ => Box::new(io::stdout()),
+    };
+    execute!(out, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(out);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
-    let tick = Duration::from_millis(cfg.tick_ms);
+    let mut tick = AdaptiveTick::new(cfg.tick_ms, cfg.max_tick_ms);
+    let mut arb_since: Option<Instant> = None;
+    let mut arb_scroll: usize = 0;
+    let mut book_cursor: Option<usize> = None; // levels from the touch; None = unscrolled books
+    let mut impact = ImpactCalc::default();
+    let mut picker = PairPicker::new(&cfg.pairs);
+    let mut pending_pair: Option<JoinHandle<Result<PairConfig>>> = None;
+    let carousel_every = Duration::from_secs(cfg.carousel_secs);
+    let mut carousel: Option<Instant> = cfg.carousel.then(Instant::now); // last rotation; None = not rotating
+    let mut grid: Option<PairGrid> = None; // Some = the body is the grid of every pair, with feeds of its own
+    // Books shown while paused, and when they were taken; the feeds keep running
+    let mut frozen: Option<(Instant, Arc<OrderBook>, Arc<OrderBook>, MergedBook)> = None;
+    // Live order waiting for Enter, and when it was armed
+    let mut live_confirm: Option<(Instant, LiveAction)> = None;
+    let mut show_chart = false;
+    let mut candle_interval: Option<usize> = None; // index into `candles::INTERVALS_MS`
+    let mut show_depth = false;
+    let mut show_diagnostics = false;
+    let mut show_logs = false;
+    let (mut show_signals, mut show_hl, mut show_pdx) = (true, true, true);
+    let mut body_tab: usize = 0; // panel shown on a narrow terminal
+    // `t` swaps the configured palette for the colour-blind one and back
+    let alt_theme = if cfg.theme == Theme::COLORBLIND { Theme::DARK } else { Theme::COLORBLIND };
+    let (mut hl_ticks, mut pdx_ticks) = (BookTicks::default(), BookTicks::default());
+    let mut redraw = true;
+    let mut drawn_versions = (0, 0); // (HL, PDX) book versions on screen
+    let mut last_draw = Instant::now();
+    let mut draw_time = Timing::default();
+    let mut clipboard = Clipboard::default();
 
     'main: loop {
-        let hl_book  = hl_rx.borrow_and_update().clone();
-        let pdx_book = pdx_rx.borrow_and_update().clone();
-        let merged   = MergedBook::build(&hl_book, &pdx_book, cfg.depth);
-
-        terminal.draw(|f| ui::draw(f, &hl_book, &pdx_book, &merged))?;
-
-        if event::poll(tick)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => break 'main,
-                        _ => {}
+        if *quit_rx.borrow() {
+            break;
+        }
+        // A pair typed into the picker has been checked against both exchanges
+        if pending_pair.as_ref().is_some_and(|h| h.is_finished())
+            && let Some(handle) = pending_pair.take()
+        {
+            match handle.await {
+                Ok(Ok(pair)) => {
+                    let msg = format!("switched to {} (HL={} PDX={})", pair.name, pair.hl_symbol, pair.pdx_symbol);
+                    if !picker.pairs.iter().any(|p| p.name == pair.name) {
+                        picker.pairs.push(pair.clone());
                     }
+                    feeds.switch(pair, FEED_SHUTDOWN_TIMEOUT).await;
+                    (hl_ticks, pdx_ticks) = (BookTicks::default(), BookTicks::default());
+                    frozen = None;
+                    picker.finish(msg, false);
                 }
+                Ok(Err(e)) => picker.finish(format!("{e:#}"), true),
+                Err(e)     => picker.finish(format!("lookup failed: {e}"), true),
+            }
+            redraw = true;
+        }
+        // Carousel: on to the next pair once this one has had its turn, unless
+        // it's being read (paused) or another pair is being picked
+        if carousel.is_some_and(|last| last.elapsed() >= carousel_every)
+            && frozen.is_none()
+            && !picker.open
+            && pending_pair.is_none()
+        {
+            carousel = Some(Instant::now());
+            let blockers = pair_picker::switch_blockers(&cfg);
+            if !blockers.is_empty() {
+                warn!("Carousel stopped: the pair can't be switched while {} is enabled", blockers.join(", "));
+                carousel = None;
+            } else if let Some(next) = picker.next_pair(&feeds.pair().name) {
+                picker.submit(next);
+            }
+        }
+        if let Some(input) = picker.take_submitted() {
+            let blockers = pair_picker::switch_blockers(&cfg);
+            let open_position = orders.sim.as_ref()
+                .is_some_and(|s| { let st = s.state_rx.borrow(); st.hl.qty != 0.0 || st.pdx.qty != 0.0 });
+            if !blockers.is_empty() {
+                picker.finish(format!("bound to {}: disable {} to switch", feeds.pair().name, blockers.join(", ")), true);
+            } else if open_position {
+                picker.finish("flatten the paper positions first ([z])".to_string(), true);
+            } else {
+                let pairs = picker.pairs.clone();
+                pending_pair = Some(tokio::spawn(async move { pair_picker::resolve(&input, &pairs).await }));
+            }
+        }
+        if let Some(grid) = &mut grid {
+            grid.poll(feeds).await;
+        }
+        // Skip the frame when nothing it shows has changed: no feed or merged book
+        // update (ignored while paused), no key or resize, no blinking border, and
+        // the idle redraw (which keeps ages and the side panels current) isn't due
+        let versions = (hl_rx.borrow().version, pdx_rx.borrow().version);
+        let dirty = redraw
+            || (frozen.is_none() && (versions != drawn_versions || merged_rx.has_changed().unwrap_or(false)))
+            || grid.as_ref().is_some_and(PairGrid::has_changed)
+            || (cfg.arb_flash && arb_since.is_some())
+            || last_draw.elapsed() >= IDLE_REDRAW;
+        if dirty {
+            let (hl_book, pdx_book, merged) = match &frozen {
+                Some((_, hl, pdx, merged)) => (hl.clone(), pdx.clone(), merged.clone()),
+                None => (
+                    hl_rx.borrow_and_update().clone(),
+                    pdx_rx.borrow_and_update().clone(),
+                    merged_rx.borrow_and_update().clone(),
+                ),
+            };
+            let alerts   = panels.alerts_rx.borrow().clone();
+            hl_ticks.update(&hl_book, Instant::now());
+            pdx_ticks.update(&pdx_book, Instant::now());
+
+            // Bell once when an arb opens; blink the border at 2 Hz while it lasts
+            let arb = merged.signals.is_arb();
+            match (arb, arb_since) {
+                (true, None) => {
+                    arb_since = Some(Instant::now());
+                    if cfg.arb_bell {
+                        terminal.backend_mut().write_all(b"\x07")?;
+                        terminal.backend_mut().flush()?;
+                    }
+                }
+                (false, Some(_)) => arb_since = None,
+                _ => {}
+            }
+            let flash = cfg.arb_flash
+                && arb_since.is_some_and(|t| (t.elapsed().as_millis() / ARB_FLASH_MS).is_multiple_of(2));
+
+            let arb_log = panels.arb_log_rx.borrow().clone();
+            let liquidations = panels.liquidations.as_ref().map(|rx| rx.borrow().clone());
+            let trades = panels.trades.as_ref().map(|rx| rx.borrow().clone());
+            let cvd = panels.cvd.as_ref().map(|rx| rx.borrow().clone());
+            let spreads = panels.spreads.as_ref().map(|rx| rx.borrow().clone());
+            let vol = panels.vol.as_ref().map(|rx| rx.borrow().clone());
+            let spread_history = panels.spread_history.as_ref().map(|rx| rx.borrow().clone());
+            let mids = show_chart.then(|| panels.mids.borrow().clone());
+            let msg_rates = panels.msg_rates.borrow().clone();
+            let logs = show_logs.then(|| panels.logs.borrow().clone());
+            let candle_series = candle_interval.and_then(|i| panels.candles.borrow().get(i).cloned());
+            let (hl_stats, pdx_stats) = match &panels.stats {
+                Some((hl, pdx)) => (Some(hl.borrow().clone()), Some(pdx.borrow().clone())),
+                None => (None, None),
+            };
+            let sim_state = orders.sim.as_ref().map(|s| s.state_rx.borrow().clone());
+            let hl_exec_state = orders.hl_exec.as_ref().map(|e| e.state_rx.borrow().clone());
+            let pdx_exec_state = orders.pdx_exec.as_ref().map(|e| e.state_rx.borrow().clone());
+            let accounts: Vec<_> = [&orders.hl_exec, &orders.pdx_exec].into_iter()
+                .flatten()
+                .map(|e| e.account_rx.borrow().clone())
+                .collect();
+            let confirm_label = live_confirm.as_ref()
+                .filter(|(at, _)| at.elapsed() < LIVE_CONFIRM)
+                .map(|(_, a)| a.label());
+            let risk_block = orders.risk_rx.borrow().clone()
+                .filter(|b| now_ms().saturating_sub(b.ts_ms) < RISK_BANNER_MS);
+            arb_scroll = arb_scroll.min(arb_log.len().saturating_sub(1));
+            if impact.open {
+                impact.refresh(&hl_book, &pdx_book);
+            }
+            if picker.open {
+                picker.refresh(&panels.markets.borrow());
+            }
+            let grid_cards = grid.as_mut().map(|g| g.cards(&feeds.pair().name, &cfg.fees));
+
+            let draw_start = Instant::now();
+            terminal.draw(|f| ui::draw(f, &ui::View {
+                hl:         &hl_book,
+                pdx:        &pdx_book,
+                merged:     &merged,
+                alerts:     &alerts,
+                arb_log:    &arb_log,
+                arb_scroll,
+                flash,
+                fees:       &cfg.fees,
+                sim:        sim_state.as_ref(),
+                hl_exec:    hl_exec_state.as_ref(),
+                pdx_exec:   pdx_exec_state.as_ref(),
+                accounts:   &accounts,
+                risk_block: risk_block.as_ref(),
+                hl_stats:   hl_stats.as_ref(),
+                pdx_stats:  pdx_stats.as_ref(),
+                liquidations: liquidations.as_ref(),
+                trades:     trades.as_ref(),
+                cvd:        cvd.as_ref(),
+                spreads:    spreads.as_ref(),
+                vol:        vol.as_ref(),
+                spread_history: spread_history.as_ref(),
+                mid_chart:  mids.as_ref(),
+                candles:    candle_series.as_ref(),
+                depth_chart: show_depth,
+                hl_ticks:   &hl_ticks,
+                pdx_ticks:  &pdx_ticks,
+                msg_rates:  &msg_rates,
+                diagnostics: show_diagnostics,
+                logs:       logs.as_ref(),
+                quarantined: panels.quarantine.len(),
+                impact:     impact.open.then_some(&impact),
+                pair_picker: picker.open.then_some(&picker),
+                pair_grid:  grid_cards.as_deref(),
+                carousel:   carousel.map(|last| carousel_every.saturating_sub(last.elapsed())),
+                sweep_size: cfg.sweep_size,
+                venue_depth: cfg.venue_depth,
+                quote_unit: &cfg.quote_unit,
+                paused:     frozen.as_ref().map(|(at, ..)| at.elapsed()),
+                live_confirm: confirm_label.as_deref(),
+                book_cursor,
+                signals_panel: show_signals,
+                hl_panel:   show_hl,
+                pdx_panel:  show_pdx,
+                narrow_width: cfg.narrow_width,
+                age_ms:     cfg.age_ms,
+                body_tab,
+                draw_time:  &draw_time,
+            }))?;
+            draw_time.record(draw_start.elapsed());
+            (drawn_versions, last_draw, redraw) = (versions, Instant::now(), false);
+        }
+
+        if !event::poll(tick.next(versions, Instant::now()))? {
+            continue;
+        }
+        let event = event::read()?;
+        redraw = true; // a key or a resize
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+            // The pair picker and the impact calculator take the keyboard while open
+            && !picker.handle_key(key.code)
+            && !impact.handle_key(key.code)
+        {
+            // Any key but Enter drops an armed live order
+            let armed = live_confirm.take().filter(|(at, _)| at.elapsed() < LIVE_CONFIRM).map(|(_, a)| a);
+            match key.code {
+                KeyCode::Enter | KeyCode::Esc if armed.is_some() => {
+                    if key.code == KeyCode::Esc {
+                        info!("Live order dropped");
+                    } else if frozen.is_some() {
+                        // The exec tasks price from the live books, not the frozen ones on screen
+                        warn!("Live orders are refused while the display is paused; press p to resume");
+                    } else if let Some(action) = &armed {
+                        orders.send_live(action);
+                    }
+                }
+                KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => break 'main,
+                // Raw mode delivers Ctrl-C as a key rather than SIGINT
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break 'main,
+                // ↓ / ↑ walk a cursor down every book from the touch and back; while it's
+                // out, PgDn / PgUp page it instead of scrolling the arb log
+                KeyCode::Down => book_cursor = Some(book_cursor.map_or(0, |c| c + 1).min(deepest(&hl_rx, &pdx_rx, &merged_rx))),
+                KeyCode::Up   => book_cursor = book_cursor.and_then(|c| c.checked_sub(1)),
+                KeyCode::Home => book_cursor = None,
+                KeyCode::PageDown if book_cursor.is_some() => {
+                    book_cursor = book_cursor.map(|c| (c + BOOK_PAGE).min(deepest(&hl_rx, &pdx_rx, &merged_rx)));
+                }
+                KeyCode::PageUp if book_cursor.is_some() => book_cursor = book_cursor.map(|c| c.saturating_sub(BOOK_PAGE)),
+                KeyCode::PageDown => arb_scroll += 1,
+                KeyCode::PageUp   => arb_scroll = arb_scroll.saturating_sub(1),
+                KeyCode::Char('g') => show_chart = !show_chart,
+                KeyCode::Char('d') => show_depth = !show_depth,
+                KeyCode::Char('l') => show_diagnostics = !show_diagnostics,
+                KeyCode::Char('e') => show_logs = !show_logs,
+                KeyCode::Char('t') => theme::set(if theme::current() == cfg.theme { alt_theme } else { cfg.theme }),
+                KeyCode::Tab => body_tab = body_tab.wrapping_add(1),
+                KeyCode::Char('1') => show_signals = !show_signals,
+                KeyCode::Char('2') => show_hl = !show_hl,
+                KeyCode::Char('3') => show_pdx = !show_pdx,
+                KeyCode::Char('r') => carousel = match carousel {
+                    Some(_) => None,
+                    None => Some(Instant::now()),
+                },
+                KeyCode::Char('G') => match grid.take() {
+                    Some(open) => open.shutdown(FEED_SHUTDOWN_TIMEOUT).await,
+                    None => grid = Some(PairGrid::open(&picker.pairs, feeds)),
+                },
+                KeyCode::Char('p') => frozen = match frozen {
+                    Some(_) => None,
+                    None => Some((Instant::now(), hl_rx.borrow().clone(), pdx_rx.borrow().clone(), merged_rx.borrow().clone())),
+                },
+                // Copy the merged quote on screen (the frozen one while paused)
+                KeyCode::Char(key @ ('c' | 'm' | 'v')) => {
+                    let quote = match key { 'c' => Quote::Bbo, 'm' => Quote::Mid, _ => Quote::Spread };
+                    let text = match &frozen {
+                        Some((_, _, _, merged)) => quote.text(&feeds.pair().name, merged),
+                        None => quote.text(&feeds.pair().name, &merged_rx.borrow()),
+                    };
+                    match text.map(|t| clipboard.copy(t.clone()).map(|()| t)) {
+                        Some(Ok(t))  => info!("Copied to the clipboard: {t}"),
+                        Some(Err(e)) => warn!("Copying the {} failed: {e:#}", quote.label()),
+                        None         => warn!("No {} to copy yet", quote.label()),
+                    }
+                }
+                // Write the books on screen to a file (the frozen ones while paused)
+                KeyCode::Char('o') => {
+                    let written = match &frozen {
+                        Some((_, hl, pdx, merged)) => book_dump::write(&cfg.dump_dir, &feeds.pair().name, hl, pdx, merged),
+                        None => book_dump::write(&cfg.dump_dir, &feeds.pair().name,
+                            &hl_rx.borrow(), &pdx_rx.borrow(), &merged_rx.borrow()),
+                    };
+                    match written {
+                        Ok(path) => info!("Wrote the books to {}", path.display()),
+                        Err(e)   => warn!("Book dump failed: {e:#}"),
+                    }
+                }
+                KeyCode::Char('a') => panels.policy_tx.send_modify(|p| p.mode = p.mode.next()),
+                // Price buckets: '=' is '+' without shift
+                KeyCode::Char('+') | KeyCode::Char('=') => panels.policy_tx.send_modify(|p| p.group = merger::step_group(p.group, true)),
+                KeyCode::Char('-') => panels.policy_tx.send_modify(|p| p.group = merger::step_group(p.group, false)),
+                KeyCode::Char('w') if !panels.quarantine.is_empty() => match panels.quarantine.dump() {
+                    Ok((path, n)) => info!("Wrote {n} quarantined payloads to {}", path.display()),
+                    Err(e)        => warn!("Quarantine dump failed: {e:#}"),
+                },
+                // Cycle the candle chart: off → 1s → 5s → 1m → off
+                KeyCode::Char('k') => candle_interval = match candle_interval {
+                    None => Some(0),
+                    Some(i) if i + 1 < candles::INTERVALS_MS.len() => Some(i + 1),
+                    Some(_) => None,
+                },
+                // Live orders only arm: `P` and `C` sit a shift away from `p` (pause) and
+                // `c` (copy), so nothing is sent until Enter confirms it
+                KeyCode::Char(key @ ('H' | 'P' | 'C')) => {
+                    let action = match key {
+                        'H' => LiveAction::ArbLeg(Exchange::Hyperliquid),
+                        'P' => LiveAction::ArbLeg(Exchange::Paradex),
+                        _   => LiveAction::CancelAll,
+                    };
+                    let enabled = match &action {
+                        LiveAction::ArbLeg(venue) => orders.live(venue),
+                        LiveAction::CancelAll     => orders.live(&Exchange::Hyperliquid) || orders.live(&Exchange::Paradex),
+                    };
+                    if enabled && frozen.is_some() {
+                        warn!("Live orders are refused while the display is paused; press p to resume");
+                    } else if enabled {
+                        live_confirm = Some((Instant::now(), action));
+                    }
+                }
+                KeyCode::Char(c) if orders.sim.is_some() => {
+                    let action = match c {
+                        'b' => Some(SimAction::Buy),
+                        's' => Some(SimAction::Sell),
+                        'x' => Some(SimAction::Arb),
+                        'z' => Some(SimAction::Flatten),
+                        _   => None,
+                    };
+                    if let (Some(action), Some(sim)) = (action, &orders.sim) {
+                        let _ = sim.tx.try_send(action);
+                    }
+                }
+                _ => {}
             }
         }
     }
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    if let Some(grid) = grid {
+        grid.shutdown(FEED_SHUTDOWN_TIMEOUT).await;
+    }
+    drop(restore);
     info!("Goodbye!");
     Ok(())
 }
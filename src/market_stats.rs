@@ -0,0 +1,125 @@
+// src/market_stats.rs — Per-venue market statistics polled over REST (open interest)
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::watch;
+use tokio::time::interval;
+use tracing::{info, warn};
+
+use crate::config::MarketStatsConfig;
+use crate::types::{now_ms, Exchange};
+
+const HL_INFO_URL: &str = "https://api.hyperliquid.xyz/info";
+const PDX_SUMMARY_URL: &str = "https://api.prod.paradex.trade/v1/markets/summary";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketStats {
+    pub exchange:       Exchange,
+    pub open_interest:  Option<f64>, // base units
+    pub oi_at_start:    Option<f64>, // first value seen this session
+    pub last_update_ms: u64,
+}
+
+impl MarketStats {
+    fn new(exchange: Exchange) -> Self {
+        Self { exchange, open_interest: None, oi_at_start: None, last_update_ms: 0 }
+    }
+
+    /// Change in open interest since the session started, in percent.
+    pub fn oi_change_pct(&self) -> Option<f64> {
+        match (self.open_interest, self.oi_at_start) {
+            (Some(now), Some(start)) if start > 0.0 => Some((now - start) / start * 100.0),
+            _ => None,
+        }
+    }
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Spawns one poller per venue; returns `(hl_rx, pdx_rx)`. A failed poll is
+/// logged and the last value kept.
+pub fn spawn_market_stats(
+    cfg: &MarketStatsConfig,
+    hl_coin: String,
+    pdx_market: String,
+) -> (watch::Receiver<MarketStats>, watch::Receiver<MarketStats>) {
+    let http = reqwest::Client::new();
+    let every = Duration::from_secs(cfg.poll_secs);
+    info!("[STATS] Polling open interest every {}s", cfg.poll_secs);
+    (
+        spawn_poller(Exchange::Hyperliquid, every, http.clone(), hl_coin),
+        spawn_poller(Exchange::Paradex, every, http, pdx_market),
+    )
+}
+
+fn spawn_poller(
+    exchange: Exchange,
+    every: Duration,
+    http: reqwest::Client,
+    market: String,
+) -> watch::Receiver<MarketStats> {
+    let (tx, rx) = watch::channel(MarketStats::new(exchange.clone()));
+    tokio::spawn(async move {
+        let mut ticker = interval(every);
+        loop {
+            ticker.tick().await;
+            let polled = match exchange {
+                Exchange::Hyperliquid => poll_hl(&http, &market).await,
+                Exchange::Paradex     => poll_pdx(&http, &market).await,
+            };
+            match polled {
+                Ok(oi) => tx.send_modify(|s| {
+                    s.open_interest = Some(oi);
+                    s.oi_at_start.get_or_insert(oi);
+                    s.last_update_ms = now_ms();
+                }),
+                Err(e) => warn!("[STATS] {} poll failed: {e:#}", exchange.short()),
+            }
+        }
+    });
+    rx
+}
+
+// ─── Venue requests ───────────────────────────────────────────────────────────
+
+/// `metaAndAssetCtxs` returns `[meta, ctxs]`, where `ctxs[i]` belongs to
+/// `meta.universe[i]`.
+async fn poll_hl(http: &reqwest::Client, coin: &str) -> Result<f64> {
+    let resp: Value = http
+        .post(HL_INFO_URL)
+        .json(&serde_json::json!({ "type": "metaAndAssetCtxs" }))
+        .send()
+        .await
+        .context("request failed")?
+        .json()
+        .await
+        .context("unparseable response")?;
+
+    let index = resp[0]["universe"].as_array()
+        .and_then(|u| u.iter().position(|a| a["name"].as_str().is_some_and(|n| n.eq_ignore_ascii_case(coin))))
+        .with_context(|| format!("{coin} not in universe"))?;
+    num(&resp[1][index]["openInterest"]).context("no openInterest")
+}
+
+/// `{results: [{symbol, open_interest, …}]}`
+async fn poll_pdx(http: &reqwest::Client, market: &str) -> Result<f64> {
+    let resp: Value = http
+        .get(PDX_SUMMARY_URL)
+        .query(&[("market", market)])
+        .send()
+        .await
+        .context("request failed")?
+        .json()
+        .await
+        .context("unparseable response")?;
+
+    num(&resp["results"][0]["open_interest"]).context("no open_interest")
+}
+
+/// Both venues send numbers as strings.
+fn num(v: &Value) -> Option<f64> {
+    v.as_str().and_then(|s| s.parse().ok())
+}
@@ -0,0 +1,173 @@
+// src/markets.rs — Both venues' perp listings, fetched once for the pair picker
+//
+// One entry per base coin with its symbol and 24h volume on each venue that
+// lists it. The pair picker fuzzy-matches what's typed against the coins, so
+// markets listed on both venues can be found without leaving the TUI.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+const HL_INFO_URL: &str = "https://api.hyperliquid.xyz/info";
+const PDX_MARKETS_URL: &str = "https://api.prod.paradex.trade/v1/markets";
+const PDX_SUMMARY_URL: &str = "https://api.prod.paradex.trade/v1/markets/summary";
+
+#[derive(Debug, Clone, Default)]
+pub struct Market {
+    pub coin:       String,         // base currency, e.g. "ETH"; the Hyperliquid coin
+    pub on_hl:      bool,
+    pub pdx_symbol: Option<String>, // None = not listed on Paradex
+    pub hl_volume:  Option<f64>,    // 24h notional, USD
+    pub pdx_volume: Option<f64>,    // 24h notional, USD
+}
+
+impl Market {
+    pub fn on_both(&self) -> bool {
+        self.on_hl && self.pdx_symbol.is_some()
+    }
+
+    pub fn volume(&self) -> f64 {
+        self.hl_volume.unwrap_or(0.0) + self.pdx_volume.unwrap_or(0.0)
+    }
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Fetches both listings in the background; the receiver holds an empty list
+/// until they arrive, and keeps it empty if either request fails.
+pub fn spawn_market_list() -> watch::Receiver<Vec<Market>> {
+    let (tx, rx) = watch::channel(Vec::new());
+    tokio::spawn(async move {
+        match fetch_markets(&reqwest::Client::new()).await {
+            Ok(markets) => {
+                let both = markets.iter().filter(|m| m.on_both()).count();
+                info!("[MARKETS] {} coins listed, {both} on both venues", markets.len());
+                tx.send_replace(markets);
+            }
+            Err(e) => warn!("[MARKETS] Listing fetch failed, the pair picker won't suggest markets: {e:#}"),
+        }
+    });
+    rx
+}
+
+/// Every perp on either venue, by coin, busiest first.
+pub async fn fetch_markets(http: &reqwest::Client) -> Result<Vec<Market>> {
+    let (hl, pdx, pdx_volumes) = tokio::try_join!(fetch_hl(http), fetch_pdx(http), fetch_pdx_volumes(http))?;
+
+    let mut by_coin: BTreeMap<String, Market> = BTreeMap::new();
+    for (coin, volume) in hl {
+        by_coin.insert(coin.clone(), Market { coin, on_hl: true, hl_volume: volume, ..Market::default() });
+    }
+    for (coin, symbol) in pdx {
+        let market = by_coin.entry(coin.clone()).or_insert_with(|| Market { coin, ..Market::default() });
+        market.pdx_volume = pdx_volumes.get(&symbol).copied();
+        market.pdx_symbol = Some(symbol);
+    }
+    let mut markets: Vec<Market> = by_coin.into_values().collect();
+    markets.sort_by(|a, b| b.volume().total_cmp(&a.volume()));
+    Ok(markets)
+}
+
+// ─── Venue requests ───────────────────────────────────────────────────────────
+
+/// `metaAndAssetCtxs` returns `[meta, ctxs]`, where `ctxs[i]` belongs to
+/// `meta.universe[i]`. Delisted coins are left out.
+async fn fetch_hl(http: &reqwest::Client) -> Result<Vec<(String, Option<f64>)>> {
+    let resp: Value = http
+        .post(HL_INFO_URL)
+        .json(&serde_json::json!({ "type": "metaAndAssetCtxs" }))
+        .send()
+        .await
+        .context("Hyperliquid request failed")?
+        .json()
+        .await
+        .context("unparseable Hyperliquid response")?;
+
+    let universe = resp[0]["universe"].as_array().context("unexpected Hyperliquid meta structure")?;
+    Ok(universe
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| !a["isDelisted"].as_bool().unwrap_or(false))
+        .filter_map(|(i, a)| Some((a["name"].as_str()?.to_uppercase(), num(&resp[1][i]["dayNtlVlm"]))))
+        .collect())
+}
+
+/// `(base currency, symbol)` of each Paradex perp, the USD-quoted one when a
+/// coin has several, as `config::resolve_pdx_symbol` picks.
+async fn fetch_pdx(http: &reqwest::Client) -> Result<Vec<(String, String)>> {
+    let resp: Value = http
+        .get(PDX_MARKETS_URL)
+        .send()
+        .await
+        .context("Paradex request failed")?
+        .json()
+        .await
+        .context("unparseable Paradex markets response")?;
+
+    let results = resp["results"].as_array().context("unexpected Paradex markets structure")?;
+    let mut perps: Vec<(String, String, bool)> = results
+        .iter()
+        .filter(|m| m["asset_kind"].as_str() == Some("PERP"))
+        .filter_map(|m| Some((
+            m["base_currency"].as_str()?.to_uppercase(),
+            m["symbol"].as_str()?.to_uppercase(),
+            m["quote_currency"].as_str() == Some("USD"),
+        )))
+        .collect();
+    perps.sort_by_key(|(_, _, usd)| !usd);
+
+    let mut seen = BTreeMap::new();
+    for (coin, symbol, _) in perps {
+        seen.entry(coin).or_insert(symbol);
+    }
+    Ok(seen.into_iter().collect())
+}
+
+/// `{results: [{symbol, volume_24h, …}]}` for every market.
+async fn fetch_pdx_volumes(http: &reqwest::Client) -> Result<BTreeMap<String, f64>> {
+    let resp: Value = http
+        .get(PDX_SUMMARY_URL)
+        .query(&[("market", "ALL")])
+        .send()
+        .await
+        .context("Paradex request failed")?
+        .json()
+        .await
+        .context("unparseable Paradex summary response")?;
+
+    let results = resp["results"].as_array().context("unexpected Paradex summary structure")?;
+    Ok(results
+        .iter()
+        .filter_map(|m| Some((m["symbol"].as_str()?.to_uppercase(), num(&m["volume_24h"])?)))
+        .collect())
+}
+
+/// Both venues send numbers as strings.
+fn num(v: &Value) -> Option<f64> {
+    v.as_str().and_then(|s| s.parse().ok())
+}
+
+// ─── Fuzzy matching ───────────────────────────────────────────────────────────
+
+/// How well `query` matches `candidate`, fzf-style: every query character must
+/// appear in order, ignoring case. Runs of consecutive characters and a match
+/// at the start score higher. None when it doesn't match.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let mut score = 0;
+    let mut chars = candidate.chars().enumerate();
+    let mut last: Option<usize> = None;
+    for q in query.chars() {
+        let (i, _) = chars.find(|(_, c)| c.eq_ignore_ascii_case(&q))?;
+        score += match last {
+            None if i == 0              => 10,
+            Some(prev) if i == prev + 1 => 8,
+            _                           => 1,
+        };
+        last = Some(i);
+    }
+    // Among equal matches, the shorter candidate is the closer one
+    Some(score * 100 - candidate.len() as i64)
+}
@@ -1,27 +1,324 @@
 // src/merger.rs — Merge two order books and compute signals
 
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tokio::sync::watch;
+use tokio::time::interval;
+use tracing::warn;
+
+use crate::config::FeesConfig;
+use crate::perf::Timing;
+use crate::supervisor::FeedStatus;
 use crate::types::{Exchange, Level, OrderBook};
 
+/// A feed counts as fully fresh up to this age; beyond it confidence halves
+/// every `CONF_HALF_LIFE_MS`.
+const CONF_GRACE_MS:     f64 = 500.0;
+const CONF_HALF_LIFE_MS: f64 = 1_000.0;
+
+/// Time constants of the short- and long-run update-rate averages. A feed whose
+/// short-run rate falls below `RATE_DROP_TOLERANCE` × its long-run rate loses
+/// confidence in proportion (normal burstiness stays above the tolerance).
+const RATE_FAST_TAU_SECS:  f64 = 2.0;
+const RATE_SLOW_TAU_SECS:  f64 = 30.0;
+const RATE_DROP_TOLERANCE: f64 = 0.5;
+
+/// How often confidence is re-evaluated when neither feed publishes.
+const CONF_RECHECK_MS: u64 = 250;
+
+/// A cross spread within this fraction of the mid counts as zero, i.e. locked:
+/// prices converted between quote currencies rarely subtract to exactly 0.
+const LOCKED_TOLERANCE: f64 = 1e-9;
+
+/// Window the order flow imbalance is summed over.
+const OFI_WINDOW: Duration = Duration::from_secs(10);
+/// Merged levels per side for `Signals::imbalance_by_depth`.
+pub const IMBALANCE_DEPTHS: [usize; 3] = [1, 5, 20];
+/// Price bucket sizes `+` / `-` step through, as (mantissa, scale): 0.01 … 1000.
+const GROUP_STEPS: [(i64, u32); 11] = [
+    (1, 2), (5, 2), (1, 1), (5, 1), (1, 0), (5, 0), (10, 0), (50, 0), (100, 0), (500, 0), (1_000, 0),
+];
+
 // ─── Merged level ─────────────────────────────────────────────────────────────
 
 /// A single level in the merged order book, tagged with its source exchange.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MergedLevel {
     pub price:    f64,
     pub size:     f64,
     pub exchange: Exchange,
+    /// (HL, PDX) shares of `size` when both venues quote this price in an
+    /// aggregated view; `exchange` is then the larger one. `None` = one venue.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split: Option<(f64, f64)>,
+}
+
+impl MergedLevel {
+    /// Size resting on `ex` at this level.
+    pub fn size_on(&self, ex: &Exchange) -> f64 {
+        match (self.split, ex) {
+            (Some((hl, _)), Exchange::Hyperliquid) => hl,
+            (Some((_, pdx)), Exchange::Paradex)    => pdx,
+            (None, _) if self.exchange == *ex      => self.size,
+            (None, _)                              => 0.0,
+        }
+    }
+
+    /// Takes `qty` off this level, from each venue in proportion to its share.
+    /// An empty level stays empty (its split 0 each) rather than going NaN.
+    fn reduce(&mut self, qty: f64) {
+        let left = (self.size - qty).max(0.0);
+        if let Some((hl, pdx)) = self.split {
+            let k = if self.size > 0.0 { left / self.size } else { 0.0 };
+            self.split = Some((hl * k, pdx * k));
+        }
+        self.size = left;
+    }
+
+    /// Adds `other`'s size to this level, keeping track of each venue's share.
+    fn absorb(&mut self, other: &MergedLevel) {
+        let split = (
+            self.size_on(&Exchange::Hyperliquid) + other.size_on(&Exchange::Hyperliquid),
+            self.size_on(&Exchange::Paradex) + other.size_on(&Exchange::Paradex),
+        );
+        self.size += other.size;
+        self.exchange = if split.0 >= split.1 { Exchange::Hyperliquid } else { Exchange::Paradex };
+        self.split = (split.0 > 0.0 && split.1 > 0.0).then_some(split);
+    }
+}
+
+// ─── Merge policy ─────────────────────────────────────────────────────────────
+
+/// How the merged book is shown. The signals, sinks and simulator always use
+/// the exact, per-venue levels; the policy only shapes `MergedBook::view`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum MergeMode {
+    /// One row per venue and price, tagged with the venue.
+    #[default]
+    Tagged,
+    /// One row per price: the venues' sizes summed, with the split shown.
+    Aggregated,
+    /// Aggregated, with the size where bids cross asks (across venues)
+    /// matched off: the book as it would stand once the cross is traded.
+    Netted,
+}
+
+impl MergeMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MergeMode::Tagged     => "tagged",
+            MergeMode::Aggregated => "aggregated",
+            MergeMode::Netted     => "netted",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            MergeMode::Tagged     => MergeMode::Aggregated,
+            MergeMode::Aggregated => MergeMode::Netted,
+            MergeMode::Netted     => MergeMode::Tagged,
+        }
+    }
+}
+
+/// The merger's settings; the TUI changes the display ones at runtime.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MergePolicy {
+    pub depth: usize,           // merged levels per side
+    pub mode:  MergeMode,
+    pub group: Option<Decimal>, // price bucket size; None = exact prices
+    pub min_notional: f64,      // USD; rows below it are hidden; 0 = all shown
+    pub outlier_band_pct: f64,  // levels further from the mid are dropped before anything else; 0 = off
+    pub quote: QuoteRates,      // each venue's prices into the common quote unit
+}
+
+/// What one unit of each venue's quote currency is worth in the merged book's
+/// unit (`[quote]`); `None` = already in it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QuoteRates {
+    pub hl:  Option<Decimal>,
+    pub pdx: Option<Decimal>,
+}
+
+impl MergePolicy {
+    /// Side-title note for anything besides plain tagged levels, e.g. "aggregated · $0.5".
+    pub fn label(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            (self.mode != MergeMode::Tagged).then(|| self.mode.label().to_string()),
+            self.group.map(|g| format!("${}", g.normalize())),
+            (self.min_notional > 0.0).then(|| format!("≥${}", self.min_notional)),
+        ].into_iter().flatten().collect();
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+}
+
+/// `book` with its touch taken from the BBO channel while that quote is newer
+/// than the book: levels the quote has moved past are dropped, and the touch
+/// takes the quote's size. The cross spread and arb signals then follow the
+/// faster channel. Borrowed when the book is as new as the quote.
+pub fn with_bbo(book: &OrderBook) -> Cow<'_, OrderBook> {
+    let Some(bbo) = book.bbo.as_ref().filter(|q| q.ts_ms > book.last_update_ms) else {
+        return Cow::Borrowed(book);
+    };
+    let mut patched = book.clone();
+    if let Some(bid) = &bbo.bid {
+        set_touch(&mut patched.bids, bid, |px| px > bid.price);
+    }
+    if let Some(ask) = &bbo.ask {
+        set_touch(&mut patched.asks, ask, |px| px < ask.price);
+    }
+    Cow::Owned(patched)
+}
+
+/// `book` with its prices converted into the common quote unit at `rate`.
+/// Borrowed when the venue already quotes in it.
+fn in_unit(book: &OrderBook, rate: Option<Decimal>) -> Cow<'_, OrderBook> {
+    let Some(rate) = rate else { return Cow::Borrowed(book) };
+    let mut converted = book.clone();
+    for l in converted.bids.iter_mut().chain(converted.asks.iter_mut()) {
+        *l = Level::new(l.price * rate, l.size, l.count);
+    }
+    Cow::Owned(converted)
+}
+
+/// Makes `touch` the first level of a side, dropping the levels `beyond` it.
+fn set_touch(levels: &mut Vec<Level>, touch: &Level, beyond: impl Fn(Decimal) -> bool) {
+    let gone = levels.iter().take_while(|l| beyond(l.price)).count();
+    levels.drain(..gone);
+    match levels.first_mut() {
+        Some(l) if l.price == touch.price => *l = touch.clone(),
+        _ => levels.insert(0, touch.clone()),
+    }
+}
+
+/// Both books without the levels more than `band_pct` % away from the mid,
+/// and how many were dropped. The mid is the median of both venues' best bid
+/// and ask, so one fat-fingered touch can't drag the band along with it.
+/// Borrowed when nothing is dropped or the band is off.
+fn trim_outliers<'a>(
+    hl: &'a OrderBook,
+    pdx: &'a OrderBook,
+    band_pct: f64,
+) -> (Cow<'a, OrderBook>, Cow<'a, OrderBook>, usize) {
+    let mut touches: Vec<f64> = [hl.best_bid(), hl.best_ask(), pdx.best_bid(), pdx.best_ask()]
+        .into_iter()
+        .flatten()
+        .collect();
+    if band_pct <= 0.0 || touches.len() < 2 {
+        return (Cow::Borrowed(hl), Cow::Borrowed(pdx), 0);
+    }
+    touches.sort_by(f64::total_cmp);
+    let n = touches.len();
+    let mid = (touches[(n - 1) / 2] + touches[n / 2]) / 2.0;
+    let band = mid * (1.0 - band_pct / 100.0)..=mid * (1.0 + band_pct / 100.0);
+    let inside = |l: &Level| band.contains(&l.price_f64());
+
+    let mut dropped = 0;
+    let mut trim = |book: &'a OrderBook| {
+        let outside = book.bids.iter().chain(&book.asks).filter(|l| !inside(l)).count();
+        if outside == 0 {
+            return Cow::Borrowed(book);
+        }
+        dropped += outside;
+        let mut book = book.clone();
+        book.bids.retain(inside);
+        book.asks.retain(inside);
+        Cow::Owned(book)
+    };
+    let (hl, pdx) = (trim(hl), trim(pdx));
+    (hl, pdx, dropped)
+}
+
+/// The next bucket size up or down `GROUP_STEPS` from `group`. Stepping down
+/// from the smallest turns grouping off; up from the largest stays there.
+pub fn step_group(group: Option<Decimal>, up: bool) -> Option<Decimal> {
+    let mut steps = GROUP_STEPS.iter().map(|&(m, s)| Decimal::new(m, s));
+    match (group, up) {
+        (None, true)     => steps.next(),
+        (None, false)    => None,
+        (Some(g), true)  => steps.find(|s| *s > g).or(Some(g)),
+        (Some(g), false) => steps.rev().find(|s| *s < g),
+    }
+}
+
+/// The merged book as displayed under a non-default `MergePolicy`.
+#[derive(Debug, Clone, Default)]
+pub struct BookView {
+    pub bids: Vec<MergedLevel>, // top N, descending price
+    pub asks: Vec<MergedLevel>, // top N, ascending price
+}
+
+impl BookView {
+    /// `None` when the policy shows the merged book as it is.
+    fn build(hl: &OrderBook, pdx: &OrderBook, policy: &MergePolicy) -> Option<Self> {
+        if policy.mode == MergeMode::Tagged && policy.group.is_none() && policy.min_notional == 0.0 {
+            return None;
+        }
+        // Every level of both books, so the view still fills `depth` rows
+        // however many are folded, netted or filtered away
+        let side = |hl_levels: &[Level], pdx_levels: &[Level], bids: bool| {
+            // Each venue bucketed on its own first, so tagged rows stay one per venue
+            let (hl_levels, pdx_levels) = match policy.group {
+                Some(g) => (Cow::Owned(bucket(hl_levels, g, bids)), Cow::Owned(bucket(pdx_levels, g, bids))),
+                None    => (Cow::Borrowed(hl_levels), Cow::Borrowed(pdx_levels)),
+            };
+            let merged = if bids {
+                merge_bids(&hl_levels, &hl.exchange, &pdx_levels, &pdx.exchange, usize::MAX)
+            } else {
+                merge_asks(&hl_levels, &hl.exchange, &pdx_levels, &pdx.exchange, usize::MAX)
+            };
+            match policy.mode {
+                MergeMode::Tagged => merged,
+                _                 => aggregate(&merged, usize::MAX),
+            }
+        };
+        let mut bids = side(&hl.bids, &pdx.bids, true);
+        let mut asks = side(&hl.asks, &pdx.asks, false);
+        if policy.mode == MergeMode::Netted {
+            net_crossed(&mut bids, &mut asks);
+        }
+        // Dust last, so it applies to the rows as shown
+        if policy.min_notional > 0.0 {
+            bids.retain(|l| l.price * l.size >= policy.min_notional);
+            asks.retain(|l| l.price * l.size >= policy.min_notional);
+        }
+        bids.truncate(policy.depth);
+        asks.truncate(policy.depth);
+        Some(Self { bids, asks })
+    }
 }
 
 // ─── Signals ──────────────────────────────────────────────────────────────────
 
 /// Computed signals derived from the two books.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Signals {
     /// Cross-exchange spread: best ask on one exchange minus best bid on the other.
     /// Negative = arbitrage opportunity exists (bid on one > ask on other).
     pub cross_spread:     Option<f64>,
     pub cross_spread_pct: Option<f64>,
 
+    /// Cross spread after paying the taker fee on both legs (buy the ask, sell
+    /// the bid). Negative = arbitrage that is profitable after fees.
+    pub net_cross_spread:     Option<f64>,
+    pub net_cross_spread_pct: Option<f64>,
+
+    /// Size executable at a profit after fees by walking both books through the
+    /// crossing region (buy the ask venue, sell the bid venue), and the USD
+    /// profit of doing so. Both 0.0 when there is no arbitrage.
+    pub arb_size:       f64,
+    pub arb_profit_usd: f64,
+
+    /// 0.0..=1.0 — how far both feeds can be trusted right now. Decays when a
+    /// book is stale or its update rate drops below its recent norm, so a cross
+    /// caused by one side lagging scores low.
+    pub arb_confidence: f64,
+
     /// Which side has the better bid and the better ask.
     pub best_bid_exchange: Option<Exchange>,
     pub best_ask_exchange: Option<Exchange>,
@@ -31,65 +328,479 @@ pub struct Signals {
     /// Range: -1.0 (pure ask pressure) to +1.0 (pure bid pressure)
     pub liquidity_imbalance: Option<f64>,
 
+    /// The same ratio over the top 1, 5 and 20 levels of both venues' books
+    /// merged (`IMBALANCE_DEPTHS`), independent of the display depth. Shallow
+    /// and deep readings often disagree in sign; seeing all three shows which.
+    pub imbalance_by_depth: [Option<f64>; 3],
+
     /// Total bid liquidity in USD (price × size) across top-N levels.
     pub total_bid_usd: f64,
     /// Total ask liquidity in USD across top-N levels.
     pub total_ask_usd: f64,
+
+    /// Midpoint of the size-weighted average bid and ask prices over the merged
+    /// top-N, and its distance from the simple (top-of-book) mid in basis
+    /// points. Positive = ask liquidity sits further from the touch than bid
+    /// liquidity, i.e. the book leans bid below the top level.
+    pub depth_weighted_mid:     Option<f64>,
+    pub depth_weighted_mid_bps: Option<f64>,
+
+    /// Average price of a market buy / sell of `vwap_notional` USD walking each
+    /// venue's book and both books combined (0.0 notional = not computed).
+    pub vwap_notional: f64,
+    pub hl_vwap:       Vwap,
+    pub pdx_vwap:      Vwap,
+    pub merged_vwap:   Vwap,
+
+    /// Bid and ask USD resting within each configured distance of the merged
+    /// mid, summed over both venues' full books. Empty when disabled.
+    pub liquidity_bands: Vec<LiquidityBand>,
+
+    /// Order flow imbalance over the last `OFI_WINDOW`, in base units: bid size
+    /// added minus removed near the touch, minus the same for asks. Positive =
+    /// buying pressure building. `None` until the feed has published.
+    pub hl_ofi:  Option<f64>,
+    pub pdx_ofi: Option<f64>,
+    pub ofi:     Option<f64>, // sum of both venues
+
+    /// Whether the top of the book is locked or crossed, and how many times it
+    /// has become so this session (counted by the merger task).
+    pub top_of_book:   TopOfBook,
+    pub locked_count:  u64,
+    pub crossed_count: u64, // across venues or within one
+
+    /// Levels dropped from this update for lying outside
+    /// `[merge] outlier_band_pct` of the mid (0 when the band is off).
+    pub outliers: usize,
+}
+
+/// State of the best bid against the best ask. Locked or crossed across venues
+/// usually means one feed is lagging the other; a venue crossed against itself
+/// means its own book is broken.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub enum TopOfBook {
+    #[default]
+    Normal,
+    Locked,                // best bid == best ask
+    Crossed,               // best bid > best ask, on different venues
+    SelfCrossed(Exchange), // one venue's own bid ≥ its own ask
+}
+
+/// VWAP of a sweep each way; `None` where the displayed book is too thin.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Vwap {
+    pub buy:  Option<f64>,
+    pub sell: Option<f64>,
+}
+
+/// Resting liquidity within `bps` of the merged mid.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct LiquidityBand {
+    pub bps:     f64,
+    pub bid_usd: f64,
+    pub ask_usd: f64,
+}
+
+impl LiquidityBand {
+    /// Same sign convention as `liquidity_imbalance`, within this band only.
+    pub fn imbalance(&self) -> Option<f64> {
+        let total = self.bid_usd + self.ask_usd;
+        (total > 0.0).then(|| (self.bid_usd - self.ask_usd) / total)
+    }
+}
+
+impl Vwap {
+    /// `bids` best first, `asks` best first.
+    fn sweep(bids: &[(f64, f64)], asks: &[(f64, f64)], notional: f64) -> Self {
+        Self { buy: vwap_to_notional(asks, notional), sell: vwap_to_notional(bids, notional) }
+    }
+}
+
+impl Signals {
+    /// True when crossing the venues is profitable after taker fees.
+    pub fn is_arb(&self) -> bool {
+        self.net_cross_spread.is_some_and(|s| s < 0.0)
+    }
+
+    fn set_ofi(&mut self, hl: Option<f64>, pdx: Option<f64>) {
+        self.hl_ofi = hl;
+        self.pdx_ofi = pdx;
+        self.ofi = match (hl, pdx) {
+            (None, None) => None,
+            _ => Some(hl.unwrap_or(0.0) + pdx.unwrap_or(0.0)),
+        };
+    }
 }
 
 // ─── MergedBook ───────────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct MergedBook {
     pub bids: Vec<MergedLevel>, // top N, descending price
     pub asks: Vec<MergedLevel>, // top N, ascending price
     pub signals: Signals,
+    /// The book as the TUI shows it when the merge policy isn't `Tagged`.
+    #[serde(skip)]
+    pub view: Option<BookView>,
+    #[serde(skip)]
+    pub policy: MergePolicy,
+    /// How long the merger took to build this book and the ones before it.
+    #[serde(skip)]
+    pub merge_time: Timing,
+    /// The symbols of the two books it was merged from, (HL, PDX).
+    #[serde(skip)]
+    pub coins: (String, String),
 }
 
 impl MergedBook {
     /// Build a merged book from two `OrderBook` snapshots, keeping the top `depth` levels.
-    pub fn build(hl: &OrderBook, pdx: &OrderBook, depth: usize) -> Self {
-        let bids = merge_bids(&hl.bids, &hl.exchange, &pdx.bids, &pdx.exchange, depth);
-        let asks = merge_asks(&hl.asks, &hl.exchange, &pdx.asks, &pdx.exchange, depth);
-        let signals = compute_signals(hl, pdx, &bids, &asks);
-        Self { bids, asks, signals }
+    pub fn build(hl: &OrderBook, pdx: &OrderBook, depth: usize, fees: &FeesConfig) -> Self {
+        // One merge deep enough for both the display and the depth imbalances
+        let deep = depth.max(IMBALANCE_DEPTHS[2]);
+        let mut bids = merge_bids(&hl.bids, &hl.exchange, &pdx.bids, &pdx.exchange, deep);
+        let mut asks = merge_asks(&hl.asks, &hl.exchange, &pdx.asks, &pdx.exchange, deep);
+        let signals = compute_signals(hl, pdx, &bids, &asks, depth, fees);
+        bids.truncate(depth);
+        asks.truncate(depth);
+        Self {
+            bids, asks, signals, view: None, policy: MergePolicy::default(), merge_time: Timing::default(),
+            coins: (hl.coin.clone(), pdx.coin.clone()),
+        }
+    }
+
+    /// Whether this book is of another pair than the one `seen` last held,
+    /// which then takes its symbols; false for the first pair seen. For the
+    /// tasks whose history must not run across a pair switch.
+    pub fn pair_changed(&self, seen: &mut (String, String)) -> bool {
+        if self.coins == *seen {
+            return false;
+        }
+        let first = seen.0.is_empty() && seen.1.is_empty();
+        seen.clone_from(&self.coins);
+        !first
+    }
+
+    /// The sides to display: the policy's view, or the merged book itself.
+    pub fn shown(&self) -> (&[MergedLevel], &[MergedLevel]) {
+        match &self.view {
+            Some(v) => (&v.bids, &v.asks),
+            None    => (&self.bids, &self.asks),
+        }
+    }
+}
+
+// ─── Merger task ──────────────────────────────────────────────────────────────
+
+/// Spawns a task that rebuilds the merged book whenever either feed publishes
+/// or the policy changes, so the TUI and any output sinks share one consistent
+/// `MergedBook`.
+pub fn spawn_merger(
+    mut hl_rx:  watch::Receiver<Arc<OrderBook>>,
+    mut pdx_rx: watch::Receiver<Arc<OrderBook>>,
+    mut policy_rx: watch::Receiver<MergePolicy>,
+    fees: FeesConfig,
+    vwap_notional: f64,
+    bands_bps: Vec<f64>,
+    merged_tx: watch::Sender<MergedBook>,
+) {
+    tokio::spawn(async move {
+        let mut hl_activity  = FeedActivity::default();
+        let mut pdx_activity = FeedActivity::default();
+        let mut hl_flow  = FlowHistory::default();
+        let mut pdx_flow = FlowHistory::default();
+        let mut top = TopCounter::default();
+        let mut coins = (String::new(), String::new()); // the pair the flow and counts are for
+        let mut merge_time = Timing::default();
+        let mut recheck = interval(Duration::from_millis(CONF_RECHECK_MS));
+        loop {
+            tokio::select! {
+                r = hl_rx.changed() => {
+                    if r.is_err() { break }
+                    hl_activity.on_update(Instant::now());
+                }
+                r = pdx_rx.changed() => {
+                    if r.is_err() { break }
+                    pdx_activity.on_update(Instant::now());
+                }
+                r = policy_rx.changed() => {
+                    if r.is_err() { break }
+                }
+                // Nothing new to merge, but confidence keeps decaying and old flow leaves the window
+                _ = recheck.tick() => {
+                    let now = Instant::now();
+                    let conf = arb_confidence(&hl_activity, &pdx_activity, now);
+                    let (hl_ofi, pdx_ofi) = (hl_flow.windowed(now), pdx_flow.windowed(now));
+                    merged_tx.send_if_modified(|m| {
+                        let changed = (m.signals.arb_confidence - conf).abs() >= 0.01
+                            || m.signals.hl_ofi != hl_ofi
+                            || m.signals.pdx_ofi != pdx_ofi;
+                        if changed {
+                            m.signals.arb_confidence = conf;
+                            m.signals.set_ofi(hl_ofi, pdx_ofi);
+                        }
+                        changed
+                    });
+                    continue;
+                }
+            }
+            let now = Instant::now();
+            let mut merged = {
+                let (hl, pdx) = (hl_rx.borrow_and_update(), pdx_rx.borrow_and_update());
+                // After a pair switch the books' order flow counts from 0 again
+                if hl.coin != coins.0 || pdx.coin != coins.1 {
+                    coins = (hl.coin.clone(), pdx.coin.clone());
+                    (hl_flow, pdx_flow, top) = Default::default();
+                }
+                if hl.message_count > 0 {
+                    hl_flow.record(now, hl.ofi_cum);
+                }
+                if pdx.message_count > 0 {
+                    pdx_flow.record(now, pdx.ofi_cum);
+                }
+                let policy = *policy_rx.borrow_and_update();
+                let (hl, pdx) = (with_bbo(&hl), with_bbo(&pdx));
+                let (hl, pdx) = (in_unit(&hl, policy.quote.hl), in_unit(&pdx, policy.quote.pdx));
+                let (hl, pdx, outliers) = trim_outliers(&hl, &pdx, policy.outlier_band_pct);
+                let mut merged = MergedBook::build(&hl, &pdx, policy.depth, &fees);
+                merged.signals.outliers = outliers;
+                merged.view = BookView::build(&hl, &pdx, &policy);
+                merged.policy = policy;
+                if vwap_notional > 0.0 {
+                    let sig = &mut merged.signals;
+                    sig.vwap_notional = vwap_notional;
+                    (sig.hl_vwap, sig.pdx_vwap, sig.merged_vwap) = sweep_vwaps(&hl, &pdx, vwap_notional);
+                }
+                if !bands_bps.is_empty() {
+                    merged.signals.liquidity_bands = liquidity_bands(&hl, &pdx, &merged, &bands_bps);
+                }
+                merged
+            };
+            merged.signals.arb_confidence = arb_confidence(&hl_activity, &pdx_activity, now);
+            merged.signals.set_ofi(hl_flow.windowed(now), pdx_flow.windowed(now));
+            top.record(&mut merged.signals);
+            merge_time.record(now.elapsed());
+            merged.merge_time = merge_time;
+            merged_tx.send_replace(merged);
+        }
+    });
+}
+
+// ─── Crossed / locked counter ─────────────────────────────────────────────────
+
+/// Counts entries into a locked or crossed top of book, not merges spent there.
+#[derive(Debug, Default)]
+struct TopCounter {
+    last:    TopOfBook,
+    locked:  u64,
+    crossed: u64,
+}
+
+impl TopCounter {
+    fn record(&mut self, sig: &mut Signals) {
+        if sig.top_of_book != self.last {
+            match &sig.top_of_book {
+                TopOfBook::Normal          => {}
+                TopOfBook::Locked          => self.locked += 1,
+                TopOfBook::Crossed         => self.crossed += 1,
+                TopOfBook::SelfCrossed(ex) => {
+                    warn!("[MERGE] {} book crossed against itself", ex.short());
+                    self.crossed += 1;
+                }
+            }
+            self.last = sig.top_of_book.clone();
+        }
+        sig.locked_count = self.locked;
+        sig.crossed_count = self.crossed;
+    }
+}
+
+// ─── Feed activity ────────────────────────────────────────────────────────────
+
+/// Per-feed update timing, measured locally so exchange clock skew doesn't matter.
+#[derive(Debug, Default)]
+struct FeedActivity {
+    last_update: Option<Instant>,
+    fast_rate:   f64, // exponentially-weighted updates/sec, as of `last_update`
+    slow_rate:   f64,
+}
+
+impl FeedActivity {
+    fn on_update(&mut self, now: Instant) {
+        if let Some(last) = self.last_update {
+            let dt = now.duration_since(last).as_secs_f64();
+            self.fast_rate *= (-dt / RATE_FAST_TAU_SECS).exp();
+            self.slow_rate *= (-dt / RATE_SLOW_TAU_SECS).exp();
+        }
+        self.fast_rate += 1.0 / RATE_FAST_TAU_SECS;
+        self.slow_rate += 1.0 / RATE_SLOW_TAU_SECS;
+        self.last_update = Some(now);
+    }
+
+    /// 1.0 = fresh and updating at its usual rate; 0.0 = never updated.
+    fn confidence(&self, now: Instant) -> f64 {
+        let Some(last) = self.last_update else { return 0.0 };
+        let age = now.duration_since(last);
+        let age_ms = age.as_secs_f64() * 1000.0;
+
+        let freshness = 0.5_f64.powf((age_ms - CONF_GRACE_MS).max(0.0) / CONF_HALF_LIFE_MS);
+
+        // Both rates decayed to `now`, so a silent feed's fast rate falls first
+        let fast = self.fast_rate * (-age.as_secs_f64() / RATE_FAST_TAU_SECS).exp();
+        let slow = self.slow_rate * (-age.as_secs_f64() / RATE_SLOW_TAU_SECS).exp();
+        let rate = if slow > 0.0 { (fast / (slow * RATE_DROP_TOLERANCE)).min(1.0) } else { 1.0 };
+
+        freshness * rate
+    }
+}
+
+/// An arb is only as trustworthy as the weaker of its two legs.
+fn arb_confidence(hl: &FeedActivity, pdx: &FeedActivity, now: Instant) -> f64 {
+    hl.confidence(now).min(pdx.confidence(now))
+}
+
+// ─── Order flow history ───────────────────────────────────────────────────────
+
+/// Samples of a feed's cumulative order flow, enough to difference it over
+/// `OFI_WINDOW`.
+#[derive(Debug, Default)]
+struct FlowHistory {
+    samples: VecDeque<(Instant, f64)>, // oldest first
+}
+
+impl FlowHistory {
+    fn record(&mut self, now: Instant, cum: f64) {
+        self.samples.push_back((now, cum));
+        // Keep one sample at or before the window start as the baseline
+        while self.samples.get(1).is_some_and(|(t, _)| now.duration_since(*t) >= OFI_WINDOW) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Flow since the window start; 0.0 once nothing has changed for a whole window.
+    fn windowed(&self, now: Instant) -> Option<f64> {
+        let (_, last) = self.samples.back()?;
+        let base = self.samples.iter()
+            .rev()
+            .find(|(t, _)| now.duration_since(*t) >= OFI_WINDOW)
+            .or(self.samples.front())
+            .map_or(*last, |(_, c)| *c);
+        Some(last - base)
     }
 }
 
 // ─── Merge helpers ────────────────────────────────────────────────────────────
 
+/// Bids: highest price first.
 fn merge_bids(
     a_levels: &[Level], a_ex: &Exchange,
     b_levels: &[Level], b_ex: &Exchange,
     depth: usize,
 ) -> Vec<MergedLevel> {
-    let mut all: Vec<MergedLevel> = a_levels.iter()
-        .map(|l| MergedLevel { price: l.price_f64(), size: l.size_f64(), exchange: a_ex.clone() })
-        .chain(b_levels.iter()
-            .map(|l| MergedLevel { price: l.price_f64(), size: l.size_f64(), exchange: b_ex.clone() }))
-        .collect();
-
-    // Bids: highest price first
-    all.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap_or(std::cmp::Ordering::Equal));
-    all.truncate(depth);
-    all
+    merge_sorted(a_levels, a_ex, b_levels, b_ex, depth, |x, y| x > y)
 }
 
+/// Asks: lowest price first.
 fn merge_asks(
     a_levels: &[Level], a_ex: &Exchange,
     b_levels: &[Level], b_ex: &Exchange,
     depth: usize,
 ) -> Vec<MergedLevel> {
-    let mut all: Vec<MergedLevel> = a_levels.iter()
-        .map(|l| MergedLevel { price: l.price_f64(), size: l.size_f64(), exchange: a_ex.clone() })
-        .chain(b_levels.iter()
-            .map(|l| MergedLevel { price: l.price_f64(), size: l.size_f64(), exchange: b_ex.clone() }))
-        .collect();
+    merge_sorted(a_levels, a_ex, b_levels, b_ex, depth, |x, y| x < y)
+}
+
+/// Two-way merge of two sides that each arrive sorted best first (Hyperliquid
+/// sends them that way, the Paradex `BTreeMap` yields them that way), stopping
+/// at `depth`: O(depth) rather than sorting both books. `better(x, y)` is true
+/// when price `x` comes before `y`; on a tie the `a` level comes first. Prices
+/// are compared exactly before converting to f64.
+fn merge_sorted(
+    a_levels: &[Level], a_ex: &Exchange,
+    b_levels: &[Level], b_ex: &Exchange,
+    depth: usize,
+    better: impl Fn(Decimal, Decimal) -> bool,
+) -> Vec<MergedLevel> {
+    let level = |l: &Level, ex: &Exchange| MergedLevel {
+        price:    l.price_f64(),
+        size:     l.size_f64(),
+        exchange: ex.clone(),
+        split:    None,
+    };
+    let mut merged = Vec::with_capacity(depth.min(a_levels.len() + b_levels.len()));
+    let (mut i, mut j) = (0, 0);
+    while merged.len() < depth {
+        let take_b = match (a_levels.get(i), b_levels.get(j)) {
+            (Some(a), Some(b)) => better(b.price, a.price),
+            (Some(_), None)    => false,
+            (None, Some(_))    => true,
+            (None, None)       => break,
+        };
+        if take_b {
+            merged.push(level(&b_levels[j], b_ex));
+            j += 1;
+        } else {
+            merged.push(level(&a_levels[i], a_ex));
+            i += 1;
+        }
+    }
+    merged
+}
+
+/// One venue's side (best first) with prices rounded to multiples of `group`,
+/// away from the touch like exchange UIs: bids down, asks up, so a bid bucket
+/// never shows above the ask it didn't cross.
+fn bucket(levels: &[Level], group: Decimal, bids: bool) -> Vec<Level> {
+    let mut out: Vec<Level> = Vec::new();
+    for l in levels {
+        let steps = l.price / group;
+        let price = if bids { steps.floor() } else { steps.ceil() } * group;
+        if let Some(last) = out.last_mut()
+            && last.price == price
+        {
+            *last = Level::new(price, last.size + l.size, last.count + l.count);
+        } else {
+            out.push(Level::new(price, l.size, l.count));
+        }
+    }
+    out
+}
+
+/// Folds levels at the same price into one, up to `depth` rows. Both venues'
+/// levels at a price sit next to each other in a merged side.
+fn aggregate(levels: &[MergedLevel], depth: usize) -> Vec<MergedLevel> {
+    let mut out: Vec<MergedLevel> = Vec::with_capacity(depth.min(levels.len()));
+    for l in levels {
+        if let Some(last) = out.last_mut()
+            && last.price == l.price
+        {
+            last.absorb(l);
+        } else if out.len() == depth {
+            break;
+        } else {
+            out.push(l.clone());
+        }
+    }
+    out
+}
 
-    // Asks: lowest price first
-    all.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap_or(std::cmp::Ordering::Equal));
-    all.truncate(depth);
-    all
+/// Matches aggregated bids against asks (both best first) while the best bid
+/// is at or above the best ask, dropping the levels used up. Normally only a
+/// cross between venues gets this far, as a venue's own book doesn't cross.
+fn net_crossed(bids: &mut Vec<MergedLevel>, asks: &mut Vec<MergedLevel>) {
+    let (mut i, mut j) = (0, 0);
+    while i < bids.len() && j < asks.len() && bids[i].price >= asks[j].price {
+        let qty = bids[i].size.min(asks[j].size);
+        bids[i].reduce(qty);
+        asks[j].reduce(qty);
+        // x − min(x, y) is exactly 0 for the smaller one
+        if bids[i].size <= 0.0 {
+            i += 1;
+        }
+        if asks[j].size <= 0.0 {
+            j += 1;
+        }
+    }
+    bids.drain(..i);
+    asks.drain(..j);
 }
 
 // ─── Signal computation ───────────────────────────────────────────────────────
@@ -97,14 +808,22 @@ fn merge_asks(
 fn compute_signals(
     hl: &OrderBook,
     pdx: &OrderBook,
-    merged_bids: &[MergedLevel],
-    merged_asks: &[MergedLevel],
+    deep_bids: &[MergedLevel],
+    deep_asks: &[MergedLevel],
+    depth: usize,
+    fees: &FeesConfig,
 ) -> Signals {
+    // Top `depth` of the merged book as displayed; the deep sides run to at
+    // least `IMBALANCE_DEPTHS[2]`
+    let merged_bids = &deep_bids[..depth.min(deep_bids.len())];
+    let merged_asks = &deep_asks[..depth.min(deep_asks.len())];
+
     // ── Best bid / ask per exchange ───────────────────────────────────────────
-    let hl_best_bid  = hl.best_bid();
-    let hl_best_ask  = hl.best_ask();
-    let pdx_best_bid = pdx.best_bid();
-    let pdx_best_ask = pdx.best_ask();
+    // A stale venue's quotes may already be gone, so it can't leg an arb
+    let hl_best_bid  = hl.best_bid().filter(|_| hl.status != FeedStatus::Stale);
+    let hl_best_ask  = hl.best_ask().filter(|_| hl.status != FeedStatus::Stale);
+    let pdx_best_bid = pdx.best_bid().filter(|_| pdx.status != FeedStatus::Stale);
+    let pdx_best_ask = pdx.best_ask().filter(|_| pdx.status != FeedStatus::Stale);
 
     // Overall best bid (highest) and ask (lowest) across both exchanges
     let best_bid = max_opt(hl_best_bid, pdx_best_bid);
@@ -136,6 +855,46 @@ fn compute_signals(
         _ => (None, None),
     };
 
+    // ── Crossed / locked ──────────────────────────────────────────────────────
+    let self_crossed = [(hl, hl_best_bid, hl_best_ask), (pdx, pdx_best_bid, pdx_best_ask)]
+        .into_iter()
+        .find(|(_, bid, ask)| matches!((bid, ask), (Some(b), Some(a)) if b >= a))
+        .map(|(book, _, _)| book.exchange.clone());
+    let tolerance = best_bid.zip(best_ask).map_or(0.0, |(bid, ask)| (bid + ask) / 2.0 * LOCKED_TOLERANCE);
+    let top_of_book = match (self_crossed, cross_spread) {
+        (Some(ex), _)                           => TopOfBook::SelfCrossed(ex),
+        (None, Some(s)) if s < -tolerance       => TopOfBook::Crossed,
+        (None, Some(s)) if s.abs() <= tolerance => TopOfBook::Locked,
+        _                                       => TopOfBook::Normal,
+    };
+
+    // ── Net of taker fees on both legs ────────────────────────────────────────
+    // ask·(1 + fee_ask) − bid·(1 − fee_bid); only across venues, since a book
+    // crossed against itself has nothing to trade against the other
+    let (net_cross_spread, net_cross_spread_pct) =
+        match (best_bid, best_ask, &best_bid_exchange, &best_ask_exchange) {
+            (Some(bid), Some(ask), Some(bid_ex), Some(ask_ex)) if bid_ex != ask_ex => {
+                let net = ask * (1.0 + fees.for_exchange(ask_ex).taker_rate())
+                    - bid * (1.0 - fees.for_exchange(bid_ex).taker_rate());
+                let mid = (bid + ask) / 2.0;
+                let pct = if mid > 0.0 { net / mid * 100.0 } else { 0.0 };
+                (Some(net), Some(pct))
+            }
+            _ => (None, None),
+        };
+
+    // ── Executable arb size (walk both books) ─────────────────────────────────
+    let (arb_size, arb_profit_usd) = match (&best_bid_exchange, &best_ask_exchange) {
+        (Some(bid_ex), Some(ask_ex)) if bid_ex != ask_ex => {
+            let (bid_book, ask_book) = if *bid_ex == hl.exchange { (hl, pdx) } else { (pdx, hl) };
+            walk_crossing(
+                &bid_book.bids, fees.for_exchange(bid_ex).taker_rate(),
+                &ask_book.asks, fees.for_exchange(ask_ex).taker_rate(),
+            )
+        }
+        _ => (0.0, 0.0),
+    };
+
     // ── Liquidity Imbalance Ratio ─────────────────────────────────────────────
     // (bid_usd - ask_usd) / (bid_usd + ask_usd)  — across merged top-N
     let total_bid_usd: f64 = merged_bids.iter().map(|l| l.price * l.size).sum();
@@ -150,15 +909,137 @@ fn compute_signals(
         }
     };
 
+    // Same ratio at fixed depths, over both full books rather than the display top-N
+    let imbalance_by_depth = IMBALANCE_DEPTHS.map(|n| {
+        let bid: f64 = deep_bids.iter().take(n).map(|l| l.price * l.size).sum();
+        let ask: f64 = deep_asks.iter().take(n).map(|l| l.price * l.size).sum();
+        (bid + ask > 0.0).then(|| (bid - ask) / (bid + ask))
+    });
+
+    // ── Depth-weighted mid ────────────────────────────────────────────────────
+    // (VWAP of merged bids + VWAP of merged asks) / 2, compared with the simple mid
+    let vwap = |levels: &[MergedLevel]| {
+        let size: f64 = levels.iter().map(|l| l.size).sum();
+        (size > 0.0).then(|| levels.iter().map(|l| l.price * l.size).sum::<f64>() / size)
+    };
+    let depth_weighted_mid = match (vwap(merged_bids), vwap(merged_asks)) {
+        (Some(b), Some(a)) => Some((b + a) / 2.0),
+        _ => None,
+    };
+    let depth_weighted_mid_bps = match (depth_weighted_mid, best_bid, best_ask) {
+        (Some(dw), Some(bid), Some(ask)) if bid + ask > 0.0 => {
+            let mid = (bid + ask) / 2.0;
+            Some((dw - mid) / mid * 10_000.0)
+        }
+        _ => None,
+    };
+
     Signals {
         cross_spread,
         cross_spread_pct,
+        net_cross_spread,
+        net_cross_spread_pct,
+        arb_size,
+        arb_profit_usd,
+        arb_confidence: 0.0, // filled in by the merger task, which sees update timing
         best_bid_exchange,
         best_ask_exchange,
         liquidity_imbalance,
+        imbalance_by_depth,
         total_bid_usd,
         total_ask_usd,
+        depth_weighted_mid,
+        depth_weighted_mid_bps,
+        // Filled in by the merger task, which knows the notional and keeps the flow history
+        vwap_notional: 0.0,
+        hl_vwap:       Vwap::default(),
+        pdx_vwap:      Vwap::default(),
+        merged_vwap:   Vwap::default(),
+        liquidity_bands: Vec::new(),
+        hl_ofi:  None,
+        pdx_ofi: None,
+        ofi:     None,
+        top_of_book,
+        locked_count:  0, // counted by the merger task, which sees every transition
+        crossed_count: 0,
+        outliers:      0, // set by the merger task, which trims the books first
+    }
+}
+
+/// Average price paid walking `levels` (best first) until `notional` USD has
+/// traded, or `None` if they run out first.
+fn vwap_to_notional(levels: &[(f64, f64)], notional: f64) -> Option<f64> {
+    let (mut spent, mut qty) = (0.0, 0.0);
+    for &(price, size) in levels {
+        let take = size.min((notional - spent) / price);
+        spent += take * price;
+        qty += take;
+        if spent >= notional * (1.0 - 1e-9) {
+            return Some(spent / qty);
+        }
     }
+    None
+}
+
+/// VWAP-to-notional per venue and for both books combined.
+fn sweep_vwaps(hl: &OrderBook, pdx: &OrderBook, notional: f64) -> (Vwap, Vwap, Vwap) {
+    let pairs = |levels: &[Level]| levels.iter().map(|l| (l.price_f64(), l.size_f64())).collect::<Vec<_>>();
+    let (hl_bids, hl_asks, pdx_bids, pdx_asks) = (pairs(&hl.bids), pairs(&hl.asks), pairs(&pdx.bids), pairs(&pdx.asks));
+
+    // Both books combined, merged rather than sorted since each side is already in order
+    let merged_pairs = |levels: Vec<MergedLevel>| levels.iter().map(|l| (l.price, l.size)).collect::<Vec<_>>();
+    let bids = merged_pairs(merge_bids(&hl.bids, &hl.exchange, &pdx.bids, &pdx.exchange, usize::MAX));
+    let asks = merged_pairs(merge_asks(&hl.asks, &hl.exchange, &pdx.asks, &pdx.exchange, usize::MAX));
+
+    (
+        Vwap::sweep(&hl_bids, &hl_asks, notional),
+        Vwap::sweep(&pdx_bids, &pdx_asks, notional),
+        Vwap::sweep(&bids, &asks, notional),
+    )
+}
+
+/// USD on each side within each of `bands_bps` of the merged top-of-book mid.
+/// Empty while either side of the merged book is.
+fn liquidity_bands(hl: &OrderBook, pdx: &OrderBook, merged: &MergedBook, bands_bps: &[f64]) -> Vec<LiquidityBand> {
+    let (Some(bid), Some(ask)) = (merged.bids.first(), merged.asks.first()) else { return Vec::new() };
+    let mid = (bid.price + ask.price) / 2.0;
+    let usd_within = |levels: &[Level], limit: f64, bids: bool| -> f64 {
+        levels.iter()
+            .map(|l| (l.price_f64(), l.size_f64()))
+            .take_while(|&(px, _)| if bids { px >= limit } else { px <= limit })
+            .map(|(px, sz)| px * sz)
+            .sum()
+    };
+    bands_bps.iter().map(|&bps| {
+        let (lo, hi) = (mid * (1.0 - bps / 10_000.0), mid * (1.0 + bps / 10_000.0));
+        LiquidityBand {
+            bps,
+            bid_usd: usd_within(&hl.bids, lo, true) + usd_within(&pdx.bids, lo, true),
+            ask_usd: usd_within(&hl.asks, hi, false) + usd_within(&pdx.asks, hi, false),
+        }
+    }).collect()
+}
+
+/// Matches bids (best first) against asks (best first) while selling at the bid
+/// still beats buying at the ask after taker fees on both legs. Returns the
+/// matched size and the total profit in quote currency.
+fn walk_crossing(bids: &[Level], bid_fee: f64, asks: &[Level], ask_fee: f64) -> (f64, f64) {
+    let mut bids = bids.iter().map(|l| (l.price_f64() * (1.0 - bid_fee), l.size_f64()));
+    let mut asks = asks.iter().map(|l| (l.price_f64() * (1.0 + ask_fee), l.size_f64()));
+    let (mut size, mut profit) = (0.0, 0.0);
+
+    let (mut bid, mut ask) = (bids.next(), asks.next());
+    while let (Some((bid_px, bid_left)), Some((ask_px, ask_left))) = (bid, ask) {
+        if bid_px <= ask_px {
+            break;
+        }
+        let qty = bid_left.min(ask_left);
+        size += qty;
+        profit += qty * (bid_px - ask_px);
+        bid = if bid_left > qty { Some((bid_px, bid_left - qty)) } else { bids.next() };
+        ask = if ask_left > qty { Some((ask_px, ask_left - qty)) } else { asks.next() };
+    }
+    (size, profit)
 }
 
 // ─── Helpers ──────────────────────────────────────────────────────────────────
@@ -180,3 +1061,288 @@ fn min_opt(a: Option<f64>, b: Option<f64>) -> Option<f64> {
         _                  => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Bbo;
+
+    fn level(price: &str, size: &str) -> Level {
+        Level::new(price.parse().unwrap(), size.parse().unwrap(), 1)
+    }
+
+    fn book(exchange: Exchange, bids: &[(&str, &str)], asks: &[(&str, &str)]) -> OrderBook {
+        let mut book = OrderBook::new(exchange, "ETH");
+        book.bids = bids.iter().map(|(p, s)| level(p, s)).collect();
+        book.asks = asks.iter().map(|(p, s)| level(p, s)).collect();
+        book
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn walk_crossing_fills_across_levels() {
+        let bids = [level("101", "1"), level("100.5", "2")];
+        let asks = [level("100", "1.5"), level("100.8", "5")];
+        // 1 @ 101/100, then 0.5 @ 100.5/100; 100.5 doesn't beat 100.8
+        let (size, profit) = walk_crossing(&bids, 0.0, &asks, 0.0);
+        assert!(close(size, 1.5), "size {size}");
+        assert!(close(profit, 1.0 + 0.25), "profit {profit}");
+    }
+
+    #[test]
+    fn walk_crossing_stops_when_fees_eat_the_edge() {
+        let bids = [level("100.05", "3")];
+        let asks = [level("100", "3")];
+        let (size, _) = walk_crossing(&bids, 0.0, &asks, 0.0);
+        assert!(close(size, 3.0));
+        // 5 bps a leg costs ~0.10 against a 0.05 edge
+        assert_eq!(walk_crossing(&bids, 0.0005, &asks, 0.0005), (0.0, 0.0));
+    }
+
+    #[test]
+    fn walk_crossing_with_an_empty_side_matches_nothing() {
+        let levels = [level("100", "1")];
+        assert_eq!(walk_crossing(&[], 0.0, &levels, 0.0), (0.0, 0.0));
+        assert_eq!(walk_crossing(&levels, 0.0, &[], 0.0), (0.0, 0.0));
+    }
+
+    fn tagged(levels: &[MergedLevel]) -> Vec<(f64, Exchange)> {
+        levels.iter().map(|l| (l.price, l.exchange.clone())).collect()
+    }
+
+    #[test]
+    fn merge_sorted_interleaves_both_sides() {
+        let hl  = [level("101", "1"), level("99", "1"), level("97", "1")];
+        let pdx = [level("100", "1"), level("98", "1")];
+        let (h, p) = (Exchange::Hyperliquid, Exchange::Paradex);
+        let bids = merge_bids(&hl, &h, &pdx, &p, 10);
+        assert_eq!(tagged(&bids), [(101.0, h.clone()), (100.0, p.clone()), (99.0, h.clone()), (98.0, p.clone()), (97.0, h.clone())]);
+        // Stops at the depth asked for
+        assert_eq!(merge_bids(&hl, &h, &pdx, &p, 3).len(), 3);
+
+        let hl  = [level("100", "1"), level("102", "1")];
+        let pdx = [level("101", "1")];
+        assert_eq!(tagged(&merge_asks(&hl, &h, &pdx, &p, 10)), [(100.0, h.clone()), (101.0, p), (102.0, h)]);
+    }
+
+    #[test]
+    fn merge_sorted_puts_the_first_book_first_at_equal_prices() {
+        let hl  = [level("100", "1"), level("99", "1")];
+        let pdx = [level("100", "2"), level("99", "2")];
+        let (h, p) = (Exchange::Hyperliquid, Exchange::Paradex);
+        let bids = merge_bids(&hl, &h, &pdx, &p, 10);
+        assert_eq!(tagged(&bids), [(100.0, h.clone()), (100.0, p.clone()), (99.0, h.clone()), (99.0, p.clone())]);
+        // Whichever book is passed first
+        let (hl, pdx) = ([level("99", "1"), level("100", "1")], [level("99", "2"), level("100", "2")]);
+        let asks = merge_asks(&pdx, &p, &hl, &h, 10);
+        assert_eq!(tagged(&asks), [(99.0, p.clone()), (99.0, h.clone()), (100.0, p), (100.0, h)]);
+    }
+
+    #[test]
+    fn merge_sorted_with_an_empty_side_is_the_other() {
+        let levels = [level("100", "1"), level("99", "2")];
+        let (h, p) = (Exchange::Hyperliquid, Exchange::Paradex);
+        assert_eq!(tagged(&merge_bids(&[], &h, &levels, &p, 10)), [(100.0, p.clone()), (99.0, p.clone())]);
+        assert_eq!(tagged(&merge_bids(&levels, &h, &[], &p, 10)), [(100.0, h.clone()), (99.0, h.clone())]);
+        assert!(merge_asks(&[], &h, &[], &p, 10).is_empty());
+        assert!(merge_asks(&levels, &h, &levels, &p, 0).is_empty());
+    }
+
+    #[test]
+    fn combined_vwap_walks_both_books_in_price_order() {
+        let hl  = book(Exchange::Hyperliquid, &[("100", "1")], &[("101", "1"), ("103", "1")]);
+        let pdx = book(Exchange::Paradex, &[("99", "1")], &[("102", "1")]);
+        let (_, _, both) = sweep_vwaps(&hl, &pdx, 203.0);
+        // 101 then 102 across venues, before HL's 103
+        assert!(close(both.buy.unwrap(), 101.5));
+        // $199 of bids in all
+        assert_eq!(both.sell, None);
+        let (_, _, both) = sweep_vwaps(&hl, &pdx, 199.0);
+        assert!(close(both.sell.unwrap(), 99.5));
+    }
+
+    fn view(hl: &OrderBook, pdx: &OrderBook, mode: MergeMode, group: Option<&str>, min_notional: f64) -> BookView {
+        let policy = MergePolicy {
+            depth: 10, mode, group: group.map(|g| g.parse().unwrap()), min_notional, ..Default::default()
+        };
+        BookView::build(hl, pdx, &policy).expect("a non-default policy has a view")
+    }
+
+    /// (price, size) of each row.
+    fn rows(levels: &[MergedLevel]) -> Vec<(f64, f64)> {
+        levels.iter().map(|l| (l.price, l.size)).collect()
+    }
+
+    #[test]
+    fn aggregated_view_sums_venues_at_a_price() {
+        let hl  = book(Exchange::Hyperliquid, &[("100", "1"), ("99", "1")], &[("101", "1")]);
+        let pdx = book(Exchange::Paradex, &[("100", "2")], &[("101", "0.5"), ("102", "1")]);
+        let v = view(&hl, &pdx, MergeMode::Aggregated, None, 0.0);
+        assert_eq!(rows(&v.bids), [(100.0, 3.0), (99.0, 1.0)]);
+        assert_eq!(v.bids[0].split, Some((1.0, 2.0)));
+        assert_eq!(v.bids[0].exchange, Exchange::Paradex); // the larger share
+        assert_eq!(v.bids[1].split, None);
+        assert_eq!(rows(&v.asks), [(101.0, 1.5), (102.0, 1.0)]);
+        assert_eq!(v.asks[0].exchange, Exchange::Hyperliquid);
+    }
+
+    #[test]
+    fn netted_view_matches_off_the_cross() {
+        let hl  = book(Exchange::Hyperliquid, &[("101", "1")], &[("102", "1")]);
+        let pdx = book(Exchange::Paradex, &[("99", "1")], &[("100", "3")]);
+        let v = view(&hl, &pdx, MergeMode::Netted, None, 0.0);
+        // HL's 101 bid is traded against 1 of PDX's 3 at 100
+        assert_eq!(rows(&v.bids), [(99.0, 1.0)]);
+        assert_eq!(rows(&v.asks), [(100.0, 2.0), (102.0, 1.0)]);
+
+        // Equal sizes net both levels away entirely
+        let hl  = book(Exchange::Hyperliquid, &[("101", "2")], &[("102", "1")]);
+        let pdx = book(Exchange::Paradex, &[("99", "1")], &[("100", "2")]);
+        let v = view(&hl, &pdx, MergeMode::Netted, None, 0.0);
+        assert_eq!(rows(&v.bids), [(99.0, 1.0)]);
+        assert_eq!(rows(&v.asks), [(102.0, 1.0)]);
+
+        // The 100.5 bid (1 HL + 1 PDX) meets 1 at 100: half of each share is left
+        let hl  = book(Exchange::Hyperliquid, &[("100.5", "1")], &[("102", "1")]);
+        let pdx = book(Exchange::Paradex, &[("100.5", "1"), ("99", "1")], &[("100", "1")]);
+        let v = view(&hl, &pdx, MergeMode::Netted, None, 0.0);
+        assert_eq!(rows(&v.bids), [(100.5, 1.0), (99.0, 1.0)]);
+        assert_eq!(v.bids[0].split, Some((0.5, 0.5)));
+        assert_eq!(rows(&v.asks), [(102.0, 1.0)]);
+    }
+
+    #[test]
+    fn reducing_an_empty_level_stays_finite() {
+        let mut l = MergedLevel { price: 100.0, size: 2.0, exchange: Exchange::Hyperliquid, split: Some((1.5, 0.5)) };
+        l.reduce(2.0);
+        assert_eq!((l.size, l.split), (0.0, Some((0.0, 0.0))));
+        l.reduce(0.0);
+        assert_eq!((l.size, l.split), (0.0, Some((0.0, 0.0))));
+    }
+
+    #[test]
+    fn grouped_view_buckets_away_from_the_touch() {
+        let hl  = book(Exchange::Hyperliquid, &[("100.7", "1"), ("100.2", "2"), ("99.9", "1")], &[("101.1", "1"), ("101.9", "1")]);
+        let pdx = book(Exchange::Paradex, &[("100.4", "1")], &[("101.5", "2")]);
+        // Tagged: one row per venue and bucket, bids down to 100, asks up to 102
+        let v = view(&hl, &pdx, MergeMode::Tagged, Some("1"), 0.0);
+        assert_eq!(rows(&v.bids), [(100.0, 3.0), (100.0, 1.0), (99.0, 1.0)]);
+        assert_eq!(v.bids[1].exchange, Exchange::Paradex);
+        assert_eq!(rows(&v.asks), [(102.0, 2.0), (102.0, 2.0)]);
+        // Aggregated on top, the buckets fold across venues
+        let v = view(&hl, &pdx, MergeMode::Aggregated, Some("1"), 0.0);
+        assert_eq!(rows(&v.bids), [(100.0, 4.0), (99.0, 1.0)]);
+        assert_eq!(rows(&v.asks), [(102.0, 4.0)]);
+    }
+
+    #[test]
+    fn min_notional_hides_dust_rows() {
+        let hl  = book(Exchange::Hyperliquid, &[("100", "0.05"), ("99", "1")], &[("101", "0.09")]);
+        let pdx = book(Exchange::Paradex, &[("100", "0.06")], &[("102", "1")]);
+        // $5 and $6 at 100 are each dust, but not once aggregated
+        let v = view(&hl, &pdx, MergeMode::Tagged, None, 10.0);
+        assert_eq!(rows(&v.bids), [(99.0, 1.0)]);
+        assert_eq!(rows(&v.asks), [(102.0, 1.0)]);
+        let v = view(&hl, &pdx, MergeMode::Aggregated, None, 10.0);
+        assert_eq!(rows(&v.bids).len(), 2);
+        assert!(close(v.bids[0].size, 0.11));
+        assert_eq!(rows(&v.asks), [(102.0, 1.0)]);
+    }
+
+    fn prices(levels: &[Level]) -> Vec<f64> {
+        levels.iter().map(Level::price_f64).collect()
+    }
+
+    #[test]
+    fn in_unit_converts_prices_only() {
+        let b = book(Exchange::Paradex, &[("100", "2")], &[("101", "3")]);
+        // No rate: already in the unit, borrowed as is
+        assert!(matches!(in_unit(&b, None), Cow::Borrowed(_)));
+
+        let eur = in_unit(&b, Some("1.08".parse().unwrap()));
+        assert_eq!(eur.bids[0].price, "108.00".parse::<Decimal>().unwrap());
+        assert_eq!(eur.asks[0].price, "109.08".parse::<Decimal>().unwrap());
+        assert!(close(eur.bids[0].price_f64(), 108.0));
+        assert_eq!((eur.bids[0].size_f64(), eur.asks[0].size_f64()), (2.0, 3.0));
+
+        // A rate of 1 changes nothing
+        let same = in_unit(&b, Some(Decimal::ONE));
+        assert_eq!((prices(&same.bids), prices(&same.asks)), (prices(&b.bids), prices(&b.asks)));
+    }
+
+    #[test]
+    fn trim_outliers_drops_levels_outside_the_band() {
+        let hl  = book(Exchange::Hyperliquid, &[("100", "1"), ("80", "1")], &[("101", "1"), ("150", "1")]);
+        let pdx = book(Exchange::Paradex, &[("99", "1")], &[("102", "1")]);
+        // Mid 100.5; a 10% band keeps 90.45..=110.55
+        let (h, p, dropped) = trim_outliers(&hl, &pdx, 10.0);
+        assert_eq!(dropped, 2);
+        assert_eq!((prices(&h.bids), prices(&h.asks)), (vec![100.0], vec![101.0]));
+        assert!(matches!(p, Cow::Borrowed(_)));
+
+        // Off, or nothing outside: both borrowed
+        let (h, p, dropped) = trim_outliers(&hl, &pdx, 0.0);
+        assert!(matches!((h, p, dropped), (Cow::Borrowed(_), Cow::Borrowed(_), 0)));
+        let (_, _, dropped) = trim_outliers(&hl, &pdx, 60.0);
+        assert_eq!(dropped, 0);
+    }
+
+    #[test]
+    fn trim_outliers_mid_ignores_one_bad_touch() {
+        // A fat-fingered 10 bid on HL: the median of the four touches stays near 100
+        let hl  = book(Exchange::Hyperliquid, &[("10", "1")], &[("101", "1")]);
+        let pdx = book(Exchange::Paradex, &[("99", "1")], &[("100", "1")]);
+        let (h, p, dropped) = trim_outliers(&hl, &pdx, 5.0);
+        assert_eq!(dropped, 1);
+        assert!(h.bids.is_empty());
+        assert_eq!(p.bids.len() + p.asks.len(), 2);
+    }
+
+    #[test]
+    fn with_bbo_patches_the_touch_only_when_newer() {
+        let mut b = book(Exchange::Paradex, &[("100", "1"), ("99", "2")], &[("101", "1"), ("102", "2")]);
+        b.last_update_ms = 1_000;
+        // No quote, or one older than the book: the book as is
+        assert!(matches!(with_bbo(&b), Cow::Borrowed(_)));
+        b.bbo = Some(Bbo { bid: Some(level("99.5", "4")), ask: Some(level("101", "7")), ts_ms: 900 });
+        assert!(matches!(with_bbo(&b), Cow::Borrowed(_)));
+
+        // Newer: the bid moved down past 100, the ask kept its price with a new size
+        b.bbo.as_mut().unwrap().ts_ms = 1_100;
+        let patched = with_bbo(&b);
+        assert_eq!(prices(&patched.bids), [99.5, 99.0]);
+        assert_eq!(patched.bids[0].size_f64(), 4.0);
+        assert_eq!(prices(&patched.asks), [101.0, 102.0]);
+        assert_eq!(patched.asks[0].size_f64(), 7.0);
+
+        // A side missing from the quote is left alone
+        b.bbo = Some(Bbo { bid: None, ask: Some(level("101.5", "1")), ts_ms: 1_100 });
+        let patched = with_bbo(&b);
+        assert_eq!(prices(&patched.bids), [100.0, 99.0]);
+        assert_eq!(prices(&patched.asks), [101.5, 102.0]);
+    }
+
+    #[test]
+    fn self_crossed_venue_is_no_arb() {
+        let hl  = book(Exchange::Hyperliquid, &[("101", "1")], &[("100", "1")]);
+        let pdx = book(Exchange::Paradex, &[("99", "1")], &[("102", "1")]);
+        let sig = MergedBook::build(&hl, &pdx, 10, &FeesConfig::default()).signals;
+        assert_eq!(sig.top_of_book, TopOfBook::SelfCrossed(Exchange::Hyperliquid));
+        assert_eq!(sig.net_cross_spread, None);
+        assert!(!sig.is_arb());
+        assert_eq!(sig.arb_size, 0.0);
+    }
+
+    #[test]
+    fn touch_within_tolerance_is_locked() {
+        let pdx = book(Exchange::Paradex, &[("99", "1")], &[("100.0000000001", "1")]);
+        let locked = book(Exchange::Hyperliquid, &[("100", "1")], &[("101", "1")]);
+        let crossed = book(Exchange::Hyperliquid, &[("100.01", "1")], &[("101", "1")]);
+        let fees = FeesConfig::default();
+        assert_eq!(MergedBook::build(&locked, &pdx, 10, &fees).signals.top_of_book, TopOfBook::Locked);
+        assert_eq!(MergedBook::build(&crossed, &pdx, 10, &fees).signals.top_of_book, TopOfBook::Crossed);
+    }
+}
@@ -0,0 +1,89 @@
+// src/mid_history.rs — Session history of HL, PDX and merged mids for the chart panel
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::sync::watch;
+use tokio::time::interval;
+
+use crate::merger::MergedBook;
+use crate::types::OrderBook;
+
+/// Starting sample interval; doubles every time the history fills up.
+const SAMPLE_MS: u64 = 1_000;
+/// Points kept. When full, every other point is dropped and the interval
+/// doubles, so the whole session always fits at a resolution the chart can draw.
+const MAX_POINTS: usize = 1_200;
+
+/// Mids at one instant; `t_secs` is seconds since startup.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct MidPoint {
+    pub t_secs: f64,
+    pub hl:     Option<f64>,
+    pub pdx:    Option<f64>,
+    pub merged: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MidHistory {
+    pub points:  Vec<MidPoint>, // oldest first
+    pub step_ms: u64,           // current sample interval
+}
+
+/// Spawns a task sampling the three mids for the rest of the session.
+pub fn spawn_mid_history(
+    hl_rx: watch::Receiver<Arc<OrderBook>>,
+    pdx_rx: watch::Receiver<Arc<OrderBook>>,
+    merged_rx: watch::Receiver<MergedBook>,
+) -> watch::Receiver<MidHistory> {
+    let (tx, rx) = watch::channel(MidHistory { points: Vec::new(), step_ms: SAMPLE_MS });
+    tokio::spawn(async move {
+        let mut started = Instant::now();
+        let mut step_ms = SAMPLE_MS;
+        let mut ticker = interval(Duration::from_millis(step_ms));
+        let mut coins = Default::default();
+        loop {
+            ticker.tick().await;
+            let point = {
+                let merged = merged_rx.borrow();
+                // Another pair: its chart starts over
+                if merged.pair_changed(&mut coins) {
+                    started = Instant::now();
+                    if step_ms != SAMPLE_MS {
+                        step_ms = SAMPLE_MS;
+                        ticker = interval(Duration::from_millis(step_ms));
+                    }
+                    tx.send_replace(MidHistory { points: Vec::new(), step_ms });
+                }
+                MidPoint {
+                    t_secs: started.elapsed().as_secs_f64(),
+                    hl:     hl_rx.borrow().mid(),
+                    pdx:    pdx_rx.borrow().mid(),
+                    merged: match (merged.bids.first(), merged.asks.first()) {
+                        (Some(b), Some(a)) => Some((b.price + a.price) / 2.0),
+                        _ => None,
+                    },
+                }
+            };
+            let mut thinned = false;
+            tx.send_modify(|h| {
+                h.points.push(point);
+                if h.points.len() >= MAX_POINTS {
+                    h.points = h.points.iter().step_by(2).copied().collect();
+                    h.step_ms *= 2;
+                    thinned = true;
+                }
+            });
+            if thinned {
+                step_ms *= 2;
+                ticker = interval(Duration::from_millis(step_ms));
+                ticker.tick().await; // the first tick of a new interval fires at once
+            }
+            if tx.is_closed() {
+                break;
+            }
+        }
+    });
+    rx
+}
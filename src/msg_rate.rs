@@ -0,0 +1,63 @@
+// src/msg_rate.rs — Rolling per-feed message rates for the footer
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::watch;
+use tokio::time::interval;
+
+use crate::types::OrderBook;
+
+/// One bucket per second.
+const BUCKET_MS: u64 = 1_000;
+/// Buckets kept per feed for the footer graph.
+const MAX_BUCKETS: usize = 60;
+/// Buckets averaged for the headline rate, so it doesn't jump every second.
+const RATE_BUCKETS: usize = 5;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MsgRates {
+    pub hl:  VecDeque<u64>, // updates per second, oldest first
+    pub pdx: VecDeque<u64>,
+}
+
+/// Updates per second averaged over the last `RATE_BUCKETS`.
+pub fn rate(buckets: &VecDeque<u64>) -> f64 {
+    let n = buckets.len().min(RATE_BUCKETS);
+    if n == 0 {
+        return 0.0;
+    }
+    buckets.iter().rev().take(n).sum::<u64>() as f64 / n as f64
+}
+
+/// Spawns a task turning both feeds' cumulative `message_count` into
+/// per-second buckets.
+pub fn spawn_msg_rates(hl_rx: watch::Receiver<Arc<OrderBook>>, pdx_rx: watch::Receiver<Arc<OrderBook>>) -> watch::Receiver<MsgRates> {
+    let (tx, rx) = watch::channel(MsgRates::default());
+    tokio::spawn(async move {
+        let mut last = (hl_rx.borrow().message_count, pdx_rx.borrow().message_count);
+        let mut ticker = interval(Duration::from_millis(BUCKET_MS));
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let now = (hl_rx.borrow().message_count, pdx_rx.borrow().message_count);
+            let push = |buckets: &mut VecDeque<u64>, n: u64| {
+                if buckets.len() == MAX_BUCKETS {
+                    buckets.pop_front();
+                }
+                buckets.push_back(n);
+            };
+            tx.send_modify(|r| {
+                push(&mut r.hl, now.0.saturating_sub(last.0));
+                push(&mut r.pdx, now.1.saturating_sub(last.1));
+            });
+            last = now;
+            if tx.is_closed() {
+                break;
+            }
+        }
+    });
+    rx
+}
@@ -0,0 +1,101 @@
+// src/nats_sink.rs — Publish normalised books and arbitrage events to NATS
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use serde_json::json;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::config::NatsConfig;
+use crate::merger::MergedBook;
+use crate::types::{now_ms, OrderBook};
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Connects to NATS (so a bad URL fails at startup) and publishes in the background:
+///
+/// - `md.merged.{coin}` — full `MergedBook` on every merge
+/// - `md.hl.{coin}` / `md.pdx.{coin}` — per-exchange `OrderBook` on every update
+/// - `signals.arb.{coin}` — on every merge while the cross spread is negative
+pub async fn spawn_nats_sink(
+    cfg: &NatsConfig,
+    coin: &str,
+    mut hl_rx: watch::Receiver<Arc<OrderBook>>,
+    mut pdx_rx: watch::Receiver<Arc<OrderBook>>,
+    mut merged_rx: watch::Receiver<MergedBook>,
+) -> Result<()> {
+    let client = async_nats::connect(&cfg.url)
+        .await
+        .with_context(|| format!("Failed to connect to NATS at {}", cfg.url))?;
+    info!("[NATS] Connected to {}", cfg.url);
+
+    let merged_subject = format!("md.merged.{coin}");
+    let hl_subject     = format!("md.hl.{coin}");
+    let pdx_subject    = format!("md.pdx.{coin}");
+    let arb_subject    = format!("signals.arb.{coin}");
+
+    tokio::spawn(async move {
+        loop {
+            // Serialise inside each arm so no watch borrow is held across an await
+            let publishes: Vec<(String, String)> = tokio::select! {
+                r = merged_rx.changed() => {
+                    if r.is_err() { break; }
+                    let merged = merged_rx.borrow_and_update().clone();
+                    let mut out = vec![(merged_subject.clone(), to_json(&merged))];
+                    if let Some(event) = arb_event(&merged) {
+                        out.push((arb_subject.clone(), event));
+                    }
+                    out
+                }
+                r = hl_rx.changed() => {
+                    if r.is_err() { break; }
+                    vec![(hl_subject.clone(), to_json(&**hl_rx.borrow_and_update()))]
+                }
+                r = pdx_rx.changed() => {
+                    if r.is_err() { break; }
+                    vec![(pdx_subject.clone(), to_json(&**pdx_rx.borrow_and_update()))]
+                }
+            };
+
+            for (subject, payload) in publishes {
+                if let Err(e) = client.publish(subject.clone(), payload.into()).await {
+                    warn!("[NATS] Publish to {subject} failed: {e}");
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+// ─── Payloads ─────────────────────────────────────────────────────────────────
+
+fn to_json<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// JSON arbitrage event, or `None` when the merged book isn't crossed across
+/// venues after fees.
+fn arb_event(merged: &MergedBook) -> Option<String> {
+    let sig = &merged.signals;
+    if !sig.is_arb() {
+        return None;
+    }
+    let bid = merged.bids.first()?;
+    let ask = merged.asks.first()?;
+    Some(json!({
+        "ts_ms":            now_ms(),
+        "cross_spread":     sig.cross_spread,
+        "cross_spread_pct": sig.cross_spread_pct,
+        "net_cross_spread": sig.net_cross_spread,
+        "arb_size":         sig.arb_size,
+        "arb_profit_usd":   sig.arb_profit_usd,
+        "arb_confidence":   sig.arb_confidence,
+        "bid_exchange":     bid.exchange,
+        "bid_price":        bid.price,
+        "bid_size":         bid.size,
+        "ask_exchange":     ask.exchange,
+        "ask_price":        ask.price,
+        "ask_size":         ask.size,
+    }).to_string())
+}
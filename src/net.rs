@@ -0,0 +1,52 @@
+// src/net.rs — WebSocket connect with the `[socket]` tuning applied
+//
+// `connect_async` resolves, connects and upgrades in one call, with no way to
+// size the socket's receive buffer. Here the TCP socket is set up first and
+// then handed to tungstenite for the TLS and WebSocket handshakes.
+
+use anyhow::{Context, Result};
+use tokio::net::{lookup_host, TcpSocket, TcpStream};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+use tokio_tungstenite::{client_async_tls_with_config, MaybeTlsStream, WebSocketStream};
+
+use crate::config::SocketConfig;
+
+pub type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Connects to `url`, trying each resolved address in turn like
+/// `TcpStream::connect`.
+pub async fn connect_ws(url: &str, cfg: &SocketConfig) -> Result<WsStream> {
+    let request = url.into_client_request()?;
+    let host = request.uri().host().context("URL has no host")?.to_string();
+    let port = request.uri().port_u16()
+        .unwrap_or(if request.uri().scheme_str() == Some("ws") { 80 } else { 443 });
+
+    let mut last_err = None;
+    let mut stream = None;
+    for addr in lookup_host((host.as_str(), port)).await.with_context(|| format!("Cannot resolve {host}"))? {
+        let socket = if addr.is_ipv4() { TcpSocket::new_v4()? } else { TcpSocket::new_v6()? };
+        if let Some(bytes) = cfg.recv_buffer_bytes {
+            socket.set_recv_buffer_size(bytes)?;
+        }
+        socket.set_nodelay(cfg.tcp_nodelay)?;
+        match socket.connect(addr).await {
+            Ok(s)  => { stream = Some(s); break; }
+            Err(e) => last_err = Some(e),
+        }
+    }
+    let stream = match (stream, last_err) {
+        (Some(s), _)    => s,
+        (None, Some(e)) => return Err(e).with_context(|| format!("Cannot connect to {host}:{port}")),
+        (None, None)    => anyhow::bail!("{host} resolved to no addresses"),
+    };
+
+    let ws_config = WebSocketConfig {
+        write_buffer_size: cfg.write_buffer_bytes,
+        max_message_size:  Some(cfg.max_message_bytes),
+        max_frame_size:    Some(cfg.max_frame_bytes),
+        ..Default::default()
+    };
+    let (ws, _) = client_async_tls_with_config(request, stream, Some(ws_config), None).await?;
+    Ok(ws)
+}
@@ -0,0 +1,44 @@
+// src/ofi.rs — Order flow imbalance: liquidity added vs removed near the touch
+//
+// Bid size added and ask size removed push the flow up; bid size removed and
+// ask size added push it down. Only the top `OFI_LEVELS` levels count, so deep
+// book churn doesn't drown out what happens at the touch.
+
+use crate::types::Level;
+
+/// Levels per side, from the touch, that count as "near" it.
+pub const OFI_LEVELS: usize = 5;
+
+/// Flow between two states of one book. Only prices inside both states' top
+/// `OFI_LEVELS` range are compared, so levels merely scrolling in or out of
+/// view aren't counted as added or removed. 0.0 when either state is empty.
+pub fn book_flow(old_bids: &[Level], old_asks: &[Level], new_bids: &[Level], new_asks: &[Level]) -> f64 {
+    side_flow(old_bids, new_bids, true) - side_flow(old_asks, new_asks, false)
+}
+
+/// Size added minus removed at prices at or better than the shallower of the
+/// two `OFI_LEVELS`-th levels.
+fn side_flow(old: &[Level], new: &[Level], bids: bool) -> f64 {
+    let (Some(old_edge), Some(new_edge)) = (edge(old), edge(new)) else { return 0.0 };
+    let edge = if bids { old_edge.max(new_edge) } else { old_edge.min(new_edge) };
+    let near = |px: f64| if bids { px >= edge } else { px <= edge };
+    let size_at = |levels: &[Level], px: f64| {
+        levels.iter().find(|l| l.price_f64() == px).map_or(0.0, Level::size_f64)
+    };
+
+    let added: f64 = new.iter().take(OFI_LEVELS)
+        .filter(|l| near(l.price_f64()))
+        .map(|l| l.size_f64() - size_at(old, l.price_f64()))
+        .sum();
+    // Prices that vanished entirely
+    let removed: f64 = old.iter().take(OFI_LEVELS)
+        .filter(|l| near(l.price_f64()) && size_at(new, l.price_f64()) == 0.0)
+        .map(Level::size_f64)
+        .sum();
+    added - removed
+}
+
+/// Price of the deepest level counted, or `None` for an empty side.
+pub fn edge(levels: &[Level]) -> Option<f64> {
+    levels.iter().take(OFI_LEVELS).next_back().map(Level::price_f64)
+}
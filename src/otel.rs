@@ -0,0 +1,290 @@
+// src/otel.rs — OpenTelemetry span export over OTLP/HTTP (JSON)
+//
+// A tracing layer turns closed spans into OTLP spans and an exporter task
+// POSTs them in batches to the collector's /v1/traces. Trace and span ids come
+// from the layer itself; a child span joins its parent's trace.
+//
+// The feeds open their per-message spans at TRACE level and everything else
+// (connect, subscribe, REST snapshots) at DEBUG. A TRACE-level root span starts
+// a trace that is exported with probability `message_ratio`; other traces are
+// always exported. Warnings and errors logged inside a span are attached to it
+// as span events, and an error marks the span's status as failed.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::{interval, timeout};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{info, warn, Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::config::OtelConfig;
+
+/// Closed spans buffered for the exporter; more are dropped until it catches up.
+const QUEUE_CAPACITY: usize = 4_096;
+/// Spans per request at most.
+const MAX_BATCH: usize = 512;
+const EXPORT_SECS: u64 = 5;
+/// How long `shutdown()` waits for the final export.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// OTLP `SPAN_KIND_INTERNAL`.
+const KIND_INTERNAL: u8 = 1;
+/// OTLP `STATUS_CODE_ERROR`.
+const STATUS_ERROR: u8 = 2;
+
+// ─── Handle ───────────────────────────────────────────────────────────────────
+
+/// Owns the exporter task. Call `shutdown()` on exit so the last batch is sent.
+pub struct OtelExporter {
+    stop_tx: oneshot::Sender<()>,
+    task:    JoinHandle<()>,
+}
+
+impl OtelExporter {
+    pub async fn shutdown(self) {
+        let _ = self.stop_tx.send(());
+        let _ = timeout(FLUSH_TIMEOUT, self.task).await;
+    }
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// The layer to add to the subscriber, and the exporter task it feeds.
+pub fn spawn_otel(cfg: &OtelConfig) -> (OtelLayer, OtelExporter) {
+    let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+    let (stop_tx, stop_rx) = oneshot::channel();
+    let task = tokio::spawn(run_exporter(cfg.clone(), rx, stop_rx));
+    let seed = unix_nanos() ^ (std::process::id() as u64) << 32;
+    let layer = OtelLayer { tx, message_ratio: cfg.message_ratio, ids: AtomicU64::new(seed) };
+    (layer, OtelExporter { stop_tx, task })
+}
+
+/// Only this crate's spans, plus the warnings and errors logged inside them.
+pub fn wanted(meta: &Metadata<'_>) -> bool {
+    meta.target().starts_with(env!("CARGO_CRATE_NAME")) && (meta.is_span() || *meta.level() <= Level::WARN)
+}
+
+// ─── Layer ────────────────────────────────────────────────────────────────────
+
+pub struct OtelLayer {
+    tx:            mpsc::Sender<SpanData>,
+    message_ratio: f64,
+    ids:           AtomicU64, // splitmix64 state
+}
+
+impl OtelLayer {
+    fn next_id(&self) -> u64 {
+        let mut z = self.ids.fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// One span in flight, kept in the registry's extensions until it closes.
+struct SpanData {
+    trace_id:   u128,
+    span_id:    u64,
+    parent_id:  Option<u64>,
+    sampled:    bool,
+    name:       &'static str,
+    start_ns:   u64,
+    end_ns:     u64,
+    attributes: Vec<(&'static str, Value)>,
+    events:     Vec<(u64, Level, String)>, // (time, level, message)
+}
+
+impl<S> Layer<S> for OtelLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let parent = span.parent().and_then(|p| {
+            p.extensions().get::<SpanData>().map(|d| (d.trace_id, d.span_id, d.sampled))
+        });
+        let (trace_id, parent_id, sampled) = match parent {
+            Some((trace_id, parent_id, sampled)) => (trace_id, Some(parent_id), sampled),
+            None => {
+                let trace_id = (self.next_id() as u128) << 64 | self.next_id() as u128;
+                let sampled = *attrs.metadata().level() != Level::TRACE
+                    || (self.next_id() as f64 / u64::MAX as f64) < self.message_ratio;
+                (trace_id, None, sampled)
+            }
+        };
+        let mut data = SpanData {
+            trace_id,
+            span_id: self.next_id(),
+            parent_id,
+            sampled,
+            name: attrs.metadata().name(),
+            start_ns: unix_nanos(),
+            end_ns: 0,
+            attributes: Vec::new(),
+            events: Vec::new(),
+        };
+        if sampled {
+            attrs.record(&mut AttrVisitor(&mut data.attributes));
+        }
+        span.extensions_mut().insert(data);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(data) = span.extensions_mut().get_mut::<SpanData>()
+            && data.sampled
+        {
+            values.record(&mut AttrVisitor(&mut data.attributes));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.event_span(event)
+            && let Some(data) = span.extensions_mut().get_mut::<SpanData>()
+            && data.sampled
+        {
+            let mut message = MessageVisitor(String::new());
+            event.record(&mut message);
+            data.events.push((unix_nanos(), *event.metadata().level(), message.0));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let Some(mut data) = span.extensions_mut().remove::<SpanData>() else { return };
+        if data.sampled {
+            data.end_ns = unix_nanos();
+            let _ = self.tx.try_send(data);
+        }
+    }
+}
+
+/// Span fields as JSON values, keeping their types.
+struct AttrVisitor<'a>(&'a mut Vec<(&'static str, Value)>);
+
+impl Visit for AttrVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name(), format!("{value:?}").into()));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name(), value.into()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.push((field.name(), value.into()));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.push((field.name(), value.into()));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.push((field.name(), value.into()));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.push((field.name(), value.into()));
+    }
+}
+
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{value:?}");
+        }
+    }
+}
+
+// ─── Exporter ─────────────────────────────────────────────────────────────────
+
+async fn run_exporter(cfg: OtelConfig, mut rx: mpsc::Receiver<SpanData>, mut stop_rx: oneshot::Receiver<()>) {
+    let http = reqwest::Client::new();
+    let mut batch = Vec::new();
+    let mut healthy = true;
+    let mut ticker = interval(Duration::from_secs(EXPORT_SECS));
+    loop {
+        let stop = tokio::select! {
+            Some(span) = rx.recv() => {
+                batch.push(span);
+                if batch.len() < MAX_BATCH {
+                    continue;
+                }
+                false
+            }
+            _ = ticker.tick() => false,
+            _ = &mut stop_rx => true,
+        };
+        if !batch.is_empty() {
+            let body = otlp_body(&cfg.service_name, &std::mem::take(&mut batch));
+            let result = http.post(&cfg.traces_url).json(&body).send().await.and_then(|r| r.error_for_status());
+            // Report only changes, so a collector that is down doesn't flood the log
+            match (result, healthy) {
+                (Err(e), true) => { warn!("[OTEL] Export failed: {e} — dropping spans until it recovers"); healthy = false; }
+                (Ok(_), false) => { info!("[OTEL] Export recovered"); healthy = true; }
+                _ => {}
+            }
+        }
+        if stop {
+            break;
+        }
+    }
+}
+
+/// `ExportTraceServiceRequest` in OTLP's JSON encoding.
+fn otlp_body(service_name: &str, spans: &[SpanData]) -> Value {
+    let spans: Vec<Value> = spans.iter().map(|s| {
+        let mut span = json!({
+            "traceId":           format!("{:032x}", s.trace_id),
+            "spanId":            format!("{:016x}", s.span_id),
+            "name":              s.name,
+            "kind":              KIND_INTERNAL,
+            "startTimeUnixNano": s.start_ns.to_string(),
+            "endTimeUnixNano":   s.end_ns.to_string(),
+            "attributes":        s.attributes.iter().map(|(k, v)| key_value(k, v)).collect::<Vec<_>>(),
+            "events": s.events.iter().map(|(t, level, message)| json!({
+                "timeUnixNano": t.to_string(),
+                "name":         message,
+                "attributes":   [key_value("level", &level.as_str().into())],
+            })).collect::<Vec<_>>(),
+        });
+        if let Some(parent) = s.parent_id {
+            span["parentSpanId"] = format!("{parent:016x}").into();
+        }
+        if s.events.iter().any(|(_, level, _)| *level == Level::ERROR) {
+            span["status"] = json!({ "code": STATUS_ERROR });
+        }
+        span
+    }).collect();
+
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": [key_value("service.name", &service_name.into())] },
+            "scopeSpans": [{ "scope": { "name": env!("CARGO_CRATE_NAME") }, "spans": spans }],
+        }]
+    })
+}
+
+/// OTLP `KeyValue`; 64-bit integers are strings in the JSON encoding.
+fn key_value(key: &str, value: &Value) -> Value {
+    let value = match value {
+        Value::Bool(b)                  => json!({ "boolValue": b }),
+        Value::Number(n) if n.is_f64()  => json!({ "doubleValue": n }),
+        Value::Number(n)                => json!({ "intValue": n.to_string() }),
+        Value::String(s)                => json!({ "stringValue": s }),
+        other                           => json!({ "stringValue": other.to_string() }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+fn unix_nanos() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0)
+}
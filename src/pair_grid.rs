@@ -0,0 +1,114 @@
+// src/pair_grid.rs — Every configured pair's merged BBO at once, for the grid layout
+//
+// `G` swaps the body for a grid of cards, one per pair. Each pair gets its own
+// two feeds, started with the same settings as the main ones while the grid is
+// up and closed when it goes; their books feed the cards only, never the
+// sinks or the main panels. A pair without a Paradex symbol has it derived
+// first, in the background.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::config::{self, FeesConfig, PairConfig};
+use crate::feeds::Feeds;
+use crate::merger::MergedBook;
+use crate::types::OrderBook;
+
+/// One card as drawn.
+#[derive(Debug, Clone)]
+pub struct GridCard {
+    pub name:   String,
+    pub active: bool,                                      // the pair the rest of the dashboard shows
+    pub books:  Option<(Arc<OrderBook>, Arc<OrderBook>)>,  // (HL, PDX); None until the feeds start
+    pub merged: Option<MergedBook>,                        // top of the two books, with its signals
+    pub note:   Option<(String, bool)>,                    // shown instead of the books; true = error
+}
+
+enum Slot {
+    Resolving(PairConfig, JoinHandle<Result<String>>), // deriving the Paradex symbol
+    Running(Box<Feeds>, watch::Receiver<Arc<OrderBook>>, watch::Receiver<Arc<OrderBook>>),
+    Failed(String, String),                            // (pair, why)
+}
+
+pub struct PairGrid {
+    slots: Vec<Slot>,
+}
+
+impl PairGrid {
+    /// Starts feeds for every pair, beside `feeds` and with its settings.
+    pub fn open(pairs: &[PairConfig], feeds: &Feeds) -> Self {
+        let slots = pairs.iter().cloned().map(|pair| {
+            if pair.pdx_symbol.is_empty() {
+                let hl_symbol = pair.hl_symbol.clone();
+                Slot::Resolving(pair, tokio::spawn(async move { config::resolve_pdx_symbol(&hl_symbol).await }))
+            } else {
+                let (feeds, hl_rx, pdx_rx) = feeds.start_other(pair);
+                Slot::Running(Box::new(feeds), hl_rx, pdx_rx)
+            }
+        }).collect();
+        Self { slots }
+    }
+
+    /// Starts the feeds of pairs whose Paradex symbol has been derived since.
+    pub async fn poll(&mut self, feeds: &Feeds) {
+        for slot in &mut self.slots {
+            let Slot::Resolving(pair, handle) = slot else { continue };
+            if !handle.is_finished() {
+                continue;
+            }
+            *slot = match handle.await {
+                Ok(Ok(pdx_symbol)) => {
+                    let (feeds, hl_rx, pdx_rx) = feeds.start_other(PairConfig { pdx_symbol, ..pair.clone() });
+                    Slot::Running(Box::new(feeds), hl_rx, pdx_rx)
+                }
+                Ok(Err(e)) => Slot::Failed(pair.name.clone(), format!("{e:#}")),
+                Err(e)     => Slot::Failed(pair.name.clone(), format!("lookup failed: {e}")),
+            };
+        }
+    }
+
+    /// Whether any card's books changed since `cards` last read them.
+    pub fn has_changed(&self) -> bool {
+        self.slots.iter().any(|slot| match slot {
+            Slot::Running(_, hl_rx, pdx_rx) => hl_rx.has_changed().unwrap_or(false) || pdx_rx.has_changed().unwrap_or(false),
+            Slot::Resolving(_, handle) => handle.is_finished(),
+            Slot::Failed(..) => false,
+        })
+    }
+
+    /// The cards, in `[pairs]` order; `active` names the main pair.
+    pub fn cards(&mut self, active: &str, fees: &FeesConfig) -> Vec<GridCard> {
+        self.slots.iter_mut().map(|slot| match slot {
+            Slot::Running(feeds, hl_rx, pdx_rx) => {
+                let (hl, pdx) = (hl_rx.borrow_and_update().clone(), pdx_rx.borrow_and_update().clone());
+                let merged = MergedBook::build(&hl, &pdx, 1, fees);
+                let name = feeds.pair().name.clone();
+                GridCard { active: name == active, name, books: Some((hl, pdx)), merged: Some(merged), note: None }
+            }
+            Slot::Resolving(pair, _) => GridCard {
+                active: pair.name == active, name: pair.name.clone(), books: None, merged: None,
+                note: Some(("finding the Paradex market…".to_string(), false)),
+            },
+            Slot::Failed(name, why) => GridCard {
+                active: name == active, name: name.clone(), books: None, merged: None,
+                note: Some((why.clone(), true)),
+            },
+        }).collect()
+    }
+
+    /// Closes every card's sockets, waiting up to `wait` for each.
+    pub async fn shutdown(self, wait: Duration) {
+        let closing = self.slots.into_iter().map(|slot| async move {
+            match slot {
+                Slot::Running(mut feeds, ..) => feeds.shutdown(wait).await,
+                Slot::Resolving(_, handle) => handle.abort(),
+                Slot::Failed(..) => {}
+            }
+        });
+        futures_util::future::join_all(closing).await;
+    }
+}
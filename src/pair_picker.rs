@@ -0,0 +1,179 @@
+// src/pair_picker.rs — Input box for switching the pair while running
+//
+// `S` opens it. What's typed is fuzzy-matched against the `[pairs]` names and
+// the coins listed on either venue (`markets`); ↑/↓ pick a match and Tab
+// completes it. Enter hands the pick, or the typed text when nothing matches,
+// to the TUI loop, which checks it against both exchanges (`resolve`) before
+// switching the feeds.
+
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use crate::config::{self, AppConfig, PairConfig};
+use crate::markets::{fuzzy_score, Market};
+
+/// Characters kept in the input box.
+const MAX_INPUT: usize = 24;
+/// Matches kept for the list, best first.
+const MAX_MATCHES: usize = 50;
+
+/// One row of the match list.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub name:       String,         // what Enter submits
+    pub market:     Option<Market>, // None until the listings arrive, or when neither venue lists it
+    pub configured: bool,           // a `[pairs]` entry
+}
+
+/// State of the picker popup, driven by the TUI keys.
+#[derive(Debug, Clone, Default)]
+pub struct PairPicker {
+    pub open:     bool,
+    pub input:    String,
+    pub status:   Option<(String, bool)>, // last outcome shown under the input; true = error
+    pub pending:  bool,                   // a submitted pair is being checked
+    pub pairs:    Vec<PairConfig>,        // `[pairs]`, plus pairs switched to this session
+    pub matches:  Vec<Candidate>,         // for the current input, best first
+    pub selected: usize,                  // index into `matches`
+    submitted:    Option<String>,
+}
+
+impl PairPicker {
+    pub fn new(pairs: &[PairConfig]) -> Self {
+        Self { pairs: pairs.to_vec(), ..Self::default() }
+    }
+
+    /// Re-ranks the `[pairs]` names and the listed coins against the input:
+    /// best fuzzy match first, then markets on both venues, then by volume.
+    pub fn refresh(&mut self, markets: &[Market]) {
+        let find = |coin: &str| markets.iter().find(|m| m.coin.eq_ignore_ascii_case(coin)).cloned();
+        let mut all: Vec<Candidate> = self.pairs.iter()
+            .map(|p| Candidate { name: p.name.clone(), market: find(&p.hl_symbol), configured: true })
+            .collect();
+        all.extend(markets.iter()
+            .filter(|m| !self.pairs.iter().any(|p| p.name == m.coin || p.hl_symbol.eq_ignore_ascii_case(&m.coin)))
+            .map(|m| Candidate { name: m.coin.clone(), market: Some(m.clone()), configured: false }));
+
+        let mut scored: Vec<(i64, Candidate)> = all.into_iter()
+            .filter_map(|c| Some((fuzzy_score(&self.input, &c.name)?, c)))
+            .collect();
+        let rank = |c: &Candidate| c.market.as_ref().map(|m| (m.on_both(), m.volume())).unwrap_or((c.configured, 0.0));
+        scored.sort_by(|(score_a, a), (score_b, b)| {
+            let ((both_a, vol_a), (both_b, vol_b)) = (rank(a), rank(b));
+            score_b.cmp(score_a).then(both_b.cmp(&both_a)).then(vol_b.total_cmp(&vol_a))
+        });
+        self.matches = scored.into_iter().take(MAX_MATCHES).map(|(_, c)| c).collect();
+        self.selected = self.selected.min(self.matches.len().saturating_sub(1));
+    }
+
+    /// Submits `name` as if typed and entered, for the carousel.
+    pub fn submit(&mut self, name: String) {
+        self.submitted = Some(name);
+        self.pending = true;
+    }
+
+    /// The pair after `current` in `pairs`, wrapping round; the first when
+    /// `current` isn't one. None with fewer than two pairs.
+    pub fn next_pair(&self, current: &str) -> Option<String> {
+        if self.pairs.len() < 2 {
+            return None;
+        }
+        let next = self.pairs.iter().position(|p| p.name == current).map_or(0, |i| (i + 1) % self.pairs.len());
+        Some(self.pairs[next].name.clone())
+    }
+
+    /// The text entered with Enter, once.
+    pub fn take_submitted(&mut self) -> Option<String> {
+        self.submitted.take()
+    }
+
+    /// Shows the outcome of the last switch and clears `pending`.
+    pub fn finish(&mut self, msg: String, error: bool) {
+        self.status = Some((msg, error));
+        self.pending = false;
+        if !error {
+            self.input.clear();
+            self.selected = 0;
+        }
+    }
+
+    /// Handles a key while the popup is open, or `S` to open it. Returns false
+    /// for keys it doesn't use, which then fall through to the normal bindings.
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        if !self.open {
+            // Capital S: lowercase `s` is already the sim sell, and the picker
+            // sees keys first, so it would swallow every sell
+            if code == KeyCode::Char('S') {
+                (self.open, self.status) = (true, None);
+                return true;
+            }
+            return false;
+        }
+        match code {
+            KeyCode::Esc  => self.open = false,
+            KeyCode::Up   => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(self.matches.len().saturating_sub(1)),
+            KeyCode::Tab  => if let Some(c) = self.matches.get(self.selected) {
+                self.input = c.name.clone();
+            },
+            KeyCode::Enter if !self.pending => {
+                let pick = self.matches.get(self.selected).map(|c| c.name.clone());
+                if let Some(name) = pick.or_else(|| (!self.input.is_empty()).then(|| self.input.clone())) {
+                    self.submitted = Some(name);
+                    self.pending = true;
+                    self.status = None;
+                }
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                self.selected = 0;
+            }
+            KeyCode::Char(c) if (c.is_ascii_alphanumeric() || c == '-') && self.input.len() < MAX_INPUT => {
+                self.input.push(c.to_ascii_uppercase());
+                self.selected = 0;
+            }
+            // Swallow everything else so `q`, `b`, … can't fire while typing
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Looks `input` up in `[pairs]`, or else takes it as a Hyperliquid coin, and
+/// checks both symbols against the exchanges. An unset Paradex market is
+/// derived as at startup.
+pub async fn resolve(input: &str, pairs: &[PairConfig]) -> Result<PairConfig> {
+    let mut pair = pairs.iter().find(|p| p.name == input).cloned().unwrap_or_else(|| PairConfig {
+        name:       input.to_string(),
+        hl_symbol:  input.to_string(),
+        pdx_symbol: String::new(),
+    });
+    config::validate_hl_symbol(&pair.hl_symbol).await?;
+    if pair.pdx_symbol.is_empty() {
+        pair.pdx_symbol = config::resolve_pdx_symbol(&pair.hl_symbol).await?;
+    } else {
+        config::validate_pdx_symbol(&pair.pdx_symbol).await?;
+    }
+    Ok(pair)
+}
+
+/// Enabled outputs that took the symbols at startup and would keep the old
+/// pair's name on the new pair's data. Switching is refused while any is on.
+pub fn switch_blockers(cfg: &AppConfig) -> Vec<&'static str> {
+    [
+        ("hl_exec", cfg.hl_exec.is_some()),
+        ("pdx_exec", cfg.pdx_exec.is_some()),
+        ("nats", cfg.nats.is_some()),
+        ("redis", cfg.redis.is_some()),
+        ("parquet", cfg.parquet.is_some()),
+        ("sqlite", cfg.sqlite.is_some()),
+        ("arb_audit", cfg.arb_audit.is_some()),
+        ("telegram", cfg.telegram.is_some()),
+        ("slack", cfg.slack.is_some()),
+        ("desktop_notify", cfg.desktop_notify.is_some()),
+        ("market_stats", cfg.market_stats.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(name, on)| on.then_some(name))
+    .collect()
+}
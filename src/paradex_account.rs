@@ -0,0 +1,227 @@
+// src/paradex_account.rs — Paradex private channels (fills, positions, account)
+//
+// Each connection authenticates with a fresh JWT (JSON-RPC "auth") before
+// subscribing, so an expired token only matters across reconnects. The
+// channels only push changes, so positions and today's fills are seeded over
+// REST once subscribed; the account counts as synced after that.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::sync::{watch, Mutex};
+use tokio::time::{interval, sleep};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, error, info, warn};
+
+use crate::account::{day_start_ms, AccountFill, AccountPosition, AccountState};
+use crate::paradex_exec::PdxAuth;
+use crate::types::{now_ms, Exchange, Side};
+
+const MAINNET_WS_URL: &str = "wss://ws.api.prod.paradex.trade/v1";
+const TESTNET_WS_URL: &str = "wss://ws.api.testnet.paradex.trade/v1";
+const RECONNECT_DELAY_SECS: u64 = 3;
+const HEARTBEAT_SECS: u64 = 20;
+const CHANNELS: [&str; 3] = ["fills.ALL", "positions", "account"];
+
+/// Spawns a background task keeping `AccountState` current for the account
+/// `auth` signs for.
+pub fn spawn_pdx_account(auth: PdxAuth) -> watch::Receiver<AccountState> {
+    let (tx, rx) = watch::channel(AccountState::new(Exchange::Paradex));
+    let url = if auth.testnet { TESTNET_WS_URL } else { MAINNET_WS_URL };
+    tokio::spawn(async move {
+        loop {
+            info!("[PDX-ACCT] Connecting…");
+            match run_connection(url, &auth, &tx).await {
+                Ok(_)  => warn!("[PDX-ACCT] Connection closed cleanly — reconnecting"),
+                Err(e) => error!("[PDX-ACCT] Connection error: {e:#} — reconnecting"),
+            }
+            tx.send_modify(|a| { a.connected = false; a.synced = false; });
+            sleep(Duration::from_secs(RECONNECT_DELAY_SECS)).await;
+        }
+    });
+    rx
+}
+
+async fn run_connection(url: &str, auth: &PdxAuth, tx: &watch::Sender<AccountState>) -> Result<()> {
+    let jwt = auth.authenticate().await?;
+    let (ws_stream, _) = connect_async(url)
+        .await
+        .context("WebSocket connect failed")?;
+
+    let (write, mut read) = ws_stream.split();
+    let write = Arc::new(Mutex::new(write));
+
+    // Authenticate, then subscribe; ids 1..=3 are the subscriptions
+    {
+        let mut w = write.lock().await;
+        let msg = json!({ "jsonrpc": "2.0", "method": "auth", "params": { "bearer": jwt }, "id": 0 });
+        w.send(Message::Text(msg.to_string())).await?;
+        for (i, channel) in CHANNELS.iter().enumerate() {
+            let msg = json!({ "jsonrpc": "2.0", "method": "subscribe", "params": { "channel": channel }, "id": i + 1 });
+            w.send(Message::Text(msg.to_string())).await?;
+        }
+    }
+    info!("[PDX-ACCT] Connected, subscribing to {}", CHANNELS.join(", "));
+    tx.send_modify(|a| a.connected = true);
+
+    // Seed after subscribing so nothing falls between the two; updates queued
+    // meanwhile are applied after the seed, and replayed fills are deduplicated.
+    let (positions, fills) = fetch_seed(auth, &jwt).await.context("seeding positions and fills")?;
+    info!("[PDX-ACCT] Synced {} position(s), {} fill(s) today", positions.len(), fills.len());
+    tx.send_modify(|a| {
+        a.positions.clear();
+        for p in positions {
+            a.set_position(p);
+        }
+        a.add_fills(fills);
+        a.synced = true;
+        a.last_update_ms = now_ms();
+    });
+
+    // Heartbeat task
+    let write_clone = Arc::clone(&write);
+    let heartbeat = tokio::spawn(async move {
+        let mut ticker = interval(Duration::from_secs(HEARTBEAT_SECS));
+        let mut hb_id: u64 = 100;
+        ticker.tick().await; // skip immediate first tick
+        loop {
+            ticker.tick().await;
+            let msg = json!({ "jsonrpc": "2.0", "method": "heartbeat", "params": {}, "id": hb_id });
+            if let Err(e) = write_clone.lock().await.send(Message::Text(msg.to_string())).await {
+                error!("[PDX-ACCT] Heartbeat send failed: {e}");
+                break;
+            }
+            hb_id += 1;
+        }
+    });
+
+    // Message loop
+    let mut result = Ok(());
+    while let Some(msg) = read.next().await {
+        match msg? {
+            Message::Text(text) => {
+                if let Err(e) = handle_text(&text, tx) {
+                    result = Err(e);
+                    break;
+                }
+            }
+            Message::Close(_) => { info!("[PDX-ACCT] Server sent close frame"); break; }
+            _ => {}
+        }
+    }
+
+    heartbeat.abort();
+    result
+}
+
+/// Open positions and every fill since UTC midnight, over REST.
+async fn fetch_seed(auth: &PdxAuth, jwt: &str) -> Result<(Vec<AccountPosition>, Vec<AccountFill>)> {
+    let body = auth.get(jwt, "/positions").await?;
+    let positions = body["results"].as_array()
+        .map(|a| a.iter().filter_map(parse_position).collect())
+        .unwrap_or_default();
+
+    let mut fills = Vec::new();
+    let since = day_start_ms();
+    let mut path = format!("/fills?start_at={since}");
+    loop {
+        let page = auth.get(jwt, &path).await?;
+        if let Some(a) = page["results"].as_array() {
+            fills.extend(a.iter().filter_map(parse_fill));
+        }
+        match page["next"].as_str() {
+            Some(cursor) if !cursor.is_empty() => path = format!("/fills?start_at={since}&cursor={cursor}"),
+            _ => break,
+        }
+    }
+    Ok((positions, fills))
+}
+
+/// Errors only for a rejected auth, which would otherwise leave the
+/// connection up with no data.
+fn handle_text(text: &str, tx: &watch::Sender<AccountState>) -> Result<()> {
+    let frame: Value = match serde_json::from_str(text) {
+        Ok(v)  => v,
+        Err(e) => { warn!("[PDX-ACCT] Parse error: {e} | {text:.200}"); return Ok(()); }
+    };
+
+    if let Some(err) = frame.get("error") {
+        if frame["id"] == 0 {
+            bail!("auth rejected: {err}");
+        }
+        warn!("[PDX-ACCT] RPC error: {err}");
+        return Ok(());
+    }
+    if frame["method"] != "subscription" {
+        debug!("[PDX-ACCT] RPC ack id={}", frame["id"]);
+        return Ok(());
+    }
+
+    let params = &frame["params"];
+    let data = &params["data"];
+    match params["channel"].as_str().unwrap_or_default() {
+        c if c.starts_with("fills.") => {
+            if let Some(f) = parse_fill(data) {
+                info!("[PDX-ACCT] Fill {:?} {} {} @ {} (fee {})", f.side, f.size, f.market, f.price, f.fee);
+                tx.send_modify(|a| {
+                    a.add_fills([f]);
+                    a.last_update_ms = now_ms();
+                });
+            }
+        }
+        "positions" => {
+            if let Some(p) = parse_position(data) {
+                tx.send_modify(|a| {
+                    a.set_position(p);
+                    a.last_update_ms = now_ms();
+                });
+            }
+        }
+        "account" => tx.send_modify(|a| {
+            a.account_value = num(&data["account_value"]);
+            a.free_collateral = num(&data["free_collateral"]);
+            a.last_update_ms = now_ms();
+        }),
+        other => debug!("[PDX-ACCT] Unhandled channel: {other}"),
+    }
+    Ok(())
+}
+
+/// Paradex sends numbers as strings.
+fn num(v: &Value) -> Option<f64> {
+    v.as_str().and_then(|s| s.parse().ok())
+}
+
+/// `{market, side: "BUY" | "SELL", size, price, fee, realized_pnl, created_at, order_id, …}`
+fn parse_fill(v: &Value) -> Option<AccountFill> {
+    Some(AccountFill {
+        ts_ms:      v["created_at"].as_u64()?,
+        market:     v["market"].as_str()?.to_string(),
+        side:       if v["side"] == "BUY" { Side::Buy } else { Side::Sell },
+        price:      num(&v["price"])?,
+        size:       num(&v["size"])?,
+        fee:        num(&v["fee"]).unwrap_or(0.0),
+        closed_pnl: num(&v["realized_pnl"]).unwrap_or(0.0),
+        order_id:   v["order_id"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+/// `{market, side: "LONG" | "SHORT", size, average_entry_price, unrealized_pnl, status}`.
+/// A closed position comes back as flat so it is removed.
+fn parse_position(v: &Value) -> Option<AccountPosition> {
+    let size = num(&v["size"])?.abs();
+    let qty = match (v["status"].as_str(), v["side"].as_str()) {
+        (Some("CLOSED"), _) => 0.0,
+        (_, Some("SHORT"))  => -size,
+        _                   => size,
+    };
+    Some(AccountPosition {
+        market:         v["market"].as_str()?.to_string(),
+        qty,
+        entry_px:       num(&v["average_entry_price"]).unwrap_or(0.0),
+        unrealized_pnl: num(&v["unrealized_pnl"]).unwrap_or(0.0),
+    })
+}
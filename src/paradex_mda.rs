@@ -1,35 +1,66 @@
 // src/paradex_mda.rs — Paradex WebSocket connection manager (JSON-RPC 2.0)
 
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use futures_util::{SinkExt, StreamExt};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use serde::Deserialize;
+use serde_json::value::RawValue;
 use serde_json::{json, Value};
-use tokio::sync::{watch, Mutex};
-use tokio::time::{interval, sleep};
-use tokio_tungstenite::{connect_async, tungstenite::Message};
-use tracing::{debug, error, info, warn};
-
-use crate::types::{Exchange, Level, OrderBook, PdxBookData, PdxLevel};
+use tokio::sync::{broadcast, watch, Mutex};
+use tokio::time::interval;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, debug_span, error, info, trace_span, warn, Instrument};
+
+use crate::clock::{self, ClockOffset};
+use crate::net;
+use crate::ofi::{self, OFI_LEVELS};
+use crate::quarantine::Quarantine;
+use crate::supervisor::{self, FeedHandle, FeedSettings, FeedStatus};
+use crate::types::{modify_book, now_ms, Bbo, Exchange, Level, OrderBook, PdxBbo, PdxBookData, PdxLevel, Side, Trade};
 
 const PDX_WS_URL: &str = "wss://ws.api.prod.paradex.trade/v1";
-const RECONNECT_DELAY_SECS: u64 = 3;
+const PDX_REST_URL: &str = "https://api.prod.paradex.trade/v1";
 const HEARTBEAT_SECS: u64 = 20;
-const MAX_BOOK_DEPTH: usize = 20;
+
+/// Most levels per side `[book] pdx_depth` may publish. The deltas channel
+/// keeps the whole book, but a REST snapshot only has `REST_SNAPSHOT_DEPTH`,
+/// and levels below those appear only once a delta touches them.
+pub const MAX_BOOK_DEPTH: usize = 15;
+/// Levels per side asked of the REST snapshot, well past `MAX_BOOK_DEPTH` so
+/// the published levels stay whole as the touch is traded through.
+const REST_SNAPSHOT_DEPTH: usize = 100;
+
+/// Id and send time of the heartbeat awaiting its ack.
+type PingSent = Arc<std::sync::Mutex<Option<(u64, Instant)>>>;
 
 // ─── JSON-RPC helpers ─────────────────────────────────────────────────────────
 
-/// Build a JSON-RPC 2.0 subscribe message for the order book channel.
-/// Channel format: order_book.{market}.snapshot@15@100ms
-fn subscribe_msg(market: &str, id: u64) -> Value {
+/// Build a JSON-RPC 2.0 subscribe message for `channel`, e.g. the order book
+/// (`order_book.{market}.deltas`) or `markets_summary.{market}`.
+fn subscribe_msg(channel: &str, id: u64) -> Value {
     json!({
         "jsonrpc": "2.0",
         "method": "subscribe",
         "params": {
-            "channel": format!("order_book.{}.snapshot@15@100ms", market)
+            "channel": channel
+        },
+        "id": id
+    })
+}
+
+/// Build a JSON-RPC 2.0 unsubscribe message for `channel`.
+fn unsubscribe_msg(channel: &str, id: u64) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "unsubscribe",
+        "params": {
+            "channel": channel
         },
         "id": id
     })
@@ -48,74 +79,167 @@ fn heartbeat_msg(id: u64) -> Value {
 // ─── Inbound envelope ────────────────────────────────────────────────────────
 
 /// Generic JSON-RPC 2.0 inbound frame — covers result, error, and subscription push.
+/// Borrows from the frame and leaves the payloads unparsed, so a book push is
+/// deserialised once, straight into `PdxBookData`.
 #[derive(Deserialize, Debug)]
-struct RpcFrame {
-    #[serde(default)]
-    pub method: Option<String>,
-    #[serde(default)]
-    pub params: Option<Value>,
-    #[serde(default)]
-    pub result: Option<Value>,
-    #[serde(default)]
-    pub error: Option<Value>,
+struct RpcFrame<'a> {
+    #[serde(default, borrow)]
+    pub method: Option<Cow<'a, str>>,
+    #[serde(default, borrow)]
+    pub params: Option<RpcParams<'a>>,
+    #[serde(default, borrow)]
+    pub result: Option<&'a RawValue>,
+    #[serde(default, borrow)]
+    pub error: Option<&'a RawValue>,
     #[serde(default)]
     pub id: Option<u64>,
 }
 
+/// `params` of a subscription push.
+#[derive(Deserialize, Debug)]
+struct RpcParams<'a> {
+    #[serde(default, borrow)]
+    pub channel: Cow<'a, str>,
+    #[serde(default, borrow)]
+    pub data: Option<&'a RawValue>,
+}
+
 // ─── In-memory book state (for delta maintenance) ────────────────────────────
 
+/// What became of a snapshot handed to `LocalBook::apply_snapshot`.
+#[derive(Debug, PartialEq)]
+pub enum Snapshot {
+    Applied,
+    Stale, // older than the book already holds
+}
+
+/// What became of a delta handed to `LocalBook::on_delta`.
+#[derive(Debug, PartialEq)]
+enum Delta {
+    Applied(f64),   // with its order flow
+    BeforeSnapshot, // nothing to apply it to yet
+    Stale,          // covered by the snapshot already
+    Gap,            // messages were missed; the book needs a new snapshot
+}
+
 /// Maintains a local copy of the book so delta updates can be applied.
 #[derive(Default)]
-struct LocalBook {
-    /// price (as ordered string key) → size string
-    bids: BTreeMap<String, String>,
-    asks: BTreeMap<String, String>,
+pub struct LocalBook {
+    /// price → level, ordered numerically (as strings "9.5" would sort after "10")
+    bids: BTreeMap<Decimal, Level>,
+    asks: BTreeMap<Decimal, Level>,
+    /// Deltas only apply on top of a snapshot; false until one arrives and
+    /// again after a sequence gap.
+    synced: bool,
+    /// Sequence number of the last message applied, when Paradex sends one.
+    seq_no: Option<u64>,
+    /// Set on a sequence gap; the connection resubscribes and clears it.
+    resync: bool,
+    /// Levels per side published to the book channel.
+    depth: usize,
 }
 
 impl LocalBook {
-    /// Apply a Paradex snapshot (update_type == "s"): replace everything.
-    fn apply_snapshot(&mut self, data: &PdxBookData) {
+    /// Apply a Paradex snapshot (update_type == "s"): replace everything,
+    /// unless it is older than the last message applied, as a WS snapshot
+    /// queued behind a newer REST one would be.
+    pub fn apply_snapshot(&mut self, data: &PdxBookData) -> Snapshot {
+        if matches!((self.seq_no, data.seq_no), (Some(last), Some(seq)) if seq < last) {
+            return Snapshot::Stale;
+        }
         self.bids.clear();
         self.asks.clear();
         for lvl in &data.inserts {
             self.apply_insert(lvl);
         }
+        self.synced = true;
+        self.seq_no = data.seq_no;
+        Snapshot::Applied
     }
 
-    /// Apply a Paradex delta (update_type == "d").
-    fn apply_delta(&mut self, data: &PdxBookData) {
-        for lvl in &data.deletes {
-            self.remove(lvl);
+    /// Whether a delta is already covered by the snapshot it would follow,
+    /// as with deltas queued on the socket while a REST snapshot was fetched.
+    fn already_applied(&self, data: &PdxBookData) -> bool {
+        matches!((self.seq_no, data.seq_no), (Some(last), Some(seq)) if seq <= last)
+    }
+
+    /// Whether a delta follows the last message applied: `prev_seq_no` must
+    /// match when sent, else `seq_no` must be the next number. Messages
+    /// without sequence numbers can't be checked and are trusted.
+    fn in_sequence(&self, data: &PdxBookData) -> bool {
+        match (self.seq_no, data.prev_seq_no, data.seq_no) {
+            (Some(last), Some(prev), _)   => prev == last,
+            (Some(last), None, Some(seq)) => seq == last + 1,
+            _                             => true,
         }
-        for lvl in &data.updates {
-            self.apply_insert(lvl);
+    }
+
+    /// Checks a delta against the book and applies it if it follows on. A
+    /// sequence gap unsyncs the book and asks the connection to resubscribe.
+    fn on_delta(&mut self, data: &PdxBookData) -> Delta {
+        if !self.synced {
+            return Delta::BeforeSnapshot;
         }
-        for lvl in &data.inserts {
-            self.apply_insert(lvl);
+        if self.already_applied(data) {
+            return Delta::Stale;
+        }
+        if !self.in_sequence(data) {
+            self.synced = false;
+            self.resync = true;
+            return Delta::Gap;
+        }
+        let flow = self.apply_delta(data);
+        self.seq_no = data.seq_no.or(self.seq_no);
+        Delta::Applied(flow)
+    }
+
+    /// Apply a Paradex delta (update_type == "d"). Returns its order flow: the
+    /// size each event adds (+) or removes (−) at prices within the top
+    /// `OFI_LEVELS` before the delta, bids positive and asks negative.
+    pub fn apply_delta(&mut self, data: &PdxBookData) -> f64 {
+        let (bids, asks) = self.to_levels(OFI_LEVELS);
+        let (bid_edge, ask_edge) = (ofi::edge(&bids), ofi::edge(&asks));
+        let near = |lvl: &PdxLevel| {
+            let px = lvl.price.to_f64().unwrap_or(0.0);
+            if lvl.side == "BUY" { bid_edge.is_some_and(|e| px >= e) } else { ask_edge.is_some_and(|e| px <= e) }
+        };
+        let signed = |lvl: &PdxLevel, change: f64| if lvl.side == "BUY" { change } else { -change };
+
+        let mut flow = 0.0;
+        for lvl in &data.deletes {
+            let old = self.remove(lvl);
+            if near(lvl) {
+                flow += signed(lvl, -old);
+            }
+        }
+        for lvl in data.updates.iter().chain(&data.inserts) {
+            let old = self.apply_insert(lvl);
+            if near(lvl) {
+                flow += signed(lvl, lvl.size.to_f64().unwrap_or(0.0) - old);
+            }
         }
+        flow
     }
 
-    fn apply_insert(&mut self, lvl: &PdxLevel) {
+    /// Sets the level; returns the size it replaced (0.0 if new).
+    fn apply_insert(&mut self, lvl: &PdxLevel) -> f64 {
         let map = if lvl.side == "BUY" { &mut self.bids } else { &mut self.asks };
-        map.insert(lvl.price.clone(), lvl.size.clone());
+        map.insert(lvl.price, Level::from_pdx(lvl)).map_or(0.0, |old| old.size_f64())
     }
 
-    fn remove(&mut self, lvl: &PdxLevel) {
+    /// Removes the level; returns its size (0.0 if absent).
+    fn remove(&mut self, lvl: &PdxLevel) -> f64 {
         let map = if lvl.side == "BUY" { &mut self.bids } else { &mut self.asks };
-        map.remove(&lvl.price);
+        map.remove(&lvl.price).map_or(0.0, |old| old.size_f64())
     }
 
     /// Materialise the top N bids (descending price) and asks (ascending price).
-    fn to_levels(&self, depth: usize) -> (Vec<Level>, Vec<Level>) {
+    pub fn to_levels(&self, depth: usize) -> (Vec<Level>, Vec<Level>) {
         // Bids: highest price first
-        let bids = self.bids.iter().rev().take(depth)
-            .map(|(px, sz)| Level { price: px.clone(), size: sz.clone(), count: 0 })
-            .collect();
+        let bids = self.bids.values().rev().take(depth) .cloned() .collect();
 
         // Asks: lowest price first
-        let asks = self.asks.iter().take(depth)
-            .map(|(px, sz)| Level { price: px.clone(), size: sz.clone(), count: 0 })
-            .collect();
+        let asks = self.asks.values().take(depth) .cloned() .collect();
 
         (bids, asks)
     }
@@ -123,55 +247,93 @@ impl LocalBook {
 
 // ─── Public entry point ───────────────────────────────────────────────────────
 
-/// Spawns a background task that maintains a live Paradex L2 book.
-/// `market` should be the Paradex market symbol e.g. "BTC-USD-PERP".
-pub fn spawn_pdx_feed(market: String, book_tx: watch::Sender<OrderBook>) {
-    tokio::spawn(async move {
-        loop {
-            info!("[PDX] Connecting…");
-            match run_connection(&market, &book_tx).await {
-                Ok(_)  => warn!("[PDX] Connection closed cleanly — reconnecting"),
-                Err(e) => error!("[PDX] Connection error: {e:#} — reconnecting"),
-            }
-            book_tx.send_modify(|b| b.connected = false);
-            sleep(Duration::from_secs(RECONNECT_DELAY_SECS)).await;
-        }
-    });
+/// Spawns a background task that maintains a live Paradex L2 book and
+/// publishes the market's trades to `trades_tx`.
+/// `market` should be the Paradex market symbol e.g. "BTC-USD-PERP". `clock_rx`
+/// is the venue clock offset used to correct message timestamps (see `clock`),
+/// and `reconnect` sets when failed reconnects slow down (see `breaker`). The
+/// connection is run by a supervisor, which also marks the feed stale after
+/// `stale_ms`; its handle closes the connection on exit.
+pub fn spawn_pdx_feed(
+    market: String,
+    book_tx: watch::Sender<Arc<OrderBook>>,
+    trades_tx: broadcast::Sender<Trade>,
+    clock_rx: watch::Receiver<ClockOffset>,
+    quarantine: Quarantine,
+    settings: FeedSettings,
+) -> FeedHandle {
+    supervisor::spawn_supervisor("PDX", book_tx.clone(), settings.reconnect, settings.stale_ms, move |stop_rx| {
+        let (market, book_tx, trades_tx, clock_rx, quarantine) =
+            (market.clone(), book_tx.clone(), trades_tx.clone(), clock_rx.clone(), quarantine.clone());
+        async move { run_connection(&market, &book_tx, &trades_tx, &clock_rx, &quarantine, &settings, stop_rx).await }
+    })
 }
 
-async fn run_connection(market: &str, book_tx: &watch::Sender<OrderBook>) -> Result<()> {
-    let (ws_stream, _) = connect_async(PDX_WS_URL)
+async fn run_connection(
+    market: &str,
+    book_tx: &watch::Sender<Arc<OrderBook>>,
+    trades_tx: &broadcast::Sender<Trade>,
+    clock_rx: &watch::Receiver<ClockOffset>,
+    quarantine: &Quarantine,
+    settings: &FeedSettings,
+    mut stop_rx: watch::Receiver<bool>,
+) -> Result<()> {
+    let ws_stream = net::connect_ws(PDX_WS_URL, &settings.socket)
+        .instrument(debug_span!("connect", exchange = "PDX", url = PDX_WS_URL))
         .await
         .context("WebSocket connect failed")?;
 
     info!("[PDX] Connected");
-    book_tx.send_modify(|b| {
-        b.connected = true;
-        b.coin = market.to_string();
-    });
+    modify_book(book_tx, |b| b.coin = market.to_string());
 
     let (write, mut read) = ws_stream.split();
     let write = Arc::new(Mutex::new(write));
 
-    // Subscribe to snapshot feed
-    {
-        let msg = serde_json::to_string(&subscribe_msg(market, 1))?;
-        write.lock().await.send(Message::Text(msg)).await?;
-        info!("[PDX] Subscribed to order_book.{market}.snapshot@15@100ms");
+    // Subscribe to the book deltas (a snapshot, then deltas), the market summary
+    // (mark / index prices), trades and the BBO when enabled; the book channel
+    // stays first, for resyncs
+    let mut channels = vec![
+        format!("order_book.{market}.deltas"),
+        format!("markets_summary.{market}"),
+        format!("trades.{market}"),
+    ];
+    if settings.bbo {
+        channels.push(format!("bbo.{market}"));
     }
+    async {
+        for (i, channel) in channels.iter().enumerate() {
+            let msg = serde_json::to_string(&subscribe_msg(channel, i as u64 + 1))?;
+            write.lock().await.send(Message::Text(msg)).await?;
+        }
+        anyhow::Ok(())
+    }
+    .instrument(debug_span!("subscribe", exchange = "PDX", market))
+    .await?;
+    info!("[PDX] Subscribed to {}", channels.join(" + "));
+    modify_book(book_tx, |b| {
+        b.status = FeedStatus::Subscribed;
+        b.connected_ms = Some(now_ms());
+    });
 
-    // Heartbeat task
+    // Heartbeat task; the send time of the heartbeat in flight gives the round trip
+    let ping_sent: PingSent = Arc::default();
     let write_clone = Arc::clone(&write);
+    let ping_clone = Arc::clone(&ping_sent);
+    let mut hb_stop_rx = stop_rx.clone();
     let heartbeat = tokio::spawn(async move {
         let mut ticker = interval(Duration::from_secs(HEARTBEAT_SECS));
         let mut hb_id: u64 = 100;
         ticker.tick().await; // skip immediate first tick
         loop {
-            ticker.tick().await;
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = supervisor::stopped(&mut hb_stop_rx) => break,
+            }
             let msg = match serde_json::to_string(&heartbeat_msg(hb_id)) {
                 Ok(s) => s,
                 Err(e) => { error!("[PDX] Failed to serialise heartbeat: {e}"); break; }
             };
+            *ping_clone.lock().unwrap() = Some((hb_id, Instant::now()));
             if let Err(e) = write_clone.lock().await.send(Message::Text(msg)).await {
                 error!("[PDX] Heartbeat send failed: {e}");
                 break;
@@ -181,36 +343,95 @@ async fn run_connection(market: &str, book_tx: &watch::Sender<OrderBook>) -> Res
         }
     });
 
-    // Local book state — lives for the duration of this connection
-    let mut local_book = LocalBook::default();
+    // Local book state — lives for the duration of this connection. It starts
+    // from a REST snapshot rather than waiting for the first "s" push; deltas
+    // queue on the socket meanwhile, and those the snapshot covers are dropped.
+    let mut local_book = LocalBook { depth: settings.depth, ..LocalBook::default() };
+    let http = reqwest::Client::new();
+    match fetch_snapshot(&http, market).await {
+        Ok(snap) => { load_snapshot(&mut local_book, &snap, book_tx); }
+        Err(e)   => warn!("[PDX] REST snapshot failed: {e:#} — waiting for the WS snapshot"),
+    }
 
-    // Message loop
-    while let Some(msg) = read.next().await {
+    // Message loop. After a sequence gap the book is reloaded over REST, or
+    // failing that the book channel is resubscribed, which starts it again
+    // from a snapshot. On stop, a close frame is sent and the loop runs on
+    // until the server's reply.
+    let mut resync_id: u64 = 1_000;
+    let mut closing = false;
+    loop {
+        let msg = tokio::select! {
+            msg = read.next() => msg,
+            _ = supervisor::stopped(&mut stop_rx), if !closing => {
+                info!("[PDX] Shutting down — sending close frame");
+                write.lock().await.send(Message::Close(None)).await?;
+                closing = true;
+                continue;
+            }
+        };
+        let Some(msg) = msg else { break };
         match msg? {
-            Message::Text(text) => handle_text(&text, &mut local_book, book_tx),
-            Message::Close(_)   => { info!("[PDX] Server sent close frame"); break; }
+            Message::Text(text)          => handle_text(&text, &mut local_book, book_tx, trades_tx, clock_rx, quarantine, &ping_sent),
+            Message::Close(_) if closing => { info!("[PDX] Closed"); break; }
+            Message::Close(_)            => { info!("[PDX] Server sent close frame"); break; }
             _ => {}
         }
+        if std::mem::take(&mut local_book.resync) && !closing {
+            match fetch_snapshot(&http, market).await {
+                Ok(snap) if load_snapshot(&mut local_book, &snap, book_tx) => continue,
+                Ok(snap) => warn!("[PDX] REST snapshot (seq {:?}) is behind the book — resubscribing", snap.seq_no),
+                Err(e)   => warn!("[PDX] REST snapshot failed: {e:#} — resubscribing"),
+            }
+            let mut write = write.lock().await;
+            write.send(Message::Text(serde_json::to_string(&unsubscribe_msg(&channels[0], resync_id))?)).await?;
+            write.send(Message::Text(serde_json::to_string(&subscribe_msg(&channels[0], resync_id + 1))?)).await?;
+            resync_id += 2;
+            info!("[PDX] Resubscribed to {}", channels[0]);
+        }
     }
 
     heartbeat.abort();
     Ok(())
 }
 
-fn handle_text(text: &str, local_book: &mut LocalBook, book_tx: &watch::Sender<OrderBook>) {
+fn handle_text(
+    text: &str,
+    local_book: &mut LocalBook,
+    book_tx: &watch::Sender<Arc<OrderBook>>,
+    trades_tx: &broadcast::Sender<Trade>,
+    clock_rx: &watch::Receiver<ClockOffset>,
+    quarantine: &Quarantine,
+    ping_sent: &PingSent,
+) {
+    let _span = trace_span!("handle_message", exchange = "PDX", bytes = text.len()).entered();
+    let recv_ms = now_ms();
     let frame: RpcFrame = match serde_json::from_str(text) {
         Ok(f)  => f,
-        Err(e) => { warn!("[PDX] Parse error: {e} | {text:.200}"); return; }
+        Err(e) => {
+            warn!("[PDX] Parse error: {e} | {text:.200}");
+            modify_book(book_tx, |state| state.errors.parse += 1);
+            quarantine.record(Exchange::Paradex, e.to_string(), text);
+            return;
+        }
     };
 
     // JSON-RPC error
     if let Some(err) = &frame.error {
         warn!("[PDX] RPC error: {err}");
+        modify_book(book_tx, |state| state.errors.rpc += 1);
         return;
     }
 
     // Subscribe/heartbeat result ack
     if frame.result.is_some() {
+        let mut ping = ping_sent.lock().unwrap();
+        if let (Some((id, sent)), Some(ack)) = (*ping, frame.id)
+            && id == ack
+        {
+            *ping = None;
+            let rtt_ms = sent.elapsed().as_secs_f64() * 1000.0;
+            modify_book(book_tx, |state| state.rtt_ms = Some(rtt_ms));
+        }
         debug!("[PDX] RPC ack id={:?}", frame.id);
         return;
     }
@@ -219,33 +440,305 @@ fn handle_text(text: &str, local_book: &mut LocalBook, book_tx: &watch::Sender<O
     if frame.method.as_deref() == Some("subscription") {
         let params = match &frame.params {
             Some(p) => p,
-            None    => { warn!("[PDX] subscription push with no params"); return; }
+            None    => {
+                warn!("[PDX] subscription push with no params");
+                modify_book(book_tx, |state| state.errors.parse += 1);
+                quarantine.record(Exchange::Paradex, "subscription push with no params", text);
+                return;
+            }
         };
 
-        let data_val = match params.get("data") {
+        let data_raw = match params.data {
             Some(d) => d,
-            None    => { warn!("[PDX] subscription push with no data field"); return; }
+            None    => {
+                warn!("[PDX] subscription push with no data field");
+                modify_book(book_tx, |state| state.errors.parse += 1);
+                quarantine.record(Exchange::Paradex, "subscription push with no data field", text);
+                return;
+            }
         };
 
-        let data: PdxBookData = match serde_json::from_value(data_val.clone()) {
+        let channel = &*params.channel;
+
+        // Trade: {created_at, price, size, side, trade_type: "FILL" | "LIQUIDATION" | …}
+        if channel.starts_with("trades.") {
+            let data_val: Value = serde_json::from_str(data_raw.get()).unwrap_or_default();
+            match parse_trade(&data_val) {
+                Some(trade) => { let _ = trades_tx.send(trade); } // no receivers is fine
+                None        => {
+                    warn!("[PDX] Unparseable trade | {text:.200}");
+                    modify_book(book_tx, |state| state.errors.parse += 1);
+                    quarantine.record(Exchange::Paradex, "unparseable trade", text);
+                }
+            }
+            return;
+        }
+
+        // Best bid / offer: {market, bid, bid_size, ask, ask_size, last_updated_at}
+        if channel.starts_with("bbo.") {
+            let bbo: PdxBbo = match serde_json::from_str(data_raw.get()) {
+                Ok(b)  => b,
+                Err(e) => {
+                    warn!("[PDX] Unparseable bbo: {e} | {text:.200}");
+                    modify_book(book_tx, |state| state.errors.parse += 1);
+                    quarantine.record(Exchange::Paradex, format!("unparseable bbo: {e}"), text);
+                    return;
+                }
+            };
+            let level = |px, sz| Some(Level::new(px?, sz?, 0));
+            modify_book(book_tx, |state| state.bbo = Some(Bbo {
+                bid:   level(bbo.bid, bbo.bid_size),
+                ask:   level(bbo.ask, bbo.ask_size),
+                ts_ms: bbo.last_updated_at,
+            }));
+            return;
+        }
+
+        // Market summary: {symbol, mark_price, underlying_price, …}, numbers as strings
+        if channel.starts_with("markets_summary") {
+            let data_val: Value = serde_json::from_str(data_raw.get()).unwrap_or_default();
+            let px = |v: &Value| v.as_str().and_then(|s| s.parse().ok());
+            modify_book(book_tx, |state| {
+                state.mark_px = px(&data_val["mark_price"]).or(state.mark_px);
+                state.index_px = px(&data_val["underlying_price"]).or(state.index_px);
+            });
+            return;
+        }
+
+        let data: PdxBookData = match serde_json::from_str(data_raw.get()) {
             Ok(d)  => d,
-            Err(e) => { warn!("[PDX] Failed to parse PdxBookData: {e}"); return; }
+            Err(e) => {
+                warn!("[PDX] Failed to parse PdxBookData: {e}");
+                modify_book(book_tx, |state| state.errors.parse += 1);
+                quarantine.record(Exchange::Paradex, format!("Failed to parse PdxBookData: {e}"), text);
+                return;
+            }
         };
 
-        // Apply to local book
-        match data.update_type.as_str() {
-            "s" => local_book.apply_snapshot(&data),
-            "d" => local_book.apply_delta(&data),
-            ut  => { debug!("[PDX] Unknown update_type: {ut}"); return; }
-        }
+        // Apply to local book, measuring the order flow near the touch: from the
+        // events themselves for a delta, by diffing against the old book for a snapshot
+        let flow = match data.update_type.as_str() {
+            "s" => {
+                let (old_bids, old_asks) = local_book.to_levels(OFI_LEVELS);
+                if local_book.apply_snapshot(&data) == Snapshot::Stale {
+                    debug!("[PDX] Snapshot older than the book (seq {:?} < {:?}) — dropped", data.seq_no, local_book.seq_no);
+                    modify_book(book_tx, |state| state.errors.dropped += 1);
+                    return;
+                }
+                let (new_bids, new_asks) = local_book.to_levels(OFI_LEVELS);
+                ofi::book_flow(&old_bids, &old_asks, &new_bids, &new_asks)
+            }
+            "d" => match local_book.on_delta(&data) {
+                Delta::Applied(flow) => flow,
+                Delta::BeforeSnapshot => {
+                    debug!("[PDX] Delta before snapshot — dropped");
+                    modify_book(book_tx, |state| state.errors.dropped += 1);
+                    return;
+                }
+                Delta::Stale => {
+                    debug!("[PDX] Delta older than snapshot — dropped");
+                    modify_book(book_tx, |state| state.errors.dropped += 1);
+                    return;
+                }
+                Delta::Gap => {
+                    warn!(
+                        "[PDX] Sequence gap: last applied {:?}, got seq {:?} (prev {:?}) — resyncing",
+                        local_book.seq_no, data.seq_no, data.prev_seq_no,
+                    );
+                    modify_book(book_tx, |state| {
+                        state.seq_gaps += 1;
+                        state.errors.dropped += 1;
+                    });
+                    return;
+                }
+            },
+            ut  => {
+                debug!("[PDX] Unknown update_type: {ut}");
+                modify_book(book_tx, |state| state.errors.dropped += 1);
+                return;
+            }
+        };
 
         // Materialise and push to watch channel
-        let (bids, asks) = local_book.to_levels(MAX_BOOK_DEPTH);
-        book_tx.send_modify(|state| {
+        let (bids, asks) = local_book.to_levels(local_book.depth);
+        modify_book(book_tx, |state| {
             state.bids = bids;
             state.asks = asks;
+            state.ofi_cum += flow;
             state.last_update_ms = data.last_updated_at / 1_000; // Paradex uses microseconds
+            state.status = FeedStatus::Live;
+            state.reconnect_failures = 0;
             state.message_count += 1;
+            state.clock_offset_ms = *clock_rx.borrow();
+            state.latency.record(clock::to_local_ms(state.last_update_ms, state.clock_offset_ms), recv_ms);
         });
+    } else {
+        debug!("[PDX] Unhandled frame: {text:.200}");
+        modify_book(book_tx, |state| state.errors.dropped += 1);
+    }
+}
+
+// ─── REST snapshot ────────────────────────────────────────────────────────────
+
+/// `GET /orderbook/{market}` → `{seq_no, last_updated_at, bids: [[px, sz]], asks: …}`,
+/// as a snapshot message so it goes through the same `LocalBook` path.
+async fn fetch_snapshot(http: &reqwest::Client, market: &str) -> Result<PdxBookData> {
+    let resp: Value = http
+        .get(format!("{PDX_REST_URL}/orderbook/{market}"))
+        .query(&[("depth", REST_SNAPSHOT_DEPTH)])
+        .send()
+        .instrument(debug_span!("rest_snapshot", exchange = "PDX", market))
+        .await
+        .context("request failed")?
+        .error_for_status()?
+        .json()
+        .await
+        .context("unparseable response")?;
+
+    let levels = |key: &str, side: &str| -> Result<Vec<PdxLevel>> {
+        resp[key].as_array().with_context(|| format!("no {key}"))?.iter()
+            .map(|l| Ok(PdxLevel {
+                price: l[0].as_str().and_then(|s| s.parse().ok()).context("bad price")?,
+                side:  side.to_string(),
+                size:  l[1].as_str().and_then(|s| s.parse().ok()).context("bad size")?,
+            }))
+            .collect()
+    };
+    let mut inserts = levels("bids", "BUY")?;
+    inserts.extend(levels("asks", "SELL")?);
+    Ok(PdxBookData {
+        inserts,
+        deletes:         Vec::new(),
+        updates:         Vec::new(),
+        last_updated_at: resp["last_updated_at"].as_u64().unwrap_or(0),
+        market:          market.to_string(),
+        update_type:     "s".to_string(),
+        seq_no:          resp["seq_no"].as_u64(),
+        prev_seq_no:     None,
+    })
+}
+
+/// Replaces the local book with a REST snapshot and publishes its levels;
+/// false if the snapshot is older than the book. It isn't a push, so the
+/// update time, message count and order flow are left to the next one.
+fn load_snapshot(local_book: &mut LocalBook, snap: &PdxBookData, book_tx: &watch::Sender<Arc<OrderBook>>) -> bool {
+    if local_book.apply_snapshot(snap) == Snapshot::Stale {
+        return false;
+    }
+    let (bids, asks) = local_book.to_levels(local_book.depth);
+    modify_book(book_tx, |state| {
+        state.bids = bids;
+        state.asks = asks;
+    });
+    info!("[PDX] Book loaded from REST snapshot (seq {:?})", snap.seq_no);
+    true
+}
+
+fn parse_trade(v: &Value) -> Option<Trade> {
+    let num = |v: &Value| v.as_str().and_then(|s| s.parse().ok());
+    Some(Trade {
+        ts_ms:       v["created_at"].as_u64()?,
+        exchange:    Exchange::Paradex,
+        side:        if v["side"] == "BUY" { Side::Buy } else { Side::Sell },
+        price:       num(&v["price"])?,
+        size:        num(&v["size"])?,
+        liquidation: v["trade_type"] == "LIQUIDATION",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(side: &str, price: &str, size: &str) -> PdxLevel {
+        PdxLevel { price: price.parse().unwrap(), side: side.to_string(), size: size.parse().unwrap() }
+    }
+
+    fn message(update_type: &str, seq: u64, prev: Option<u64>, inserts: Vec<PdxLevel>, updates: Vec<PdxLevel>) -> PdxBookData {
+        PdxBookData {
+            inserts, deletes: Vec::new(), updates,
+            last_updated_at: 0, market: "ETH-USD-PERP".to_string(), update_type: update_type.to_string(),
+            seq_no: Some(seq), prev_seq_no: prev,
+        }
+    }
+
+    /// Synced at seq 10 with 1 bid at 100 and 1 ask at 101.
+    fn synced_book() -> LocalBook {
+        let mut book = LocalBook { depth: 15, ..Default::default() };
+        book.apply_snapshot(&message("s", 10, None, vec![level("BUY", "100", "1"), level("SELL", "101", "1")], Vec::new()));
+        book
+    }
+
+    fn best_bid_size(book: &LocalBook) -> f64 {
+        book.to_levels(1).0[0].size_f64()
+    }
+
+    #[test]
+    fn in_order_delta_is_applied() {
+        let mut book = synced_book();
+        let delta = message("d", 11, Some(10), Vec::new(), vec![level("BUY", "100", "3")]);
+        assert_eq!(book.on_delta(&delta), Delta::Applied(2.0));
+        assert_eq!(book.seq_no, Some(11));
+        assert_eq!(best_bid_size(&book), 3.0);
+
+        // Without prev_seq_no, the next seq_no follows on too
+        let delta = message("d", 12, None, Vec::new(), vec![level("BUY", "100", "4")]);
+        assert_eq!(book.on_delta(&delta), Delta::Applied(1.0));
+        assert_eq!(book.seq_no, Some(12));
+    }
+
+    #[test]
+    fn stale_delta_is_dropped() {
+        let mut book = synced_book();
+        let delta = message("d", 10, Some(9), Vec::new(), vec![level("BUY", "100", "5")]);
+        assert_eq!(book.on_delta(&delta), Delta::Stale);
+        assert_eq!(book.seq_no, Some(10));
+        assert_eq!(best_bid_size(&book), 1.0);
+        assert!(book.synced && !book.resync);
+    }
+
+    #[test]
+    fn older_snapshot_is_dropped() {
+        let mut book = synced_book();
+        let delta = message("d", 11, Some(10), Vec::new(), vec![level("BUY", "100", "3")]);
+        assert_eq!(book.on_delta(&delta), Delta::Applied(2.0));
+
+        // A WS snapshot queued behind a newer REST one doesn't roll the book back
+        let older = message("s", 9, None, vec![level("BUY", "99", "7")], Vec::new());
+        assert_eq!(book.apply_snapshot(&older), Snapshot::Stale);
+        assert_eq!(book.seq_no, Some(11));
+        assert_eq!(best_bid_size(&book), 3.0);
+
+        // The same or a newer one replaces it
+        let newer = message("s", 11, None, vec![level("BUY", "99", "7")], Vec::new());
+        assert_eq!(book.apply_snapshot(&newer), Snapshot::Applied);
+        assert_eq!(best_bid_size(&book), 7.0);
+    }
+
+    #[test]
+    fn gap_is_resynced_by_a_newer_snapshot() {
+        let mut book = synced_book();
+        let delta = message("d", 13, Some(12), Vec::new(), vec![level("BUY", "100", "5")]);
+        assert_eq!(book.on_delta(&delta), Delta::Gap);
+
+        // The channel restarts from a snapshot, and deltas follow on from it
+        let snap = message("s", 20, None, vec![level("BUY", "100", "2")], Vec::new());
+        assert_eq!(book.apply_snapshot(&snap), Snapshot::Applied);
+        assert!(book.synced);
+        let delta = message("d", 21, Some(20), Vec::new(), vec![level("BUY", "100", "4")]);
+        assert_eq!(book.on_delta(&delta), Delta::Applied(2.0));
+    }
+
+    #[test]
+    fn gap_unsyncs_and_sets_resync() {
+        let mut book = synced_book();
+        let delta = message("d", 13, Some(12), Vec::new(), vec![level("BUY", "100", "5")]);
+        assert_eq!(book.on_delta(&delta), Delta::Gap);
+        assert!(!book.synced && book.resync);
+        assert_eq!(best_bid_size(&book), 1.0);
+
+        // Nothing more applies until the next snapshot
+        let delta = message("d", 14, Some(13), Vec::new(), vec![level("BUY", "100", "6")]);
+        assert_eq!(book.on_delta(&delta), Delta::BeforeSnapshot);
     }
 }
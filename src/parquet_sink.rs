@@ -0,0 +1,208 @@
+// src/parquet_sink.rs — Rolling Parquet files of top-N merged levels per tick
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use arrow::array::{
+    ArrayRef, Float64Builder, StringBuilder, TimestampMillisecondBuilder, UInt16Builder,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use tokio::sync::watch;
+use tracing::{error, info};
+
+use crate::config::ParquetConfig;
+use crate::merger::{MergedBook, MergedLevel};
+use crate::types::now_ms;
+
+// ─── Handle ───────────────────────────────────────────────────────────────────
+
+/// Owns the writer thread. Call `shutdown()` on exit so the open file gets its
+/// footer written — a Parquet file without one is unreadable.
+pub struct ParquetSink {
+    stop_tx: mpsc::Sender<()>,
+    thread:  JoinHandle<()>,
+}
+
+impl ParquetSink {
+    pub fn shutdown(self) {
+        let _ = self.stop_tx.send(());
+        if self.thread.join().is_err() {
+            error!("[PARQUET] Writer thread panicked");
+        }
+    }
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Creates the output directory (so a bad path fails at startup) and samples
+/// the merged book every `interval_ms` on a dedicated thread.
+pub fn spawn_parquet_sink(
+    cfg: &ParquetConfig,
+    coin: &str,
+    merged_rx: watch::Receiver<MergedBook>,
+) -> Result<ParquetSink> {
+    fs::create_dir_all(&cfg.dir)
+        .with_context(|| format!("Cannot create Parquet output directory '{}'", cfg.dir.display()))?;
+
+    let (stop_tx, stop_rx) = mpsc::channel();
+    let mut writer = RollingWriter::new(cfg, coin);
+    let interval = Duration::from_millis(cfg.interval_ms);
+    let batch_ticks = cfg.batch_ticks;
+
+    let thread = thread::Builder::new()
+        .name("parquet-sink".into())
+        .spawn(move || {
+            let mut batch = BatchBuilder::default();
+            loop {
+                let stopping = match stop_rx.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => false,
+                    _ => true, // stop requested or handle dropped
+                };
+
+                if !stopping {
+                    batch.push_tick(now_ms(), &merged_rx.borrow());
+                }
+                if (stopping || batch.ticks >= batch_ticks)
+                    && let Err(e) = writer.write(batch.finish())
+                {
+                    error!("[PARQUET] Write failed: {e:#}");
+                }
+                if stopping {
+                    writer.close();
+                    break;
+                }
+            }
+        })
+        .context("Failed to spawn Parquet writer thread")?;
+
+    info!("[PARQUET] Writing to {}", cfg.dir.display());
+    Ok(ParquetSink { stop_tx, thread })
+}
+
+// ─── Schema + batch building ──────────────────────────────────────────────────
+
+/// Long format: one row per merged level per tick.
+fn schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("ts", DataType::Timestamp(TimeUnit::Millisecond, None), false),
+        Field::new("side", DataType::Utf8, false),     // "bid" | "ask"
+        Field::new("level", DataType::UInt16, false),  // 0 = best
+        Field::new("price", DataType::Float64, false),
+        Field::new("size", DataType::Float64, false),
+        Field::new("exchange", DataType::Utf8, false), // "HL" | "PDX"
+    ]))
+}
+
+#[derive(Default)]
+struct BatchBuilder {
+    ts:       TimestampMillisecondBuilder,
+    side:     StringBuilder,
+    level:    UInt16Builder,
+    price:    Float64Builder,
+    size:     Float64Builder,
+    exchange: StringBuilder,
+    ticks:    usize,
+}
+
+impl BatchBuilder {
+    fn push_tick(&mut self, ts_ms: u64, merged: &MergedBook) {
+        self.push_side(ts_ms, "bid", &merged.bids);
+        self.push_side(ts_ms, "ask", &merged.asks);
+        self.ticks += 1;
+    }
+
+    fn push_side(&mut self, ts_ms: u64, side: &str, levels: &[MergedLevel]) {
+        for (i, lvl) in levels.iter().enumerate() {
+            self.ts.append_value(ts_ms as i64);
+            self.side.append_value(side);
+            self.level.append_value(i as u16);
+            self.price.append_value(lvl.price);
+            self.size.append_value(lvl.size);
+            self.exchange.append_value(lvl.exchange.short());
+        }
+    }
+
+    /// Drain the builders into a record batch (`None` if nothing was sampled).
+    fn finish(&mut self) -> Option<RecordBatch> {
+        if self.ticks == 0 {
+            return None;
+        }
+        self.ticks = 0;
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(self.ts.finish()),
+            Arc::new(self.side.finish()),
+            Arc::new(self.level.finish()),
+            Arc::new(self.price.finish()),
+            Arc::new(self.size.finish()),
+            Arc::new(self.exchange.finish()),
+        ];
+        RecordBatch::try_new(schema(), columns).ok()
+    }
+}
+
+// ─── Rolling file writer ──────────────────────────────────────────────────────
+
+/// Writes batches to `{dir}/merged_{coin}_{YYYYmmdd_HHMMSS}.parquet`, starting a
+/// new file every `rotate_secs`.
+struct RollingWriter {
+    dir:     PathBuf,
+    coin:    String,
+    rotate:  Duration,
+    current: Option<(ArrowWriter<File>, Instant)>,
+}
+
+impl RollingWriter {
+    fn new(cfg: &ParquetConfig, coin: &str) -> Self {
+        Self {
+            dir:     cfg.dir.clone(),
+            coin:    coin.to_string(),
+            rotate:  Duration::from_secs(cfg.rotate_secs),
+            current: None,
+        }
+    }
+
+    fn write(&mut self, batch: Option<RecordBatch>) -> Result<()> {
+        let Some(batch) = batch else { return Ok(()) };
+
+        if self.current.as_ref().is_some_and(|(_, opened)| opened.elapsed() >= self.rotate) {
+            self.close();
+        }
+        if self.current.is_none() {
+            self.current = Some((open_writer(&self.dir, &self.coin)?, Instant::now()));
+        }
+        if let Some((writer, _)) = &mut self.current {
+            writer.write(&batch).context("Failed to write record batch")?;
+            writer.flush().context("Failed to flush row group")?;
+        }
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        if let Some((writer, _)) = self.current.take()
+            && let Err(e) = writer.close()
+        {
+            error!("[PARQUET] Failed to finalise file: {e}");
+        }
+    }
+}
+
+fn open_writer(dir: &Path, coin: &str) -> Result<ArrowWriter<File>> {
+    let stamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+    let path = dir.join(format!("merged_{coin}_{stamp}.parquet"));
+    let file = File::create(&path)
+        .with_context(|| format!("Cannot create '{}'", path.display()))?;
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    info!("[PARQUET] Opened {}", path.display());
+    ArrowWriter::try_new(file, schema(), Some(props)).context("Failed to create Parquet writer")
+}
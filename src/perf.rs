@@ -0,0 +1,36 @@
+// src/perf.rs — Timing counters for the hot paths (merge, draw)
+//
+// Cheap enough to run all the time; the diagnostics panel shows them so a
+// slow merge or frame can be spotted without a profiler. `benches/` measures
+// the same paths in isolation.
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Weight of the newest sample in the running average.
+const AVG_WEIGHT: f64 = 0.05;
+
+/// Last, running average and worst duration of one stage, in µs.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Timing {
+    pub last_us: u64,
+    pub avg_us:  f64,
+    pub max_us:  u64,
+    pub count:   u64,
+}
+
+impl Timing {
+    pub fn record(&mut self, elapsed: Duration) {
+        let us = elapsed.as_micros() as u64;
+        self.avg_us = if self.count == 0 { us as f64 } else { self.avg_us + (us as f64 - self.avg_us) * AVG_WEIGHT };
+        self.last_us = us;
+        self.max_us = self.max_us.max(us);
+        self.count += 1;
+    }
+}
+
+/// "850µs", "12.3ms".
+pub fn fmt_us(us: f64) -> String {
+    if us < 1_000.0 { format!("{us:.0}µs") } else { format!("{:.1}ms", us / 1_000.0) }
+}
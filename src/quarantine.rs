@@ -0,0 +1,87 @@
+// src/quarantine.rs — Raw payloads of messages that failed to parse
+//
+// Every parse failure in a feed keeps the offending frame here, up to the last
+// `max_payloads`, so a schema change can be reported with the exact payloads
+// without re-running at trace level. `w` in the TUI writes them to a JSONL file.
+
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use tokio::sync::watch;
+
+use crate::config::QuarantineConfig;
+use crate::types::{now_ms, Exchange};
+
+/// Longest payload kept, in bytes; longer ones are cut (on a char boundary).
+const MAX_PAYLOAD_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone)]
+struct Quarantined {
+    ts_ms:    u64,
+    exchange: Exchange,
+    reason:   String,
+    payload:  String,
+}
+
+/// Cheap to clone; every clone adds to the same buffer.
+#[derive(Clone)]
+pub struct Quarantine {
+    cfg: QuarantineConfig,
+    tx:  Arc<watch::Sender<VecDeque<Quarantined>>>, // newest first
+}
+
+impl Quarantine {
+    pub fn new(cfg: &QuarantineConfig) -> Self {
+        Self { cfg: cfg.clone(), tx: Arc::new(watch::channel(VecDeque::new()).0) }
+    }
+
+    pub fn record(&self, exchange: Exchange, reason: impl Into<String>, payload: &str) {
+        let mut end = payload.len().min(MAX_PAYLOAD_BYTES);
+        while !payload.is_char_boundary(end) {
+            end -= 1;
+        }
+        let entry = Quarantined { ts_ms: now_ms(), exchange, reason: reason.into(), payload: payload[..end].to_string() };
+        self.tx.send_modify(|q| {
+            q.push_front(entry);
+            q.truncate(self.cfg.max_payloads);
+        });
+    }
+
+    /// Payloads currently held.
+    pub fn len(&self) -> usize {
+        self.tx.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tx.borrow().is_empty()
+    }
+
+    /// Writes the held payloads, oldest first, to
+    /// `{dir}/quarantine_{YYYYmmdd_HHMMSS}.jsonl`; returns the path and count.
+    pub fn dump(&self) -> Result<(PathBuf, usize)> {
+        let entries: Vec<Quarantined> = self.tx.borrow().iter().rev().cloned().collect();
+        fs::create_dir_all(&self.cfg.dir)
+            .with_context(|| format!("Cannot create '{}'", self.cfg.dir.display()))?;
+        let stamp = Utc::now().format("%Y%m%d_%H%M%S");
+        let path = self.cfg.dir.join(format!("quarantine_{stamp}.jsonl"));
+        let file = File::create(&path).with_context(|| format!("Cannot create '{}'", path.display()))?;
+        let mut out = BufWriter::new(file);
+        for e in &entries {
+            let ts = DateTime::from_timestamp_millis(e.ts_ms as i64).map(|t| t.to_rfc3339()).unwrap_or_default();
+            let line = serde_json::json!({
+                "ts":       ts,
+                "exchange": e.exchange,
+                "reason":   e.reason,
+                "payload":  e.payload,
+            });
+            writeln!(out, "{line}")?;
+        }
+        out.flush()?;
+        Ok((path, entries.len()))
+    }
+}
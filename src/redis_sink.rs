@@ -0,0 +1,79 @@
+// src/redis_sink.rs — Publish merged BBO + signals to Redis channels and keys
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::config::RedisConfig;
+use crate::merger::MergedBook;
+use crate::types::now_ms;
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Connects to Redis (so a bad URL fails at startup) and, on every merge,
+/// in a single pipeline:
+///
+/// - `PUBLISH {prefix}:bbo:{coin}` / `{prefix}:signals:{coin}`
+/// - `SET` the same two keys plus `{prefix}:snapshot:{coin}` (full merged book)
+pub async fn spawn_redis_sink(
+    cfg: &RedisConfig,
+    coin: &str,
+    mut merged_rx: watch::Receiver<MergedBook>,
+) -> Result<()> {
+    let client = redis::Client::open(cfg.url.as_str())
+        .with_context(|| format!("Invalid Redis URL '{}'", cfg.url))?;
+    let mut conn = client
+        .get_multiplexed_async_connection()
+        .await
+        .with_context(|| format!("Failed to connect to Redis at {}", cfg.url))?;
+    info!("[REDIS] Connected to {}", cfg.url);
+
+    let bbo_key      = format!("{}:bbo:{coin}", cfg.key_prefix);
+    let signals_key  = format!("{}:signals:{coin}", cfg.key_prefix);
+    let snapshot_key = format!("{}:snapshot:{coin}", cfg.key_prefix);
+
+    tokio::spawn(async move {
+        while merged_rx.changed().await.is_ok() {
+            let merged = merged_rx.borrow_and_update().clone();
+            let bbo      = bbo_json(&merged).to_string();
+            let signals  = serde_json::to_string(&merged.signals).unwrap_or_default();
+            let snapshot = serde_json::to_string(&merged).unwrap_or_default();
+
+            let result: redis::RedisResult<()> = redis::pipe()
+                .publish(&bbo_key, &bbo).ignore()
+                .publish(&signals_key, &signals).ignore()
+                .set(&bbo_key, &bbo).ignore()
+                .set(&signals_key, &signals).ignore()
+                .set(&snapshot_key, &snapshot).ignore()
+                .query_async(&mut conn)
+                .await;
+            if let Err(e) = result {
+                warn!("[REDIS] Publish failed: {e}");
+            }
+        }
+    });
+    Ok(())
+}
+
+// ─── Payloads ─────────────────────────────────────────────────────────────────
+
+/// Merged best bid/offer with source venues.
+fn bbo_json(merged: &MergedBook) -> Value {
+    let bid = merged.bids.first();
+    let ask = merged.asks.first();
+    let mid = match (bid, ask) {
+        (Some(b), Some(a)) => Some((b.price + a.price) / 2.0),
+        _ => None,
+    };
+    json!({
+        "ts_ms":        now_ms(),
+        "bid_price":    bid.map(|l| l.price),
+        "bid_size":     bid.map(|l| l.size),
+        "bid_exchange": bid.map(|l| &l.exchange),
+        "ask_price":    ask.map(|l| l.price),
+        "ask_size":     ask.map(|l| l.size),
+        "ask_exchange": ask.map(|l| &l.exchange),
+        "mid":          mid,
+    })
+}
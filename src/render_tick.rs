@@ -0,0 +1,54 @@
+// src/render_tick.rs — Render interval that follows the book update rate
+//
+// The TUI waits one tick for input between frames. Roughly one frame per book
+// update is enough, so the tick is the time between updates, kept between
+// `[display] tick_ms` and `max_tick_ms`. A burst tightens it at once; after one
+// it relaxes over a few seconds, so a brief lull doesn't make the screen lag
+// when the burst resumes. Keys still take effect at once at any tick.
+
+use std::time::{Duration, Instant};
+
+/// Time constant for the rate to decay after a burst.
+const DECAY_SECS: f64 = 3.0;
+/// Shortest window the rate is measured over, so one update straight after a
+/// keypress doesn't count as a burst.
+const MIN_SAMPLE_SECS: f64 = 0.25;
+
+pub struct AdaptiveTick {
+    floor:    Duration,
+    ceiling:  Duration,
+    rate:     f64,        // book updates per second, smoothed
+    versions: (u64, u64), // (HL, PDX) book versions at the last sample
+    sampled:  Instant,
+}
+
+impl AdaptiveTick {
+    pub fn new(tick_ms: u64, max_tick_ms: u64) -> Self {
+        Self {
+            floor:    Duration::from_millis(tick_ms),
+            ceiling:  Duration::from_millis(max_tick_ms),
+            rate:     0.0,
+            versions: (0, 0),
+            sampled:  Instant::now(),
+        }
+    }
+
+    /// Takes the current book versions and returns how long to wait for
+    /// input before the next frame.
+    pub fn next(&mut self, versions: (u64, u64), now: Instant) -> Duration {
+        let dt = now.duration_since(self.sampled).as_secs_f64();
+        if dt >= MIN_SAMPLE_SECS {
+            let updates = versions.0.saturating_sub(self.versions.0) + versions.1.saturating_sub(self.versions.1);
+            let rate = updates as f64 / dt;
+            self.rate = if rate >= self.rate {
+                rate
+            } else {
+                self.rate + (rate - self.rate) * (1.0 - (-dt / DECAY_SECS).exp())
+            };
+            self.versions = versions;
+            self.sampled = now;
+        }
+        // Clamped as f64: a rate decayed towards 0 gives an interval too long for Duration
+        Duration::from_secs_f64((1.0 / self.rate).clamp(self.floor.as_secs_f64(), self.ceiling.as_secs_f64()))
+    }
+}
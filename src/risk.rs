@@ -0,0 +1,189 @@
+// src/risk.rs — Pre-trade risk limits shared by the simulator and live execution
+
+use std::sync::Arc;
+
+use tokio::sync::watch;
+use tracing::warn;
+
+use crate::account::AccountState;
+use crate::config::RiskConfig;
+use crate::types::{now_ms, Exchange, Side};
+
+/// Tolerance for float comparisons against limits.
+const EPS: f64 = 1e-9;
+
+/// The most recent order a limit stopped, for the TUI banner.
+#[derive(Debug, Clone)]
+pub struct RiskBlock {
+    pub source: &'static str, // "SIM", "HL-EXEC", "PDX-EXEC"
+    pub reason: String,
+    pub ts_ms:  u64,
+}
+
+/// One venue's part of an order, as seen by the limits.
+pub struct OrderCheck<'a> {
+    pub venue:      &'a Exchange,
+    pub delta:      f64, // signed change to this venue's position
+    pub order_size: f64, // size of the whole order
+    pub position:   f64, // signed position on this venue before the order
+    pub working:    f64, // signed size of this venue's orders not yet filled, pending or resting
+    pub pnl_today:  f64, // realized + unrealized − fees, USD
+}
+
+impl OrderCheck<'_> {
+    /// The position once every working order has filled, as the limits see it.
+    fn exposure(&self) -> f64 {
+        self.position + self.working
+    }
+
+    /// True when the order only shrinks the exposure (never flips it).
+    fn reduces(&self) -> bool {
+        let before = self.exposure();
+        let after = before + self.delta;
+        after.abs() <= before.abs() + EPS && after * before >= 0.0
+    }
+}
+
+/// Cheap to clone; every clone reports blocks to the same banner channel.
+#[derive(Clone)]
+pub struct RiskGate {
+    limits:     RiskConfig,
+    blocked_tx: Arc<watch::Sender<Option<RiskBlock>>>,
+}
+
+impl RiskGate {
+    pub fn new(limits: RiskConfig) -> (Self, watch::Receiver<Option<RiskBlock>>) {
+        let (tx, rx) = watch::channel(None);
+        (Self { limits, blocked_tx: Arc::new(tx) }, rx)
+    }
+
+    /// `Err(reason)` if a limit forbids the order; the block is logged and
+    /// published for the banner.
+    pub fn check(&self, source: &'static str, o: &OrderCheck) -> Result<(), String> {
+        match self.violation(o) {
+            Some(reason) => self.block(source, reason),
+            None         => Ok(()),
+        }
+    }
+
+    /// `check` for a live order of `size` on `side`, against the venue
+    /// account's position in `market` plus the `working` orders, and its PnL
+    /// since UTC midnight. Refused while the account channel is down or hasn't
+    /// synced: its position and PnL are unknown then, not flat and zero.
+    pub fn check_live(
+        &self,
+        source: &'static str,
+        account: &AccountState,
+        working: f64,
+        market: &str,
+        side: Side,
+        size: f64,
+    ) -> Result<(), String> {
+        if !account.connected {
+            return self.block(source, format!("{} account channel is down", account.exchange.short()));
+        }
+        if !account.synced {
+            return self.block(source, format!("{} account channel hasn't synced yet", account.exchange.short()));
+        }
+        self.check(source, &OrderCheck {
+            venue:      &account.exchange,
+            delta:      side.sign() * size,
+            order_size: size,
+            position:   account.position(market).map_or(0.0, |p| p.qty),
+            working,
+            pnl_today:  account.pnl_today(),
+        })
+    }
+
+    /// Logs `reason`, publishes it for the banner and returns it as the error.
+    fn block(&self, source: &'static str, reason: String) -> Result<(), String> {
+        warn!("[RISK] {source} order blocked: {reason}");
+        self.blocked_tx.send_replace(Some(RiskBlock { source, reason: reason.clone(), ts_ms: now_ms() }));
+        Err(reason)
+    }
+
+    fn violation(&self, o: &OrderCheck) -> Option<String> {
+        if o.reduces() {
+            return None;
+        }
+        let l = &self.limits;
+        if let Some(max) = l.max_order_size
+            && o.order_size > max + EPS
+        {
+            return Some(format!("order size {} exceeds max_order_size {max}", o.order_size));
+        }
+        let after = o.exposure() + o.delta;
+        if let Some(max) = l.max_position
+            && after.abs() > max + EPS
+        {
+            return Some(format!("{} position would be {after:+} (max_position {max})", o.venue.short()));
+        }
+        if let Some(max) = l.max_daily_loss
+            && o.pnl_today <= -max
+        {
+            return Some(format!("PnL today ${:.2} is past max_daily_loss ${max}", o.pnl_today));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::{day_start_ms, AccountFill, AccountPosition};
+
+    fn gate(max_position: Option<f64>, max_daily_loss: Option<f64>) -> RiskGate {
+        RiskGate::new(RiskConfig { max_position, max_order_size: None, max_daily_loss }).0
+    }
+
+    fn synced_account(qty: f64) -> AccountState {
+        let mut a = AccountState::new(Exchange::Hyperliquid);
+        a.connected = true;
+        a.synced = true;
+        a.set_position(AccountPosition { market: "BTC".into(), qty, entry_px: 100.0, unrealized_pnl: 0.0 });
+        a
+    }
+
+    #[test]
+    fn unknown_account_refuses_live_orders() {
+        let risk = gate(None, None);
+        let mut a = synced_account(0.0);
+        a.synced = false;
+        assert!(risk.check_live("HL-EXEC", &a, 0.0, "BTC", Side::Buy, 1.0).is_err());
+        a.connected = false;
+        assert!(risk.check_live("HL-EXEC", &a, 0.0, "BTC", Side::Buy, 1.0).is_err());
+        // Not even a reducing order: the position it would reduce is unknown
+        assert!(risk.check_live("HL-EXEC", &a, 0.0, "BTC", Side::Sell, 1.0).is_err());
+        a.connected = true;
+        a.synced = true;
+        assert!(risk.check_live("HL-EXEC", &a, 0.0, "BTC", Side::Buy, 1.0).is_ok());
+    }
+
+    #[test]
+    fn working_orders_count_towards_max_position() {
+        let risk = gate(Some(2.0), None);
+        let a = synced_account(1.0);
+        assert!(risk.check_live("HL-EXEC", &a, 0.0, "BTC", Side::Buy, 1.0).is_ok());
+        // One more resting buy: 1 held + 1 working + 1 new = 3 > 2
+        assert!(risk.check_live("HL-EXEC", &a, 1.0, "BTC", Side::Buy, 1.0).is_err());
+        // Against working sells the same buy only reduces the exposure
+        assert!(risk.check_live("HL-EXEC", &a, -2.0, "BTC", Side::Buy, 1.0).is_ok());
+    }
+
+    #[test]
+    fn daily_loss_counts_fills_beyond_the_kept_list() {
+        let risk = gate(None, Some(100.0));
+        let mut a = synced_account(0.0);
+        let ts = day_start_ms() + 1;
+        let fill = |i: u64| AccountFill {
+            ts_ms: ts, market: "BTC".into(), side: Side::Sell, price: 100.0, size: 1.0,
+            fee: 0.0, closed_pnl: -2.0, order_id: i.to_string(),
+        };
+        a.add_fills((0..60).map(fill));
+        // Replayed fills don't count twice
+        a.add_fills((0..60).map(fill));
+        assert!(a.fills.len() < 60);
+        assert!((a.pnl_today() + 120.0).abs() < 1e-9);
+        assert!(risk.check_live("HL-EXEC", &a, 0.0, "BTC", Side::Buy, 1.0).is_err());
+    }
+}
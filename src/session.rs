@@ -0,0 +1,246 @@
+// src/session.rs — Totals for the whole run, printed when the TUI exits
+//
+// Message and reconnect counts carry on across pair switches, which start the
+// books' own counters over. Everything else is kept per pair, by the symbols
+// the merged book was built from: the cross spread's minimum and the imbalance
+// extreme are taken from every merged book, its mean is sampled on a fixed
+// clock so it's time-weighted, and arb windows are counted off the arb log
+// against the pair shown when they opened.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::time::interval;
+
+use crate::arb::ArbWindow;
+use crate::merger::MergedBook;
+use crate::types::{now_ms, OrderBook};
+
+/// How often the counts, the arb log and the mean spread are sampled.
+const SAMPLE_MS: u64 = 250;
+
+#[derive(Debug, Clone, Default)]
+pub struct SessionSummary {
+    pub started_ms: u64,
+    pub messages:   (u64, u64),       // (HL, PDX) book messages
+    pub reconnects: (u64, u64),       // (HL, PDX) connections lost
+    pub pairs:      Vec<PairSession>, // in the order first shown
+    pub current:    Option<usize>,    // index of the one shown now
+}
+
+/// The figures of one pair, over every stretch it was shown.
+#[derive(Debug, Clone, Default)]
+pub struct PairSession {
+    pub coins:         (String, String),   // (HL, PDX) symbols
+    pub shown_ms:      u64,                // total of the stretches before the current one
+    pub shown_since:   Option<u64>,        // start of the current stretch
+    pub min_cross:     Option<(f64, f64)>, // tightest cross spread seen, (quote units, %)
+    pub cross_sum:     (f64, f64),         // over `cross_samples`, (quote units, %)
+    pub cross_samples: u64,                // taken with both feeds connected
+    pub arb_windows:   u64,                // closed windows
+    pub arb_ms:        u64,                // their total duration
+    pub arb_open_ms:   Option<u64>,        // start of a window still open
+    pub max_imbalance: Option<f64>,        // liquidity imbalance furthest from 0, signed
+}
+
+impl SessionSummary {
+    /// The summary as printed on exit: the run's totals, then each pair's figures.
+    pub fn report(&self, quote_unit: &str) -> String {
+        let now = now_ms();
+        let mut lines = vec![
+            format!("Session summary ({})", fmt_duration(now.saturating_sub(self.started_ms))),
+            format!("  Messages       HL {:<10} PDX {}", self.messages.0, self.messages.1),
+            format!("  Reconnects     HL {:<10} PDX {}", self.reconnects.0, self.reconnects.1),
+        ];
+        for pair in &self.pairs {
+            lines.extend(pair.report(now, quote_unit));
+        }
+        lines.join("\n")
+    }
+
+    fn current(&mut self) -> Option<&mut PairSession> {
+        self.pairs.get_mut(self.current?)
+    }
+
+    /// Makes the pair with `coins` the one being shown, from `now_ms`.
+    fn show(&mut self, coins: &(String, String), now_ms: u64) -> usize {
+        if let Some(cur) = self.current() {
+            cur.shown_ms += now_ms.saturating_sub(cur.shown_since.take().unwrap_or(now_ms));
+        }
+        let i = match self.pairs.iter().position(|p| p.coins == *coins) {
+            Some(i) => i,
+            None => {
+                self.pairs.push(PairSession { coins: coins.clone(), ..Default::default() });
+                self.pairs.len() - 1
+            }
+        };
+        self.pairs[i].shown_since = Some(now_ms);
+        self.current = Some(i);
+        i
+    }
+}
+
+impl PairSession {
+    pub fn mean_cross(&self) -> Option<(f64, f64)> {
+        let n = self.cross_samples as f64;
+        (self.cross_samples > 0).then(|| (self.cross_sum.0 / n, self.cross_sum.1 / n))
+    }
+
+    /// Arb windows and their total duration, counting one still open up to `now_ms`.
+    pub fn arbs(&self, now_ms: u64) -> (u64, u64) {
+        match self.arb_open_ms {
+            Some(opened) => (self.arb_windows + 1, self.arb_ms + now_ms.saturating_sub(opened)),
+            None         => (self.arb_windows, self.arb_ms),
+        }
+    }
+
+    /// How long it has been shown, up to `now_ms`.
+    pub fn shown(&self, now_ms: u64) -> u64 {
+        self.shown_ms + self.shown_since.map_or(0, |since| now_ms.saturating_sub(since))
+    }
+
+    fn report(&self, now: u64, quote_unit: &str) -> [String; 4] {
+        let (arbs, arb_ms) = self.arbs(now);
+        let cross = |c: Option<(f64, f64)>| c.map_or("–".to_string(), |(px, pct)| format!("{px:.4} {quote_unit} ({pct:.4}%)"));
+        let imbalance = match self.max_imbalance {
+            Some(i) => format!("{i:+.2} ({})", if i >= 0.0 { "bid-heavy" } else { "ask-heavy" }),
+            None    => "–".to_string(),
+        };
+        [
+            format!("  {} / {}, shown {}", self.coins.0, self.coins.1, fmt_duration(self.shown(now))),
+            format!("    Cross spread   min {}, mean {}", cross(self.min_cross), cross(self.mean_cross())),
+            format!("    Arb windows    {arbs}, {} in total", fmt_duration(arb_ms)),
+            format!("    Max imbalance  {imbalance}"),
+        ]
+    }
+}
+
+/// A book counter summed across the resets of a pair switch.
+#[derive(Default)]
+struct Total {
+    last:  u64,
+    total: u64,
+}
+
+impl Total {
+    fn update(&mut self, count: u64) -> u64 {
+        // A count below the last one is a new book counting from 0
+        self.total += count.checked_sub(self.last).unwrap_or(count);
+        self.last = count;
+        self.total
+    }
+}
+
+/// Spawns a task keeping the session totals.
+pub fn spawn_session_summary(
+    hl_rx: watch::Receiver<Arc<OrderBook>>,
+    pdx_rx: watch::Receiver<Arc<OrderBook>>,
+    mut merged_rx: watch::Receiver<MergedBook>,
+    arb_log_rx: watch::Receiver<VecDeque<ArbWindow>>,
+) -> watch::Receiver<SessionSummary> {
+    let mut s = SessionSummary { started_ms: now_ms(), ..Default::default() };
+    let (tx, rx) = watch::channel(s.clone());
+
+    tokio::spawn(async move {
+        let (mut hl_msgs, mut pdx_msgs, mut hl_reconnects, mut pdx_reconnects) =
+            (Total::default(), Total::default(), Total::default(), Total::default());
+        let mut counted_through = 0; // opened_ms of the newest window counted
+        let mut shown: Vec<(u64, usize)> = Vec::new(); // (from, pair) for each stretch, oldest first
+        let mut ticker = interval(Duration::from_millis(SAMPLE_MS));
+
+        loop {
+            tokio::select! {
+                changed = merged_rx.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    let merged = merged_rx.borrow_and_update();
+                    // The books before the first merge have no symbols yet. A
+                    // switch resets one book, then the other: a merge between
+                    // the two, with one symbol of each pair, counts for neither.
+                    if merged.coins.0.is_empty() {
+                        continue;
+                    }
+                    let (hl_coin, pdx_coin) = &merged.coins;
+                    if s.current.is_none_or(|i| s.pairs[i].coins.0 != *hl_coin && s.pairs[i].coins.1 != *pdx_coin) {
+                        let now = now_ms();
+                        shown.push((now, s.show(&merged.coins, now)));
+                    }
+                    let connected = both_connected(&hl_rx, &pdx_rx);
+                    let Some(pair) = s.current().filter(|p| p.coins == merged.coins) else { continue };
+                    let sig = &merged.signals;
+                    if connected
+                        && let (Some(px), Some(pct)) = (sig.cross_spread, sig.cross_spread_pct)
+                        && pair.min_cross.is_none_or(|(min, _)| px < min)
+                    {
+                        pair.min_cross = Some((px, pct));
+                    }
+                    if let Some(i) = sig.liquidity_imbalance
+                        && pair.max_imbalance.is_none_or(|max| i.abs() > max.abs())
+                    {
+                        pair.max_imbalance = Some(i);
+                    }
+                }
+                _ = ticker.tick() => {
+                    {
+                        let (hl, pdx) = (hl_rx.borrow(), pdx_rx.borrow());
+                        s.messages = (hl_msgs.update(hl.message_count), pdx_msgs.update(pdx.message_count));
+                        s.reconnects = (hl_reconnects.update(hl.reconnects), pdx_reconnects.update(pdx.reconnects));
+                    }
+                    let (sig, coins) = {
+                        let merged = merged_rx.borrow();
+                        (merged.signals.clone(), merged.coins.clone())
+                    };
+                    let connected = both_connected(&hl_rx, &pdx_rx);
+                    if let Some(pair) = s.current()
+                        && pair.coins == coins
+                        && connected
+                        && let (Some(px), Some(pct)) = (sig.cross_spread, sig.cross_spread_pct)
+                    {
+                        pair.cross_sum = (pair.cross_sum.0 + px, pair.cross_sum.1 + pct);
+                        pair.cross_samples += 1;
+                    }
+
+                    // Newest first; only the front one can still be open. Each
+                    // window goes to the pair shown when it opened.
+                    let log = arb_log_rx.borrow();
+                    let pair_at = |ms: u64| shown.iter().rev().find(|(from, _)| *from <= ms).or(shown.first()).map(|(_, i)| *i);
+                    let mut newest = counted_through;
+                    for w in log.iter().take_while(|w| w.opened_ms > counted_through) {
+                        if let Some(closed) = w.closed_ms
+                            && let Some(i) = pair_at(w.opened_ms)
+                        {
+                            s.pairs[i].arb_windows += 1;
+                            s.pairs[i].arb_ms += closed.saturating_sub(w.opened_ms);
+                            newest = newest.max(w.opened_ms);
+                        }
+                    }
+                    counted_through = newest;
+                    let open = log.front().filter(|w| w.closed_ms.is_none()).map(|w| w.opened_ms);
+                    drop(log);
+                    for (i, pair) in s.pairs.iter_mut().enumerate() {
+                        pair.arb_open_ms = open.filter(|&ms| pair_at(ms) == Some(i));
+                    }
+                    tx.send_replace(s.clone());
+                }
+            }
+        }
+    });
+    rx
+}
+
+fn both_connected(hl_rx: &watch::Receiver<Arc<OrderBook>>, pdx_rx: &watch::Receiver<Arc<OrderBook>>) -> bool {
+    hl_rx.borrow().status.is_connected() && pdx_rx.borrow().status.is_connected()
+}
+
+/// "1h 02m 03s", "4m 05s" or "6.7s".
+fn fmt_duration(ms: u64) -> String {
+    let secs = ms / 1_000;
+    match secs {
+        0..60     => format!("{:.1}s", ms as f64 / 1_000.0),
+        60..3_600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _         => format!("{}h {:02}m {:02}s", secs / 3_600, secs % 3_600 / 60, secs % 60),
+    }
+}
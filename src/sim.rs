@@ -0,0 +1,375 @@
+// src/sim.rs — Paper trading: simulated market orders filled against the live merged book
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::sync::{mpsc, watch};
+use tracing::{info, warn};
+
+use crate::config::{FeesConfig, SimConfig};
+use crate::merger::{MergedBook, MergedLevel};
+use crate::risk::{OrderCheck, RiskGate};
+use crate::supervisor::FeedStatus;
+use crate::types::{now_ms, Exchange, OrderBook, Side};
+
+/// Fills kept in `SimState::fills` (oldest dropped first).
+const MAX_FILLS: usize = 200;
+
+// ─── Orders ───────────────────────────────────────────────────────────────────
+
+/// What a keypress or alert rule asks the simulator to do. Sizes come from
+/// `[sim] order_size`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimAction {
+    Buy,     // market buy across both venues' displayed asks
+    Sell,    // market sell across both venues' displayed bids
+    Arb,     // buy the ask venue + sell the bid venue, up to the executable arb size
+    Flatten, // close every open position on its own venue
+}
+
+impl SimAction {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "buy"     => Some(SimAction::Buy),
+            "sell"    => Some(SimAction::Sell),
+            "arb"     => Some(SimAction::Arb),
+            "flatten" => Some(SimAction::Flatten),
+            _ => None,
+        }
+    }
+}
+
+// ─── State ────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimFill {
+    pub ts_ms:    u64,
+    pub exchange: Exchange,
+    pub side:     Side,
+    pub price:    f64,
+    pub size:     f64,
+    pub fee:      f64, // taker fee paid, quote currency
+}
+
+/// Net position on one venue. `qty` is signed (+long / −short).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SimPosition {
+    pub qty:          f64,
+    pub avg_entry:    f64,
+    pub realized_pnl: f64, // before fees
+    pub fees_paid:    f64,
+}
+
+impl SimPosition {
+    fn apply(&mut self, fill: &SimFill) {
+        let q = fill.side.sign() * fill.size;
+        self.fees_paid += fill.fee;
+
+        if self.qty == 0.0 || self.qty.signum() == q.signum() {
+            // Opening or adding: size-weighted average entry
+            let total = self.qty.abs() + fill.size;
+            self.avg_entry = (self.avg_entry * self.qty.abs() + fill.price * fill.size) / total;
+            self.qty += q;
+            return;
+        }
+
+        // Reducing, closing or flipping
+        let closed = self.qty.abs().min(fill.size);
+        self.realized_pnl += closed * (fill.price - self.avg_entry) * self.qty.signum();
+        self.qty += q;
+        if self.qty.abs() < 1e-12 {
+            self.qty = 0.0;
+            self.avg_entry = 0.0;
+        } else if self.qty.signum() == q.signum() {
+            self.avg_entry = fill.price; // flipped: the remainder opened at this price
+        }
+    }
+}
+
+/// Everything the simulator has done this session, published after each order.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SimState {
+    pub hl:       SimPosition,
+    pub pdx:      SimPosition,
+    pub fills:    VecDeque<SimFill>, // newest first
+    pub orders:   u64,
+    pub rejected: u64,
+    pub last_msg: Option<String>,    // outcome of the most recent order
+}
+
+impl SimState {
+    pub fn position(&self, ex: &Exchange) -> &SimPosition {
+        match ex {
+            Exchange::Hyperliquid => &self.hl,
+            Exchange::Paradex     => &self.pdx,
+        }
+    }
+
+    fn position_mut(&mut self, ex: &Exchange) -> &mut SimPosition {
+        match ex {
+            Exchange::Hyperliquid => &mut self.hl,
+            Exchange::Paradex     => &mut self.pdx,
+        }
+    }
+
+    /// Realized + unrealized − fees across both venues, each marked at its own
+    /// mid within the merged book (unmarked while a side is missing).
+    pub fn net_pnl(&self, merged: &MergedBook) -> f64 {
+        [Exchange::Hyperliquid, Exchange::Paradex].iter().map(|ex| {
+            let pos = self.position(ex);
+            let bid = merged.bids.iter().find(|l| l.exchange == *ex).map(|l| l.price);
+            let ask = merged.asks.iter().find(|l| l.exchange == *ex).map(|l| l.price);
+            let upnl = match (bid, ask) {
+                (Some(b), Some(a)) => ((b + a) / 2.0 - pos.avg_entry) * pos.qty,
+                _ => 0.0,
+            };
+            pos.realized_pnl + upnl - pos.fees_paid
+        }).sum()
+    }
+
+    fn record(&mut self, fill: SimFill) {
+        self.position_mut(&fill.exchange).apply(&fill);
+        self.fills.push_front(fill);
+        self.fills.truncate(MAX_FILLS);
+    }
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Submit orders through `tx`; read positions and fills from `state_rx`.
+pub struct SimHandle {
+    pub tx:       mpsc::Sender<SimAction>,
+    pub state_rx: watch::Receiver<SimState>,
+}
+
+/// Spawns the simulator task. Orders fill immediately against the merged book
+/// as displayed (top `depth` levels), paying each venue's taker fee, unless a
+/// risk limit blocks them. `hl_rx` / `pdx_rx` say which venues are stale; their
+/// levels may already be gone, so nothing fills against them.
+pub fn spawn_sim(
+    cfg: &SimConfig,
+    fees: FeesConfig,
+    risk: RiskGate,
+    merged_rx: watch::Receiver<MergedBook>,
+    hl_rx: watch::Receiver<Arc<OrderBook>>,
+    pdx_rx: watch::Receiver<Arc<OrderBook>>,
+) -> SimHandle {
+    let (tx, mut rx) = mpsc::channel::<SimAction>(32);
+    let (state_tx, state_rx) = watch::channel(SimState::default());
+    let order_size = cfg.order_size;
+
+    tokio::spawn(async move {
+        info!("[SIM] Paper trading enabled, order size {order_size}");
+        while let Some(action) = rx.recv().await {
+            let merged = merged_rx.borrow().clone();
+            let stale: Vec<Exchange> = [&hl_rx, &pdx_rx].into_iter()
+                .map(|rx| rx.borrow())
+                .filter(|b| b.status == FeedStatus::Stale)
+                .map(|b| b.exchange.clone())
+                .collect();
+            state_tx.send_modify(|state| execute(state, action, order_size, &merged, &stale, &fees, &risk));
+        }
+    });
+
+    SimHandle { tx, state_rx }
+}
+
+// ─── Execution ────────────────────────────────────────────────────────────────
+
+fn execute(
+    state: &mut SimState,
+    action: SimAction,
+    order_size: f64,
+    merged: &MergedBook,
+    stale: &[Exchange],
+    fees: &FeesConfig,
+    risk: &RiskGate,
+) {
+    state.orders += 1;
+    let legs: Vec<(Side, Option<Exchange>, f64)> = match action {
+        SimAction::Buy  => vec![(Side::Buy, None, order_size)],
+        SimAction::Sell => vec![(Side::Sell, None, order_size)],
+        SimAction::Arb  => {
+            let sig = &merged.signals;
+            match (&sig.best_ask_exchange, &sig.best_bid_exchange) {
+                (Some(ask_ex), Some(bid_ex)) if sig.is_arb() => {
+                    let qty = order_size.min(sig.arb_size);
+                    vec![(Side::Buy, Some(ask_ex.clone()), qty), (Side::Sell, Some(bid_ex.clone()), qty)]
+                }
+                _ => return reject(state, "arb: no arbitrage after fees right now"),
+            }
+        }
+        SimAction::Flatten => [Exchange::Hyperliquid, Exchange::Paradex].into_iter()
+            .filter_map(|ex| {
+                let qty = state.position(&ex).qty;
+                let side = if qty > 0.0 { Side::Sell } else { Side::Buy };
+                (qty != 0.0).then_some((side, Some(ex), qty.abs()))
+            })
+            .collect(),
+    };
+    if legs.is_empty() {
+        return reject(state, "flatten: no open positions");
+    }
+
+    // Sweep every leg first so a blocked leg leaves nothing half-filled
+    let pnl_today = state.net_pnl(merged);
+    let mut fills = Vec::new();
+    for (side, venue, qty) in legs {
+        let levels = match side { Side::Buy => &merged.asks, Side::Sell => &merged.bids };
+        let leg = sweep(levels, venue.as_ref(), stale, side, qty, fees);
+        let filled: f64 = leg.iter().map(|f| f.size).sum();
+        if filled < qty - 1e-12 {
+            warn!("[SIM] {side:?} {qty} filled only {filled} within displayed depth");
+        }
+        for ex in [Exchange::Hyperliquid, Exchange::Paradex] {
+            let delta: f64 = leg.iter().filter(|f| f.exchange == ex).map(|f| f.side.sign() * f.size).sum();
+            if delta == 0.0 {
+                continue;
+            }
+            let check = OrderCheck { venue: &ex, delta, order_size: qty, position: state.position(&ex).qty, working: 0.0, pnl_today };
+            if let Err(reason) = risk.check("SIM", &check) {
+                return reject(state, &format!("{action:?}: {reason}"));
+            }
+        }
+        fills.extend(leg);
+    }
+
+    let mut summary = Vec::new();
+    for fill in fills {
+        info!("[SIM] {:?} {} {} @ {:.2} (fee {:.4})", fill.side, fill.size, fill.exchange.short(), fill.price, fill.fee);
+        summary.push(format!("{:?} {:.4} {} @ {:.2}", fill.side, fill.size, fill.exchange.short(), fill.price));
+        state.record(fill);
+    }
+    state.last_msg = Some(if summary.is_empty() {
+        state.rejected += 1;
+        format!("{action:?}: nothing to fill at displayed depth")
+    } else {
+        summary.join(", ")
+    });
+}
+
+fn reject(state: &mut SimState, msg: &str) {
+    state.rejected += 1;
+    state.last_msg = Some(msg.to_string());
+    info!("[SIM] Rejected — {msg}");
+}
+
+/// Walks `levels` (best first), optionally restricted to one venue and never
+/// on a `stale` one, until `qty` is filled or the displayed depth runs out.
+fn sweep(
+    levels: &[MergedLevel],
+    venue: Option<&Exchange>,
+    stale: &[Exchange],
+    side: Side,
+    qty: f64,
+    fees: &FeesConfig,
+) -> Vec<SimFill> {
+    let ts_ms = now_ms();
+    let mut left = qty;
+    let mut fills = Vec::new();
+    for lvl in levels.iter().filter(|l| venue.is_none_or(|v| *v == l.exchange) && !stale.contains(&l.exchange)) {
+        if left <= 1e-12 {
+            break;
+        }
+        let size = left.min(lvl.size);
+        left -= size;
+        fills.push(SimFill {
+            ts_ms,
+            exchange: lvl.exchange.clone(),
+            side,
+            price: lvl.price,
+            size,
+            fee: lvl.price * size * fees.for_exchange(&lvl.exchange).taker_rate(),
+        });
+    }
+    fills
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RiskConfig;
+
+    fn fill(side: Side, price: f64, size: f64) -> SimFill {
+        SimFill { ts_ms: 0, exchange: Exchange::Hyperliquid, side, price, size, fee: 0.1 }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn adding_averages_the_entry() {
+        let mut pos = SimPosition::default();
+        pos.apply(&fill(Side::Buy, 100.0, 1.0));
+        pos.apply(&fill(Side::Buy, 103.0, 2.0));
+        assert!(close(pos.qty, 3.0));
+        assert!(close(pos.avg_entry, 102.0));
+        assert_eq!(pos.realized_pnl, 0.0);
+        assert!(close(pos.fees_paid, 0.2));
+    }
+
+    #[test]
+    fn reducing_realizes_pnl_and_keeps_the_entry() {
+        let mut pos = SimPosition::default();
+        pos.apply(&fill(Side::Sell, 100.0, 2.0));
+        pos.apply(&fill(Side::Buy, 97.0, 0.5));
+        assert!(close(pos.qty, -1.5));
+        assert!(close(pos.avg_entry, 100.0));
+        assert!(close(pos.realized_pnl, 1.5)); // short 0.5 covered 3 lower
+    }
+
+    #[test]
+    fn closing_goes_flat() {
+        let mut pos = SimPosition::default();
+        pos.apply(&fill(Side::Buy, 0.1, 3.0));
+        pos.apply(&fill(Side::Sell, 0.2, 1.0));
+        pos.apply(&fill(Side::Sell, 0.2, 2.0));
+        // 0.1 + 0.2 leaves float dust, which closing clears
+        assert_eq!((pos.qty, pos.avg_entry), (0.0, 0.0));
+        assert!(close(pos.realized_pnl, 0.3));
+    }
+
+    #[test]
+    fn flipping_opens_the_rest_at_the_fill_price() {
+        let mut pos = SimPosition::default();
+        pos.apply(&fill(Side::Buy, 100.0, 1.0));
+        pos.apply(&fill(Side::Sell, 110.0, 3.0));
+        assert!(close(pos.qty, -2.0));
+        assert!(close(pos.avg_entry, 110.0));
+        assert!(close(pos.realized_pnl, 10.0)); // only the 1 closed counts
+        pos.apply(&fill(Side::Buy, 105.0, 2.0));
+        assert_eq!(pos.qty, 0.0);
+        assert!(close(pos.realized_pnl, 20.0));
+    }
+
+    #[test]
+    fn stale_venue_is_not_filled_against() {
+        let level = |px: &str| crate::types::Level::new(px.parse().unwrap(), "1".parse().unwrap(), 1);
+        let mut hl = OrderBook::new(Exchange::Hyperliquid, "ETH");
+        hl.asks = vec![level("100")];
+        let mut pdx = OrderBook::new(Exchange::Paradex, "ETH");
+        pdx.asks = vec![level("101")];
+        let fees = FeesConfig::default();
+        let merged = MergedBook::build(&hl, &pdx, 10, &fees);
+        let risk = RiskGate::new(RiskConfig::default()).0;
+
+        let mut state = SimState::default();
+        execute(&mut state, SimAction::Buy, 1.0, &merged, &[], &fees, &risk);
+        assert_eq!(state.fills[0].exchange, Exchange::Hyperliquid);
+
+        // HL's cheaper ask is skipped while its feed is stale
+        let mut state = SimState::default();
+        execute(&mut state, SimAction::Buy, 1.0, &merged, &[Exchange::Hyperliquid], &fees, &risk);
+        assert_eq!(state.fills.len(), 1);
+        assert_eq!(state.fills[0].exchange, Exchange::Paradex);
+        assert_eq!(state.hl.qty, 0.0);
+
+        // Nothing at all when both are
+        let mut state = SimState::default();
+        execute(&mut state, SimAction::Buy, 1.0, &merged, &[Exchange::Hyperliquid, Exchange::Paradex], &fees, &risk);
+        assert!(state.fills.is_empty());
+        assert_eq!(state.rejected, 1);
+    }
+}
@@ -0,0 +1,64 @@
+// src/slack.rs — Slack incoming-webhook alerts with rate limiting
+
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::arb::spawn_sustained_arb_watcher;
+use crate::config::SlackConfig;
+use crate::merger::MergedBook;
+use crate::types::now_ms;
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Spawns a task that posts sustained arb windows to a Slack webhook, at most
+/// one message per `min_interval_secs`. Windows that arrive while rate-limited
+/// are counted and mentioned in the next message instead of being posted.
+pub fn spawn_slack_alerts(cfg: SlackConfig, coin: String, merged_rx: watch::Receiver<MergedBook>) {
+    let mut arbs = spawn_sustained_arb_watcher(merged_rx, cfg.min_duration_ms, cfg.min_size);
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        let min_interval = Duration::from_secs(cfg.min_interval_secs);
+        let mut last_sent: Option<Instant> = None;
+        let mut suppressed: u32 = 0;
+
+        info!("[SLACK] Alerts enabled");
+        while let Some(win) = arbs.recv().await {
+            if last_sent.is_some_and(|t| t.elapsed() < min_interval) {
+                suppressed += 1;
+                continue;
+            }
+
+            let mut text = win.alert_text(&coin, now_ms());
+            if suppressed > 0 {
+                text.push_str(&format!("\n(+{suppressed} more windows since the last alert)"));
+            }
+            match post(&client, &cfg.webhook_url, &text).await {
+                Ok(()) => {
+                    last_sent = Some(Instant::now());
+                    suppressed = 0;
+                }
+                Err(e) => warn!("[SLACK] Failed to post alert: {e:#}"),
+            }
+        }
+    });
+}
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+async fn post(client: &reqwest::Client, webhook_url: &str, text: &str) -> Result<()> {
+    let resp = client
+        .post(webhook_url)
+        .json(&json!({ "text": text }))
+        .send()
+        .await
+        .map_err(|e| e.without_url()) // webhook URLs are secrets
+        .context("Failed to reach Slack webhook")?;
+    if !resp.status().is_success() {
+        bail!("Slack webhook returned {}: {}", resp.status(), resp.text().await.unwrap_or_default());
+    }
+    Ok(())
+}
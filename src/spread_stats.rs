@@ -0,0 +1,161 @@
+// src/spread_stats.rs — Rolling spread statistics per venue and across venues,
+// and the cross-spread history behind the signals panel sparkline
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::watch;
+use tokio::time::interval;
+use tracing::info;
+
+use crate::config::SpreadStatsConfig;
+use crate::merger::MergedBook;
+use crate::types::OrderBook;
+
+/// Spreads are sampled on a fixed clock so the statistics are time-weighted,
+/// not weighted by how often each feed happens to publish.
+const SAMPLE_MS: u64 = 250;
+
+/// Distribution of one spread over the window, in basis points of mid.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SpreadSummary {
+    pub mean:   f64,
+    pub median: f64,
+    pub p95:    f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SpreadStats {
+    pub window_secs:            u64,
+    pub hl:                     Option<SpreadSummary>, // None until the window has a sample
+    pub pdx:                    Option<SpreadSummary>,
+    pub cross:                  Option<SpreadSummary>, // best ask − best bid across venues
+    pub neg_cross_pct:          Option<f64>,           // share of the window with a negative cross spread
+    pub session_neg_cross_secs: f64,
+    pub session_secs:           f64,                   // time sampled with both books live
+}
+
+impl SpreadStats {
+    pub fn session_neg_cross_pct(&self) -> Option<f64> {
+        (self.session_secs > 0.0).then(|| self.session_neg_cross_secs / self.session_secs * 100.0)
+    }
+}
+
+/// One sample: (hl, pdx, cross) spread in bps, each `None` while unknown.
+type Sample = (Option<f64>, Option<f64>, Option<f64>);
+
+/// Spawns a task sampling both venues' spreads and the cross spread every
+/// `SAMPLE_MS`, summarised over the configured rolling window.
+pub fn spawn_spread_stats(
+    cfg: &SpreadStatsConfig,
+    hl_rx: watch::Receiver<Arc<OrderBook>>,
+    pdx_rx: watch::Receiver<Arc<OrderBook>>,
+    merged_rx: watch::Receiver<MergedBook>,
+) -> watch::Receiver<SpreadStats> {
+    let window_secs = cfg.window_secs;
+    let max_samples = (window_secs * 1_000 / SAMPLE_MS) as usize;
+    let (tx, rx) = watch::channel(SpreadStats { window_secs, ..Default::default() });
+    info!("[SPREAD] Rolling window {window_secs}s");
+
+    tokio::spawn(async move {
+        let mut samples: VecDeque<Sample> = VecDeque::with_capacity(max_samples);
+        let (mut session_secs, mut session_neg_cross_secs) = (0.0, 0.0);
+        let mut coins = Default::default();
+        let mut ticker = interval(Duration::from_millis(SAMPLE_MS));
+
+        loop {
+            ticker.tick().await;
+            let sample = {
+                let (hl, pdx, merged) = (hl_rx.borrow(), pdx_rx.borrow(), merged_rx.borrow());
+                // The window and the session totals are of one pair
+                if merged.pair_changed(&mut coins) {
+                    samples.clear();
+                    (session_secs, session_neg_cross_secs) = (0.0, 0.0);
+                }
+                let cross = merged.signals.cross_spread_pct.filter(|_| hl.status.is_connected() && pdx.status.is_connected());
+                (spread_bps(&hl), spread_bps(&pdx), cross.map(|p| p * 100.0))
+            };
+            if let Some(cross) = sample.2 {
+                session_secs += SAMPLE_MS as f64 / 1_000.0;
+                if cross < 0.0 {
+                    session_neg_cross_secs += SAMPLE_MS as f64 / 1_000.0;
+                }
+            }
+            if samples.len() == max_samples {
+                samples.pop_front();
+            }
+            samples.push_back(sample);
+
+            let crosses: Vec<f64> = samples.iter().filter_map(|s| s.2).collect();
+            let stats = SpreadStats {
+                window_secs,
+                hl:    summarise(samples.iter().filter_map(|s| s.0).collect()),
+                pdx:   summarise(samples.iter().filter_map(|s| s.1).collect()),
+                neg_cross_pct: (!crosses.is_empty())
+                    .then(|| crosses.iter().filter(|c| **c < 0.0).count() as f64 / crosses.len() as f64 * 100.0),
+                cross: summarise(crosses),
+                session_neg_cross_secs,
+                session_secs,
+            };
+            if tx.send(stats).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// Spawns a task sampling the cross-exchange spread (bps of mid) every
+/// `SAMPLE_MS`, keeping the last `secs` seconds oldest first. `None` marks
+/// samples where either side of the merged book was empty.
+pub fn spawn_spread_history(secs: u64, merged_rx: watch::Receiver<MergedBook>) -> watch::Receiver<VecDeque<Option<f64>>> {
+    let max_samples = (secs * 1_000 / SAMPLE_MS) as usize;
+    let (tx, rx) = watch::channel(VecDeque::with_capacity(max_samples));
+    tokio::spawn(async move {
+        let mut coins = Default::default();
+        let mut ticker = interval(Duration::from_millis(SAMPLE_MS));
+        loop {
+            ticker.tick().await;
+            let (cross, switched) = {
+                let merged = merged_rx.borrow();
+                (merged.signals.cross_spread_pct.map(|p| p * 100.0), merged.pair_changed(&mut coins))
+            };
+            tx.send_modify(|history| {
+                if switched {
+                    history.clear();
+                }
+                if history.len() == max_samples {
+                    history.pop_front();
+                }
+                history.push_back(cross);
+            });
+            if tx.is_closed() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// Best ask − best bid on one venue, in bps of its mid.
+fn spread_bps(book: &OrderBook) -> Option<f64> {
+    if !book.status.is_connected() {
+        return None;
+    }
+    book.spread_pct().map(|p| p * 100.0)
+}
+
+fn summarise(mut values: Vec<f64>) -> Option<SpreadSummary> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let at = |q: f64| values[((values.len() - 1) as f64 * q).round() as usize];
+    Some(SpreadSummary {
+        mean:   values.iter().sum::<f64>() / values.len() as f64,
+        median: at(0.5),
+        p95:    at(0.95),
+    })
+}
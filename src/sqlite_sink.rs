@@ -0,0 +1,268 @@
+// src/sqlite_sink.rs — Persist per-tick signals and arbitrage windows to SQLite
+
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use tokio::sync::watch;
+use tokio::time::interval;
+use tracing::{error, info};
+
+use crate::arb::{ArbEvent, ArbTracker, ArbWindow};
+use crate::config::SqliteConfig;
+use crate::merger::MergedBook;
+use crate::types::now_ms;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS symbols (
+    id          INTEGER PRIMARY KEY,
+    hl_symbol   TEXT NOT NULL,
+    pdx_symbol  TEXT NOT NULL,
+    UNIQUE (hl_symbol, pdx_symbol)
+);
+CREATE TABLE IF NOT EXISTS sessions (
+    id          INTEGER PRIMARY KEY,
+    symbol_id   INTEGER NOT NULL REFERENCES symbols(id),
+    started_ms  INTEGER NOT NULL,
+    ended_ms    INTEGER
+);
+CREATE TABLE IF NOT EXISTS ticks (
+    session_id          INTEGER NOT NULL REFERENCES sessions(id),
+    ts_ms               INTEGER NOT NULL,
+    best_bid            REAL,
+    best_ask            REAL,
+    bid_exchange        TEXT,
+    ask_exchange        TEXT,
+    cross_spread        REAL,
+    cross_spread_pct    REAL,
+    liquidity_imbalance REAL,
+    total_bid_usd       REAL NOT NULL,
+    total_ask_usd       REAL NOT NULL
+);
+CREATE INDEX IF NOT EXISTS ticks_session_ts ON ticks (session_id, ts_ms);
+CREATE TABLE IF NOT EXISTS arb_events (
+    id              INTEGER PRIMARY KEY,
+    session_id      INTEGER NOT NULL REFERENCES sessions(id),
+    opened_ms       INTEGER NOT NULL,
+    closed_ms       INTEGER,
+    bid_exchange    TEXT NOT NULL,
+    ask_exchange    TEXT NOT NULL,
+    min_spread      REAL NOT NULL,
+    min_spread_pct  REAL NOT NULL,
+    bid_price       REAL NOT NULL,
+    ask_price       REAL NOT NULL,
+    size            REAL NOT NULL
+);
+";
+
+// ─── Messages to the writer thread ────────────────────────────────────────────
+
+struct TickRow {
+    ts_ms:               u64,
+    best_bid:            Option<f64>,
+    best_ask:            Option<f64>,
+    bid_exchange:        Option<&'static str>,
+    ask_exchange:        Option<&'static str>,
+    cross_spread:        Option<f64>,
+    cross_spread_pct:    Option<f64>,
+    liquidity_imbalance: Option<f64>,
+    total_bid_usd:       f64,
+    total_ask_usd:       f64,
+}
+
+impl TickRow {
+    fn new(ts_ms: u64, merged: &MergedBook) -> Self {
+        let sig = &merged.signals;
+        Self {
+            ts_ms,
+            best_bid:            merged.bids.first().map(|l| l.price),
+            best_ask:            merged.asks.first().map(|l| l.price),
+            bid_exchange:        sig.best_bid_exchange.as_ref().map(|e| e.short()),
+            ask_exchange:        sig.best_ask_exchange.as_ref().map(|e| e.short()),
+            cross_spread:        sig.cross_spread,
+            cross_spread_pct:    sig.cross_spread_pct,
+            liquidity_imbalance: sig.liquidity_imbalance,
+            total_bid_usd:       sig.total_bid_usd,
+            total_ask_usd:       sig.total_ask_usd,
+        }
+    }
+}
+
+enum DbMsg {
+    Tick(TickRow),
+    Arb(ArbEvent),
+    Shutdown,
+}
+
+// ─── Handle ───────────────────────────────────────────────────────────────────
+
+/// Owns the sampler task and writer thread. Call `shutdown()` on exit so the
+/// session row gets its end time.
+pub struct SqliteSink {
+    tx:      mpsc::Sender<DbMsg>,
+    sampler: tokio::task::JoinHandle<()>,
+    thread:  JoinHandle<()>,
+}
+
+impl SqliteSink {
+    pub fn shutdown(self) {
+        self.sampler.abort();
+        let _ = self.tx.send(DbMsg::Shutdown);
+        if self.thread.join().is_err() {
+            error!("[SQLITE] Writer thread panicked");
+        }
+    }
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Opens the database and creates the schema (so problems fail at startup),
+/// then records one `ticks` row every `interval_ms` and every arb window as it
+/// opens and closes.
+pub fn spawn_sqlite_sink(
+    cfg: &SqliteConfig,
+    hl_symbol: &str,
+    pdx_symbol: &str,
+    mut merged_rx: watch::Receiver<MergedBook>,
+) -> Result<SqliteSink> {
+    let conn = open_db(&cfg.path)?;
+    let session_id = start_session(&conn, hl_symbol, pdx_symbol)?;
+    info!("[SQLITE] Recording session {session_id} to {}", cfg.path.display());
+
+    let (tx, rx) = mpsc::channel::<DbMsg>();
+
+    let thread = thread::Builder::new()
+        .name("sqlite-sink".into())
+        .spawn(move || run_writer(conn, session_id, rx))
+        .context("Failed to spawn SQLite writer thread")?;
+
+    let tick = Duration::from_millis(cfg.interval_ms);
+    let sampler_tx = tx.clone();
+    let sampler = tokio::spawn(async move {
+        let mut ticker = interval(tick);
+        let mut tracker = ArbTracker::default();
+        loop {
+            // Arb windows are tracked on every merge so short ones aren't missed
+            let msg = tokio::select! {
+                _ = ticker.tick() => Some(DbMsg::Tick(TickRow::new(now_ms(), &merged_rx.borrow()))),
+                r = merged_rx.changed() => {
+                    if r.is_err() { break; }
+                    tracker.update(now_ms(), &merged_rx.borrow_and_update()).map(DbMsg::Arb)
+                }
+            };
+            if let Some(msg) = msg
+                && sampler_tx.send(msg).is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    Ok(SqliteSink { tx, sampler, thread })
+}
+
+// ─── Writer thread ────────────────────────────────────────────────────────────
+
+fn open_db(path: &Path) -> Result<Connection> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Cannot create SQLite directory '{}'", dir.display()))?;
+    }
+    let conn = Connection::open(path)
+        .with_context(|| format!("Cannot open SQLite database '{}'", path.display()))?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    conn.execute_batch(SCHEMA).context("Failed to create SQLite schema")?;
+    Ok(conn)
+}
+
+fn start_session(conn: &Connection, hl_symbol: &str, pdx_symbol: &str) -> Result<i64> {
+    conn.execute(
+        "INSERT OR IGNORE INTO symbols (hl_symbol, pdx_symbol) VALUES (?1, ?2)",
+        params![hl_symbol, pdx_symbol],
+    )?;
+    let symbol_id: i64 = conn.query_row(
+        "SELECT id FROM symbols WHERE hl_symbol = ?1 AND pdx_symbol = ?2",
+        params![hl_symbol, pdx_symbol],
+        |r| r.get(0),
+    )?;
+    conn.execute(
+        "INSERT INTO sessions (symbol_id, started_ms) VALUES (?1, ?2)",
+        params![symbol_id, now_ms() as i64],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+fn run_writer(conn: Connection, session_id: i64, rx: mpsc::Receiver<DbMsg>) {
+    let mut open_arb_id: Option<i64> = None;
+
+    while let Ok(msg) = rx.recv() {
+        let result = match msg {
+            DbMsg::Shutdown  => break,
+            DbMsg::Tick(row) => insert_tick(&conn, session_id, &row),
+            DbMsg::Arb(ArbEvent::Opened(win)) => insert_arb(&conn, session_id, &win).map(|id| {
+                open_arb_id = Some(id);
+            }),
+            DbMsg::Arb(ArbEvent::Closed(win)) => match open_arb_id.take() {
+                Some(id) => close_arb(&conn, id, &win),
+                None     => insert_arb(&conn, session_id, &win).map(|_| ()),
+            },
+        };
+        if let Err(e) = result {
+            error!("[SQLITE] Write failed: {e}");
+        }
+    }
+
+    if let Err(e) = conn.execute(
+        "UPDATE sessions SET ended_ms = ?1 WHERE id = ?2",
+        params![now_ms() as i64, session_id],
+    ) {
+        error!("[SQLITE] Failed to close session: {e}");
+    }
+}
+
+fn insert_tick(conn: &Connection, session_id: i64, r: &TickRow) -> rusqlite::Result<()> {
+    conn.prepare_cached(
+        "INSERT INTO ticks (session_id, ts_ms, best_bid, best_ask, bid_exchange, ask_exchange,
+                            cross_spread, cross_spread_pct, liquidity_imbalance,
+                            total_bid_usd, total_ask_usd)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+    )?
+    .execute(params![
+        session_id, r.ts_ms as i64, r.best_bid, r.best_ask, r.bid_exchange, r.ask_exchange,
+        r.cross_spread, r.cross_spread_pct, r.liquidity_imbalance,
+        r.total_bid_usd, r.total_ask_usd,
+    ])?;
+    Ok(())
+}
+
+fn insert_arb(conn: &Connection, session_id: i64, w: &ArbWindow) -> rusqlite::Result<i64> {
+    conn.execute(
+        "INSERT INTO arb_events (session_id, opened_ms, closed_ms, bid_exchange, ask_exchange,
+                                 min_spread, min_spread_pct, bid_price, ask_price, size)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            session_id, w.opened_ms as i64, w.closed_ms.map(|t| t as i64),
+            w.bid_exchange.short(), w.ask_exchange.short(),
+            w.min_spread, w.min_spread_pct, w.bid_price, w.ask_price, w.size,
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+fn close_arb(conn: &Connection, id: i64, w: &ArbWindow) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE arb_events
+         SET closed_ms = ?1, bid_exchange = ?2, ask_exchange = ?3, min_spread = ?4,
+             min_spread_pct = ?5, bid_price = ?6, ask_price = ?7, size = ?8
+         WHERE id = ?9",
+        params![
+            w.closed_ms.map(|t| t as i64), w.bid_exchange.short(), w.ask_exchange.short(),
+            w.min_spread, w.min_spread_pct, w.bid_price, w.ask_price, w.size, id,
+        ],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,190 @@
+// src/supervisor.rs — Per-exchange feed supervisor and connection state machine
+//
+//   Connecting → Subscribed → Live ⇄ Stale
+//        ↑                       │
+//        └── Reconnecting ←──────┘  (any connection end)
+//        └── Failed ←─────────────  (circuit breaker tripped, see `breaker`)
+//
+// The feed sets Subscribed once its subscriptions are sent and Live on every
+// book; the supervisor owns every other transition. `FeedHandle::shutdown`
+// ends the loop: the running connection closes its socket and returns, and
+// no reconnect follows.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Serialize;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::{interval, sleep, timeout};
+use tracing::{error, info, warn};
+
+use crate::breaker::Breaker;
+use crate::config::{HlAggregation, ReconnectConfig, SocketConfig};
+use crate::types::{modify_book, now_ms, OrderBook};
+
+/// How often a live feed's book age is checked against `stale_ms`.
+const STALE_CHECK_MS: u64 = 250;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub enum FeedStatus {
+    #[default]
+    Connecting,   // opening the socket
+    Subscribed,   // subscriptions sent, no book yet
+    Live,         // books arriving
+    Stale,        // connected, but no book within `stale_ms`
+    Reconnecting, // connection ended; waiting to retry
+    Failed,       // circuit breaker tripped; retrying slowly
+}
+
+impl FeedStatus {
+    /// The socket is open (whether or not books are arriving).
+    pub fn is_connected(self) -> bool {
+        matches!(self, FeedStatus::Subscribed | FeedStatus::Live | FeedStatus::Stale)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FeedStatus::Connecting   => "CONNECTING",
+            FeedStatus::Subscribed   => "SUBSCRIBED",
+            FeedStatus::Live         => "LIVE",
+            FeedStatus::Stale        => "STALE",
+            FeedStatus::Reconnecting => "RECONNECTING",
+            FeedStatus::Failed       => "FAILED",
+        }
+    }
+}
+
+// ─── Handle ───────────────────────────────────────────────────────────────────
+
+/// Owns a feed's supervisor task. Call `shutdown()` on exit so the socket is
+/// closed rather than dropped with the runtime.
+pub struct FeedHandle {
+    tag:     &'static str,
+    stop_tx: watch::Sender<bool>,
+    task:    JoinHandle<()>,
+}
+
+impl FeedHandle {
+    /// Signals the feed to stop and waits up to `wait` for its close
+    /// handshake; a feed still running after that is aborted.
+    pub async fn shutdown(mut self, wait: Duration) {
+        let _ = self.stop_tx.send(true);
+        if timeout(wait, &mut self.task).await.is_err() {
+            warn!("[{}] Did not stop within {}ms — aborting", self.tag, wait.as_millis());
+            self.task.abort();
+        }
+    }
+}
+
+// ─── Settings ─────────────────────────────────────────────────────────────────
+
+/// How a market data feed connects, reconnects and goes stale; the same for
+/// both feeds.
+#[derive(Debug, Clone, Copy)]
+pub struct FeedSettings {
+    pub reconnect: ReconnectConfig,
+    pub stale_ms:  u64, // 0 = never stale
+    pub socket:    SocketConfig,
+    pub depth:     usize, // levels per side kept and published
+    pub aggregation: HlAggregation, // Hyperliquid's l2Book nSigFigs / mantissa; unused by Paradex
+    pub bbo:       bool,  // also subscribe to the venue's BBO channel
+}
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Spawns a task running `connect` until it ends, then again after the
+/// breaker's delay, until shut down. `connect` gets the stop signal, on which
+/// it should close its socket and return. While a connection runs, a live
+/// book older than `stale_ms` is marked stale (0 = never).
+pub fn spawn_supervisor<F, Fut>(
+    tag: &'static str,
+    book_tx: watch::Sender<Arc<OrderBook>>,
+    reconnect: ReconnectConfig,
+    stale_ms: u64,
+    mut connect: F,
+) -> FeedHandle
+where
+    F: FnMut(watch::Receiver<bool>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<()>> + Send,
+{
+    let (stop_tx, mut stop_rx) = watch::channel(false);
+    let task = tokio::spawn(async move {
+        let mut breaker = Breaker::new(reconnect);
+        loop {
+            info!("[{tag}] Connecting…");
+            modify_book(&book_tx, |b| {
+                b.status = FeedStatus::Connecting;
+                b.connected_ms = None;
+            });
+            let pushes = book_tx.borrow().message_count;
+
+            let conn = connect(stop_rx.clone());
+            tokio::pin!(conn);
+            let mut check = interval(Duration::from_millis(STALE_CHECK_MS));
+            let result = loop {
+                tokio::select! {
+                    r = &mut conn => break r,
+                    _ = check.tick(), if stale_ms > 0 => check_stale(tag, &book_tx, stale_ms),
+                }
+            };
+            if *stop_rx.borrow() {
+                if let Err(e) = result {
+                    warn!("[{tag}] Error while closing: {e:#}");
+                }
+                info!("[{tag}] Stopped");
+                break;
+            }
+            match result {
+                Ok(_)  => warn!("[{tag}] Connection closed cleanly — reconnecting"),
+                Err(e) => error!("[{tag}] Connection error: {e:#} — reconnecting"),
+            }
+
+            let delay = breaker.record(book_tx.borrow().message_count > pushes);
+            if breaker.degraded() {
+                warn!("[{tag}] {} failed reconnects in a row — retrying in {}s", breaker.failures(), delay.as_secs());
+            }
+            modify_book(&book_tx, |b| {
+                b.status = if breaker.degraded() { FeedStatus::Failed } else { FeedStatus::Reconnecting };
+                b.reconnect_failures = breaker.failures();
+                b.reconnects += 1;
+            });
+            tokio::select! {
+                _ = sleep(delay) => {}
+                _ = stopped(&mut stop_rx) => { info!("[{tag}] Stopped"); break; }
+            }
+        }
+    });
+    FeedHandle { tag, stop_tx, task }
+}
+
+/// Resolves once `stop_rx` is set (or its `FeedHandle` is gone).
+pub async fn stopped(stop_rx: &mut watch::Receiver<bool>) {
+    let _ = stop_rx.wait_for(|stop| *stop).await;
+}
+
+/// Live ⇄ Stale by book age; the next book also puts a stale feed back to Live.
+fn check_stale(tag: &str, book_tx: &watch::Sender<Arc<OrderBook>>, stale_ms: u64) {
+    let now = now_ms();
+    book_tx.send_if_modified(|b| {
+        let old = b.age_ms(now).is_some_and(|a| a > stale_ms);
+        match b.status {
+            FeedStatus::Live if old => {
+                warn!("[{tag}] No book update for {stale_ms}ms — marking stale");
+                let b = Arc::make_mut(b);
+                b.status = FeedStatus::Stale;
+                b.version += 1;
+                true
+            }
+            FeedStatus::Stale if !old => {
+                let b = Arc::make_mut(b);
+                b.status = FeedStatus::Live;
+                b.version += 1;
+                true
+            }
+            _ => false,
+        }
+    });
+}
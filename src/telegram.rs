@@ -0,0 +1,48 @@
+// src/telegram.rs — Telegram bot alerts for sustained arbitrage windows
+
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::arb::spawn_sustained_arb_watcher;
+use crate::config::TelegramConfig;
+use crate::merger::MergedBook;
+use crate::types::now_ms;
+
+const TELEGRAM_API: &str = "https://api.telegram.org";
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Spawns a task that sends one Telegram message per arb window, once it has
+/// stayed open for `min_duration_ms` with at least `min_size` executable.
+pub fn spawn_telegram_alerts(cfg: TelegramConfig, coin: String, merged_rx: watch::Receiver<MergedBook>) {
+    let mut arbs = spawn_sustained_arb_watcher(merged_rx, cfg.min_duration_ms, cfg.min_size);
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        info!("[TG] Alerts enabled for chat {}", cfg.chat_id);
+        while let Some(win) = arbs.recv().await {
+            let text = win.alert_text(&coin, now_ms());
+            if let Err(e) = send_message(&client, &cfg, &text).await {
+                warn!("[TG] Failed to send alert: {e:#}");
+            }
+        }
+    });
+}
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+async fn send_message(client: &reqwest::Client, cfg: &TelegramConfig, text: &str) -> Result<()> {
+    let url = format!("{TELEGRAM_API}/bot{}/sendMessage", cfg.bot_token);
+    let resp = client
+        .post(url)
+        .json(&json!({ "chat_id": cfg.chat_id, "text": text }))
+        .send()
+        .await
+        .map_err(|e| e.without_url()) // the URL contains the bot token
+        .context("Failed to reach Telegram API")?;
+    if !resp.status().is_success() {
+        bail!("Telegram API returned {}: {}", resp.status(), resp.text().await.unwrap_or_default());
+    }
+    Ok(())
+}
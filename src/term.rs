@@ -0,0 +1,88 @@
+// src/term.rs — Terminal restoration on quit signals, errors and panics
+//
+// The TUI puts the terminal in raw mode on the alternate screen. Anything that
+// ends the process without undoing that leaves the shell unusable, so:
+//   - SIGINT / SIGTERM / SIGHUP ask the TUI loop to quit the normal way (a
+//     second one restores the terminal and exits at once);
+//   - a `RestoreGuard` held by the TUI undoes it when `run_tui` returns early
+//     with an error;
+//   - the panic hook undoes it before the panic message is printed, so the
+//     message lands on the normal screen instead of vanishing with the other.
+
+use std::fs::OpenOptions;
+use std::io;
+
+use anyhow::{Context, Result};
+use crossterm::{
+    cursor::Show,
+    execute,
+    terminal::{disable_raw_mode, is_raw_mode_enabled, LeaveAlternateScreen},
+};
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::watch;
+use tracing::warn;
+
+/// Exit status after a second signal, as a shell reports for SIGINT.
+const FORCED_EXIT_CODE: i32 = 130;
+
+/// Leaves raw mode and the alternate screen, if the TUI is up. Raw mode is the
+/// first thing the TUI sets, so it doubles as the "TUI is up" flag, and this is
+/// a no-op when called again.
+pub fn restore() {
+    if !is_raw_mode_enabled().unwrap_or(false) {
+        return;
+    }
+    let _ = disable_raw_mode();
+    // The TUI may draw on /dev/tty rather than stdout (Arrow IPC on stdout)
+    let _ = match OpenOptions::new().write(true).open("/dev/tty") {
+        Ok(mut tty) => execute!(tty, LeaveAlternateScreen, Show),
+        Err(_)      => execute!(io::stdout(), LeaveAlternateScreen, Show),
+    };
+}
+
+/// Restores the terminal when dropped.
+pub struct RestoreGuard;
+
+impl Drop for RestoreGuard {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+/// Chains a terminal restore in front of the default panic hook. Only panics
+/// on the main thread (the TUI's) restore: tokio catches a panic in a spawned
+/// task, and the TUI carries on without it.
+pub fn install_panic_hook() {
+    let default = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() == Some("main") {
+            restore();
+        }
+        default(info);
+    }));
+}
+
+/// Spawns a task that sets the returned flag on the first SIGINT, SIGTERM or
+/// SIGHUP, and restores the terminal and exits on the next.
+pub fn spawn_quit_signals() -> Result<watch::Receiver<bool>> {
+    let mut int  = signal(SignalKind::interrupt()).context("SIGINT handler")?;
+    let mut term = signal(SignalKind::terminate()).context("SIGTERM handler")?;
+    let mut hup  = signal(SignalKind::hangup()).context("SIGHUP handler")?;
+    let (tx, rx) = watch::channel(false);
+    tokio::spawn(async move {
+        loop {
+            let name = tokio::select! {
+                _ = int.recv()  => "SIGINT",
+                _ = term.recv() => "SIGTERM",
+                _ = hup.recv()  => "SIGHUP",
+            };
+            if *tx.borrow() {
+                restore();
+                std::process::exit(FORCED_EXIT_CODE);
+            }
+            warn!("{name} received — quitting");
+            tx.send_replace(true);
+        }
+    });
+    Ok(rx)
+}
@@ -0,0 +1,151 @@
+// src/theme.rs — TUI colour palette: built-in presets plus `[theme]` overrides
+//
+// The palette is set from config before the first frame (and swapped by `t`)
+// and read by every draw function through `current()`, so the colours needn't
+// be threaded through the whole of `ui`.
+
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use ratatui::style::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub bid:    Color,
+    pub ask:    Color,
+    pub mid:    Color, // highlights, prompts, the mid price
+    pub dim:    Color, // labels and secondary text
+    pub header: Color, // table headers and panel titles
+    pub border: Color,
+    pub hl:     Color, // Hyperliquid's tag and levels
+    pub pdx:    Color, // Paradex's tag and levels
+    pub arb:    Color, // open arbs
+    pub warn:   Color, // failures and banners
+    pub text:   Color, // values
+    pub sweep:  Color, // background of merged levels a sweep would take
+    pub track:  Color, // unfilled part of gauges
+}
+
+impl Theme {
+    /// For dark terminals.
+    pub const DARK: Theme = Theme {
+        bid:    Color::Rgb(0,   210, 140), // emerald
+        ask:    Color::Rgb(255, 80,  80),  // coral
+        mid:    Color::Rgb(255, 210, 80),  // amber
+        dim:    Color::Rgb(110, 110, 130), // muted
+        header: Color::Rgb(160, 160, 220), // lavender
+        border: Color::Rgb(55,  55,  90),  // dark indigo
+        hl:     Color::Rgb(60,  160, 255), // HL blue
+        pdx:    Color::Rgb(180, 100, 255), // PDX purple
+        arb:    Color::Rgb(255, 180, 0),   // arb amber
+        warn:   Color::Rgb(255, 60,  60),  // danger red
+        text:   Color::White,
+        sweep:  Color::Rgb(45,  45,  80),
+        track:  Color::Rgb(30,  30,  50),
+    };
+
+    /// For light terminals: the same hues, darker, on pale fills.
+    pub const LIGHT: Theme = Theme {
+        bid:    Color::Rgb(0,   135, 90),
+        ask:    Color::Rgb(200, 30,  45),
+        mid:    Color::Rgb(170, 110, 0),
+        dim:    Color::Rgb(120, 120, 135),
+        header: Color::Rgb(70,  70,  150),
+        border: Color::Rgb(180, 180, 205),
+        hl:     Color::Rgb(20,  100, 200),
+        pdx:    Color::Rgb(125, 50,  190),
+        arb:    Color::Rgb(200, 120, 0),
+        warn:   Color::Rgb(205, 20,  20),
+        text:   Color::Black,
+        sweep:  Color::Rgb(215, 215, 240),
+        track:  Color::Rgb(225, 225, 235),
+    };
+
+    /// The terminal's own 16 colours, for terminals without true colour or
+    /// with a palette of their own.
+    pub const ANSI: Theme = Theme {
+        bid:    Color::Green,
+        ask:    Color::Red,
+        mid:    Color::Yellow,
+        dim:    Color::DarkGray,
+        header: Color::Cyan,
+        border: Color::DarkGray,
+        hl:     Color::Blue,
+        pdx:    Color::Magenta,
+        arb:    Color::LightYellow,
+        warn:   Color::LightRed,
+        text:   Color::Reset,
+        sweep:  Color::DarkGray,
+        track:  Color::Reset,
+    };
+
+    /// For deuteranopia and the other red–green deficiencies, from the
+    /// Okabe–Ito palette: bids blue and asks orange, Hyperliquid bluish green
+    /// and Paradex reddish purple. On a dark background.
+    pub const COLORBLIND: Theme = Theme {
+        bid:    Color::Rgb(86,  180, 233), // sky blue
+        ask:    Color::Rgb(230, 159, 0),   // orange
+        mid:    Color::Rgb(240, 228, 66),  // yellow
+        dim:    Color::Rgb(120, 120, 130),
+        header: Color::Rgb(200, 200, 210),
+        border: Color::Rgb(70,  70,  80),
+        hl:     Color::Rgb(0,   158, 115), // bluish green
+        pdx:    Color::Rgb(204, 121, 167), // reddish purple
+        arb:    Color::Rgb(240, 228, 66),  // yellow
+        warn:   Color::Rgb(213, 94,  0),   // vermillion
+        text:   Color::White,
+        sweep:  Color::Rgb(40,  50,  80),
+        track:  Color::Rgb(30,  30,  40),
+    };
+
+    /// Preset names, as `[theme] preset` takes them.
+    pub const PRESETS: [(&'static str, Theme); 4] = [
+        ("dark", Self::DARK), ("light", Self::LIGHT), ("ansi", Self::ANSI), ("colorblind", Self::COLORBLIND),
+    ];
+
+    pub fn preset(name: &str) -> Option<Theme> {
+        Self::PRESETS.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|&(_, t)| t)
+    }
+
+    /// Sets the colour called `field` (as in config.toml) from "#rrggbb", a
+    /// colour name or an ANSI index. Errors name what was wrong.
+    pub fn set(&mut self, field: &str, value: &str) -> Result<(), String> {
+        let color = Color::from_str(value.trim()).map_err(|_| format!("'{value}' is not a colour"))?;
+        let slot = match field {
+            "bid"    => &mut self.bid,
+            "ask"    => &mut self.ask,
+            "mid"    => &mut self.mid,
+            "dim"    => &mut self.dim,
+            "header" => &mut self.header,
+            "border" => &mut self.border,
+            "hl"     => &mut self.hl,
+            "pdx"    => &mut self.pdx,
+            "arb"    => &mut self.arb,
+            "warn"   => &mut self.warn,
+            "text"   => &mut self.text,
+            "sweep"  => &mut self.sweep,
+            "track"  => &mut self.track,
+            _ => return Err("is not a theme colour".to_string()),
+        };
+        *slot = color;
+        Ok(())
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+static CURRENT: RwLock<Theme> = RwLock::new(Theme::DARK);
+
+/// Makes `theme` the palette every later frame is drawn in.
+pub fn set(theme: Theme) {
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = theme;
+}
+
+/// The palette to draw in.
+pub fn current() -> Theme {
+    *CURRENT.read().unwrap_or_else(|e| e.into_inner())
+}
@@ -0,0 +1,29 @@
+// src/trade_tape.rs — Unified trade tape across both venues
+
+use std::collections::VecDeque;
+
+use tokio::sync::{broadcast, watch};
+use tracing::warn;
+
+use crate::types::Trade;
+
+/// Prints kept on the tape (oldest dropped first).
+const MAX_TRADES: usize = 100;
+
+/// Spawns a task keeping the latest trades from `trades_rx`, newest first.
+pub fn spawn_trade_tape(mut trades_rx: broadcast::Receiver<Trade>) -> watch::Receiver<VecDeque<Trade>> {
+    let (tx, rx) = watch::channel(VecDeque::new());
+    tokio::spawn(async move {
+        loop {
+            match trades_rx.recv().await {
+                Ok(trade) => tx.send_modify(|tape| {
+                    tape.push_front(trade);
+                    tape.truncate(MAX_TRADES);
+                }),
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[TAPE] Skipped {n} trades (consumer lagging)"),
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+    rx
+}
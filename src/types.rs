@@ -1,6 +1,17 @@
 // src/types.rs — Shared data types for all exchange feeds
 
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use tokio::sync::watch;
+
+use crate::clock;
+use crate::latency::LatencyHistogram;
+use crate::supervisor::FeedStatus;
 
 // ─── Hyperliquid outbound messages ───────────────────────────────────────────
 
@@ -21,7 +32,25 @@ pub enum OutboundMsg {
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum Subscription {
     #[serde(rename = "l2Book")]
-    L2Book { coin: String },
+    L2Book {
+        coin: String,
+        /// Server-side aggregation: prices rounded to this many significant
+        /// figures (2–5), and with 5, to a multiple of `mantissa` (1, 2 or 5).
+        #[serde(rename = "nSigFigs", skip_serializing_if = "Option::is_none")]
+        n_sig_figs: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mantissa: Option<u32>,
+    },
+    #[serde(rename = "userFills")]
+    UserFills { user: String },
+    #[serde(rename = "webData2")]
+    WebData2 { user: String },
+    #[serde(rename = "activeAssetCtx")]
+    ActiveAssetCtx { coin: String },
+    #[serde(rename = "bbo")]
+    Bbo { coin: String },
+    #[serde(rename = "trades")]
+    Trades { coin: String },
 }
 
 // ─── Hyperliquid inbound messages ────────────────────────────────────────────
@@ -33,6 +62,17 @@ pub struct InboundEnvelope {
     pub data: serde_json::Value,
 }
 
+/// The same envelope borrowing from the frame, with `data` left unparsed. The
+/// market data feed deserialises `data` straight into the channel's own type,
+/// instead of building a `Value` tree and then converting it.
+#[derive(Deserialize, Debug)]
+pub struct RawEnvelope<'a> {
+    #[serde(borrow)]
+    pub channel: Cow<'a, str>,
+    #[serde(borrow)]
+    pub data: &'a RawValue,
+}
+
 /// Parsed Hyperliquid l2Book update.
 #[derive(Deserialize, Debug, Clone)]
 pub struct WsBook {
@@ -41,17 +81,21 @@ pub struct WsBook {
     pub time: u64,
 }
 
-/// A single Hyperliquid price level.
+/// Parsed Hyperliquid bbo update; a side is null while empty.
 #[derive(Deserialize, Debug, Clone)]
-pub struct WsLevel {
-    pub px: String,
-    pub sz: String,
-    pub n: u32,
+pub struct WsBbo {
+    pub coin: String,
+    pub time: u64,
+    pub bbo: (Option<WsLevel>, Option<WsLevel>), // (bid, ask)
 }
 
-impl WsLevel {
-    pub fn price_f64(&self) -> f64 { self.px.parse().unwrap_or(0.0) }
-    pub fn size_f64(&self)  -> f64 { self.sz.parse().unwrap_or(0.0) }
+/// A single Hyperliquid price level. Prices and sizes arrive as strings and
+/// are parsed exactly; a malformed one fails the whole message.
+#[derive(Deserialize, Debug, Clone)]
+pub struct WsLevel {
+    pub px: Decimal,
+    pub sz: Decimal,
+    pub n: u32,
 }
 
 // ─── Paradex inbound messages ────────────────────────────────────────────────
@@ -59,14 +103,9 @@ impl WsLevel {
 /// A single Paradex order book level (snapshot + delta messages).
 #[derive(Deserialize, Debug, Clone)]
 pub struct PdxLevel {
-    pub price: String,
+    pub price: Decimal,
     pub side: String, // "BUY" | "SELL"
-    pub size: String,
-}
-
-impl PdxLevel {
-    pub fn price_f64(&self) -> f64 { self.price.parse().unwrap_or(0.0) }
-    pub fn size_f64(&self)  -> f64 { self.size.parse().unwrap_or(0.0) }
+    pub size: Decimal,
 }
 
 /// The `data` payload inside a Paradex `subscription` push.
@@ -79,34 +118,66 @@ pub struct PdxBookData {
     pub market: String,
     /// "s" = snapshot, "d" = delta
     pub update_type: String,
+    /// Message sequence number, and the one this message follows
+    #[serde(default)]
+    pub seq_no: Option<u64>,
+    #[serde(default)]
+    pub prev_seq_no: Option<u64>,
+}
+
+/// The `data` payload of a Paradex `bbo.{market}` push.
+#[derive(Deserialize, Debug, Clone)]
+pub struct PdxBbo {
+    #[serde(default)]
+    pub bid: Option<Decimal>,
+    #[serde(default)]
+    pub bid_size: Option<Decimal>,
+    #[serde(default)]
+    pub ask: Option<Decimal>,
+    #[serde(default)]
+    pub ask_size: Option<Decimal>,
+    pub last_updated_at: u64, // ms
 }
 
 // ─── Normalised price level (shared by both exchanges) ───────────────────────
 
-/// Canonical price level stored in `OrderBook`.
-#[derive(Debug, Clone)]
+/// Canonical price level stored in `OrderBook`. Decimal keeps the exchange's
+/// exact value and precision (and serialises as the same string); the f64
+/// accessors are for arithmetic where rounding doesn't matter.
+///
+/// The f64 values are converted once, when the level is built from a message,
+/// since the merger, signals and every render read them many times per tick.
+#[derive(Debug, Clone, Serialize)]
 pub struct Level {
-    pub price: String,
-    pub size: String,
+    pub price: Decimal,
+    pub size: Decimal,
     pub count: u32, // Paradex doesn't provide order count → 0
+    #[serde(skip)]
+    px: f64,
+    #[serde(skip)]
+    sz: f64,
 }
 
 impl Level {
-    pub fn price_f64(&self) -> f64 { self.price.parse().unwrap_or(0.0) }
-    pub fn size_f64(&self)  -> f64 { self.size.parse().unwrap_or(0.0) }
+    pub fn new(price: Decimal, size: Decimal, count: u32) -> Self {
+        Self { price, size, count, px: price.to_f64().unwrap_or(0.0), sz: size.to_f64().unwrap_or(0.0) }
+    }
+
+    pub fn price_f64(&self) -> f64 { self.px }
+    pub fn size_f64(&self)  -> f64 { self.sz }
 
     pub fn from_hl(l: &WsLevel) -> Self {
-        Self { price: l.px.clone(), size: l.sz.clone(), count: l.n }
+        Self::new(l.px, l.sz, l.n)
     }
 
     pub fn from_pdx(l: &PdxLevel) -> Self {
-        Self { price: l.price.clone(), size: l.size.clone(), count: 0 }
+        Self::new(l.price, l.size, 0)
     }
 }
 
 // ─── Exchange label ───────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub enum Exchange {
     #[default]
     Hyperliquid,
@@ -130,15 +201,60 @@ impl Exchange {
 
 // ─── Normalised order book ───────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct OrderBook {
     pub exchange: Exchange,
     pub coin: String,
     pub bids: Vec<Level>,
     pub asks: Vec<Level>,
     pub last_update_ms: u64,
-    pub connected: bool,
+    pub status: FeedStatus,
+    /// Failed reconnects in a row (see `breaker`).
+    pub reconnect_failures: u32,
+    /// Connections lost (and retried) since this book was started.
+    pub reconnects: u64,
+    /// When the current connection subscribed; None while (re)connecting.
+    pub connected_ms: Option<u64>,
     pub message_count: u64,
+    /// Venue mark price and index (oracle) price, from their own channels.
+    pub mark_px: Option<f64>,
+    pub index_px: Option<f64>,
+    /// Order flow imbalance near the touch summed since startup (see `ofi`).
+    pub ofi_cum: f64,
+    /// Venue clock minus local clock, when clock sync is on (see `clock`).
+    pub clock_offset_ms: Option<i64>,
+    /// Round trip of the last heartbeat (HL ping → pong, PDX heartbeat → ack).
+    pub rtt_ms: Option<f64>,
+    /// Sequence gaps detected this session, each followed by a resync.
+    pub seq_gaps: u64,
+    /// Messages that failed or were discarded this session.
+    pub errors: FeedErrors,
+    /// Latest quote from the venue's BBO channel, when subscribed. It usually
+    /// arrives ahead of the book, and the merger uses it for the touch while it is newer.
+    #[serde(skip)]
+    pub bbo: Option<Bbo>,
+    /// Exchange timestamp → local receive delay of every book message this session.
+    #[serde(skip)]
+    pub latency: LatencyHistogram,
+    /// Bumped on every change, so readers can tell a new book from one they've seen.
+    #[serde(skip)]
+    pub version: u64,
+}
+
+/// Best bid and offer from a BBO channel, with the venue's timestamp.
+#[derive(Debug, Clone, Default)]
+pub struct Bbo {
+    pub bid:   Option<Level>,
+    pub ask:   Option<Level>,
+    pub ts_ms: u64,
+}
+
+/// Per-feed counts of messages that didn't make it into the book or tape.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct FeedErrors {
+    pub parse:   u64, // unparseable or missing expected fields
+    pub rpc:     u64, // error replies from the exchange
+    pub dropped: u64, // parsed but not applied (unknown channel, out of sequence, …)
 }
 
 impl OrderBook {
@@ -167,6 +283,23 @@ impl OrderBook {
         }
     }
 
+    /// Milliseconds since the last exchange update, or `None` before the first one.
+    /// The exchange timestamp is moved onto the local clock first, so skew
+    /// between the two doesn't read as staleness.
+    pub fn age_ms(&self, now_ms: u64) -> Option<u64> {
+        (self.last_update_ms > 0)
+            .then(|| now_ms.saturating_sub(clock::to_local_ms(self.last_update_ms, self.clock_offset_ms)))
+    }
+
+    /// (mark − mid) / mid in basis points. A mark pulling away from the book is
+    /// what liquidation engines act on, so a widening gap often leads a cascade.
+    pub fn mark_divergence_bps(&self) -> Option<f64> {
+        match (self.mark_px, self.mid()) {
+            (Some(mark), Some(m)) if m > 0.0 => Some((mark - m) / m * 10_000.0),
+            _ => None,
+        }
+    }
+
     pub fn spread_pct(&self) -> Option<f64> {
         match (self.spread(), self.mid()) {
             (Some(s), Some(m)) if m > 0.0 => Some(s / m * 100.0),
@@ -174,3 +307,50 @@ impl OrderBook {
         }
     }
 }
+
+/// Applies `f` to a feed's published book, bumps its version and notifies its
+/// readers. Readers share the book through the `Arc` instead of copying its
+/// levels; it is only copied here when one still holds the previous snapshot.
+pub fn modify_book(book_tx: &watch::Sender<Arc<OrderBook>>, f: impl FnOnce(&mut OrderBook)) {
+    book_tx.send_modify(|book| {
+        let book = Arc::make_mut(book);
+        f(book);
+        book.version += 1;
+    });
+}
+
+// ─── Public trades ────────────────────────────────────────────────────────────
+
+/// A public trade print, normalised across venues.
+#[derive(Debug, Clone, Serialize)]
+pub struct Trade {
+    pub ts_ms:       u64,
+    pub exchange:    Exchange,
+    pub side:        Side, // aggressor (taker) side
+    pub price:       f64,
+    pub size:        f64,
+    pub liquidation: bool, // forced close, when the venue flags it
+}
+
+impl Trade {
+    pub fn notional(&self) -> f64 { self.price * self.size }
+}
+
+// ─── Order side ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum Side { Buy, Sell }
+
+impl Side {
+    /// +1 for buys, −1 for sells (signed position deltas).
+    pub fn sign(self) -> f64 {
+        match self { Side::Buy => 1.0, Side::Sell => -1.0 }
+    }
+}
+
+// ─── Time ─────────────────────────────────────────────────────────────────────
+
+/// Current wall-clock time in milliseconds since the Unix epoch.
+pub fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
@@ -1,31 +1,55 @@
 // src/ui.rs — Terminal UI: merged book + individual books + signals panel
 
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Table},
+    symbols::Marker,
+    widgets::canvas::{Canvas, Line as CanvasLine},
+    widgets::{Axis, Block, Borders, Cell, Chart, Clear, Dataset, Gauge, GraphType, Paragraph, Row, Sparkline, Table, TableState, Tabs},
     Frame,
 };
+use tracing::Level;
+
+use crate::account::AccountState;
+use crate::alerts::ActiveAlert;
+use crate::arb::ArbWindow;
+use crate::config::{FeesConfig, PanelDepth};
+use crate::candles::CandleSeries;
+use crate::cvd::CvdState;
+use crate::direction::{BookTicks, Dir, Tick};
+use crate::exec::ExecState;
+use crate::impact::ImpactCalc;
+use crate::pair_grid::GridCard;
+use crate::pair_picker::PairPicker;
+use crate::liquidations::{LiqTotals, LiquidationTape};
+use crate::log_panel::LogLine;
+use crate::market_stats::MarketStats;
+use crate::merger::{LiquidityBand, MergedBook, MergedLevel, Signals, TopOfBook, Vwap, IMBALANCE_DEPTHS};
+use crate::mid_history::{MidHistory, MidPoint};
+use crate::msg_rate::{self, MsgRates};
+use crate::perf::{fmt_us, Timing};
+use crate::risk::RiskBlock;
+use crate::sim::SimState;
+use crate::spread_stats::{SpreadStats, SpreadSummary};
+use crate::supervisor::FeedStatus;
+use crate::theme::current as theme;
+use crate::types::{now_ms, Exchange, OrderBook, Trade};
+use crate::volatility::VolState;
 
-use crate::merger::{MergedBook, MergedLevel};
-use crate::types::{Exchange, OrderBook};
-
-// ─── Colour palette ───────────────────────────────────────────────────────────
-const C_BID:       Color = Color::Rgb(0,   210, 140); // emerald
-const C_ASK:       Color = Color::Rgb(255, 80,  80);  // coral
-const C_MID:       Color = Color::Rgb(255, 210, 80);  // amber
-const C_DIM:       Color = Color::Rgb(110, 110, 130); // muted
-const C_HEADER:    Color = Color::Rgb(160, 160, 220); // lavender
-const C_BORDER:    Color = Color::Rgb(55,  55,  90);  // dark indigo
-const C_HL:        Color = Color::Rgb(60,  160, 255); // HL blue
-const C_PDX:       Color = Color::Rgb(180, 100, 255); // PDX purple
-const C_ARB:       Color = Color::Rgb(255, 180, 0);   // arb amber
-const C_WARN:      Color = Color::Rgb(255, 60,  60);  // danger red
-const C_WHITE:     Color = Color::White;
+/// Mark−mid divergence (either way) highlighted in the signals panel.
+const MARK_DIV_WARN_BPS: f64 = 10.0;
+/// Smallest terminal the dashboard is drawn in: the header, the arb log, the
+/// footer and a few book levels, with the body as tabs.
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 24;
 
 fn ex_color(ex: &Exchange) -> Color {
-    match ex { Exchange::Hyperliquid => C_HL, Exchange::Paradex => C_PDX }
+    match ex { Exchange::Hyperliquid => theme().hl, Exchange::Paradex => theme().pdx }
 }
 
 fn ex_tag(ex: &Exchange) -> &'static str {
@@ -34,88 +58,498 @@ fn ex_tag(ex: &Exchange) -> &'static str {
 
 // ─── Public entry point ───────────────────────────────────────────────────────
 
-pub fn draw(frame: &mut Frame, hl: &OrderBook, pdx: &OrderBook, merged: &MergedBook) {
+/// Everything one frame renders, gathered by the TUI loop.
+pub struct View<'a> {
+    pub hl:         &'a OrderBook,
+    pub pdx:        &'a OrderBook,
+    pub merged:     &'a MergedBook,
+    pub alerts:     &'a [ActiveAlert],
+    pub arb_log:    &'a VecDeque<ArbWindow>, // newest first
+    pub arb_scroll: usize,                   // rows scrolled down in the arb log
+    pub flash:      bool,                    // paint the merged-book border in the arb colour
+    pub fees:       &'a FeesConfig,
+    pub sim:        Option<&'a SimState>,    // None = paper trading disabled
+    pub hl_exec:    Option<&'a ExecState>,   // None = live trading disabled
+    pub pdx_exec:   Option<&'a ExecState>,   // None = live trading disabled
+    pub accounts:   &'a [AccountState],      // one per venue with live trading
+    pub risk_block: Option<&'a RiskBlock>,   // Some = a limit blocked an order moments ago
+    pub hl_stats:   Option<&'a MarketStats>, // None = market stats polling disabled
+    pub pdx_stats:  Option<&'a MarketStats>,
+    pub liquidations: Option<&'a LiquidationTape>, // None = liquidations panel off
+    pub trades:     Option<&'a VecDeque<Trade>>,   // None = trade tape off; newest first
+    pub cvd:        Option<&'a CvdState>,          // None = CVD disabled
+    pub spreads:    Option<&'a SpreadStats>,       // None = rolling spread statistics disabled
+    pub vol:        Option<&'a VolState>,          // None = realized volatility disabled
+    pub spread_history: Option<&'a VecDeque<Option<f64>>>, // None = sparkline off; cross spread bps, oldest first
+    pub mid_chart:  Option<&'a MidHistory>,        // Some = mid-price chart shown
+    pub candles:    Option<&'a CandleSeries>,      // Some = candle chart shown at this interval
+    pub depth_chart: bool,                         // show the merged cumulative depth chart
+    pub hl_ticks:   &'a BookTicks,                 // direction of the last HL mid / BBO moves
+    pub pdx_ticks:  &'a BookTicks,
+    pub msg_rates:  &'a MsgRates,
+    pub diagnostics: bool,                         // show the feed diagnostics panel
+    pub logs:       Option<&'a VecDeque<LogLine>>, // Some = log panel shown; newest first
+    pub quarantined: usize,                        // unparseable payloads held for `w`
+    pub impact:     Option<&'a ImpactCalc>,        // Some = impact calculator open
+    pub pair_picker: Option<&'a PairPicker>,       // Some = pair picker open
+    pub pair_grid:  Option<&'a [GridCard]>,        // Some = the body is the grid of every pair
+    pub carousel:   Option<Duration>,              // Some = rotating through the pairs, this long to the next
+    pub sweep_size: f64,                           // base units highlighted in the merged book; 0 = off
+    pub venue_depth: (PanelDepth, PanelDepth),     // (HL, PDX) rows per side in the venue panels
+    pub quote_unit: &'a str,                       // currency of the merged prices, e.g. "USD"
+    pub paused:     Option<Duration>,              // Some = books frozen this long ago
+    pub live_confirm: Option<&'a str>,             // Some = a live order armed, waiting for Enter; what it will do
+    pub book_cursor: Option<usize>,                // Some = level this many from the touch selected in every book
+    pub signals_panel: bool,                       // show the signals panel
+    pub hl_panel:   bool,                          // show the HL book panel
+    pub pdx_panel:  bool,                          // show the PDX book panel
+    pub narrow_width: u16,                         // below this many columns the body shows one panel at a time
+    pub age_ms:     (u64, u64),                    // footer book age turns amber / red past these
+    pub body_tab:   usize,                         // panel shown when narrow, counting shown panels; wraps
+    pub draw_time:  &'a Timing,                    // previous frames; the merge time is on `merged`
+}
+
+pub fn draw(frame: &mut Frame, v: &View) {
     let area = frame.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        draw_too_small(frame, area);
+        return;
+    }
 
-    // Root: header(3) | body(min) | footer(3)
+    // Root: header(3) | confirm banner(1, only while a live order is armed)
+    //       | risk banner(1, only after a block) | alert banner(1, only while alerts fire)
+    //       | pause banner(1, only while paused) | body(min) | arb log(8) | footer(3)
     let root = Layout::vertical([
         Constraint::Length(3),
+        Constraint::Length(if v.live_confirm.is_some() { 1 } else { 0 }),
+        Constraint::Length(if v.risk_block.is_some() { 1 } else { 0 }),
+        Constraint::Length(if v.alerts.is_empty() { 0 } else { 1 }),
+        Constraint::Length(if v.paused.is_some() { 1 } else { 0 }),
         Constraint::Min(0),
+        Constraint::Length(8),
         Constraint::Length(3),
     ]).split(area);
 
-    draw_header(frame, root[0], hl, pdx);
+    draw_header(frame, root[0], v);
+    if let Some(what) = v.live_confirm {
+        draw_confirm_banner(frame, root[1], what);
+    }
+    if let Some(block) = v.risk_block {
+        draw_risk_banner(frame, root[2], block);
+    }
+    draw_alert_banner(frame, root[3], v.alerts);
+    if let Some(age) = v.paused {
+        draw_pause_banner(frame, root[4], age);
+    }
+
+    match v.pair_grid {
+        Some(cards) => draw_pair_grid(frame, root[5], cards, v),
+        None        => draw_body(frame, root[5], v),
+    }
+
+    // Bottom strip: arb log | liquidations | positions (only with paper trading)
+    //               | account (only with live trading), each optional panel only when enabled
+    let mut widths = vec![Constraint::Min(0)];
+    if v.liquidations.is_some() {
+        widths.push(Constraint::Percentage(22));
+    }
+    if v.sim.is_some() {
+        widths.push(Constraint::Percentage(if v.accounts.is_empty() { 35 } else { 28 }));
+    }
+    if !v.accounts.is_empty() {
+        widths.push(Constraint::Percentage(if v.sim.is_some() { 32 } else { 40 }));
+    }
+    let strip = Layout::horizontal(widths).split(root[6]);
+    let mut next = strip.iter().copied();
+    draw_arb_log(frame, next.next().unwrap_or_default(), v.arb_log, v.arb_scroll);
+    if let Some(tape) = v.liquidations {
+        draw_liquidations(frame, next.next().unwrap_or_default(), tape);
+    }
+    if let Some(sim) = v.sim {
+        draw_positions(frame, next.next().unwrap_or_default(), sim, v.hl, v.pdx);
+    }
+    if !v.accounts.is_empty() {
+        draw_account(frame, next.next().unwrap_or_default(), v.accounts, v.hl, v.pdx);
+    }
+    draw_footer(frame, root[7], v);
+}
+
+/// The panels between the banners and the arb log, with the charts toggled on under them.
+fn draw_body(frame: &mut Frame, area: Rect, v: &View) {
+    // Mid-price, candle and depth charts, diagnostics and the log, when toggled on, share the lower part of the body
+    let n_charts = v.mid_chart.is_some() as usize + v.candles.is_some() as usize
+        + v.depth_chart as usize + v.diagnostics as usize + v.logs.is_some() as usize;
+    let (body_area, chart_area) = if n_charts > 0 {
+        let split = Layout::vertical([Constraint::Min(0), Constraint::Percentage(40)]).split(area);
+        (split[0], split[1])
+    } else {
+        (area, Rect::default())
+    };
+    let charts = Layout::horizontal(vec![Constraint::Fill(1); n_charts]).split(chart_area);
+    let mut next_chart = charts.iter().copied();
+    if let Some(history) = v.mid_chart {
+        draw_mid_chart(frame, next_chart.next().unwrap_or_default(), history);
+    }
+    if let Some(series) = v.candles {
+        draw_candles(frame, next_chart.next().unwrap_or_default(), series);
+    }
+    if v.depth_chart {
+        draw_depth_chart(frame, next_chart.next().unwrap_or_default(), v.merged);
+    }
+    if v.diagnostics {
+        draw_diagnostics(frame, next_chart.next().unwrap_or_default(), v);
+    }
+    if let Some(logs) = v.logs {
+        draw_logs(frame, next_chart.next().unwrap_or_default(), logs);
+    }
+
+    // Body: merged book (40%) | signals panel (20%) | HL book (20%) | PDX book (20%),
+    // with the trade tape taking a slice of the books when enabled. Hidden panels
+    // give their share to the rest in proportion, up to the merged book alone.
+    let tape = v.trades.is_some();
+    let panels: Vec<(BodyPanel, u16)> = [
+        (BodyPanel::Merged, true, if tape { 34 } else { 40 }),
+        (BodyPanel::Signals, v.signals_panel, 20),
+        (BodyPanel::Hl, v.hl_panel, if tape { 15 } else { 20 }),
+        (BodyPanel::Pdx, v.pdx_panel, if tape { 15 } else { 20 }),
+        (BodyPanel::Trades, tape, 16),
+    ].into_iter().filter(|&(_, on, _)| on).map(|(panel, _, share)| (panel, share)).collect();
+    let body: Vec<(BodyPanel, Rect)> = if body_area.width < v.narrow_width {
+        // Too narrow for side by side: one panel at a time under a tab bar. The
+        // merged book stays up while an overlay drawn on it has the keyboard.
+        let [tabs_area, panel_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(body_area);
+        let selected = if v.impact.is_some() || v.pair_picker.is_some() { 0 } else { v.body_tab % panels.len() };
+        draw_body_tabs(frame, tabs_area, &panels, selected);
+        vec![(panels[selected].0, panel_area)]
+    } else {
+        let areas = Layout::horizontal(panels.iter().map(|&(_, share)| Constraint::Fill(share))).split(body_area);
+        panels.iter().map(|&(panel, _)| panel).zip(areas.iter().copied()).collect()
+    };
+
+    for (panel, panel_area) in body {
+        match panel {
+            BodyPanel::Merged => {
+                draw_merged_book(frame, panel_area, v);
+                if let Some(calc) = v.impact {
+                    draw_impact(frame, panel_area, calc);
+                }
+                if let Some(picker) = v.pair_picker {
+                    draw_pair_picker(frame, panel_area, picker);
+                }
+            }
+            BodyPanel::Signals => draw_signals(frame, panel_area, v),
+            BodyPanel::Hl      => draw_individual_book(frame, panel_area, v.hl, v.hl_ticks, v.venue_depth.0, v.book_cursor),
+            BodyPanel::Pdx     => draw_individual_book(frame, panel_area, v.pdx, v.pdx_ticks, v.venue_depth.1, v.book_cursor),
+            BodyPanel::Trades  => if let Some(trades) = v.trades {
+                draw_trade_tape(frame, panel_area, trades);
+            },
+        }
+    }
+}
+
+/// Stands in for the dashboard while the terminal is under `MIN_WIDTH` × `MIN_HEIGHT`.
+fn draw_too_small(frame: &mut Frame, area: Rect) {
+    let lines = vec![
+        Line::from(Span::styled("terminal too small", Style::default().fg(theme().warn).add_modifier(Modifier::BOLD))),
+        Line::from(Span::styled(
+            format!("need {MIN_WIDTH}x{MIN_HEIGHT}, have {}x{}", area.width, area.height),
+            Style::default().fg(theme().dim),
+        )),
+    ];
+    let [_, middle, _] = Layout::vertical([Constraint::Fill(1), Constraint::Length(2), Constraint::Fill(1)]).areas(area);
+    frame.render_widget(Paragraph::new(lines).centered(), middle);
+}
+
+// ─── Body tabs ────────────────────────────────────────────────────────────────
 
-    // Body: merged book (40%) | signals panel (20%) | HL book (20%) | PDX book (20%)
-    let body = Layout::horizontal([
-        Constraint::Percentage(40),
-        Constraint::Percentage(20),
-        Constraint::Percentage(20),
-        Constraint::Percentage(20),
-    ]).split(root[1]);
+/// The body's columns, in the order they're laid out.
+#[derive(Debug, Clone, Copy)]
+enum BodyPanel { Merged, Signals, Hl, Pdx, Trades }
 
-    draw_merged_book(frame, body[0], merged);
-    draw_signals(frame, body[1], hl, pdx, merged);
-    draw_individual_book(frame, body[2], hl);
-    draw_individual_book(frame, body[3], pdx);
+impl BodyPanel {
+    fn title(self) -> &'static str {
+        match self {
+            BodyPanel::Merged  => "Book",
+            BodyPanel::Signals => "Signals",
+            BodyPanel::Hl      => "HL",
+            BodyPanel::Pdx     => "PDX",
+            BodyPanel::Trades  => "Trades",
+        }
+    }
+}
+
+/// Tab bar over the body on narrow terminals; `Tab` moves to the next panel.
+fn draw_body_tabs(frame: &mut Frame, area: Rect, panels: &[(BodyPanel, u16)], selected: usize) {
+    let tabs = Tabs::new(panels.iter().map(|(panel, _)| panel.title()))
+        .select(selected)
+        .style(Style::default().fg(theme().dim))
+        .highlight_style(Style::default().fg(theme().mid).add_modifier(Modifier::BOLD))
+        .divider(Span::styled("│", Style::default().fg(theme().border)));
+    let hint = Paragraph::new(Span::styled("[Tab] next ", Style::default().fg(theme().dim))).right_aligned();
+    frame.render_widget(tabs, area);
+    frame.render_widget(hint, area);
+}
+
+// ─── Pair grid ────────────────────────────────────────────────────────────────
 
-    draw_footer(frame, root[2], hl, pdx);
+/// One card per pair in a near-square grid (2×2 up to four, 3×3 up to nine, …),
+/// in place of the body. The overlays open over it as over the merged book.
+fn draw_pair_grid(frame: &mut Frame, area: Rect, cards: &[GridCard], v: &View) {
+    let cols = (cards.len() as f64).sqrt().ceil().max(1.0) as usize;
+    let rows = cards.len().div_ceil(cols).max(1);
+    let row_areas = Layout::vertical(vec![Constraint::Fill(1); rows]).split(area);
+    for (row, chunk) in row_areas.iter().zip(cards.chunks(cols)) {
+        let cells = Layout::horizontal(vec![Constraint::Fill(1); cols]).split(*row);
+        for (cell, card) in cells.iter().zip(chunk) {
+            draw_grid_card(frame, *cell, card);
+        }
+    }
+    if let Some(calc) = v.impact {
+        draw_impact(frame, area, calc);
+    }
+    if let Some(picker) = v.pair_picker {
+        draw_pair_picker(frame, area, picker);
+    }
+}
+
+/// Merged best bid and ask with their venues, the cross spread, and each
+/// feed's state and book age. The border lights up while the pair has an arb.
+fn draw_grid_card(frame: &mut Frame, area: Rect, card: &GridCard) {
+    let sig = card.merged.as_ref().map(|m| &m.signals);
+    let arb = sig.is_some_and(Signals::is_arb);
+    let border = if arb { theme().arb } else if card.active { theme().mid } else { theme().border };
+    let title = if card.active { format!(" ● {} ", card.name) } else { format!(" {} ", card.name) };
+    let block = Block::default()
+        .title(Span::styled(title, Style::default().fg(theme().header).add_modifier(Modifier::BOLD)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border));
+
+    let lines = match (&card.note, &card.books, &card.merged) {
+        (Some((note, error)), ..) => vec![
+            Line::from(Span::styled(note.clone(), Style::default().fg(if *error { theme().warn } else { theme().dim }))),
+        ],
+        (None, Some((hl, pdx)), Some(merged)) => {
+            let touch = |level: Option<&MergedLevel>, label: &'static str, color: Color| match level {
+                Some(l) => vec![
+                    Span::styled(format!("{label} "), Style::default().fg(theme().dim)),
+                    Span::styled(format!("{:.2} ", l.price), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                    Span::styled(l.exchange.short(), Style::default().fg(ex_color(&l.exchange))),
+                ],
+                None => vec![Span::styled(format!("{label} –"), Style::default().fg(theme().dim))],
+            };
+            let mut quote = touch(merged.bids.first(), "bid", theme().bid);
+            quote.push(Span::raw("   "));
+            quote.extend(touch(merged.asks.first(), "ask", theme().ask));
+
+            let sig = &merged.signals;
+            let (spread, spread_color) = match (sig.cross_spread, sig.cross_spread_pct) {
+                (Some(s), Some(pct)) => (
+                    format!("{s:.4} ({pct:.4}%)"),
+                    if sig.is_arb() { theme().arb } else if s < 0.0 { theme().mid } else { theme().text },
+                ),
+                _ => ("–".to_string(), theme().dim),
+            };
+            let mut feeds = Vec::new();
+            let now = now_ms();
+            for book in [hl, pdx] {
+                let (dot, dot_color) = conn_dot(book);
+                let age = book.age_ms(now).map_or("–".to_string(), fmt_age);
+                feeds.push(Span::styled(format!("{} ", book.exchange.short()), Style::default().fg(ex_color(&book.exchange))));
+                feeds.push(Span::styled(format!("{dot} "), Style::default().fg(dot_color)));
+                feeds.push(Span::styled(format!("{age}   "), Style::default().fg(theme().dim)));
+            }
+            vec![
+                Line::from(quote),
+                Line::from(vec![
+                    Span::styled("spread ", Style::default().fg(theme().dim)),
+                    Span::styled(spread, Style::default().fg(spread_color)),
+                    Span::styled(if arb { "  ⚡ ARB" } else { "" }, Style::default().fg(theme().arb).add_modifier(Modifier::BOLD)),
+                ]),
+                Line::from(feeds),
+            ]
+        }
+        _ => Vec::new(),
+    };
+    frame.render_widget(Paragraph::new(lines).block(block), area);
 }
 
 // ─── Header ───────────────────────────────────────────────────────────────────
 
-fn draw_header(frame: &mut Frame, area: Rect, hl: &OrderBook, pdx: &OrderBook) {
-    fn conn(book: &OrderBook, color: Color) -> Vec<Span<'static>> {
-        let dot = if book.connected { "●" } else { "○" };
-        let dot_color = if book.connected { C_BID } else { C_WARN };
-        vec![
+/// Connection marker for each `FeedStatus`.
+fn conn_dot(book: &OrderBook) -> (&'static str, Color) {
+    match book.status {
+        FeedStatus::Live                                => ("●", theme().bid),
+        FeedStatus::Stale                               => ("◐", theme().mid),
+        FeedStatus::Connecting | FeedStatus::Subscribed => ("◌", theme().mid),
+        FeedStatus::Reconnecting | FeedStatus::Failed   => ("○", theme().warn),
+    }
+}
+
+fn draw_header(frame: &mut Frame, area: Rect, v: &View) {
+    fn conn(book: &OrderBook, ticks: &BookTicks, stats: Option<&MarketStats>, color: Color) -> Vec<Span<'static>> {
+        let (dot, dot_color) = conn_dot(book);
+        let mut spans = vec![
             Span::styled(format!("{} ", ex_tag(&book.exchange)), Style::default().fg(color).add_modifier(Modifier::BOLD)),
             Span::styled(dot.to_string(), Style::default().fg(dot_color)),
-            Span::styled(
-                match book.mid() {
-                    Some(m) => format!(" ${m:.2}"),
-                    None    => " –".to_string(),
-                },
-                Style::default().fg(C_MID).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("  ", Style::default()),
-        ]
+            Span::raw(" "),
+        ];
+        match book.status {
+            FeedStatus::Live => {}
+            FeedStatus::Failed => {
+                spans.push(Span::styled(" FAILED ", Style::default().fg(Color::Black).bg(theme().warn).add_modifier(Modifier::BOLD)));
+                spans.push(Span::raw(" "));
+            }
+            status => spans.push(Span::styled(format!("{} ", status.label()), Style::default().fg(dot_color))),
+        }
+        // Uptime of this connection and reconnects so far, so a flapping feed stands out
+        if let Some(since) = book.connected_ms {
+            spans.push(Span::styled(format!("up {} ", fmt_uptime(now_ms().saturating_sub(since))), Style::default().fg(theme().dim)));
+        }
+        if book.reconnects > 0 {
+            spans.push(Span::styled(format!("↻{} ", book.reconnects), Style::default().fg(theme().mid)));
+        }
+        let mid = tick_span(book.mid().map(|m| format!("${m:.2}")), &ticks.mid, theme().mid);
+        spans.push(mid.patch_style(Modifier::BOLD));
+        // Open interest and its change since the session started
+        if let Some(oi) = stats.and_then(|s| s.open_interest) {
+            spans.push(Span::styled(format!("  OI {}", fmt_usd(oi)), Style::default().fg(theme().dim)));
+            if let Some(pct) = stats.and_then(MarketStats::oi_change_pct) {
+                let c = if pct >= 0.0 { theme().bid } else { theme().ask };
+                spans.push(Span::styled(format!(" {pct:+.2}%"), Style::default().fg(c)));
+            }
+        }
+        spans.push(Span::styled("  ", Style::default()));
+        spans
     }
 
-    let coin = &hl.coin;
+    let coin = &v.hl.coin;
     let mut spans = vec![
         Span::styled(
-            format!("  ◈ {coin} Merged Order Book   "),
-            Style::default().fg(C_HEADER).add_modifier(Modifier::BOLD),
+            format!("  ◈ {coin}/{} Merged Order Book   ", v.quote_unit),
+            Style::default().fg(theme().header).add_modifier(Modifier::BOLD),
         ),
     ];
-    spans.extend(conn(hl,  C_HL));
-    spans.extend(conn(pdx, C_PDX));
+    if let Some(next) = v.carousel {
+        spans.push(Span::styled(format!("⟳ {}s   ", next.as_secs()), Style::default().fg(theme().dim)));
+    }
+    spans.extend(conn(v.hl,  v.hl_ticks,  v.hl_stats,  theme().hl));
+    spans.extend(conn(v.pdx, v.pdx_ticks, v.pdx_stats, theme().pdx));
 
-    let block = Block::default().borders(Borders::ALL).border_style(Style::default().fg(C_BORDER));
+    let block = Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme().border));
     frame.render_widget(Paragraph::new(Line::from(spans)).block(block), area);
 }
 
+// ─── Alert banner ─────────────────────────────────────────────────────────────
+
+fn draw_alert_banner(frame: &mut Frame, area: Rect, alerts: &[ActiveAlert]) {
+    if alerts.is_empty() {
+        return;
+    }
+    let now = now_ms();
+    let text = alerts.iter()
+        .map(|a| format!("{} ({}) {:.0}s", a.rule, a.when, now.saturating_sub(a.since_ms) as f64 / 1000.0))
+        .collect::<Vec<_>>()
+        .join("  ·  ");
+    let banner = Paragraph::new(Line::from(Span::styled(
+        format!(" ⚠ ALERT  {text}"),
+        Style::default().fg(theme().text).bg(theme().warn).add_modifier(Modifier::BOLD),
+    )))
+    .style(Style::default().bg(theme().warn));
+    frame.render_widget(banner, area);
+}
+
+fn draw_confirm_banner(frame: &mut Frame, area: Rect, what: &str) {
+    let banner = Paragraph::new(Line::from(Span::styled(
+        format!(" ⚠ LIVE  [Enter] {what}  [Esc] or any other key: drop it"),
+        Style::default().fg(Color::Black).bg(theme().arb).add_modifier(Modifier::BOLD),
+    )))
+    .style(Style::default().bg(theme().arb));
+    frame.render_widget(banner, area);
+}
+
+fn draw_risk_banner(frame: &mut Frame, area: Rect, block: &RiskBlock) {
+    let banner = Paragraph::new(Line::from(Span::styled(
+        format!(" ⛔ RISK LIMIT  {} order blocked: {}", block.source, block.reason),
+        Style::default().fg(theme().text).bg(theme().warn).add_modifier(Modifier::BOLD),
+    )))
+    .style(Style::default().bg(theme().warn));
+    frame.render_widget(banner, area);
+}
+
+/// `age` is how long ago the frozen books were taken.
+fn draw_pause_banner(frame: &mut Frame, area: Rect, age: Duration) {
+    let banner = Paragraph::new(Line::from(Span::styled(
+        format!(" ⏸ PAUSED  books as of {}s ago; feeds still running  [p] resume", age.as_secs()),
+        Style::default().fg(Color::Black).bg(theme().mid).add_modifier(Modifier::BOLD),
+    )))
+    .style(Style::default().bg(theme().mid));
+    frame.render_widget(banner, area);
+}
+
 // ─── Merged order book ────────────────────────────────────────────────────────
 
-fn draw_merged_book(frame: &mut Frame, area: Rect, merged: &MergedBook) {
+fn draw_merged_book(frame: &mut Frame, area: Rect, v: &View) {
     // Split: top half = asks (reversed, best at bottom), bottom half = bids
     let halves = Layout::vertical([
         Constraint::Percentage(50),
         Constraint::Percentage(50),
     ]).split(area);
 
-    draw_merged_side(frame, halves[0], &merged.asks, Side::Ask);
-    draw_merged_side(frame, halves[1], &merged.bids, Side::Bid);
+    draw_merged_side(frame, halves[0], v, Side::Ask);
+    draw_merged_side(frame, halves[1], v, Side::Bid);
+}
+
+/// Table state for a book side of `n` rows, `fit` of them in view, with the
+/// level `cursor` levels from the touch selected and scrolled into view.
+/// `reversed` = drawn deepest first, so unscrolled it shows the last rows.
+fn book_state(n: usize, fit: usize, cursor: Option<usize>, reversed: bool) -> TableState {
+    let cursor = cursor.filter(|_| n > 0).map(|c| c.min(n - 1));
+    let (offset, selected) = if reversed {
+        (n.saturating_sub(fit), cursor.map(|c| n - 1 - c))
+    } else {
+        (0, cursor)
+    };
+    TableState::new().with_offset(offset).with_selected(selected)
+}
+
+/// Size a market order of `size` takes from each level (best first).
+fn sweep_levels(levels: &[MergedLevel], size: f64) -> Vec<f64> {
+    let mut left = size;
+    levels.iter().map(|l| {
+        let take = l.size.min(left);
+        left -= take;
+        take
+    }).collect()
 }
 
 enum Side { Bid, Ask }
 
-fn draw_merged_side(frame: &mut Frame, area: Rect, levels: &[MergedLevel], side: Side) {
-    let (title, price_color, border_color) = match side {
-        Side::Bid => ("BIDS", C_BID, C_BID),
-        Side::Ask => ("ASKS", C_ASK, C_ASK),
+/// A stale venue's levels are greyed out.
+fn draw_merged_side(frame: &mut Frame, area: Rect, v: &View, side: Side) {
+    let (merged, flash, sweep_size) = (v.merged, v.flash, v.sweep_size);
+    let stale = (v.hl.status == FeedStatus::Stale, v.pdx.status == FeedStatus::Stale);
+    let (shown_bids, shown_asks) = merged.shown();
+    let (levels, title, price_color, border_color) = match side {
+        Side::Bid => (shown_bids, "BIDS", theme().bid, theme().bid),
+        Side::Ask => (shown_asks, "ASKS", theme().ask, theme().ask),
+    };
+    let title = match merged.policy.label() {
+        Some(label) => format!(" {title} · {label} "),
+        None        => format!(" {title} "),
     };
+    // A buy sweeps the asks, a sell the bids
+    let swept = if sweep_size > 0.0 { sweep_levels(levels, sweep_size) } else { vec![0.0; levels.len()] };
+    let sweep_label = (sweep_size > 0.0).then(|| {
+        let filled: f64 = swept.iter().sum();
+        let usd: f64 = levels.iter().zip(&swept).map(|(l, t)| l.price * t).sum();
+        let verb = match side { Side::Bid => "sell", Side::Ask => "buy" };
+        if filled < sweep_size {
+            format!("{verb} {sweep_size}: {:.4} short ", sweep_size - filled)
+        } else {
+            format!("{verb} {sweep_size} @ {:.2} ", usd / filled)
+        }
+    });
+    let border_color = if flash { theme().arb } else { border_color };
 
     let max_usd = levels.iter()
         .map(|l| l.price * l.size)
@@ -123,28 +557,54 @@ fn draw_merged_side(frame: &mut Frame, area: Rect, levels: &[MergedLevel], side:
         .max(1.0);
 
     let header = Row::new([
-        Cell::from("Exch").style(Style::default().fg(C_HEADER).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
-        Cell::from("Price").style(Style::default().fg(C_HEADER).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
-        Cell::from("Size").style(Style::default().fg(C_HEADER).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
-        Cell::from("Depth").style(Style::default().fg(C_HEADER).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
+        Cell::from("Exch").style(Style::default().fg(theme().header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
+        Cell::from("Price").style(Style::default().fg(theme().header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
+        Cell::from("Size").style(Style::default().fg(theme().header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
+        Cell::from("Depth").style(Style::default().fg(theme().header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
     ]).height(1);
 
-    // For asks, display bottom-to-top so best ask is closest to the midpoint
-    let display_levels: Vec<&MergedLevel> = match side {
-        Side::Ask => levels.iter().rev().collect(),
-        Side::Bid => levels.iter().collect(),
+    // For asks, display bottom-to-top so best ask is closest to the midpoint.
+    // Scrolled to the bottom, so a deep book hides its far asks rather than the best.
+    let fit = area.height.saturating_sub(3) as usize; // borders and header
+    let display_levels: Vec<(&MergedLevel, f64)> = match side {
+        Side::Ask => levels.iter().zip(swept.iter().copied()).rev().collect(),
+        Side::Bid => levels.iter().zip(swept.iter().copied()).collect(),
     };
+    let mut state = book_state(display_levels.len(), fit, v.book_cursor, matches!(side, Side::Ask));
 
-    let rows: Vec<Row> = display_levels.iter().map(|lvl| {
+    let rows: Vec<Row> = display_levels.iter().map(|&(lvl, taken)| {
         let bar_len = ((lvl.price * lvl.size) / max_usd * 14.0).round() as usize;
-        let bar = "█".repeat(bar_len);
-        let ex_color = ex_color(&lvl.exchange);
-        Row::new([
-            Cell::from(ex_tag(&lvl.exchange)).style(Style::default().fg(ex_color).add_modifier(Modifier::BOLD)),
+        let dim = match (lvl.split, &lvl.exchange) {
+            (Some(_), _)                  => stale.0 && stale.1,
+            (None, Exchange::Hyperliquid) => stale.0,
+            (None, Exchange::Paradex)     => stale.1,
+        };
+        let (ex_color, price_color) = if dim { (theme().dim, theme().dim) } else { (ex_color(&lvl.exchange), price_color) };
+        // A level both venues quote: the bar split by venue, HL first
+        let (tag, bar) = match lvl.split {
+            Some((hl, _)) if !dim => {
+                let hl_len = ((hl / lvl.size) * bar_len as f64).round() as usize;
+                ("H+P", Line::from(vec![
+                    Span::styled("█".repeat(hl_len), Style::default().fg(theme().hl)),
+                    Span::styled("█".repeat(bar_len - hl_len), Style::default().fg(theme().pdx)),
+                ]))
+            }
+            Some(_) => ("H+P", Line::from("█".repeat(bar_len))),
+            None    => (ex_tag(&lvl.exchange), Line::from("█".repeat(bar_len))),
+        };
+        // A partly swept level shows the size taken instead of the size resting
+        let (size, size_color) = if taken > 0.0 && taken < lvl.size {
+            (format!("▸{taken:.4}"), theme().mid)
+        } else {
+            (format!("{:.4}", lvl.size), if dim { theme().dim } else { theme().text })
+        };
+        let row = Row::new([
+            Cell::from(tag).style(Style::default().fg(ex_color).add_modifier(Modifier::BOLD)),
             Cell::from(format!("{:.2}", lvl.price)).style(Style::default().fg(price_color).add_modifier(Modifier::BOLD)),
-            Cell::from(format!("{:.4}", lvl.size)).style(Style::default().fg(C_WHITE)),
+            Cell::from(size).style(Style::default().fg(size_color)),
             Cell::from(bar).style(Style::default().fg(price_color)),
-        ]).height(1)
+        ]).height(1);
+        if taken > 0.0 { row.style(Style::default().bg(theme().sweep)) } else { row }
     }).collect();
 
     let widths = [
@@ -154,50 +614,71 @@ fn draw_merged_side(frame: &mut Frame, area: Rect, levels: &[MergedLevel], side:
         Constraint::Min(0),
     ];
 
-    let block = Block::default()
-        .title(Span::styled(format!(" {title} "), Style::default().fg(price_color).add_modifier(Modifier::BOLD)))
+    let mut block = Block::default()
+        .title(Span::styled(title, Style::default().fg(price_color).add_modifier(Modifier::BOLD)))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color));
+    if let Some(label) = sweep_label {
+        block = block.title(Line::from(Span::styled(label, Style::default().fg(theme().mid))).right_aligned());
+    }
 
-    frame.render_widget(
+    frame.render_stateful_widget(
         Table::new(rows, widths).header(header).block(block)
             .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
         area,
+        &mut state,
     );
 }
 
 // ─── Signals panel ────────────────────────────────────────────────────────────
 
-fn draw_signals(frame: &mut Frame, area: Rect, hl: &OrderBook, pdx: &OrderBook, merged: &MergedBook) {
-    let sig = &merged.signals;
+fn draw_signals(frame: &mut Frame, area: Rect, v: &View) {
+    let (hl, pdx, fees) = (v.hl, v.pdx, v.fees);
+    let sig = &v.merged.signals;
 
     let rows_area = Layout::vertical([
         Constraint::Length(3),  // panel title block
-        Constraint::Length(5),  // cross-exchange spread
+        Constraint::Length(if v.spread_history.is_some() { 9 } else { 8 }), // cross-exchange spread (+ sparkline)
+        Constraint::Length(1),  // spacer
+        Constraint::Length(10), // liquidity imbalance gauge, by depth, depth-weighted mid + order flow
+        Constraint::Length(match sig.liquidity_bands.len() { 0 => 0, n => n as u16 + 3 }), // liquidity within bps of mid
+        Constraint::Length(v.cvd.map_or(0, |c| c.windows.len() as u16 + 5)), // CVD (only when enabled)
         Constraint::Length(1),  // spacer
-        Constraint::Length(7),  // liquidity imbalance gauge
+        Constraint::Length(7),  // per-exchange bbo + fees
         Constraint::Length(1),  // spacer
-        Constraint::Length(6),  // per-exchange bbo
+        Constraint::Length(7),  // mark / index vs mid
+        Constraint::Length(if sig.vwap_notional > 0.0 { 6 } else { 0 }), // VWAP to notional (only when enabled)
+        Constraint::Length(if v.spreads.is_some() { 7 } else { 0 }), // rolling spread stats (only when enabled)
+        Constraint::Length(if v.vol.is_some() { 3 } else { 0 }), // realized volatility (only when enabled)
         Constraint::Min(0),
     ]).split(area);
 
     // ── Panel title ───────────────────────────────────────────────────────────
     let title_block = Block::default()
-        .title(Span::styled(" ◈ Signals ", Style::default().fg(C_MID).add_modifier(Modifier::BOLD)))
+        .title(Span::styled(" ◈ Signals ", Style::default().fg(theme().mid).add_modifier(Modifier::BOLD)))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(C_BORDER));
+        .border_style(Style::default().fg(theme().border));
     frame.render_widget(title_block, rows_area[0]);
 
     // ── Cross-exchange spread ─────────────────────────────────────────────────
-    let (spread_val, spread_color, arb_label) = match sig.cross_spread {
-        Some(s) if s < 0.0 => (
-            format!("{s:.4}"),
-            C_ARB,
-            " ⚡ ARB",
-        ),
-        Some(s) => (format!("{s:.4}"), C_BID, ""),
-        None    => ("–".to_string(), C_DIM, ""),
+    // The ARB flag requires a profit after taker fees; a gross-only cross is dimmed
+    let arb_label = if sig.is_arb() { " ⚡ ARB" } else { "" };
+    let conf_color = if sig.arb_confidence >= 0.8 { theme().bid } else if sig.arb_confidence >= 0.4 { theme().mid } else { theme().warn };
+    let conf_label = if sig.is_arb() { format!(" {:.0}%", sig.arb_confidence * 100.0) } else { String::new() };
+    let (spread_val, spread_color) = match sig.cross_spread {
+        Some(s) if s < 0.0 && sig.is_arb() => (format!("{s:.4}"), theme().arb),
+        Some(s) if s < 0.0 => (format!("{s:.4}"), theme().mid),
+        Some(s) => (format!("{s:.4}"), theme().bid),
+        None    => ("–".to_string(), theme().dim),
     };
+    let (net_val, net_color) = match sig.net_cross_spread {
+        Some(s) if s < 0.0 => (format!("{s:.4}"), theme().arb),
+        Some(s) => (format!("{s:.4}"), theme().dim),
+        None    => ("–".to_string(), theme().dim),
+    };
+    let net_pct = sig.net_cross_spread_pct
+        .map(|p| format!("({p:.4}%)"))
+        .unwrap_or_else(|| "–".to_string());
     let spread_pct = sig.cross_spread_pct
         .map(|p| format!("({p:.4}%)"))
         .unwrap_or_else(|| "–".to_string());
@@ -209,22 +690,53 @@ fn draw_signals(frame: &mut Frame, area: Rect, hl: &OrderBook, pdx: &OrderBook,
         .map(|e| Span::styled(format!("Best ask: {}", ex_tag(e)), Style::default().fg(ex_color(e))))
         .unwrap_or_else(|| Span::raw(""));
 
-    let spread_lines = vec![
+    // Locked / crossed flag. A cross that pays after fees already reads ARB.
+    let top_label = match &sig.top_of_book {
+        TopOfBook::Normal                  => String::new(),
+        TopOfBook::Locked                  => "  ⚠ LOCKED".to_string(),
+        TopOfBook::Crossed if sig.is_arb() => String::new(),
+        TopOfBook::Crossed                 => "  ⚠ CROSSED".to_string(),
+        TopOfBook::SelfCrossed(ex)         => format!("  ⚠ {} SELF-CROSSED", ex_tag(ex)),
+    };
+
+    let mut spread_lines = vec![
         Line::from(vec![
-            Span::styled("Cross Spread  ", Style::default().fg(C_HEADER).add_modifier(Modifier::BOLD)),
-            Span::styled(arb_label, Style::default().fg(C_ARB).add_modifier(Modifier::BOLD)),
+            Span::styled("Cross Spread  ", Style::default().fg(theme().header).add_modifier(Modifier::BOLD)),
+            Span::styled(arb_label, Style::default().fg(theme().arb).add_modifier(Modifier::BOLD)),
+            Span::styled(conf_label, Style::default().fg(conf_color)),
+            Span::styled(top_label, Style::default().fg(theme().warn).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(vec![
             Span::styled(format!("  {spread_val}  "), Style::default().fg(spread_color).add_modifier(Modifier::BOLD)),
-            Span::styled(spread_pct, Style::default().fg(C_DIM)),
+            Span::styled(spread_pct, Style::default().fg(theme().dim)),
         ]),
+        Line::from(vec![
+            Span::styled("  net ", Style::default().fg(theme().dim)),
+            Span::styled(format!("{net_val}  "), Style::default().fg(net_color).add_modifier(Modifier::BOLD)),
+            Span::styled(net_pct, Style::default().fg(theme().dim)),
+        ]),
+        if sig.is_arb() {
+            Line::from(Span::styled(
+                format!("  size {:.4} → ${}", sig.arb_size, fmt_usd(sig.arb_profit_usd)),
+                Style::default().fg(theme().arb),
+            ))
+        } else {
+            Line::from("")
+        },
         Line::from(vec![Span::raw("  "), best_bid_ex]),
         Line::from(vec![Span::raw("  "), best_ask_ex]),
     ];
+    if let Some(history) = v.spread_history {
+        spread_lines.push(spread_sparkline(history, rows_area[1].width.saturating_sub(4) as usize));
+    }
 
     let spread_block = Block::default()
+        .title_bottom(Line::from(Span::styled(
+            format!(" locked {} · crossed {} ", sig.locked_count, sig.crossed_count),
+            Style::default().fg(theme().dim),
+        )).right_aligned())
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(C_BORDER));
+        .border_style(Style::default().fg(theme().border));
     frame.render_widget(Paragraph::new(spread_lines).block(spread_block), rows_area[1]);
 
     // ── Liquidity Imbalance Ratio gauge ───────────────────────────────────────
@@ -236,87 +748,374 @@ fn draw_signals(frame: &mut Frame, area: Rect, hl: &OrderBook, pdx: &OrderBook,
                 r,
                 if r > 0.1 { "▲ BID HEAVY" } else if r < -0.1 { "▼ ASK HEAVY" } else { "≈ BALANCED" }
             );
-            let color = if r > 0.2 { C_BID } else if r < -0.2 { C_ASK } else { C_MID };
+            let color = if r > 0.2 { theme().bid } else if r < -0.2 { theme().ask } else { theme().mid };
             (pct, label, color)
         }
-        None => (50, "–".to_string(), C_DIM),
+        None => (50, "–".to_string(), theme().dim),
     };
 
     let bid_usd_str  = fmt_usd(sig.total_bid_usd);
     let ask_usd_str  = fmt_usd(sig.total_ask_usd);
 
     let imb_text = vec![
-        Line::from(Span::styled("Liquidity Imbalance", Style::default().fg(C_HEADER).add_modifier(Modifier::BOLD))),
+        Line::from(Span::styled("Liquidity Imbalance", Style::default().fg(theme().header).add_modifier(Modifier::BOLD))),
         Line::from(Span::styled(format!("  {imb_label}"), Style::default().fg(imb_color).add_modifier(Modifier::BOLD))),
         Line::from(vec![
-            Span::styled(format!("  Bid ${bid_usd_str}"), Style::default().fg(C_BID)),
-            Span::styled(format!("  Ask ${ask_usd_str}"), Style::default().fg(C_ASK)),
+            Span::styled(format!("  Bid ${bid_usd_str}"), Style::default().fg(theme().bid)),
+            Span::styled(format!("  Ask ${ask_usd_str}"), Style::default().fg(theme().ask)),
+        ]),
+        imbalance_depths_line(sig),
+        Line::from(vec![
+            Span::styled("  DW mid ", Style::default().fg(theme().dim)),
+            Span::styled(
+                sig.depth_weighted_mid.map(|m| format!("{m:.2}")).unwrap_or("–".into()),
+                Style::default().fg(theme().mid),
+            ),
+            Span::styled(
+                sig.depth_weighted_mid_bps.map(|d| format!("  {d:+.1} bps vs mid")).unwrap_or_default(),
+                Style::default().fg(match sig.depth_weighted_mid_bps {
+                    Some(d) if d > 0.0 => theme().bid,
+                    Some(d) if d < 0.0 => theme().ask,
+                    _ => theme().dim,
+                }),
+            ),
         ]),
+        ofi_line(sig),
     ];
 
-    let imb_block = Block::default()
+    let mut imb_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(C_BORDER));
+        .border_style(Style::default().fg(theme().border));
+    if sig.outliers > 0 {
+        imb_block = imb_block.title(Line::from(Span::styled(
+            format!(" {} outlier levels dropped ", sig.outliers),
+            Style::default().fg(theme().warn),
+        )).right_aligned());
+    }
 
     // Render text above a gauge
     let imb_inner = Layout::vertical([
-        Constraint::Length(4),
+        Constraint::Length(7),
         Constraint::Length(1),
     ]).split(rows_area[3]);
 
     frame.render_widget(Paragraph::new(imb_text).block(imb_block), rows_area[3]);
     let gauge = Gauge::default()
-        .gauge_style(Style::default().fg(imb_color).bg(Color::Rgb(30, 30, 50)))
+        .gauge_style(Style::default().fg(imb_color).bg(theme().track))
         .percent(imb_ratio.min(100))
         .label(Span::raw(""));
     frame.render_widget(gauge, imb_inner[1]);
 
+    // ── Liquidity within bps of mid ───────────────────────────────────────────
+    if !sig.liquidity_bands.is_empty() {
+        draw_liquidity_bands(frame, rows_area[4], &sig.liquidity_bands);
+    }
+
+    // ── Cumulative volume delta ───────────────────────────────────────────────
+    if let Some(cvd) = v.cvd {
+        draw_cvd(frame, rows_area[5], cvd);
+    }
+
     // ── Per-exchange BBO ──────────────────────────────────────────────────────
     let bbo_lines = vec![
-        Line::from(Span::styled("Per-Exchange BBO", Style::default().fg(C_HEADER).add_modifier(Modifier::BOLD))),
+        Line::from(Span::styled("Per-Exchange BBO", Style::default().fg(theme().header).add_modifier(Modifier::BOLD))),
+        Line::from([vec![Span::styled("  HL  ", Style::default().fg(theme().hl).add_modifier(Modifier::BOLD))], bbo_spans(hl, v.hl_ticks)].concat()),
         Line::from(vec![
-            Span::styled("  HL  ", Style::default().fg(C_HL).add_modifier(Modifier::BOLD)),
+            Span::styled("  HL  spread: ", Style::default().fg(theme().dim)),
             Span::styled(
-                fmt_bbo(hl.best_bid(), hl.best_ask()),
-                Style::default().fg(C_WHITE),
+                hl.spread().map(|s| format!("{s:.4}")).unwrap_or("–".into()),
+                Style::default().fg(theme().dim),
             ),
         ]),
+        Line::from([vec![Span::styled("  PDX ", Style::default().fg(theme().pdx).add_modifier(Modifier::BOLD))], bbo_spans(pdx, v.pdx_ticks)].concat()),
         Line::from(vec![
-            Span::styled("  HL  spread: ", Style::default().fg(C_DIM)),
+            Span::styled("  PDX spread: ", Style::default().fg(theme().dim)),
             Span::styled(
-                hl.spread().map(|s| format!("{s:.4}")).unwrap_or("–".into()),
-                Style::default().fg(C_DIM),
+                pdx.spread().map(|s| format!("{s:.4}")).unwrap_or("–".into()),
+                Style::default().fg(theme().dim),
             ),
         ]),
-        Line::from(vec![
-            Span::styled("  PDX ", Style::default().fg(C_PDX).add_modifier(Modifier::BOLD)),
-            Span::styled(
-                fmt_bbo(pdx.best_bid(), pdx.best_ask()),
-                Style::default().fg(C_WHITE),
+        Line::from(Span::styled(
+            format!(
+                "  fees m/t bps  HL {}/{}  PDX {}/{}",
+                fees.hl.maker_bps, fees.hl.taker_bps, fees.pdx.maker_bps, fees.pdx.taker_bps,
             ),
+            Style::default().fg(theme().dim),
+        )),
+    ];
+
+    let bbo_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+    frame.render_widget(Paragraph::new(bbo_lines).block(bbo_block), rows_area[7]);
+
+    // ── Mark / index vs mid ───────────────────────────────────────────────────
+    fn mark_lines(tag: &'static str, book: &OrderBook, color: Color) -> [Line<'static>; 2] {
+        let px = |v: Option<f64>| v.map(|p| format!("{p:.2}")).unwrap_or("–".into());
+        let (div, div_color) = match book.mark_divergence_bps() {
+            Some(d) if d.abs() >= MARK_DIV_WARN_BPS => (format!("{d:+.1} bps ⚠"), theme().warn),
+            Some(d) => (format!("{d:+.1} bps"), theme().dim),
+            None    => ("–".to_string(), theme().dim),
+        };
+        [
+            Line::from(vec![
+                Span::styled(format!("  {tag:<4}"), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("mark {}  idx {}", px(book.mark_px), px(book.index_px)), Style::default().fg(theme().text)),
+            ]),
+            Line::from(vec![
+                Span::styled(format!("  {tag:<4}mark−mid: "), Style::default().fg(theme().dim)),
+                Span::styled(div, Style::default().fg(div_color)),
+            ]),
+        ]
+    }
+
+    let mut mark_text = vec![
+        Line::from(Span::styled("Mark / Index", Style::default().fg(theme().header).add_modifier(Modifier::BOLD))),
+    ];
+    mark_text.extend(mark_lines("HL", hl, theme().hl));
+    mark_text.extend(mark_lines("PDX", pdx, theme().pdx));
+
+    let mark_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+    frame.render_widget(Paragraph::new(mark_text).block(mark_block), rows_area[9]);
+
+    // ── VWAP to notional ──────────────────────────────────────────────────────
+    if sig.vwap_notional > 0.0 {
+        draw_vwap(frame, rows_area[10], v.merged);
+    }
+
+    // ── Rolling spread statistics ─────────────────────────────────────────────
+    if let Some(spreads) = v.spreads {
+        draw_spread_stats(frame, rows_area[11], spreads);
+    }
+
+    // ── Realized volatility ───────────────────────────────────────────────────
+    if let Some(vol) = v.vol {
+        let mut spans = vec![Span::styled("RV ann. ", Style::default().fg(theme().header).add_modifier(Modifier::BOLD))];
+        for w in &vol.windows {
+            let label = if w.secs.is_multiple_of(60) { format!("{}m", w.secs / 60) } else { format!("{}s", w.secs) };
+            spans.push(Span::styled(format!(" {label} "), Style::default().fg(theme().dim)));
+            spans.push(Span::styled(
+                w.vol_pct.map(|p| format!("{p:.1}%")).unwrap_or("–".into()),
+                Style::default().fg(theme().text),
+            ));
+        }
+        let vol_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().border));
+        frame.render_widget(Paragraph::new(Line::from(spans)).block(vol_block), rows_area[12]);
+    }
+}
+
+/// Average fill price of a market buy / sell of the configured notional on each
+/// venue and across both books, with its cost in bps from the merged mid.
+fn draw_vwap(frame: &mut Frame, area: Rect, merged: &MergedBook) {
+    let sig = &merged.signals;
+    let mid = match (merged.bids.first(), merged.asks.first()) {
+        (Some(b), Some(a)) => Some((b.price + a.price) / 2.0),
+        _ => None,
+    };
+    let leg = |px: Option<f64>, color: Color| -> Vec<Span<'static>> {
+        let Some(px) = px else { return vec![Span::styled(format!("{:<19}", "thin"), Style::default().fg(theme().dim))] };
+        let cost = mid.map(|m| format!(" {:>4.1}", (px - m).abs() / m * 10_000.0)).unwrap_or_default();
+        vec![
+            Span::styled(format!("{px:>10.2}"), Style::default().fg(color)),
+            Span::styled(format!("{cost:<9}"), Style::default().fg(theme().dim)),
+        ]
+    };
+    let row = |tag: &'static str, tag_color: Color, vwap: &Vwap| {
+        let mut spans = vec![Span::styled(format!("  {tag:<4}"), Style::default().fg(tag_color).add_modifier(Modifier::BOLD))];
+        spans.extend(leg(vwap.buy, theme().ask));
+        spans.extend(leg(vwap.sell, theme().bid));
+        Line::from(spans)
+    };
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(format!("VWAP ${}", fmt_usd(sig.vwap_notional)), Style::default().fg(theme().header).add_modifier(Modifier::BOLD)),
+            Span::styled("  buy / sell, bps", Style::default().fg(theme().dim)),
+        ]),
+        row("HL", theme().hl, &sig.hl_vwap),
+        row("PDX", theme().pdx, &sig.pdx_vwap),
+        row("ALL", theme().mid, &sig.merged_vwap),
+    ];
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Cross spread history squeezed into `width` cells, each the tightest spread
+/// of its slice so a brief arb isn't averaged away. Bars scale between the
+/// window's min and max; negative (crossed) slices are drawn in the arb colour.
+fn spread_sparkline(history: &VecDeque<Option<f64>>, width: usize) -> Line<'static> {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    if width == 0 || history.is_empty() {
+        return Line::from("");
+    }
+    let per_cell = history.len().div_ceil(width);
+    let cells: Vec<Option<f64>> = history.iter().copied().collect::<Vec<_>>()
+        .chunks(per_cell)
+        .map(|c| c.iter().flatten().copied().reduce(f64::min))
+        .collect();
+    let (lo, hi) = cells.iter().flatten().fold((f64::MAX, f64::MIN), |(lo, hi), &x| (lo.min(x), hi.max(x)));
+    let mut spans = vec![Span::raw("  ")];
+    spans.extend(cells.iter().map(|cell| match *cell {
+        Some(x) => {
+            let level = if hi > lo { ((x - lo) / (hi - lo) * 7.0).round() as usize } else { 0 };
+            let color = if x < 0.0 { theme().arb } else { theme().dim };
+            Span::styled(BARS[level.min(7)].to_string(), Style::default().fg(color))
+        }
+        None => Span::raw(" "),
+    }));
+    Line::from(spans)
+}
+
+/// Mean / median / p95 of each spread over the rolling window, and how much of
+/// the time the venues were crossed.
+fn draw_spread_stats(frame: &mut Frame, area: Rect, st: &SpreadStats) {
+    let window = if st.window_secs.is_multiple_of(60) { format!("{}m", st.window_secs / 60) } else { format!("{}s", st.window_secs) };
+    let row = |tag: &'static str, color: Color, sum: Option<SpreadSummary>| {
+        let vals = sum
+            .map(|s| format!("{:>6.2} {:>6.2} {:>6.2}", s.mean, s.median, s.p95))
+            .unwrap_or_else(|| format!("{:>6}", "–"));
+        Line::from(vec![
+            Span::styled(format!("  {tag:<6}"), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::styled(vals, Style::default().fg(theme().text)),
+        ])
+    };
+    let pct = |p: Option<f64>| p.map(|p| format!("{p:.1}%")).unwrap_or("–".into());
+    let neg_color = if st.neg_cross_pct.is_some_and(|p| p > 0.0) { theme().arb } else { theme().dim };
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(format!("Spread {window}"), Style::default().fg(theme().header).add_modifier(Modifier::BOLD)),
+            Span::styled("  bps  mean    med    p95", Style::default().fg(theme().dim)),
         ]),
+        row("HL", theme().hl, st.hl),
+        row("PDX", theme().pdx, st.pdx),
+        row("Cross", theme().mid, st.cross),
         Line::from(vec![
-            Span::styled("  PDX spread: ", Style::default().fg(C_DIM)),
+            Span::styled("  crossed ", Style::default().fg(theme().dim)),
+            Span::styled(format!("{} ({window})", pct(st.neg_cross_pct)), Style::default().fg(neg_color)),
             Span::styled(
-                pdx.spread().map(|s| format!("{s:.4}")).unwrap_or("–".into()),
-                Style::default().fg(C_DIM),
+                format!("  session {:.0}s / {}", st.session_neg_cross_secs, pct(st.session_neg_cross_pct())),
+                Style::default().fg(theme().dim),
             ),
         ]),
     ];
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
 
-    let bbo_block = Block::default()
+/// Bid / ask USD within each band of the merged mid across both venues, with
+/// the imbalance inside that band.
+fn draw_liquidity_bands(frame: &mut Frame, area: Rect, bands: &[LiquidityBand]) {
+    let mut lines = vec![Line::from(Span::styled(
+        "Liquidity Near Mid",
+        Style::default().fg(theme().header).add_modifier(Modifier::BOLD),
+    ))];
+    for b in bands {
+        let (imb, imb_color) = match b.imbalance() {
+            Some(r) => (format!("{r:+.2}"), if r > 0.2 { theme().bid } else if r < -0.2 { theme().ask } else { theme().mid }),
+            None    => ("–".to_string(), theme().dim),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  ±{:<4}bp", b.bps), Style::default().fg(theme().dim)),
+            Span::styled(format!(" Bid ${:<8}", fmt_usd(b.bid_usd)), Style::default().fg(theme().bid)),
+            Span::styled(format!("Ask ${:<8}", fmt_usd(b.ask_usd)), Style::default().fg(theme().ask)),
+            Span::styled(imb, Style::default().fg(imb_color)),
+        ]));
+    }
+    let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(C_BORDER));
-    frame.render_widget(Paragraph::new(bbo_lines).block(bbo_block), rows_area[5]);
+        .border_style(Style::default().fg(theme().border));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Imbalance ratio over the top 1 / 5 / 20 merged levels.
+fn imbalance_depths_line(sig: &Signals) -> Line<'static> {
+    let mut spans = vec![Span::styled("  top", Style::default().fg(theme().dim))];
+    for (n, r) in IMBALANCE_DEPTHS.iter().zip(sig.imbalance_by_depth) {
+        let (val, color) = match r {
+            Some(r) => (format!("{r:+.2}"), if r > 0.2 { theme().bid } else if r < -0.2 { theme().ask } else { theme().mid }),
+            None    => ("–".to_string(), theme().dim),
+        };
+        spans.push(Span::styled(format!(" {n}:"), Style::default().fg(theme().dim)));
+        spans.push(Span::styled(val, Style::default().fg(color)));
+    }
+    Line::from(spans)
+}
+
+/// Order flow imbalance near the touch over the merger's window, per venue and combined.
+fn ofi_line(sig: &Signals) -> Line<'static> {
+    let signed = |x: Option<f64>| match x {
+        Some(x) => {
+            let c = if x > 0.0 { theme().bid } else if x < 0.0 { theme().ask } else { theme().dim };
+            Span::styled(format!("{x:+.2}"), Style::default().fg(c))
+        }
+        None => Span::styled("–", Style::default().fg(theme().dim)),
+    };
+    Line::from(vec![
+        Span::styled("  OFI ", Style::default().fg(theme().dim)),
+        Span::styled("HL ", Style::default().fg(theme().hl)),
+        signed(sig.hl_ofi),
+        Span::styled(" PDX ", Style::default().fg(theme().pdx)),
+        signed(sig.pdx_ofi),
+        Span::styled(" Σ ", Style::default().fg(theme().dim)),
+        signed(sig.ofi),
+    ])
+}
+
+/// Per-window CVD by venue, then a sparkline of the session CVD. Green when
+/// aggressive buying dominates, agreeing with a bid-heavy imbalance.
+fn draw_cvd(frame: &mut Frame, area: Rect, cvd: &CvdState) {
+    let signed = |x: f64| {
+        let c = if x > 0.0 { theme().bid } else if x < 0.0 { theme().ask } else { theme().dim };
+        Span::styled(format!("{x:+.3}"), Style::default().fg(c))
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled("CVD  ", Style::default().fg(theme().header).add_modifier(Modifier::BOLD)),
+        Span::styled("session ", Style::default().fg(theme().dim)),
+        signed(cvd.session),
+    ])];
+    for w in &cvd.windows {
+        let label = if w.secs % 60 == 0 { format!("{}m", w.secs / 60) } else { format!("{}s", w.secs) };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {label:<4}"), Style::default().fg(theme().dim)),
+            Span::styled("HL ", Style::default().fg(theme().hl)),
+            signed(w.hl),
+            Span::styled(" PDX ", Style::default().fg(theme().pdx)),
+            signed(w.pdx),
+            Span::styled(" Σ ", Style::default().fg(theme().dim)),
+            signed(w.combined()),
+        ]));
+    }
+
+    let block = Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme().border));
+    let inner = block.inner(area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    // Sparkline: the rightmost `width` samples, shifted so the lowest is 0
+    let width = inner.width as usize;
+    let tail: Vec<f64> = cvd.history.iter().skip(cvd.history.len().saturating_sub(width)).copied().collect();
+    let low = tail.iter().copied().fold(f64::INFINITY, f64::min);
+    let data: Vec<u64> = tail.iter().map(|x| ((x - low) * 1_000.0) as u64).collect();
+    let spark_area = Rect { y: inner.bottom().saturating_sub(2), height: 2.min(inner.height), ..inner };
+    let color = if cvd.session >= 0.0 { theme().bid } else { theme().ask };
+    frame.render_widget(Sparkline::default().data(&data).style(Style::default().fg(color)), spark_area);
 }
 
 // ─── Individual exchange book (compact) ───────────────────────────────────────
 
-fn draw_individual_book(frame: &mut Frame, area: Rect, book: &OrderBook) {
+/// `cursor` selects the level that many from the touch on both sides.
+fn draw_individual_book(frame: &mut Frame, area: Rect, book: &OrderBook, ticks: &BookTicks, depth: PanelDepth, cursor: Option<usize>) {
     let accent = ex_color(&book.exchange);
     let label  = book.exchange.label();
-    let conn   = if book.connected { "●" } else { "○" };
-    let conn_c = if book.connected { C_BID } else { C_WARN };
+    let (conn, conn_c) = conn_dot(book);
 
     // Split: title(3) | asks(%) | bids(%)
     let parts = Layout::vertical([
@@ -326,17 +1125,19 @@ fn draw_individual_book(frame: &mut Frame, area: Rect, book: &OrderBook) {
     ]).split(area);
 
     // Title
-    let title_line = Line::from(vec![
+    let mut title_line = Line::from(vec![
         Span::styled(format!(" {label} "), Style::default().fg(accent).add_modifier(Modifier::BOLD)),
         Span::styled(conn, Style::default().fg(conn_c)),
-        Span::styled(
-            match book.mid() {
-                Some(m) => format!(" ${m:.2}"),
-                None    => " –".to_string(),
-            },
-            Style::default().fg(C_MID),
-        ),
+        Span::raw(" "),
+        tick_span(book.mid().map(|m| format!("${m:.2}")), &ticks.mid, theme().mid),
     ]);
+    let status = match book.status {
+        FeedStatus::Live   => String::new(),
+        FeedStatus::Stale  => format!("  STALE {:.1}s", book.age_ms(now_ms()).unwrap_or(0) as f64 / 1000.0),
+        FeedStatus::Failed => format!("  FAILED — {} failed reconnects", book.reconnect_failures),
+        other              => format!("  {}", other.label()),
+    };
+    title_line.push_span(Span::styled(status, Style::default().fg(conn_c).add_modifier(Modifier::BOLD)));
     frame.render_widget(
         Paragraph::new(title_line).block(
             Block::default().borders(Borders::ALL).border_style(Style::default().fg(accent))
@@ -344,31 +1145,35 @@ fn draw_individual_book(frame: &mut Frame, area: Rect, book: &OrderBook) {
         parts[0],
     );
 
-    draw_indiv_side(frame, parts[1], book, IndivSide::Ask, accent);
-    draw_indiv_side(frame, parts[2], book, IndivSide::Bid, accent);
+    draw_indiv_side(frame, parts[1], book, IndivSide::Ask, accent, depth.asks, cursor);
+    draw_indiv_side(frame, parts[2], book, IndivSide::Bid, accent, depth.bids, cursor);
 }
 
 enum IndivSide { Bid, Ask }
 
-fn draw_indiv_side(frame: &mut Frame, area: Rect, book: &OrderBook, side: IndivSide, accent: Color) {
+/// `depth` levels of one side, best first; 0 = all the feed keeps.
+fn draw_indiv_side(frame: &mut Frame, area: Rect, book: &OrderBook, side: IndivSide, accent: Color, depth: usize, cursor: Option<usize>) {
     let (levels, title, price_color) = match side {
-        IndivSide::Bid => (&book.bids, "BIDS", C_BID),
-        IndivSide::Ask => (&book.asks, "ASKS", C_ASK),
+        IndivSide::Bid => (&book.bids, "BIDS", theme().bid),
+        IndivSide::Ask => (&book.asks, "ASKS", theme().ask),
     };
+    let levels = if depth == 0 { &levels[..] } else { &levels[..depth.min(levels.len())] };
 
+    let price_color = if book.status == FeedStatus::Stale { theme().dim } else { price_color };
+    let size_color  = if book.status == FeedStatus::Stale { theme().dim } else { theme().text };
     let max_sz = levels.iter().map(|l| l.size_f64()).fold(0.0_f64, f64::max).max(1.0);
 
     let header = Row::new([
-        Cell::from("Price").style(Style::default().fg(C_HEADER).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
-        Cell::from("Size").style(Style::default().fg(C_HEADER).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
-        Cell::from("▐").style(Style::default().fg(C_HEADER)),
+        Cell::from("Price").style(Style::default().fg(theme().header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
+        Cell::from("Size").style(Style::default().fg(theme().header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
+        Cell::from("▐").style(Style::default().fg(theme().header)),
     ]).height(1);
 
     let rows: Vec<Row> = levels.iter().map(|lvl| {
         let bar = "█".repeat(((lvl.size_f64() / max_sz) * 6.0).round() as usize);
         Row::new([
             Cell::from(format!("{:.2}", lvl.price_f64())).style(Style::default().fg(price_color).add_modifier(Modifier::BOLD)),
-            Cell::from(format!("{:.3}", lvl.size_f64())).style(Style::default().fg(C_WHITE)),
+            Cell::from(format!("{:.3}", lvl.size_f64())).style(Style::default().fg(size_color)),
             Cell::from(bar).style(Style::default().fg(price_color)),
         ]).height(1)
     }).collect();
@@ -379,28 +1184,769 @@ fn draw_indiv_side(frame: &mut Frame, area: Rect, book: &OrderBook, side: IndivS
         .border_style(Style::default().fg(accent));
 
     let widths = [Constraint::Length(10), Constraint::Length(9), Constraint::Min(0)];
-    frame.render_widget(
+    let mut state = book_state(levels.len(), area.height.saturating_sub(3) as usize, cursor, false);
+    frame.render_stateful_widget(
         Table::new(rows, widths).header(header).block(block)
             .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
         area,
+        &mut state,
     );
 }
 
+// ─── Trade tape ───────────────────────────────────────────────────────────────
+
+fn draw_trade_tape(frame: &mut Frame, area: Rect, trades: &VecDeque<Trade>) {
+    let hdr = Style::default().fg(theme().header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let header = Row::new(["Time", "Ex", "Price", "Size"].map(|h| Cell::from(h).style(hdr))).height(1);
+
+    // Coloured by aggressor: buys lift the ask, sells hit the bid
+    let rows: Vec<Row> = trades.iter().map(|t| {
+        let time = DateTime::from_timestamp_millis(t.ts_ms as i64)
+            .map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string())
+            .unwrap_or_default();
+        let color = match t.side {
+            crate::types::Side::Buy  => theme().bid,
+            crate::types::Side::Sell => theme().ask,
+        };
+        let mut px_style = Style::default().fg(color);
+        if t.liquidation {
+            px_style = px_style.add_modifier(Modifier::REVERSED);
+        }
+        Row::new([
+            Cell::from(time).style(Style::default().fg(theme().dim)),
+            Cell::from(ex_tag(&t.exchange)).style(Style::default().fg(ex_color(&t.exchange))),
+            Cell::from(format!("{:.2}", t.price)).style(px_style),
+            Cell::from(format!("{:.4}", t.size)).style(Style::default().fg(color)),
+        ]).height(1)
+    }).collect();
+
+    let block = Block::default()
+        .title(Span::styled(" TRADES ", Style::default().fg(theme().header).add_modifier(Modifier::BOLD)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+
+    let widths = [Constraint::Length(8), Constraint::Length(3), Constraint::Length(10), Constraint::Min(0)];
+    frame.render_widget(Table::new(rows, widths).header(header).block(block), area);
+}
+
+// ─── Arb log ──────────────────────────────────────────────────────────────────
+
+fn draw_arb_log(frame: &mut Frame, area: Rect, log: &VecDeque<ArbWindow>, scroll: usize) {
+    let now = now_ms();
+    let hdr = Style::default().fg(theme().header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let header = Row::new(
+        ["Start", "Duration", "Min spread", "Spread %", "Size", "Buy", "Sell", "Est. profit"]
+            .map(|h| Cell::from(h).style(hdr)),
+    ).height(1);
+
+    let scroll = scroll.min(log.len().saturating_sub(1));
+    let rows: Vec<Row> = log.iter().skip(scroll).map(|w| {
+        let start = DateTime::from_timestamp_millis(w.opened_ms as i64)
+            .map(|t| t.with_timezone(&Local).format("%H:%M:%S%.3f").to_string())
+            .unwrap_or_default();
+        let (duration, dur_color) = match w.closed_ms {
+            Some(_) => (format!("{:.3}s", w.duration_ms(now) as f64 / 1000.0), theme().text),
+            None    => (format!("{:.3}s ●", w.duration_ms(now) as f64 / 1000.0), theme().arb),
+        };
+        Row::new([
+            Cell::from(start).style(Style::default().fg(theme().dim)),
+            Cell::from(duration).style(Style::default().fg(dur_color)),
+            Cell::from(format!("{:.4}", w.min_spread)).style(Style::default().fg(theme().arb).add_modifier(Modifier::BOLD)),
+            Cell::from(format!("{:.4}%", w.min_spread_pct)).style(Style::default().fg(theme().arb)),
+            Cell::from(format!("{:.4}", w.size)).style(Style::default().fg(theme().text)),
+            Cell::from(format!("{} @ {:.2}", ex_tag(&w.ask_exchange), w.ask_price)).style(Style::default().fg(ex_color(&w.ask_exchange))),
+            Cell::from(format!("{} @ {:.2}", ex_tag(&w.bid_exchange), w.bid_price)).style(Style::default().fg(ex_color(&w.bid_exchange))),
+            Cell::from(format!("${}", fmt_usd(w.profit_usd))).style(Style::default().fg(theme().bid)),
+        ]).height(1)
+    }).collect();
+
+    let title = if log.is_empty() {
+        " ARB LOG ".to_string()
+    } else {
+        format!(" ARB LOG  {}/{}  [PgUp/PgDn] ", scroll + 1, log.len())
+    };
+    let block = Block::default()
+        .title(Span::styled(title, Style::default().fg(theme().arb).add_modifier(Modifier::BOLD)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+
+    let widths = [
+        Constraint::Length(13),
+        Constraint::Length(11),
+        Constraint::Length(11),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(15),
+        Constraint::Length(15),
+        Constraint::Min(0),
+    ];
+    frame.render_widget(Table::new(rows, widths).header(header).block(block), area);
+}
+
+// ─── Liquidations ─────────────────────────────────────────────────────────────
+
+fn draw_liquidations(frame: &mut Frame, area: Rect, tape: &LiquidationTape) {
+    let hdr = Style::default().fg(theme().header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let header = Row::new(["Time", "Venue", "Liq", "Price", "Notional"].map(|h| Cell::from(h).style(hdr))).height(1);
+
+    // A forced sell closes a long, a forced buy closes a short
+    let rows: Vec<Row> = tape.recent.iter().map(|t| {
+        let time = DateTime::from_timestamp_millis(t.ts_ms as i64)
+            .map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string())
+            .unwrap_or_default();
+        let (kind, color) = match t.side {
+            crate::types::Side::Sell => ("LONG", theme().ask),
+            crate::types::Side::Buy  => ("SHORT", theme().bid),
+        };
+        Row::new([
+            Cell::from(time).style(Style::default().fg(theme().dim)),
+            Cell::from(ex_tag(&t.exchange)).style(Style::default().fg(ex_color(&t.exchange))),
+            Cell::from(kind).style(Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Cell::from(format!("{:.2}", t.price)).style(Style::default().fg(theme().text)),
+            Cell::from(format!("${}", fmt_usd(t.notional()))).style(Style::default().fg(color)),
+        ]).height(1)
+    }).collect();
+
+    // Session totals; Hyperliquid doesn't flag liquidations publicly, so it stays "–"
+    let totals = |tag: &str, t: &LiqTotals| match t.count {
+        0 => format!("{tag} –"),
+        _ => format!("{tag} L ${} S ${}", fmt_usd(t.longs_usd), fmt_usd(t.shorts_usd)),
+    };
+    let title = format!(" LIQUIDATIONS  {} · {} ", totals("PDX", &tape.pdx), totals("HL", &tape.hl));
+    let block = Block::default()
+        .title(Span::styled(title, Style::default().fg(theme().warn).add_modifier(Modifier::BOLD)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+
+    let widths = [
+        Constraint::Length(9),
+        Constraint::Length(4),
+        Constraint::Length(6),
+        Constraint::Length(10),
+        Constraint::Min(0),
+    ];
+    frame.render_widget(Table::new(rows, widths).header(header).block(block), area);
+}
+
+// ─── Positions + PnL ──────────────────────────────────────────────────────────
+
+fn draw_positions(frame: &mut Frame, area: Rect, sim: &SimState, hl: &OrderBook, pdx: &OrderBook) {
+    let hdr = Style::default().fg(theme().header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let header = Row::new(["Venue", "Qty", "Entry", "Mark", "uPnL", "rPnL", "Fees"].map(|h| Cell::from(h).style(hdr)))
+        .height(1);
+    let pnl_style = |v: f64| Style::default().fg(if v > 0.0 { theme().bid } else if v < 0.0 { theme().ask } else { theme().dim });
+
+    let mut total_upnl = 0.0;
+    let mut rows: Vec<Row> = [hl, pdx].into_iter().map(|book| {
+        let pos = sim.position(&book.exchange);
+        // Marked to this venue's own mid; no mark until its book has both sides
+        let mark = book.mid();
+        let upnl = mark.map(|m| (m - pos.avg_entry) * pos.qty).unwrap_or(0.0);
+        total_upnl += upnl;
+        Row::new([
+            Cell::from(ex_tag(&book.exchange)).style(Style::default().fg(ex_color(&book.exchange)).add_modifier(Modifier::BOLD)),
+            Cell::from(format!("{:+.4}", pos.qty)).style(Style::default().fg(theme().text)),
+            Cell::from(if pos.qty == 0.0 { "–".to_string() } else { format!("{:.2}", pos.avg_entry) }).style(Style::default().fg(theme().dim)),
+            Cell::from(mark.map(|m| format!("{m:.2}")).unwrap_or("–".into())).style(Style::default().fg(theme().dim)),
+            Cell::from(format!("{upnl:+.2}")).style(pnl_style(upnl)),
+            Cell::from(format!("{:+.2}", pos.realized_pnl)).style(pnl_style(pos.realized_pnl)),
+            Cell::from(format!("{:.2}", pos.fees_paid)).style(Style::default().fg(theme().dim)),
+        ]).height(1)
+    }).collect();
+
+    let realized = sim.hl.realized_pnl + sim.pdx.realized_pnl;
+    let fees = sim.hl.fees_paid + sim.pdx.fees_paid;
+    let net = realized + total_upnl - fees;
+    rows.push(Row::new([
+        Cell::from("Net").style(Style::default().fg(theme().header).add_modifier(Modifier::BOLD)),
+        Cell::from(format!("{:+.4}", sim.hl.qty + sim.pdx.qty)).style(Style::default().fg(theme().dim)),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(format!("{total_upnl:+.2}")).style(pnl_style(total_upnl)),
+        Cell::from(format!("{realized:+.2}")).style(pnl_style(realized)),
+        Cell::from(format!("{fees:.2}")).style(Style::default().fg(theme().dim)),
+    ]).height(1));
+
+    let block = Block::default()
+        .title(Span::styled(" POSITIONS (SIM) ", Style::default().fg(theme().mid).add_modifier(Modifier::BOLD)))
+        .title(Line::from(Span::styled(format!(" PnL ${net:+.2} "), pnl_style(net).add_modifier(Modifier::BOLD))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+
+    let widths = [
+        Constraint::Length(5),
+        Constraint::Length(9),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Min(0),
+    ];
+    frame.render_widget(Table::new(rows, widths).header(header).block(block), area);
+}
+
+/// Live account as reported by the venues' private channels: equity, free
+/// collateral and the position in the displayed market, then the latest fills.
+fn draw_account(frame: &mut Frame, area: Rect, accounts: &[AccountState], hl: &OrderBook, pdx: &OrderBook) {
+    let hdr = Style::default().fg(theme().header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let header = Row::new(["Venue", "Equity", "Free", "Pos", "Entry", "uPnL"].map(|h| Cell::from(h).style(hdr)))
+        .height(1);
+    let pnl_style = |v: f64| Style::default().fg(if v > 0.0 { theme().bid } else if v < 0.0 { theme().ask } else { theme().dim });
+    let usd = |v: Option<f64>| v.map(fmt_usd).unwrap_or("–".into());
+
+    let rows: Vec<Row> = accounts.iter().map(|acct| {
+        let market = if acct.exchange == Exchange::Hyperliquid { &hl.coin } else { &pdx.coin };
+        let pos = acct.position(market);
+        // Venue name dims while its private channel syncs, and is struck through while it is down
+        let venue_style = if acct.connected && acct.synced {
+            Style::default().fg(ex_color(&acct.exchange)).add_modifier(Modifier::BOLD)
+        } else if acct.connected {
+            Style::default().fg(theme().dim)
+        } else {
+            Style::default().fg(theme().dim).add_modifier(Modifier::CROSSED_OUT)
+        };
+        Row::new([
+            Cell::from(ex_tag(&acct.exchange)).style(venue_style),
+            Cell::from(usd(acct.account_value)).style(Style::default().fg(theme().text)),
+            Cell::from(usd(acct.free_collateral)).style(Style::default().fg(theme().dim)),
+            Cell::from(pos.map(|p| format!("{:+.4}", p.qty)).unwrap_or("0".into())).style(Style::default().fg(theme().text)),
+            Cell::from(pos.map(|p| format!("{:.2}", p.entry_px)).unwrap_or("–".into())).style(Style::default().fg(theme().dim)),
+            Cell::from(pos.map(|p| format!("{:+.2}", p.unrealized_pnl)).unwrap_or("–".into()))
+                .style(pnl_style(pos.map(|p| p.unrealized_pnl).unwrap_or(0.0))),
+        ]).height(1)
+    }).collect();
+
+    let block = Block::default()
+        .title(Span::styled(" ACCOUNT (LIVE) ", Style::default().fg(theme().arb).add_modifier(Modifier::BOLD)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let parts = Layout::vertical([
+        Constraint::Length(1 + rows.len() as u16),
+        Constraint::Min(0),
+    ]).split(inner);
+
+    let widths = [
+        Constraint::Length(5),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(9),
+        Constraint::Length(10),
+        Constraint::Min(0),
+    ];
+    frame.render_widget(Table::new(rows, widths).header(header), parts[0]);
+
+    // Latest fills across venues, newest first
+    let mut fills: Vec<_> = accounts.iter()
+        .flat_map(|a| a.fills.iter().map(move |f| (&a.exchange, f)))
+        .collect();
+    fills.sort_by_key(|(_, f)| std::cmp::Reverse(f.ts_ms));
+    let lines: Vec<Line> = fills.into_iter().take(parts[1].height as usize).map(|(ex, f)| {
+        let time = DateTime::from_timestamp_millis(f.ts_ms as i64)
+            .map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string())
+            .unwrap_or_default();
+        let side_color = if f.side == crate::types::Side::Buy { theme().bid } else { theme().ask };
+        Line::from(vec![
+            Span::styled(format!("{time} "), Style::default().fg(theme().dim)),
+            Span::styled(format!("{} ", ex_tag(ex)), Style::default().fg(ex_color(ex))),
+            Span::styled(format!("{:<4} ", format!("{:?}", f.side)), Style::default().fg(side_color)),
+            Span::styled(format!("{} {} @ {:.2}", f.size, f.market, f.price), Style::default().fg(theme().text)),
+        ])
+    }).collect();
+    frame.render_widget(Paragraph::new(lines), parts[1]);
+}
+
 // ─── Footer ───────────────────────────────────────────────────────────────────
 
-fn draw_footer(frame: &mut Frame, area: Rect, hl: &OrderBook, pdx: &OrderBook) {
-    let line = Line::from(vec![
-        Span::styled(
-            format!("  HL: {} updates   PDX: {} updates", hl.message_count, pdx.message_count),
-            Style::default().fg(C_DIM),
-        ),
-        Span::styled(
-            format!("{:>width$}", " [q] Quit ", width = area.width.saturating_sub(44) as usize),
-            Style::default().fg(C_HEADER),
-        ),
-    ]);
-    let block = Block::default().borders(Borders::ALL).border_style(Style::default().fg(C_BORDER));
-    frame.render_widget(Paragraph::new(line).block(block), area);
+fn draw_footer(frame: &mut Frame, area: Rect, v: &View) {
+    let mut hints = String::from(" ");
+    if v.sim.is_some() {
+        hints.push_str("[b/s] Buy/Sell  [x] Arb  [z] Flatten  ");
+    }
+    if v.hl_exec.is_some() {
+        hints.push_str("[H] HL leg  ");
+    }
+    if v.pdx_exec.is_some() {
+        hints.push_str("[P] PDX leg  ");
+    }
+    if v.hl_exec.is_some() || v.pdx_exec.is_some() {
+        hints.push_str("[C] Cancel  ");
+    }
+    if v.quarantined > 0 {
+        hints.push_str("[w] Dump  ");
+    }
+    hints.push_str("[c/m/v] Copy  [o] Dump books  [t] Colours  [1/2/3] Panels  [p] Pause  [S] Pair  [r] Rotate  [G] Grid  [a] Merge  [+/-] Group  [i] Impact  [g] Chart  [k] Candles  [d] Depth  [l] Diag  [e] Log  [q] Quit ");
+    let block = Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme().border));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let cols = Layout::horizontal([
+        Constraint::Min(0),
+        Constraint::Length(hints.chars().count() as u16),
+    ]).split(inner);
+
+    let mut left = Vec::new();
+    let now = now_ms();
+    for (tag, book, buckets) in [("HL", v.hl, &v.msg_rates.hl), ("PDX", v.pdx, &v.msg_rates.pdx)] {
+        // Time since the book last changed, clock-corrected like the stale check
+        let (age, age_color) = match book.age_ms(now) {
+            Some(ms) if ms > v.age_ms.1 => (fmt_age(ms), theme().warn),
+            Some(ms) if ms > v.age_ms.0 => (fmt_age(ms), theme().mid),
+            Some(ms)                    => (fmt_age(ms), theme().dim),
+            None                        => ("–".to_string(), theme().dim),
+        };
+        left.push(Span::styled(format!("  {tag}: age "), Style::default().fg(theme().dim)));
+        left.push(Span::styled(format!("{age} "), Style::default().fg(age_color)));
+        left.extend(rate_spans(buckets));
+        if let Some(rtt) = book.rtt_ms {
+            left.push(Span::styled(format!(" rtt {rtt:.0}ms"), Style::default().fg(theme().dim)));
+        }
+        // Parse and RPC errors point at a schema change or a bad request; some
+        // dropped frames are routine (deltas before the first snapshot)
+        let e = &book.errors;
+        for (n, label, color) in [(e.parse, "parse err", theme().warn), (e.rpc, "rpc err", theme().warn), (e.dropped, "dropped", theme().mid)] {
+            if n > 0 {
+                left.push(Span::styled(format!(" {n} {label}"), Style::default().fg(color)));
+            }
+        }
+    }
+    if v.quarantined > 0 {
+        left.push(Span::styled(format!("   {} quarantined", v.quarantined), Style::default().fg(theme().warn)));
+    }
+    // Outcome of the last simulated order; positions are in their own panel
+    if let Some(msg) = v.sim.and_then(|s| s.last_msg.as_ref()) {
+        left.push(Span::styled(format!("   SIM · {msg}"), Style::default().fg(theme().mid)));
+    }
+    // Live orders are real money: make their outcome stand out
+    for (name, exec) in [("HL", v.hl_exec), ("PDX", v.pdx_exec)] {
+        if let Some(exec) = exec {
+            let resting = exec.resting_oids().len();
+            let msg = exec.last_msg.as_deref().unwrap_or("ready");
+            left.push(Span::styled(format!("   {name} LIVE · {resting} resting · {msg}"), Style::default().fg(theme().arb)));
+        }
+    }
+    frame.render_widget(Paragraph::new(Line::from(left)), cols[0]);
+    frame.render_widget(Paragraph::new(Span::styled(hints, Style::default().fg(theme().header))), cols[1]);
+}
+
+// ─── Mid-price chart ──────────────────────────────────────────────────────────
+
+/// HL, PDX and merged mid over the session, both axes scaled to the data.
+fn draw_mid_chart(frame: &mut Frame, area: Rect, history: &MidHistory) {
+    let series = |pick: fn(&MidPoint) -> Option<f64>| -> Vec<(f64, f64)> {
+        history.points.iter().filter_map(|p| pick(p).map(|m| (p.t_secs, m))).collect()
+    };
+    let hl = series(|p| p.hl);
+    let pdx = series(|p| p.pdx);
+    let merged = series(|p| p.merged);
+
+    let (lo, hi) = hl.iter().chain(&pdx).chain(&merged)
+        .fold((f64::MAX, f64::MIN), |(lo, hi), &(_, y)| (lo.min(y), hi.max(y)));
+    let (lo, hi) = if lo <= hi {
+        let pad = ((hi - lo) * 0.05).max(hi * 1e-5);
+        (lo - pad, hi + pad)
+    } else {
+        (0.0, 1.0)
+    };
+    let t_end = history.points.last().map_or(1.0, |p| p.t_secs.max(1.0));
+    let fmt_t = |t: f64| if t >= 3_600.0 { format!("{:.1}h", t / 3_600.0) } else if t >= 60.0 { format!("{:.0}m", t / 60.0) } else { format!("{t:.0}s") };
+
+    let datasets = vec![
+        Dataset::default().name("HL").marker(Marker::Braille).graph_type(GraphType::Line)
+            .style(Style::default().fg(theme().hl)).data(&hl),
+        Dataset::default().name("PDX").marker(Marker::Braille).graph_type(GraphType::Line)
+            .style(Style::default().fg(theme().pdx)).data(&pdx),
+        Dataset::default().name("Merged").marker(Marker::Braille).graph_type(GraphType::Line)
+            .style(Style::default().fg(theme().mid)).data(&merged),
+    ];
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" ◈ Mid Price  (every {}s) ", history.step_ms / 1_000),
+                    Style::default().fg(theme().mid).add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme().dim))
+                .bounds([0.0, t_end])
+                .labels(vec![fmt_t(0.0), fmt_t(t_end / 2.0), fmt_t(t_end)]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme().dim))
+                .bounds([lo, hi])
+                .labels(vec![format!("{lo:.2}"), format!("{:.2}", (lo + hi) / 2.0), format!("{hi:.2}")]),
+        );
+    frame.render_widget(chart, area);
+}
+
+// ─── Candle chart ─────────────────────────────────────────────────────────────
+
+/// OHLC candles of the merged mid, one per two columns, newest on the right:
+/// `│` spans high to low, `█` open to close, green when the candle closed up.
+fn draw_candles(frame: &mut Frame, area: Rect, series: &CandleSeries) {
+    const AXIS_W: u16 = 10;
+    let secs = series.interval_ms / 1_000;
+    let label = if secs.is_multiple_of(60) { format!("{}m", secs / 60) } else { format!("{secs}s") };
+    let last = series.candles.back();
+    let title = match last {
+        Some(c) => format!(" ◈ Candles {label}  O {:.2}  H {:.2}  L {:.2}  C {:.2} ", c.open, c.high, c.low, c.close),
+        None    => format!(" ◈ Candles {label} "),
+    };
+    let block = Block::default()
+        .title(Span::styled(title, Style::default().fg(theme().mid).add_modifier(Modifier::BOLD)))
+        .title_bottom(Line::from(Span::styled(" [k] next interval ", Style::default().fg(theme().dim))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.width <= AXIS_W || inner.height < 2 {
+        return;
+    }
+
+    let plot_w = inner.width - AXIS_W;
+    let shown: Vec<_> = series.candles.iter().rev().take((plot_w / 2) as usize).rev().collect();
+    if shown.is_empty() {
+        return;
+    }
+    let (lo, hi) = shown.iter().fold((f64::MAX, f64::MIN), |(lo, hi), c| (lo.min(c.low), hi.max(c.high)));
+    let rows = inner.height - 1;
+    let row_of = |px: f64| -> u16 {
+        if hi > lo { inner.y + ((hi - px) / (hi - lo) * rows as f64).round() as u16 } else { inner.y + rows / 2 }
+    };
+
+    let buf = frame.buffer_mut();
+    for (i, c) in shown.iter().enumerate() {
+        let x = inner.x + i as u16 * 2;
+        let color = if c.close >= c.open { theme().bid } else { theme().ask };
+        let (body_top, body_bot) = (row_of(c.open.max(c.close)), row_of(c.open.min(c.close)));
+        for y in row_of(c.high)..=row_of(c.low) {
+            let ch = if (body_top..=body_bot).contains(&y) { '█' } else { '│' };
+            buf[(x, y)].set_char(ch).set_fg(color);
+        }
+    }
+    // Price axis: high, middle and low of the visible candles
+    let axis_x = inner.x + plot_w;
+    for (y, px) in [(inner.y, hi), (inner.y + rows / 2, (hi + lo) / 2.0), (inner.y + rows, lo)] {
+        buf.set_string(axis_x + 1, y, format!("{px:.2}"), Style::default().fg(theme().dim));
+    }
+}
+
+// ─── Depth chart ──────────────────────────────────────────────────────────────
+
+/// Columns sampled across the price range when filling the depth curves.
+const DEPTH_CHART_STEPS: usize = 240;
+
+/// Cumulative size of the merged book against price: bids filled to the left
+/// of the mid, asks to the right, each stepping up at every level.
+fn draw_depth_chart(frame: &mut Frame, area: Rect, merged: &MergedBook) {
+    let mut block = Block::default()
+        .title(Span::styled(" ◈ Depth (cumulative size) ", Style::default().fg(theme().mid).add_modifier(Modifier::BOLD)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+    let (Some(lo), Some(hi)) = (merged.bids.last(), merged.asks.last()) else {
+        frame.render_widget(block, area);
+        return;
+    };
+    let (lo, hi) = (lo.price, hi.price);
+    // Price axis along the bottom border: deepest bid … deepest ask
+    block = block
+        .title_bottom(Line::from(Span::styled(format!(" {lo:.2} "), Style::default().fg(theme().dim))).left_aligned())
+        .title_bottom(Line::from(Span::styled(format!(" {hi:.2} "), Style::default().fg(theme().dim))).right_aligned());
+    let total = |levels: &[MergedLevel]| levels.iter().map(|l| l.size).sum::<f64>();
+    let y_max = total(&merged.bids).max(total(&merged.asks)).max(f64::EPSILON) * 1.05;
+
+    // Size resting at or better than `px` on one side
+    let cum_at = |levels: &[MergedLevel], px: f64, bids: bool| -> f64 {
+        levels.iter().filter(|l| if bids { l.price >= px } else { l.price <= px }).map(|l| l.size).sum()
+    };
+    let mid = match (merged.bids.first(), merged.asks.first()) {
+        (Some(b), Some(a)) => (b.price + a.price) / 2.0,
+        _ => (lo + hi) / 2.0,
+    };
+
+    let canvas = Canvas::default()
+        .block(block)
+        .marker(Marker::Braille)
+        .x_bounds([lo, hi])
+        .y_bounds([0.0, y_max])
+        .paint(|ctx| {
+            for i in 0..=DEPTH_CHART_STEPS {
+                let px = lo + (hi - lo) * i as f64 / DEPTH_CHART_STEPS as f64;
+                let (cum, color) = if px <= mid {
+                    (cum_at(&merged.bids, px, true), theme().bid)
+                } else {
+                    (cum_at(&merged.asks, px, false), theme().ask)
+                };
+                if cum > 0.0 {
+                    ctx.draw(&CanvasLine { x1: px, y1: 0.0, x2: px, y2: cum, color });
+                }
+            }
+            ctx.print(lo, y_max, Span::styled(format!("{:.4}", y_max / 1.05), Style::default().fg(theme().dim)));
+            ctx.print(mid, y_max, Span::styled(format!("{mid:.2}"), Style::default().fg(theme().mid)));
+        });
+    frame.render_widget(canvas, area);
+}
+
+// ─── Diagnostics ──────────────────────────────────────────────────────────────
+
+/// Per-feed health: the exchange-timestamp → local-receive latency distribution.
+fn draw_diagnostics(frame: &mut Frame, area: Rect, v: &View) {
+    let ms = |q: Option<Option<i64>>| match q {
+        Some(Some(b)) => format!("≤{b}ms"),
+        Some(None)    => ">10s".to_string(),
+        None          => "–".to_string(),
+    };
+    let mut lines = vec![
+        Line::from(Span::styled("Latency  exchange ts → local receive", Style::default().fg(theme().header).add_modifier(Modifier::BOLD))),
+        Line::from(Span::styled(
+            format!("       {:>8}{:>8}{:>8}{:>9}{:>8}{:>10}", "p50", "p95", "p99", "max", "last", "msgs"),
+            Style::default().fg(theme().dim),
+        )),
+    ];
+    for (tag, color, book) in [("HL", theme().hl, v.hl), ("PDX", theme().pdx, v.pdx)] {
+        let h = &book.latency;
+        let p99 = h.quantile_ms(0.99);
+        let p99_color = if matches!(p99, Some(Some(b)) if b <= 500) { theme().text } else if h.count == 0 { theme().dim } else { theme().warn };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {tag:<5}"), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{:>8}{:>8}", ms(h.quantile_ms(0.5)), ms(h.quantile_ms(0.95))), Style::default().fg(theme().text)),
+            Span::styled(format!("{:>8}", ms(p99)), Style::default().fg(p99_color)),
+            Span::styled(
+                format!(
+                    "{:>9}{:>8}{:>10}",
+                    h.max_ms.map(|m| format!("{m}ms")).unwrap_or("–".into()),
+                    h.last_ms.map(|m| format!("{m}ms")).unwrap_or("–".into()),
+                    h.count,
+                ),
+                Style::default().fg(theme().dim),
+            ),
+        ]));
+        if h.negative > 0 {
+            lines.push(Line::from(Span::styled(
+                format!("         {} msgs stamped ahead of the local clock", h.negative),
+                Style::default().fg(theme().mid),
+            )));
+        }
+    }
+    let offset = |book: &OrderBook| match book.clock_offset_ms {
+        Some(ms) => format!("{ms:+}ms"),
+        None     => "–".to_string(),
+    };
+    lines.push(Line::from(Span::styled(
+        format!("  Clock offset (venue − local)   HL {}   PDX {}", offset(v.hl), offset(v.pdx)),
+        Style::default().fg(theme().dim),
+    )));
+    let rtt = |book: &OrderBook| book.rtt_ms.map(|ms| format!("{ms:.1}ms")).unwrap_or("–".into());
+    lines.push(Line::from(Span::styled(
+        format!("  Heartbeat round trip           HL {}   PDX {}", rtt(v.hl), rtt(v.pdx)),
+        Style::default().fg(theme().dim),
+    )));
+    lines.push(Line::from(Span::styled(
+        format!("  Sequence gaps (resynced)       PDX {}", v.pdx.seq_gaps),
+        Style::default().fg(if v.pdx.seq_gaps > 0 { theme().mid } else { theme().dim }),
+    )));
+    let timing = |t: &Timing| match t.count {
+        0 => "–".to_string(),
+        _ => format!("avg {} max {}", fmt_us(t.avg_us), fmt_us(t.max_us as f64)),
+    };
+    lines.push(Line::from(Span::styled(
+        format!("  Merge {}   Draw {}", timing(&v.merged.merge_time), timing(v.draw_time)),
+        Style::default().fg(theme().dim),
+    )));
+
+    let block = Block::default()
+        .title(Span::styled(" ◈ Diagnostics ", Style::default().fg(theme().mid).add_modifier(Modifier::BOLD)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+// ─── Log ──────────────────────────────────────────────────────────────────────
+
+/// Recent log events, newest at the bottom like a terminal.
+fn draw_logs(frame: &mut Frame, area: Rect, logs: &VecDeque<LogLine>) {
+    let rows = area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = logs.iter().take(rows).rev().map(|l| {
+        let time = DateTime::from_timestamp_millis(l.ts_ms as i64)
+            .map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string())
+            .unwrap_or_default();
+        let (level_color, text_color) = match l.level {
+            Level::ERROR => (theme().warn, theme().warn),
+            Level::WARN  => (theme().mid, theme().text),
+            _            => (theme().dim, theme().dim),
+        };
+        Line::from(vec![
+            Span::styled(format!("{time} "), Style::default().fg(theme().dim)),
+            Span::styled(format!("{:<5} ", l.level.as_str()), Style::default().fg(level_color)),
+            Span::styled(l.message.clone(), Style::default().fg(text_color)),
+        ])
+    }).collect();
+
+    let warnings = logs.iter().filter(|l| l.level <= Level::WARN).count();
+    let block = Block::default()
+        .title(Span::styled(" ◈ Log ", Style::default().fg(theme().mid).add_modifier(Modifier::BOLD)))
+        .title_bottom(Line::from(Span::styled(
+            format!(" {warnings} warnings / errors in last {} ", logs.len()),
+            Style::default().fg(theme().dim),
+        )).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+// ─── Impact calculator ────────────────────────────────────────────────────────
+
+/// Popup over the bottom of the merged book: the size box, then the expected
+/// fill of a market order of that size and its split across venues.
+fn draw_impact(frame: &mut Frame, book_area: Rect, calc: &ImpactCalc) {
+    let height = 8.min(book_area.height);
+    let area = Rect { y: book_area.bottom() - height, height, ..book_area };
+
+    let (side_label, side_color) = match calc.side {
+        crate::types::Side::Buy  => ("BUY ", theme().bid),
+        crate::types::Side::Sell => ("SELL", theme().ask),
+    };
+    let cursor = if calc.editing { "▏" } else { "" };
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("  {side_label} "), Style::default().fg(side_color).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("{}{cursor}", calc.input), Style::default().fg(theme().text).add_modifier(Modifier::BOLD)),
+    ])];
+
+    match (&calc.result, calc.size()) {
+        (Some(imp), Some(size)) if imp.avg_px.is_some() => {
+            let px = |p: Option<f64>| p.map(|p| format!("{p:.2}")).unwrap_or("–".into());
+            let (slip, slip_color) = match imp.slippage_bps {
+                Some(b) if b > 0.0 => (format!("{b:+.1} bps"), theme().ask),
+                Some(b) => (format!("{b:+.1} bps"), theme().bid),
+                None    => ("–".to_string(), theme().dim),
+            };
+            lines.push(Line::from(vec![
+                Span::styled("  avg ", Style::default().fg(theme().dim)),
+                Span::styled(px(imp.avg_px), Style::default().fg(theme().text)),
+                Span::styled("  slip ", Style::default().fg(theme().dim)),
+                Span::styled(slip, Style::default().fg(slip_color)),
+                Span::styled(format!("  worst {}", px(imp.worst_px)), Style::default().fg(theme().dim)),
+            ]));
+            for (tag, color, sz, usd) in [("HL", theme().hl, imp.hl_size, imp.hl_usd), ("PDX", theme().pdx, imp.pdx_size, imp.pdx_usd)] {
+                let leg_px = if sz > 0.0 { format!("{:.2}", usd / sz) } else { "–".to_string() };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {tag:<4}"), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                    Span::styled(format!("{sz:.4} @ {leg_px}"), Style::default().fg(theme().text)),
+                    Span::styled(format!("  {:.0}%", sz / imp.filled * 100.0), Style::default().fg(theme().dim)),
+                ]));
+            }
+            if imp.filled < size {
+                lines.push(Line::from(Span::styled(
+                    format!("  ⚠ books exhausted: {:.4} unfilled", size - imp.filled),
+                    Style::default().fg(theme().warn),
+                )));
+            }
+        }
+        (_, Some(_)) => lines.push(Line::from(Span::styled("  no liquidity", Style::default().fg(theme().dim)))),
+        (_, None)    => lines.push(Line::from(Span::styled("  type a size in base units", Style::default().fg(theme().dim)))),
+    }
+
+    let hint = if calc.editing { " [Enter] done  [Tab] side  [Esc] close " } else { " [i] edit  [Tab] side  [Esc] close " };
+    let block = Block::default()
+        .title(Span::styled(" ◈ Market Impact ", Style::default().fg(theme().mid).add_modifier(Modifier::BOLD)))
+        .title_bottom(Line::from(Span::styled(hint, Style::default().fg(theme().dim))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().mid));
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Pair picker popup at the top of the merged book: the typed name, the best
+/// matches with each venue's 24h volume, and the outcome of the last switch.
+fn draw_pair_picker(frame: &mut Frame, book_area: Rect, picker: &PairPicker) {
+    const MAX_ROWS: usize = 10;
+    let rows = picker.matches.len().clamp(1, MAX_ROWS);
+    let area = Rect { height: (rows as u16 + 4).min(book_area.height), ..book_area };
+
+    let cursor = if picker.pending { "" } else { "▏" };
+    let mut lines = vec![
+        Line::from(Span::styled(format!("  {}{cursor}", picker.input), Style::default().fg(theme().text).add_modifier(Modifier::BOLD))),
+    ];
+    // Scroll the list so the selected row stays in view
+    let first = picker.selected.saturating_sub(rows - 1);
+    for (i, c) in picker.matches.iter().enumerate().skip(first).take(rows) {
+        let selected = i == picker.selected;
+        let vol = |v: Option<f64>, listed: bool| match (v, listed) {
+            (Some(v), _)  => format!("${:>8}", fmt_usd(v)),
+            (None, true)  => format!("{:>9}", "?"),
+            (None, false) => format!("{:>9}", "–"),
+        };
+        let (hl, pdx, note) = match &c.market {
+            Some(m) => (
+                vol(m.hl_volume, m.on_hl),
+                vol(m.pdx_volume, m.pdx_symbol.is_some()),
+                match (m.on_hl, m.pdx_symbol.is_some()) {
+                    (true, false) => "  HL only",
+                    (false, true) => "  PDX only",
+                    _ => "",
+                },
+            ),
+            None => (vol(None, true), vol(None, true), ""),
+        };
+        let on_both = c.market.as_ref().is_none_or(|m| m.on_both());
+        let name_color = if !on_both { theme().dim } else if selected { theme().mid } else { theme().text };
+        let mut spans = vec![
+            Span::styled(if selected { "  ▸ " } else { "    " }, Style::default().fg(theme().mid)),
+            Span::styled(format!("{:<10}", c.name), Style::default().fg(name_color).add_modifier(if selected { Modifier::BOLD } else { Modifier::empty() })),
+            Span::styled(" HL ", Style::default().fg(theme().dim)),
+            Span::styled(hl, Style::default().fg(if on_both { theme().hl } else { theme().dim })),
+            Span::styled("  PDX ", Style::default().fg(theme().dim)),
+            Span::styled(pdx, Style::default().fg(if on_both { theme().pdx } else { theme().dim })),
+            Span::styled(note, Style::default().fg(theme().dim)),
+        ];
+        if c.configured {
+            spans.push(Span::styled("  [pairs]", Style::default().fg(theme().dim)));
+        }
+        lines.push(Line::from(spans));
+    }
+    if picker.matches.is_empty() {
+        lines.push(Line::from(Span::styled("    no listed market matches", Style::default().fg(theme().dim))));
+    }
+    let status = match (&picker.status, picker.pending) {
+        (_, true)               => Span::styled("  checking both exchanges…", Style::default().fg(theme().mid)),
+        (Some((msg, true)), _)  => Span::styled(format!("  ✗ {msg}"), Style::default().fg(theme().ask)),
+        (Some((msg, false)), _) => Span::styled(format!("  ✓ {msg}"), Style::default().fg(theme().bid)),
+        (None, _)               => Span::styled("  a [pairs] name or a Hyperliquid coin", Style::default().fg(theme().dim)),
+    };
+    lines.push(Line::from(status));
+
+    let block = Block::default()
+        .title(Span::styled(" ◈ Switch Pair ", Style::default().fg(theme().mid).add_modifier(Modifier::BOLD)))
+        .title_bottom(Line::from(Span::styled(" [↑/↓] select  [Tab] complete  [Enter] switch  [Esc] close ", Style::default().fg(theme().dim))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().mid));
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Recent updates/s and a bar per second over the last `RATE_GRAPH_SECS`.
+/// Amber once the recent rate falls under half the longer-run average, which a
+/// cumulative count would hide.
+fn rate_spans(buckets: &VecDeque<u64>) -> [Span<'static>; 2] {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    const RATE_GRAPH_SECS: usize = 20;
+    let now = msg_rate::rate(buckets);
+    let avg = if buckets.is_empty() { 0.0 } else { buckets.iter().sum::<u64>() as f64 / buckets.len() as f64 };
+    let color = if avg > 0.0 && now < avg / 2.0 { theme().mid } else { theme().dim };
+    let recent: Vec<u64> = buckets.iter().skip(buckets.len().saturating_sub(RATE_GRAPH_SECS)).copied().collect();
+    let max = recent.iter().copied().max().unwrap_or(0).max(1);
+    let bars: String = recent.iter().map(|&n| BARS[(n * 7 / max) as usize]).collect();
+    [
+        Span::styled(format!("{now:.0}/s "), Style::default().fg(color)),
+        Span::styled(bars, Style::default().fg(color)),
+    ]
 }
 
 // ─── Format helpers ───────────────────────────────────────────────────────────
@@ -411,8 +1957,39 @@ fn fmt_usd(v: f64) -> String {
     else { format!("{v:.2}") }
 }
 
-fn fmt_bbo(bid: Option<f64>, ask: Option<f64>) -> String {
-    let b = bid.map(|v| format!("{v:.2}")).unwrap_or("–".into());
-    let a = ask.map(|v| format!("{v:.2}")).unwrap_or("–".into());
-    format!("bid {b}  ask {a}")
+/// "85ms" below a second, "3.2s" above.
+fn fmt_age(ms: u64) -> String {
+    if ms < 1_000 { format!("{ms}ms") } else { format!("{:.1}s", ms as f64 / 1_000.0) }
+}
+
+/// "42s", "17m", "3h05m" or "2d04h".
+fn fmt_uptime(ms: u64) -> String {
+    let secs = ms / 1_000;
+    match secs {
+        0..60         => format!("{secs}s"),
+        60..3_600     => format!("{}m", secs / 60),
+        3_600..86_400 => format!("{}h{:02}m", secs / 3_600, secs % 3_600 / 60),
+        _             => format!("{}d{:02}h", secs / 86_400, secs % 86_400 / 3_600),
+    }
+}
+
+fn bbo_spans(book: &OrderBook, ticks: &BookTicks) -> Vec<Span<'static>> {
+    let px = |v: Option<f64>| v.map(|v| format!("{v:.2}"));
+    vec![
+        Span::styled("bid ", Style::default().fg(theme().dim)),
+        tick_span(px(book.best_bid()), &ticks.bid, theme().text),
+        Span::styled("  ask ", Style::default().fg(theme().dim)),
+        tick_span(px(book.best_ask()), &ticks.ask, theme().text),
+    ]
+}
+
+/// `text` followed by ▲ / ▼ in the direction of its last move, both coloured
+/// until the move decays; a blank keeps the width steady in between.
+fn tick_span(text: Option<String>, tick: &Tick, neutral: Color) -> Span<'static> {
+    let Some(text) = text else { return Span::styled("–", Style::default().fg(theme().dim)) };
+    match tick.dir(Instant::now()) {
+        Some(Dir::Up)   => Span::styled(format!("{text}▲"), Style::default().fg(theme().bid)),
+        Some(Dir::Down) => Span::styled(format!("{text}▼"), Style::default().fg(theme().ask)),
+        None            => Span::styled(format!("{text} "), Style::default().fg(neutral)),
+    }
 }
@@ -0,0 +1,92 @@
+// src/volatility.rs — Short-horizon realized volatility from merged mid returns
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::watch;
+use tokio::time::interval;
+use tracing::info;
+
+use crate::config::VolatilityConfig;
+use crate::merger::MergedBook;
+
+/// Mid sampling interval; one log return per sample.
+const SAMPLE_MS: u64 = 1_000;
+/// Crypto trades around the clock, so a year is 365 full days.
+const SECS_PER_YEAR: f64 = 365.0 * 86_400.0;
+
+/// Annualized realized volatility over one rolling window, in percent.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct VolWindow {
+    pub secs:    u64,
+    pub vol_pct: Option<f64>, // None until the window holds two returns
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VolState {
+    pub windows: Vec<VolWindow>, // ascending by length
+}
+
+/// Spawns a task sampling the merged mid every `SAMPLE_MS` into a ring buffer
+/// of log returns and publishing realized volatility per configured window.
+pub fn spawn_volatility(cfg: &VolatilityConfig, merged_rx: watch::Receiver<MergedBook>) -> watch::Receiver<VolState> {
+    let windows_secs = cfg.windows_secs.clone();
+    let capacity = (windows_secs.iter().max().copied().unwrap_or(0) * 1_000 / SAMPLE_MS) as usize;
+    let (tx, rx) = watch::channel(VolState::default());
+    info!("[VOL] Windows {windows_secs:?}s");
+
+    tokio::spawn(async move {
+        let mut returns: VecDeque<f64> = VecDeque::with_capacity(capacity);
+        let mut last_mid: Option<f64> = None;
+        let mut coins = Default::default();
+        let mut ticker = interval(Duration::from_millis(SAMPLE_MS));
+
+        loop {
+            ticker.tick().await;
+            let mid = {
+                let merged = merged_rx.borrow();
+                // No return runs from one pair's mid to another's
+                if merged.pair_changed(&mut coins) {
+                    returns.clear();
+                    last_mid = None;
+                }
+                match (merged.bids.first(), merged.asks.first()) {
+                    (Some(b), Some(a)) => Some((b.price + a.price) / 2.0),
+                    _ => None,
+                }
+            };
+            // A missing mid breaks the series rather than bridging the gap with one big return
+            match (last_mid, mid) {
+                (Some(prev), Some(now)) if prev > 0.0 && now > 0.0 => {
+                    if returns.len() == capacity {
+                        returns.pop_front();
+                    }
+                    returns.push_back((now / prev).ln());
+                }
+                (_, None) => returns.clear(),
+                _ => {}
+            }
+            last_mid = mid;
+
+            let windows = windows_secs.iter().map(|&secs| {
+                let n = ((secs * 1_000 / SAMPLE_MS) as usize).min(returns.len());
+                VolWindow { secs, vol_pct: realized_vol_pct(returns.iter().rev().take(n)) }
+            }).collect();
+            if tx.send(VolState { windows }).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// √(Σ r²) scaled from the span the returns cover to a year, in percent.
+fn realized_vol_pct<'a>(returns: impl Iterator<Item = &'a f64>) -> Option<f64> {
+    let (n, sum_sq) = returns.fold((0usize, 0.0), |(n, s), r| (n + 1, s + r * r));
+    if n < 2 {
+        return None;
+    }
+    let span_secs = n as f64 * SAMPLE_MS as f64 / 1_000.0;
+    Some((sum_sq * SECS_PER_YEAR / span_secs).sqrt() * 100.0)
+}
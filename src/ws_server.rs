@@ -0,0 +1,75 @@
+// src/ws_server.rs — Outbound WebSocket server re-broadcasting the merged book
+
+use std::net::SocketAddr;
+
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio_tungstenite::{accept_async, tungstenite::Message};
+use tracing::{debug, error, info, warn};
+
+use crate::config::WsServerConfig;
+use crate::merger::MergedBook;
+
+// ─── Public entry point ───────────────────────────────────────────────────────
+
+/// Binds the listener (so a bad address fails at startup) and accepts clients
+/// in the background. Every client receives the full `MergedBook` as JSON on
+/// each update.
+pub async fn spawn_ws_server(cfg: &WsServerConfig, merged_rx: watch::Receiver<MergedBook>) -> Result<()> {
+    let listener = TcpListener::bind(cfg.bind)
+        .await
+        .with_context(|| format!("Failed to bind WebSocket server on {}", cfg.bind))?;
+
+    info!("[WSS] Listening on {}", cfg.bind);
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    tokio::spawn(serve_client(stream, peer, merged_rx.clone()));
+                }
+                Err(e) => error!("[WSS] Accept failed: {e}"),
+            }
+        }
+    });
+    Ok(())
+}
+
+// ─── Per-client session ───────────────────────────────────────────────────────
+
+async fn serve_client(stream: TcpStream, peer: SocketAddr, mut merged_rx: watch::Receiver<MergedBook>) {
+    let ws_stream = match accept_async(stream).await {
+        Ok(ws) => ws,
+        Err(e) => { warn!("[WSS] Handshake with {peer} failed: {e}"); return; }
+    };
+    info!("[WSS] Client connected: {peer}");
+
+    let (mut write, mut read) = ws_stream.split();
+
+    // Send the current state immediately so clients don't wait for the next update
+    merged_rx.mark_changed();
+
+    loop {
+        tokio::select! {
+            changed = merged_rx.changed() => {
+                if changed.is_err() { break; }
+                let text = match serde_json::to_string(&*merged_rx.borrow_and_update()) {
+                    Ok(t)  => t,
+                    Err(e) => { error!("[WSS] Failed to serialise MergedBook: {e}"); continue; }
+                };
+                if let Err(e) = write.send(Message::Text(text)).await {
+                    debug!("[WSS] Send to {peer} failed: {e}");
+                    break;
+                }
+            }
+            msg = read.next() => match msg {
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_))                        => {} // read-only consumers; pongs are automatic
+                Some(Err(e)) => { debug!("[WSS] Read from {peer} failed: {e}"); break; }
+            },
+        }
+    }
+
+    info!("[WSS] Client disconnected: {peer}");
+}